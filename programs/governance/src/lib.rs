@@ -8,8 +8,11 @@
 //! - Comprehensive transaction types for protocol management
 //!
 //! ## Security Features
-//! - Minimum 2 approvals required (prevents single-point-of-failure)
+//! - Minimum 2 distinct approvers required (prevents single-point-of-failure)
 //! - Cooldown periods prevent instant execution
+//! - Queued transactions expire if not executed within the expiry window (default 14 days)
+//! - Optional approval cooldown delays the first approval after a transaction is queued
+//! - Optional bootstrap window (fixed at init) exempts setup transactions from cooldown
 //! - All queue operations require authorized signer
 //! - Reentrancy protection on critical functions
 //! - Duplicate signer prevention
@@ -17,20 +20,71 @@
 //! ## Transaction Flow
 //! 1. Queue: Authorized signer queues a transaction
 //! 2. Approve: Multiple signers approve the transaction
+//!    (a signer may revoke their approval while the transaction is pending)
 //! 3. Execute: After cooldown, transaction is executed via CPI
 //!
+//! A pending transaction with no approvals can be cancelled by its initiator
+//! (or any authorized signer), closing the transaction account.
+//!
+//! Token transactions act on one of up to five registered token programs,
+//! chosen by `token_program_index` at queue time (0 = the primary program set
+//! with `set_token_program`; others are added with `add_token_program`).
+//!
 //! ## Transaction Types
 //! - Unpause: Unpause the token program
+//! - SetGovernanceOpsDuringPause: Let governance mint/burn/seize while the token is paused
+//! - ToggleWhitelistMode: Turn whitelist mode on/off (enabling also has a single-signer fast path)
 //! - Blacklist: Add/remove addresses from blacklist
+//! - BlacklistBatch: Add/remove up to 20 addresses from blacklist in one transaction
 //! - NoSellLimit: Grant/revoke sell limit exemptions
 //! - Restricted: Add/remove restricted addresses
 //! - LiquidityPool: Mark/unmark liquidity pools
+//! - PairBatch: Mark/unmark up to 8 liquidity pools in one transaction
 //! - BridgeAddress: Update bridge contract address
 //! - BondAddress: Update bond contract address
 //! - TreasuryAddress: Update treasury address
 //! - WithdrawToTreasury: Withdraw funds to treasury
+//! - SetVestingConfig: Enable/disable presale vesting and set its cliff/duration
+//! - UpdateTokenPrice: Change the presale token price
+//! - SetPurchaseLimits: Set the presale per-transaction minimum and maximum
+//! - UpdatePresaleCap / UpdateMaxPerUser: Change the presale cap or per-user limit
+//! - SetPriceTiers: Set the presale price tiers by tokens sold
+//! - StartPresale / StopPresale: Open or close the presale
+//! - BurnTokens: Burn tokens from a token account (clawback)
+//! - Mint: Mint tokens to a token account
+//! - MintWithVesting: Mint tokens into a vault that unlocks to a beneficiary over time
+//! - SetEmissionSchedule: Set the per-second emission rate, recipient and period
+//! - SetSellTrackerMode: Track sell limits per wallet or per token account
+//! - Seize: Move tokens from a blacklisted holder to a custody account
+//! - SetMaxSupply: Lower or remove the token supply cap
+//! - SetTransferFee: Set the fee deducted from token transfers (burned or sent to a treasury)
+//! - SetCircuitBreaker: Reject single transfers above a share of the token supply
 //! - SetRequiredApprovals: Change approval requirements
 //! - SetCooldownPeriod: Change cooldown period
+//! - SetHighValuePolicy: Require more approvals for large treasury withdrawals
+//! - SetTypeApprovals: Override the required approvals for one transaction type
+//! - SetExpiryWindow: Change how long an executable transaction stays valid
+//! - SetApprovalCooldown: Change the review delay before a queued transaction can be approved
+//! - SetKeeperReward: Change the lamports paid to whoever executes a ready transaction
+//! - AddSigner / RemoveSigner: Rotate multisig signers
+//! - SetSignerWeight: Change how much a signer's approval counts toward the quorum
+//! - SetBackupSigners: Designate signers that take over after long primary inactivity
+//!
+//! `validate_transaction_params` runs the execute-time parameter checks for a
+//! transaction type and data without queuing anything, so front-ends can
+//! reject bad parameters before asking signers to approve.
+//!
+//! ## Weighted Approvals
+//! Each signer has a voting weight (default 1). A transaction executes once the
+//! summed weight of its approvals reaches `required_approvals` (or the override
+//! for its transaction type, see `type_approvals`) and at least two distinct
+//! signers have approved.
+//!
+//! ## Backup Signers
+//! Backup signers are authorized only once no primary signer has approved or
+//! executed a transaction for `backup_activation_period` seconds (at least 90
+//! days), measured from `last_activity`. Each backup approval counts as weight 1
+//! and emits `BackupSignerApproved`. Any primary signer activity deactivates them.
 
 use anchor_lang::prelude::*;
 
@@ -43,6 +97,201 @@ use spl_project::program::SplProject;
 #[allow(unused_imports)]
 use presale::program::Presale;
 
+#[event]
+pub struct SignerAdded {
+    pub signer: Pubkey,
+    pub signer_count: u8,
+}
+
+#[event]
+pub struct SignerRemoved {
+    pub signer: Pubkey,
+    pub signer_count: u8,
+}
+
+#[event]
+pub struct SignerWeightChanged {
+    pub signer: Pubkey,
+    pub weight: u8,
+    pub total_weight: u16,
+}
+
+#[event]
+pub struct RequiredApprovalsChanged {
+    pub old: u8,
+    pub new: u8,
+    pub queued: bool, // false when set through the deprecated direct setter
+}
+
+#[event]
+pub struct CooldownPeriodChanged {
+    pub old: i64,
+    pub new: i64,
+    pub queued: bool, // false when set through the deprecated direct setter
+}
+
+#[event]
+pub struct ProgramLinked {
+    pub program: Pubkey,
+    pub is_presale: bool,
+}
+
+#[event]
+pub struct TokenProgramAdded {
+    pub program_id: Pubkey,
+    pub index: u8,
+}
+
+#[event]
+pub struct TokenProgramRemoved {
+    pub program_id: Pubkey,
+    pub index: u8,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub account: Pubkey,
+    pub role: u8,
+    pub granted: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct EmergencyPauseActivated {
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct WhitelistModeEnabled {
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct WhitelistModeDisabled {
+    pub tx_id: u64,
+}
+
+#[event]
+pub struct TransactionQueued {
+    pub id: u64,
+    pub tx_type: TransactionType,
+    pub initiator: Pubkey,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct PriceUpdateQueued {
+    pub old_price: u64,
+    pub new_price: u64,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct PresaleCapUpdateExecuted {
+    pub old_cap: u64,
+    pub new_cap: u64,
+}
+
+#[event]
+pub struct MaxPerUserUpdateExecuted {
+    pub old_max: u64,
+    pub new_max: u64,
+}
+
+#[event]
+pub struct PresaleStartQueued {
+    pub tx_id: u64,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct PresaleStopQueued {
+    pub tx_id: u64,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct MintQueued {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub tx_id: u64,
+}
+
+#[event]
+pub struct BurnQueued {
+    pub from: Pubkey,
+    pub amount: u64,
+    pub tx_id: u64,
+}
+
+#[event]
+pub struct TransactionApproved {
+    pub id: u64,
+    pub approver: Pubkey,
+    pub approval_count: u8,
+    pub approval_weight: u16,
+}
+
+#[event]
+pub struct ApprovalRevoked {
+    pub tx_id: u64,
+    pub signer: Pubkey,
+    pub remaining: u8,
+}
+
+#[event]
+pub struct TransactionExecuted {
+    pub id: u64,
+    pub tx_type: TransactionType,
+}
+
+#[event]
+pub struct BlacklistBatchExecuted {
+    pub count: u8,
+    pub tx_id: u64,
+}
+
+#[event]
+pub struct KeeperRewarded {
+    pub tx_id: u64,
+    pub executor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TransactionRejected {
+    pub id: u64,
+    pub rejector: Pubkey,
+    pub reason: String,
+}
+
+#[event]
+pub struct TransactionCancelled {
+    pub tx_id: u64,
+    pub tx_type: TransactionType,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct BackupSignersChanged {
+    pub backup_signers: Vec<Pubkey>,
+    pub activation_period: i64,
+}
+
+#[event]
+pub struct BackupSignerApproved {
+    pub tx_id: u64,
+    pub signer: Pubkey,
+    pub last_activity: i64,
+}
+
+#[event]
+pub struct TransactionValidated {
+    pub tx_type: TransactionType,
+    pub is_valid: bool,
+    pub reason: Option<String>,
+}
+
 #[program]
 pub mod governance {
     use super::*;
@@ -57,6 +306,10 @@ pub mod governance {
     /// - `required_approvals`: Minimum number of approvals needed (must be >= 2)
     /// - `cooldown_period`: Minimum cooldown period in seconds (must be >= 1800)
     /// - `signers`: List of authorized signer addresses (must be unique, max 10)
+    /// - `bootstrap_window_seconds`: Window after init during which setup transactions
+    ///   skip the cooldown (0 disables bootstrap mode, max 24h)
+    /// - `bootstrap_max_transactions`: Maximum number of setup transactions that may
+    ///   use the bootstrap exemption (max 20); each one is spent when queued
     ///
     /// # Returns
     /// - `Result<()>`: Success if initialization completes
@@ -64,6 +317,7 @@ pub mod governance {
     /// # Errors
     /// - `GovernanceError::RequiredApprovalsTooLow` if required_approvals < 2
     /// - `GovernanceError::CooldownPeriodTooLow` if cooldown < 1800 seconds
+    /// - `GovernanceError::InvalidBootstrapConfig` if bootstrap window or count exceed limits
     /// - `GovernanceError::DuplicateSigners` if signers list contains duplicates
     /// - `GovernanceError::InvalidRequiredApprovals` if required_approvals > signers.len()
     ///
@@ -71,11 +325,16 @@ pub mod governance {
     /// - Prevents duplicate signers
     /// - Enforces minimum approval threshold
     /// - Validates all parameters before initialization
+    /// - Bootstrap window and count are fixed here and cannot be extended later
+    /// - Any signer can spend bootstrap exemptions by queueing setup transactions,
+    ///   so keep the count to what the initial setup needs
     pub fn initialize(
         ctx: Context<Initialize>,
         required_approvals: u8,
         cooldown_period: i64,
         signers: Vec<Pubkey>,
+        bootstrap_window_seconds: i64,
+        bootstrap_max_transactions: u8,
     ) -> Result<()> {
         require!(
             required_approvals >= GovernanceState::MIN_REQUIRED_APPROVALS,
@@ -106,6 +365,16 @@ pub mod governance {
             GovernanceError::DuplicateSigners
         );
 
+        require!(
+            (0..=GovernanceState::MAX_BOOTSTRAP_WINDOW_SECONDS).contains(&bootstrap_window_seconds),
+            GovernanceError::InvalidBootstrapConfig
+        );
+        require!(
+            bootstrap_max_transactions <= GovernanceState::MAX_BOOTSTRAP_TRANSACTIONS,
+            GovernanceError::InvalidBootstrapConfig
+        );
+
+        let clock = Clock::get()?;
         let governance_state = &mut ctx.accounts.governance_state;
         governance_state.authority = ctx.accounts.authority.key();
         governance_state.required_approvals = required_approvals;
@@ -116,7 +385,24 @@ pub mod governance {
         governance_state.presale_program = Pubkey::default();
         governance_state.presale_program_set = false;
         governance_state.bump = ctx.bumps.governance_state;
+        governance_state.signer_weights = vec![1; signers.len()];
         governance_state.signers = signers;
+        governance_state.bootstrap_ends_at = clock.unix_timestamp + bootstrap_window_seconds;
+        governance_state.bootstrap_transactions_remaining = if bootstrap_window_seconds > 0 {
+            bootstrap_max_transactions
+        } else {
+            0
+        };
+        governance_state.high_value_threshold = 0; // 0 = disabled
+        governance_state.high_value_required_approvals = 0;
+        governance_state.expiry_window = GovernanceState::DEFAULT_EXPIRY_WINDOW_SECONDS;
+        governance_state.approval_cooldown = 0; // 0 = approvals allowed immediately
+        governance_state.keeper_reward = 0; // No keeper reward until configured
+        governance_state.type_approvals = Vec::new(); // Every type uses required_approvals
+        governance_state.last_activity = clock.unix_timestamp;
+        governance_state.backup_signers = Vec::new(); // No backups until configured
+        governance_state.backup_activation_period = 0;
+        governance_state.token_programs = Vec::new(); // Filled by set_token_program
 
         msg!(
             "Governance initialized with {} required approvals, {}s cooldown, and {} signers",
@@ -124,6 +410,74 @@ pub mod governance {
             cooldown_period,
             governance_state.signers.len()
         );
+        if governance_state.bootstrap_transactions_remaining > 0 {
+            msg!(
+                "Bootstrap mode active until {} for up to {} setup transactions",
+                governance_state.bootstrap_ends_at,
+                governance_state.bootstrap_transactions_remaining
+            );
+        }
+        Ok(())
+    }
+
+    /// Grows a governance state created before the current layout
+    ///
+    /// States initialized by earlier versions were sized for the signer list only.
+    /// Below the signer maximum they still deserialize, since the fields added since
+    /// then read as zero from the unused signer capacity, but writing backup signers,
+    /// token programs or more signers overflows the account and fails with
+    /// `AccountDidNotSerialize`. The
+    /// account is reallocated to `8 + GovernanceState::LEN` with the new bytes
+    /// zero-filled; existing data is left as is. Anyone can migrate the state; the payer
+    /// funds the additional rent.
+    ///
+    /// # Parameters
+    /// - `ctx`: MigrateGovernanceState context
+    ///
+    /// # Errors
+    /// - `GovernanceError::InvalidAccount` if `governance_state` is not an initialized
+    ///   GovernanceState of this program
+    pub fn migrate_governance_state(ctx: Context<MigrateGovernanceState>) -> Result<()> {
+        let governance_state = ctx.accounts.governance_state.to_account_info();
+        require!(
+            governance_state.owner == &crate::ID,
+            GovernanceError::InvalidAccount
+        );
+        {
+            let data = governance_state.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *GovernanceState::DISCRIMINATOR,
+                GovernanceError::InvalidAccount
+            );
+        }
+
+        let new_size = 8 + GovernanceState::LEN;
+        let old_size = governance_state.data_len();
+        if old_size >= new_size {
+            msg!("Governance state already migrated");
+            return Ok(());
+        }
+
+        let additional_lamports = Rent::get()?
+            .minimum_balance(new_size)
+            .saturating_sub(governance_state.lamports());
+        if additional_lamports > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: governance_state.clone(),
+                    },
+                ),
+                additional_lamports,
+            )?;
+        }
+
+        #[allow(deprecated)]
+        governance_state.realloc(new_size, true)?;
+
+        msg!("Governance state migrated from {} to {} bytes", old_size, new_size);
         Ok(())
     }
 
@@ -165,10 +519,116 @@ pub mod governance {
         );
         governance_state.token_program = token_program;
         governance_state.token_program_set = true;
+        governance_state.token_programs = vec![token_program];
+        emit!(ProgramLinked {
+            program: token_program,
+            is_presale: false,
+        });
         msg!("Token program set to: {}", token_program);
         Ok(())
     }
 
+    /// Registers an additional token program for governance to manage
+    ///
+    /// Transactions select a token program by index at queue time; the new
+    /// program's index is emitted in `TokenProgramAdded`. Index 0 is always the
+    /// primary program from `set_token_program`.
+    ///
+    /// # Parameters
+    /// - `ctx`: ManageTokenPrograms context (requires authorized signer)
+    /// - `program_id`: Token program to register (must not be default)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if the primary token program is not set
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not an authorized signer
+    /// - `GovernanceError::InvalidAccount` if `program_id` is default
+    /// - `GovernanceError::DuplicateTokenProgram` if `program_id` is already registered
+    /// - `GovernanceError::TooManyTokenPrograms` if all `MAX_TOKEN_PROGRAMS` slots are used
+    ///
+    /// # Events
+    /// - Emits `TokenProgramAdded`
+    pub fn add_token_program(ctx: Context<ManageTokenPrograms>, program_id: Pubkey) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            program_id != Pubkey::default(),
+            GovernanceError::InvalidAccount
+        );
+        // States linked before the registry existed hold only the primary program
+        if governance_state.token_programs.is_empty() {
+            let primary = governance_state.token_program;
+            governance_state.token_programs.push(primary);
+        }
+        require!(
+            !governance_state.token_programs.contains(&program_id),
+            GovernanceError::DuplicateTokenProgram
+        );
+        require!(
+            governance_state.token_programs.len() < GovernanceState::MAX_TOKEN_PROGRAMS,
+            GovernanceError::TooManyTokenPrograms
+        );
+
+        governance_state.token_programs.push(program_id);
+        let index = (governance_state.token_programs.len() - 1) as u8;
+
+        emit!(TokenProgramAdded {
+            program_id,
+            index,
+        });
+        msg!("Token program {} added at index {}", program_id, index);
+        Ok(())
+    }
+
+    /// Unregisters an additional token program
+    ///
+    /// The slot is cleared rather than removed and is never reused, so pending
+    /// transactions queued for this index fail instead of reaching another program.
+    /// The primary program (index 0) cannot be removed.
+    ///
+    /// # Parameters
+    /// - `ctx`: ManageTokenPrograms context (requires authorized signer)
+    /// - `program_id`: Token program to unregister
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not an authorized signer
+    /// - `GovernanceError::InvalidTokenProgramIndex` if `program_id` is the primary program
+    /// - `GovernanceError::InvalidAccount` if `program_id` is not registered
+    ///
+    /// # Events
+    /// - Emits `TokenProgramRemoved`
+    pub fn remove_token_program(ctx: Context<ManageTokenPrograms>, program_id: Pubkey) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            program_id != governance_state.token_program,
+            GovernanceError::InvalidTokenProgramIndex
+        );
+        let index = governance_state
+            .token_programs
+            .iter()
+            .position(|p| *p == program_id && program_id != Pubkey::default())
+            .ok_or(GovernanceError::InvalidAccount)?;
+
+        governance_state.token_programs[index] = Pubkey::default();
+
+        emit!(TokenProgramRemoved {
+            program_id,
+            index: index as u8,
+        });
+        msg!("Token program {} removed from index {}", program_id, index);
+        Ok(())
+    }
+
     /// Set the presale program address
     /// Sets the presale program address for CPI calls
     ///
@@ -207,6 +667,10 @@ pub mod governance {
         );
         governance_state.presale_program = presale_program;
         governance_state.presale_program_set = true;
+        emit!(ProgramLinked {
+            program: presale_program,
+            is_presale: true,
+        });
         msg!("Presale program set to: {}", presale_program);
         Ok(())
     }
@@ -219,6 +683,8 @@ pub mod governance {
     ///
     /// # Parameters
     /// - `ctx`: QueueUnpause context (requires authorized signer)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Context for approvers (max 128 bytes)
     ///
     /// # Returns
     /// - `Result<u64>`: Transaction ID if queued successfully
@@ -226,11 +692,13 @@ pub mod governance {
     /// # Errors
     /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
     /// - `GovernanceError::TokenProgramNotSet` if token program not configured
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
     ///
     /// # Security
     /// - Requires authorized signer to queue
     /// - Transaction must be approved and executed separately
-    pub fn queue_unpause(ctx: Context<QueueUnpause>) -> Result<u64> {
+    pub fn queue_unpause(ctx: Context<QueueUnpause>, token_program_index: u8, description: String) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
             governance_state.token_program_set,
@@ -241,6 +709,11 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
@@ -257,10 +730,21 @@ pub mod governance {
         transaction.data = vec![];
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
             "Transaction {} queued (unpause), will execute after {}",
@@ -279,6 +763,8 @@ pub mod governance {
     /// - `ctx`: QueueSetBlacklist context (requires authorized signer)
     /// - `account`: Address to blacklist/unblacklist (must not be default)
     /// - `value`: `true` to blacklist, `false` to unblacklist
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Context for approvers (max 128 bytes)
     ///
     /// # Returns
     /// - `Result<u64>`: Transaction ID if queued successfully
@@ -287,15 +773,21 @@ pub mod governance {
     /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
     /// - `GovernanceError::InvalidAccount` if account is default
     /// - `GovernanceError::InvalidDataLength` if data encoding fails
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::CannotBlacklistProtected` if blacklisting a protected address
     ///
     /// # Security
     /// - Requires authorized signer to queue
     /// - Validates account is not default
+    /// - Rejects blacklisting governance or presale vault PDAs
     /// - Validates data length (33 bytes: 32 for pubkey + 1 for bool)
     pub fn queue_set_blacklist(
         ctx: Context<QueueSetBlacklist>,
         account: Pubkey,
         value: bool,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -307,17 +799,27 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
         // Validate account is not default
         require!(
             account != Pubkey::default(),
             GovernanceError::InvalidAccount
         );
+        require!(
+            !value || !governance_state.is_protected_address(&governance_state.key(), &account),
+            GovernanceError::CannotBlacklistProtected
+        );
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::Blacklist, clock.unix_timestamp);
 
         let mut data = Vec::new();
         data.extend_from_slice(&account.to_bytes());
@@ -332,10 +834,21 @@ pub mod governance {
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
             "Transaction {} queued (blacklist {}: {}), will execute after {}",
@@ -347,11 +860,14 @@ pub mod governance {
         Ok(tx_id)
     }
 
-    /// Queue a transaction to set no sell limit
+    /// Queue a transaction to set no sell limit (optionally expiring at `expires_at`)
     pub fn queue_set_no_sell_limit(
         ctx: Context<QueueSetNoSellLimit>,
         account: Pubkey,
         value: bool,
+        expires_at: Option<i64>,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -363,6 +879,11 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
         // Validate account is not default
         require!(
             account != Pubkey::default(),
@@ -373,14 +894,23 @@ pub mod governance {
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::NoSellLimit, clock.unix_timestamp);
 
         let mut data = Vec::new();
         data.extend_from_slice(&account.to_bytes());
         data.push(if value { 1 } else { 0 });
+        // Time-bounded grants append the expiry (8 bytes)
+        if let Some(expiry) = expires_at {
+            require!(
+                value && expiry > execute_after,
+                GovernanceError::InvalidExemptionExpiry
+            );
+            data.extend_from_slice(&expiry.to_le_bytes());
+        }
         // Validate data length
         require!(
-            data.len() == 33,
+            data.len() == 33 || data.len() == 41,
             GovernanceError::InvalidDataLength
         );
 
@@ -393,10 +923,21 @@ pub mod governance {
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
             "Transaction {} queued (no sell limit {}: {}), will execute after {}",
@@ -413,6 +954,8 @@ pub mod governance {
         ctx: Context<QueueSetRestricted>,
         account: Pubkey,
         value: bool,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -424,6 +967,11 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
         // Validate account is not default
         require!(
             account != Pubkey::default(),
@@ -434,7 +982,8 @@ pub mod governance {
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::Restrict, clock.unix_timestamp);
 
         let mut data = Vec::new();
         data.extend_from_slice(&account.to_bytes());
@@ -449,10 +998,21 @@ pub mod governance {
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
             "Transaction {} queued (restrict {}: {}), will execute after {}",
@@ -469,6 +1029,8 @@ pub mod governance {
         ctx: Context<QueueSetLiquidityPool>,
         pool: Pubkey,
         value: bool,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -480,6 +1042,11 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
         // Validate pool is not default
         require!(
             pool != Pubkey::default(),
@@ -490,7 +1057,8 @@ pub mod governance {
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::Pair, clock.unix_timestamp);
 
         let mut data = Vec::new();
         data.extend_from_slice(&pool.to_bytes());
@@ -505,10 +1073,21 @@ pub mod governance {
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
             "Transaction {} queued (liquidity pool {}: {}), will execute after {}",
@@ -520,10 +1099,29 @@ pub mod governance {
         Ok(tx_id)
     }
 
-    /// Queue a transaction to set bridge address
-    pub fn queue_set_bridge_address(
-        ctx: Context<QueueSetBridgeAddress>,
-        bridge_address: Pubkey,
+    /// Queue a transaction to set several liquidity pools at once
+    ///
+    /// On execution, calls the token program's `set_liquidity_pool` for every entry.
+    /// The batch is atomic: if any entry fails, none are applied.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetLiquidityPools context
+    /// - `pools`: Pool addresses and their flags (1 to `MAX_POOL_BATCH` entries)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidBatchSize` if `pools` is empty or too long
+    /// - `GovernanceError::InvalidAccount` if a pool is the default pubkey
+    pub fn queue_set_liquidity_pools(
+        ctx: Context<QueueSetLiquidityPools>,
+        pools: Vec<PoolFlag>,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -535,9 +1133,17 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
-        // Validate bridge address is not default
         require!(
-            bridge_address != Pubkey::default(),
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        require!(
+            !pools.is_empty() && pools.len() <= GovernanceState::MAX_POOL_BATCH,
+            GovernanceError::InvalidBatchSize
+        );
+        require!(
+            pools.iter().all(|entry| entry.pool != Pubkey::default()),
             GovernanceError::InvalidAccount
         );
 
@@ -545,38 +1151,74 @@ pub mod governance {
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
-
-        let mut data = Vec::new();
-        data.extend_from_slice(&bridge_address.to_bytes());
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::PairBatch, clock.unix_timestamp);
+
+        // Same 33-byte layout as a Pair transaction, once per entry
+        let mut data = Vec::with_capacity(pools.len() * 33);
+        for entry in pools.iter() {
+            data.extend_from_slice(&entry.pool.to_bytes());
+            data.push(if entry.is_pool { 1 } else { 0 });
+        }
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::SetBridgeAddress;
+        transaction.tx_type = TransactionType::PairBatch;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = bridge_address;
+        transaction.target = pools[0].pool;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (set bridge address: {}), will execute after {}",
+            "Transaction {} queued (set {} liquidity pools), will execute after {}",
             tx_id,
-            bridge_address,
+            pools.len(),
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Queue a transaction to set bond address
-    pub fn queue_set_bond_address(
-        ctx: Context<QueueSetBondAddress>,
-        bond_address: Pubkey,
+    /// Queue a transaction to set the blacklist status of several addresses at once
+    ///
+    /// On execution, calls the token program's `set_blacklist` for every entry.
+    /// The batch is atomic: if any entry fails, none are applied.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetBlacklistBatch context
+    /// - `entries`: Addresses and their blacklist flags (1 to `MAX_BLACKLIST_BATCH` entries)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidBatchSize` if `entries` is empty or too long
+    /// - `GovernanceError::InvalidAccount` if an address is the default pubkey
+    /// - `GovernanceError::CannotBlacklistProtected` if blacklisting a protected address
+    pub fn queue_set_blacklist_batch(
+        ctx: Context<QueueSetBlacklistBatch>,
+        entries: Vec<BlacklistFlag>,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
@@ -588,62 +1230,100 @@ pub mod governance {
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
-        // Validate bond address is not default
         require!(
-            bond_address != Pubkey::default(),
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        require!(
+            !entries.is_empty() && entries.len() <= GovernanceState::MAX_BLACKLIST_BATCH,
+            GovernanceError::InvalidBatchSize
+        );
+        require!(
+            entries.iter().all(|entry| entry.account != Pubkey::default()),
             GovernanceError::InvalidAccount
         );
+        let governance_key = governance_state.key();
+        require!(
+            entries.iter().all(|entry| {
+                !entry.blacklisted || !governance_state.is_protected_address(&governance_key, &entry.account)
+            }),
+            GovernanceError::CannotBlacklistProtected
+        );
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
-
-        let mut data = Vec::new();
-        data.extend_from_slice(&bond_address.to_bytes());
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::BlacklistBatch, clock.unix_timestamp);
+
+        // Same 33-byte layout as a Blacklist transaction, once per entry
+        let mut data = Vec::with_capacity(entries.len() * 33);
+        for entry in entries.iter() {
+            data.extend_from_slice(&entry.account.to_bytes());
+            data.push(if entry.blacklisted { 1 } else { 0 });
+        }
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::SetBondAddress;
+        transaction.tx_type = TransactionType::BlacklistBatch;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = bond_address;
+        transaction.target = entries[0].account;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (set bond address: {}), will execute after {}",
+            "Transaction {} queued (blacklist {} addresses), will execute after {}",
             tx_id,
-            bond_address,
+            entries.len(),
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Queue a transaction to set treasury address
-    pub fn queue_set_treasury_address(
-        ctx: Context<QueueSetTreasuryAddress>,
-        treasury_address: Pubkey,
+    /// Queue a transaction to set bridge address
+    pub fn queue_set_bridge_address(
+        ctx: Context<QueueSetBridgeAddress>,
+        bridge_address: Pubkey,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
-            governance_state.presale_program_set,
-            GovernanceError::PresaleProgramNotSet
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
         // Enforce multisig at queue step
         require!(
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
-        // Validate treasury address is not default
         require!(
-            treasury_address != Pubkey::default(),
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        // Validate bridge address is not default
+        require!(
+            bridge_address != Pubkey::default(),
             GovernanceError::InvalidAccount
         );
 
@@ -651,980 +1331,6813 @@ pub mod governance {
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetBridgeAddress, clock.unix_timestamp);
 
         let mut data = Vec::new();
-        data.extend_from_slice(&treasury_address.to_bytes());
+        data.extend_from_slice(&bridge_address.to_bytes());
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::SetTreasuryAddress;
+        transaction.tx_type = TransactionType::SetBridgeAddress;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = treasury_address;
+        transaction.target = bridge_address;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (set treasury address: {}), will execute after {}",
+            "Transaction {} queued (set bridge address: {}), will execute after {}",
             tx_id,
-            treasury_address,
+            bridge_address,
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Queue a transaction to withdraw to treasury
-    pub fn queue_withdraw_to_treasury(
-        ctx: Context<QueueWithdrawToTreasury>,
-        amount: u64,
+    /// Queue a transaction to set bond address
+    pub fn queue_set_bond_address(
+        ctx: Context<QueueSetBondAddress>,
+        bond_address: Pubkey,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
         require!(
-            governance_state.presale_program_set,
-            GovernanceError::PresaleProgramNotSet
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
         // Enforce multisig at queue step
         require!(
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
-        // Validate amount is greater than 0
         require!(
-            amount > 0,
-            GovernanceError::InvalidAmount
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        // Validate bond address is not default
+        require!(
+            bond_address != Pubkey::default(),
+            GovernanceError::InvalidAccount
         );
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetBondAddress, clock.unix_timestamp);
 
         let mut data = Vec::new();
-        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&bond_address.to_bytes());
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::WithdrawToTreasury;
+        transaction.tx_type = TransactionType::SetBondAddress;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = Pubkey::default();
+        transaction.target = bond_address;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (withdraw to treasury: {}), will execute after {}",
+            "Transaction {} queued (set bond address: {}), will execute after {}",
             tx_id,
-            amount,
+            bond_address,
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Queues a transaction to change required approval threshold
+    /// Queue a transaction to set the token supply cap
     ///
-    /// Creates a queued transaction that will update the minimum number of approvals
-    /// required for transaction execution. This is a critical governance parameter.
+    /// On execution, calls the token program's `set_max_supply`, which only allows
+    /// lowering an existing cap and never below current supply.
     ///
     /// # Parameters
-    /// - `ctx`: QueueSetRequiredApprovals context (requires authorized signer)
-    /// - `required`: New required approval count (must be >= 2 and <= signers.len())
-    ///
-    /// # Returns
-    /// - `Result<u64>`: Transaction ID if queued successfully
+    /// - `ctx`: QueueSetMaxSupply context
+    /// - `cap`: New supply cap, or `None` to remove the cap
+    /// - `confirm_unlimited`: Must be true when `cap` is `None`
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
     ///
     /// # Errors
-    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
-    /// - `GovernanceError::RequiredApprovalsTooLow` if required < 2
-    /// - `GovernanceError::RequiredApprovalsTooHigh` if required > signers.len()
-    ///
-    /// # Security
-    /// - Requires authorized signer to queue
-    /// - Enforces minimum 2 approvals
-    /// - Prevents setting threshold higher than signer count
-    pub fn queue_set_required_approvals(
-        ctx: Context<QueueSetRequiredApprovals>,
-        required: u8,
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::MaxSupplyRemovalNotConfirmed` if removing the cap without confirmation
+    pub fn queue_set_max_supply(
+        ctx: Context<QueueSetMaxSupply>,
+        cap: Option<u64>,
+        confirm_unlimited: bool,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
         // Enforce multisig at queue step
         require!(
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
         require!(
-            required >= GovernanceState::MIN_REQUIRED_APPROVALS,
-            GovernanceError::RequiredApprovalsTooLow
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
         );
+        let token_program = governance_state.token_program_at(token_program_index)?;
         require!(
-            required <= governance_state.signers.len() as u8,
-            GovernanceError::RequiredApprovalsTooHigh
+            cap.is_some() || confirm_unlimited,
+            GovernanceError::MaxSupplyRemovalNotConfirmed
         );
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetMaxSupply, clock.unix_timestamp);
 
+        // Encoded as has_cap (1) + cap (8) + confirm_unlimited (1)
         let mut data = Vec::new();
-        data.push(required);
+        data.push(if cap.is_some() { 1 } else { 0 });
+        data.extend_from_slice(&cap.unwrap_or(0).to_le_bytes());
+        data.push(if confirm_unlimited { 1 } else { 0 });
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::SetRequiredApprovals;
+        transaction.tx_type = TransactionType::SetMaxSupply;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = Pubkey::default();
+        transaction.target = token_program;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (set required approvals to {}), will execute after {}",
+            "Transaction {} queued (set max supply: {:?}), will execute after {}",
             tx_id,
-            required,
+            cap,
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Queues a transaction to change cooldown period
+    /// Queue a transaction to set the token transfer fee
     ///
-    /// Creates a queued transaction that will update the minimum cooldown period
-    /// required before transaction execution. This is a critical governance parameter.
+    /// On execution, calls the token program's `set_transfer_fee`.
     ///
     /// # Parameters
-    /// - `ctx`: QueueSetCooldownPeriod context (requires authorized signer)
-    /// - `period`: New cooldown period in seconds (must be >= 1800 and <= MAX_COOLDOWN_SECONDS)
-    ///
-    /// # Returns
-    /// - `Result<u64>`: Transaction ID if queued successfully
+    /// - `ctx`: QueueSetTransferFee context
+    /// - `fee_bps`: Fee in basis points deducted from each transfer (0 disables the fee)
+    /// - `burn`: `true` to burn the fee instead of sending it to the treasury
+    /// - `treasury_address`: Owner of the token account receiving the fee (required unless burning)
+    /// - `whitelist_exempt`: `true` to skip the fee for whitelisted senders
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
     ///
     /// # Errors
-    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
-    /// - `GovernanceError::CooldownPeriodTooLow` if period < 1800 seconds
-    /// - `GovernanceError::CooldownPeriodTooHigh` if period > MAX_COOLDOWN_SECONDS
-    ///
-    /// # Security
-    /// - Requires authorized signer to queue
-    /// - Enforces minimum 30-minute cooldown
-    /// - Enforces maximum cooldown limit
-    pub fn queue_set_cooldown_period(
-        ctx: Context<QueueSetCooldownPeriod>,
-        period: i64,
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidTransferFee` if the fee is too high or has no destination
+    pub fn queue_set_transfer_fee(
+        ctx: Context<QueueSetTransferFee>,
+        fee_bps: u16,
+        burn: bool,
+        treasury_address: Pubkey,
+        whitelist_exempt: bool,
+        token_program_index: u8,
+        description: String,
     ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
         // Enforce multisig at queue step
         require!(
             governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
         require!(
-            period >= GovernanceState::MIN_COOLDOWN_SECONDS,
-            GovernanceError::CooldownPeriodTooLow
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
         );
+        let token_program = governance_state.token_program_at(token_program_index)?;
         require!(
-            period <= GovernanceState::MAX_COOLDOWN_SECONDS,
-            GovernanceError::CooldownPeriodTooHigh
+            fee_bps <= spl_project::TokenState::MAX_TRANSFER_FEE_BPS
+                && (fee_bps == 0 || burn || treasury_address != Pubkey::default()),
+            GovernanceError::InvalidTransferFee
         );
 
         let tx_id = governance_state.next_transaction_id;
         governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
-        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetTransferFee, clock.unix_timestamp);
 
+        // Encoded as fee_bps (2) + burn (1) + treasury_address (32) + whitelist_exempt (1)
         let mut data = Vec::new();
-        data.extend_from_slice(&period.to_le_bytes());
+        data.extend_from_slice(&fee_bps.to_le_bytes());
+        data.push(if burn { 1 } else { 0 });
+        data.extend_from_slice(treasury_address.as_ref());
+        data.push(if whitelist_exempt { 1 } else { 0 });
 
         let transaction = &mut ctx.accounts.transaction;
         transaction.id = tx_id;
-        transaction.tx_type = TransactionType::SetCooldownPeriod;
+        transaction.tx_type = TransactionType::SetTransferFee;
         transaction.status = TransactionStatus::Pending;
         transaction.initiator = ctx.accounts.initiator.key();
-        transaction.target = Pubkey::default();
+        transaction.target = token_program;
         transaction.data = data;
         transaction.timestamp = clock.unix_timestamp;
         transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
         transaction.approval_count = 0;
+        transaction.approval_weight = 0;
         transaction.approvals = vec![];
         transaction.rejection_reason = String::new();
         transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} queued (set cooldown period to {}s), will execute after {}",
+            "Transaction {} queued (set transfer fee: {} bps), will execute after {}",
             tx_id,
-            period,
+            fee_bps,
             execute_after
         );
         Ok(tx_id)
     }
 
-    /// Approve a transaction
-    /// Approves a queued transaction
+    /// Queue a transaction to allow governance operations during an emergency pause
     ///
-    /// Adds the caller's approval to a queued transaction. When enough approvals
-    /// are collected (meeting the required_approvals threshold), the transaction
-    /// can be executed after the cooldown period expires.
+    /// On execution, calls the token program's `set_governance_ops_during_pause`.
+    /// While enabled, queued mint, burn and seize transactions can execute with
+    /// the token paused; user transfers stay frozen.
     ///
     /// # Parameters
-    /// - `ctx`: ApproveTransaction context (requires authorized signer)
-    /// - `tx_id`: The transaction ID to approve
-    ///
-    /// # Returns
-    /// - `Result<()>`: Success if approval is added
+    /// - `ctx`: QueueSetGovernanceOpsDuringPause context
+    /// - `value`: `true` to let governance operations run while paused
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
     ///
     /// # Errors
-    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
-    /// - `GovernanceError::TransactionNotFound` if transaction doesn't exist
-    /// - `GovernanceError::TransactionAlreadyExecuted` if transaction already executed
-    /// - `GovernanceError::AlreadyApproved` if signer already approved
-    ///
-    /// # Security
-    /// - Reentrancy protection (checks status before modification)
-    /// - Prevents duplicate approvals
-    /// - Only authorized signers can approve
-    pub fn approve_transaction(ctx: Context<ApproveTransaction>, tx_id: u64) -> Result<()> {
-        let governance_state = &ctx.accounts.governance_state;
-        let transaction = &mut ctx.accounts.transaction;
-
-        require!(
-            transaction.id == tx_id,
-            GovernanceError::InvalidTransactionId
-        );
-        // Reentrancy guard - check transaction not already executed
-        require!(
-            transaction.status == TransactionStatus::Pending,
-            GovernanceError::TransactionNotPending
-        );
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    pub fn queue_set_governance_ops_during_pause(
+        ctx: Context<QueueSetGovernanceOpsDuringPause>,
+        value: bool,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
         require!(
-            !transaction.has_approved(ctx.accounts.approver.key()),
-            GovernanceError::AlreadyApproved
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
-        // Only authorized signers can approve
+        // Enforce multisig at queue step
         require!(
-            governance_state.is_authorized_signer(&ctx.accounts.approver.key()),
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
             GovernanceError::NotAuthorizedSigner
         );
-
-        transaction.add_approval(ctx.accounts.approver.key());
-
-        msg!(
-            "Transaction {} approved by {} ({} of {} required)",
-            tx_id,
-            ctx.accounts.approver.key(),
-            transaction.approval_count,
-            governance_state.required_approvals
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
         );
+        let token_program = governance_state.token_program_at(token_program_index)?;
 
-        // Execution should only occur via execute_transaction after cooldown expires
-        // Do not auto-execute or check cooldown here
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
 
-        Ok(())
-    }
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetGovernanceOpsDuringPause, clock.unix_timestamp);
 
-    /// Reject a transaction
-    pub fn reject_transaction(
-        ctx: Context<RejectTransaction>,
-        tx_id: u64,
-        reason: String,
-    ) -> Result<()> {
-        let governance_state = &ctx.accounts.governance_state;
         let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetGovernanceOpsDuringPause;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = vec![if value { 1 } else { 0 }];
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
 
-        // Enforce multisig - only authorized signers can reject
-        require!(
-            governance_state.is_authorized_signer(&ctx.accounts.approver.key()),
-            GovernanceError::NotAuthorizedSigner
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set governance ops during pause: {}), will execute after {}",
+            tx_id,
+            value,
+            execute_after
         );
+        Ok(tx_id)
+    }
 
+    /// Queue a transaction to change how the token tracks sell limits
+    ///
+    /// On execution, calls the token program's `set_sell_tracker_mode`. In
+    /// per-token-account mode, each source token account gets its own sell
+    /// tracker instead of sharing one per wallet.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetSellTrackerMode context
+    /// - `per_token_account`: `true` for `PerTokenAccount`, `false` for `PerWallet`
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    pub fn queue_set_sell_tracker_mode(
+        ctx: Context<QueueSetSellTrackerMode>,
+        per_token_account: bool,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
         require!(
-            transaction.id == tx_id,
-            GovernanceError::InvalidTransactionId
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
+        // Enforce multisig at queue step
         require!(
-            transaction.status == TransactionStatus::Pending,
-            GovernanceError::TransactionNotPending
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
         );
-        require!(!reason.is_empty(), GovernanceError::EmptyRejectionReason);
-        // Limit reason length to prevent log overflow
         require!(
-            reason.len() <= 256,
-            GovernanceError::EmptyRejectionReason
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
         );
+        let token_program = governance_state.token_program_at(token_program_index)?;
 
-        transaction.status = TransactionStatus::Rejected;
-        transaction.rejection_reason = reason.clone();
-        transaction.rejector = ctx.accounts.approver.key();
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetSellTrackerMode, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetSellTrackerMode;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = vec![if per_token_account { 1 } else { 0 }];
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
         msg!(
-            "Transaction {} rejected by {}: {}",
+            "Transaction {} queued (set sell tracker per token account: {}), will execute after {}",
             tx_id,
-            ctx.accounts.approver.key(),
-            reason
+            per_token_account,
+            execute_after
         );
-
-        Ok(())
+        Ok(tx_id)
     }
 
-    /// Execute a transaction (if cooldown expired and approved)
-    /// Executes a queued transaction after cooldown
+    /// Queue a transaction to turn the token's whitelist mode on or off
     ///
-    /// Executes a transaction that has received sufficient approvals and passed
-    /// the cooldown period. Performs actual CPI calls to apply state changes.
+    /// On execution, calls the token program's `toggle_whitelist_mode`. Disabling
+    /// must go through this queue; enabling can also be done immediately by any
+    /// signer with `emergency_enable_whitelist_mode`.
     ///
     /// # Parameters
-    /// - `ctx`: ExecuteTransaction context with all required accounts for CPI
-    /// - `tx_id`: The transaction ID to execute
-    ///
-    /// # Returns
-    /// - `Result<()>`: Success if transaction is executed
+    /// - `ctx`: QueueToggleWhitelistMode context
+    /// - `enabled`: `true` to restrict transfers to whitelisted addresses
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
     ///
     /// # Errors
-    /// - `GovernanceError::TransactionNotFound` if transaction doesn't exist
-    /// - `GovernanceError::TransactionAlreadyExecuted` if already executed
-    /// - `GovernanceError::InsufficientApprovals` if not enough approvals
-    /// - `GovernanceError::CooldownNotExpired` if cooldown period hasn't passed
-    ///
-    /// # Security
-    /// - Reentrancy protection (marks as executed immediately)
-    /// - Enforces cooldown period
-    /// - Validates approval count before execution
-    /// - Performs actual CPI calls to apply changes
-    pub fn execute_transaction(ctx: Context<ExecuteTransaction>, tx_id: u64) -> Result<()> {
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    pub fn queue_toggle_whitelist_mode(
+        ctx: Context<QueueToggleWhitelistMode>,
+        enabled: bool,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
         let governance_state = &mut ctx.accounts.governance_state;
-        let transaction = &mut ctx.accounts.transaction;
-
         require!(
-            transaction.id == tx_id,
-            GovernanceError::InvalidTransactionId
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
-        // Reentrancy guard - check transaction not already executed
+        // Enforce multisig at queue step
         require!(
-            transaction.status == TransactionStatus::Pending,
-            GovernanceError::TransactionNotPending
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
         );
-        // Mark as executing immediately to prevent reentrancy
-        transaction.status = TransactionStatus::Executed;
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        let token_program = governance_state.token_program_at(token_program_index)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
 
         let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::ToggleWhitelistMode, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::ToggleWhitelistMode;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = vec![if enabled { 1 } else { 0 }];
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (toggle whitelist mode: {}), will execute after {}",
+            tx_id,
+            enabled,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to configure the token circuit breaker
+    ///
+    /// On execution, calls the token program's `set_circuit_breaker`.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetCircuitBreaker context
+    /// - `enabled`: `true` to reject transfers above the threshold
+    /// - `threshold_bps`: Largest single transfer as basis points of current supply (50 to 10000)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidCircuitBreaker` if the threshold is out of range
+    pub fn queue_set_circuit_breaker(
+        ctx: Context<QueueSetCircuitBreaker>,
+        enabled: bool,
+        threshold_bps: u16,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
         require!(
-            clock.unix_timestamp >= transaction.execute_after,
-            GovernanceError::CooldownNotExpired
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
         );
+        // Enforce multisig at queue step
         require!(
-            transaction.approval_count >= governance_state.required_approvals,
-            GovernanceError::InsufficientApprovals
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        let token_program = governance_state.token_program_at(token_program_index)?;
+        require!(
+            (spl_project::TokenState::MIN_CIRCUIT_BREAKER_BPS..=10_000).contains(&threshold_bps),
+            GovernanceError::InvalidCircuitBreaker
         );
 
-        // Execute real CPI calls based on transaction type
-        match transaction.tx_type {
-            TransactionType::Unpause => {
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetEmergencyPause {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_emergency_pause(cpi_ctx, false)?;
-                msg!("Transaction {} executed: Unpause", tx_id);
-            }
-            TransactionType::Blacklist => {
-                if transaction.data.len() < 33 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
-                let value = transaction.data[32] != 0;
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
 
-                // Verify target account matches
-                require!(
-                    account_pubkey == ctx.accounts.target_account.key(),
-                    GovernanceError::InvalidAccount
-                );
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetCircuitBreaker, clock.unix_timestamp);
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetBlacklist {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    blacklist: ctx.accounts.blacklist_account.to_account_info(),
-                    account: ctx.accounts.target_account.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_blacklist(cpi_ctx, account_pubkey, value)?;
-                msg!("Transaction {} executed: Blacklist {} = {}", tx_id, account_pubkey, value);
-            }
-            TransactionType::NoSellLimit => {
-                if transaction.data.len() < 33 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
-                let value = transaction.data[32] != 0;
+        // Encoded as enabled (1) + threshold_bps (2)
+        let mut data = Vec::new();
+        data.push(if enabled { 1 } else { 0 });
+        data.extend_from_slice(&threshold_bps.to_le_bytes());
 
-                // Verify target account matches
-                require!(
-                    account_pubkey == ctx.accounts.target_account.key(),
-                    GovernanceError::InvalidAccount
-                );
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetCircuitBreaker;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetNoSellLimit {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    no_sell_limit: ctx.accounts.no_sell_limit_account.to_account_info(),
-                    account: ctx.accounts.target_account.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_no_sell_limit(cpi_ctx, account_pubkey, value)?;
-                msg!("Transaction {} executed: NoSellLimit {} = {}", tx_id, account_pubkey, value);
-            }
-            TransactionType::Restrict => {
-                if transaction.data.len() < 33 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
-                let value = transaction.data[32] != 0;
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
 
-                // Verify target account matches
-                require!(
-                    account_pubkey == ctx.accounts.target_account.key(),
-                    GovernanceError::InvalidAccount
-                );
+        msg!(
+            "Transaction {} queued (set circuit breaker: {} at {} bps), will execute after {}",
+            tx_id,
+            enabled,
+            threshold_bps,
+            execute_after
+        );
+        Ok(tx_id)
+    }
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetRestricted {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    restricted: ctx.accounts.restricted_account.to_account_info(),
-                    account: ctx.accounts.target_account.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_restricted(cpi_ctx, account_pubkey, value)?;
-                msg!("Transaction {} executed: Restrict {} = {}", tx_id, account_pubkey, value);
-            }
-            TransactionType::Pair => {
-                if transaction.data.len() < 33 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let pool_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
-                let value = transaction.data[32] != 0;
+    /// Queue a transaction to set treasury address
+    pub fn queue_set_treasury_address(
+        ctx: Context<QueueSetTreasuryAddress>,
+        treasury_address: Pubkey,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        // Validate treasury address is not default
+        require!(
+            treasury_address != Pubkey::default(),
+            GovernanceError::InvalidAccount
+        );
 
-                // Verify pool address matches
-                require!(
-                    pool_pubkey == ctx.accounts.pool_address.key(),
-                    GovernanceError::InvalidAccount
-                );
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetLiquidityPool {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    liquidity_pool: ctx.accounts.liquidity_pool_account.to_account_info(),
-                    pool: ctx.accounts.pool_address.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_liquidity_pool(cpi_ctx, pool_pubkey, value)?;
-                msg!("Transaction {} executed: LiquidityPool {} = {}", tx_id, pool_pubkey, value);
-            }
-            TransactionType::SetRequiredApprovals => {
-                if transaction.data.len() < 1 {
-                    return Err(GovernanceError::InvalidRequiredApprovals.into());
-                }
-                let required = transaction.data[0];
-                require!(
-                    required >= GovernanceState::MIN_REQUIRED_APPROVALS,
-                    GovernanceError::RequiredApprovalsTooLow
-                );
-                require!(
-                    required <= governance_state.signers.len() as u8,
-                    GovernanceError::RequiredApprovalsTooHigh
-                );
-                governance_state.required_approvals = required;
-                msg!(
-                    "Transaction {} executed: SetRequiredApprovals = {}",
-                    tx_id,
-                    required
-                );
-            }
-            TransactionType::SetCooldownPeriod => {
-                if transaction.data.len() < 8 {
-                    return Err(GovernanceError::InvalidCooldownPeriod.into());
-                }
-                let period = i64::from_le_bytes(
-                    transaction.data[0..8]
-                        .try_into()
-                        .map_err(|_| GovernanceError::InvalidCooldownPeriod)?,
-                );
-                require!(
-                    period >= GovernanceState::MIN_COOLDOWN_SECONDS,
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetTreasuryAddress, clock.unix_timestamp);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&treasury_address.to_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetTreasuryAddress;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = treasury_address;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set treasury address: {}), will execute after {}",
+            tx_id,
+            treasury_address,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to configure presale vesting
+    ///
+    /// On execution, calls the presale program's `set_vesting_config`. Only purchases
+    /// made after execution are affected; existing vesting schedules keep their terms.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetVestingConfig context
+    /// - `enabled`: Whether presale purchases vest instead of being delivered immediately
+    /// - `cliff_seconds`: Time after a buyer's first purchase before anything is claimable
+    /// - `duration_seconds`: Total linear vesting duration
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidVestingConfig` if enabled with a zero or too long
+    ///   duration, or a cliff longer than the duration
+    pub fn queue_set_vesting_config(
+        ctx: Context<QueueSetVestingConfig>,
+        enabled: bool,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        // Mirror the presale program's checks so a bad config fails at queue time
+        if enabled {
+            require!(
+                duration_seconds > 0
+                    && duration_seconds <= presale::MAX_VESTING_DURATION_SECONDS
+                    && cliff_seconds <= duration_seconds,
+                GovernanceError::InvalidVestingConfig
+            );
+        }
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetVestingConfig, clock.unix_timestamp);
+
+        let mut data = Vec::new();
+        data.push(if enabled { 1 } else { 0 });
+        data.extend_from_slice(&cliff_seconds.to_le_bytes());
+        data.extend_from_slice(&duration_seconds.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetVestingConfig;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set vesting config: enabled {}, cliff {}s, duration {}s), will execute after {}",
+            tx_id,
+            enabled,
+            cliff_seconds,
+            duration_seconds,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to change the presale token price
+    ///
+    /// On execution, calls the presale program's `set_token_price_usd`, so price
+    /// changes made through governance are subject to the cooldown.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueUpdateTokenPrice context
+    /// - `new_price_usd_micro`: New token price in micro-USD (must be non-zero)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidAmount` if the price is zero
+    ///
+    /// # Events
+    /// - Emits `PriceUpdateQueued` with the current and proposed price
+    pub fn queue_update_token_price(
+        ctx: Context<QueueUpdateTokenPrice>,
+        new_price_usd_micro: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(new_price_usd_micro > 0, GovernanceError::InvalidAmount);
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::UpdateTokenPrice, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::UpdateTokenPrice;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = new_price_usd_micro.to_le_bytes().to_vec();
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+        emit!(PriceUpdateQueued {
+            old_price: ctx.accounts.presale_state.token_price_usd_micro,
+            new_price: new_price_usd_micro,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (update token price: {} micro-USD), will execute after {}",
+            tx_id,
+            new_price_usd_micro,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to start the presale
+    ///
+    /// On execution, calls the presale program's `start_presale` with the governance
+    /// PDA as authority, so a single signer cannot start the sale.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueStartPresale context
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Events
+    /// - Emits `PresaleStartQueued`
+    pub fn queue_start_presale(ctx: Context<QueueStartPresale>, description: String) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::StartPresale, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::StartPresale;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = vec![];
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+        emit!(PresaleStartQueued {
+            tx_id,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (start presale), will execute after {}",
+            tx_id,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to stop the presale
+    ///
+    /// On execution, calls the presale program's `stop_presale` with the governance
+    /// PDA as authority, so a single signer cannot stop the sale.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueStopPresale context
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Events
+    /// - Emits `PresaleStopQueued`
+    pub fn queue_stop_presale(ctx: Context<QueueStopPresale>, description: String) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::StopPresale, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::StopPresale;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = vec![];
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+        emit!(PresaleStopQueued {
+            tx_id,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (stop presale), will execute after {}",
+            tx_id,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to change the presale cap
+    ///
+    /// On execution, calls the presale program's `update_presale_cap` with the governance
+    /// PDA as authority. The value is re-validated against the presale state at
+    /// execution time, since sales may have moved on while the transaction waited.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueUpdatePresaleCap context
+    /// - `new_cap`: New presale cap in the cap's unit (0 = unlimited)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_update_presale_cap(
+        ctx: Context<QueueUpdatePresaleCap>,
+        new_cap: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::UpdatePresaleCap, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::UpdatePresaleCap;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = new_cap.to_le_bytes().to_vec();
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (update presale cap: {}), will execute after {}",
+            tx_id,
+            new_cap,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to change the presale per-user limit
+    ///
+    /// On execution, calls the presale program's `update_max_per_user` with the governance
+    /// PDA as authority. The value is re-validated against the presale state at
+    /// execution time, since sales may have moved on while the transaction waited.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueUpdateMaxPerUser context
+    /// - `new_max`: New per-user purchase limit in token base units (0 = unlimited)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_update_max_per_user(
+        ctx: Context<QueueUpdateMaxPerUser>,
+        new_max: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::UpdateMaxPerUser, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::UpdateMaxPerUser;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = new_max.to_le_bytes().to_vec();
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (update max per user: {}), will execute after {}",
+            tx_id,
+            new_max,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to set the presale price tiers
+    ///
+    /// On execution, calls the presale program's `set_price_tiers` with the governance
+    /// PDA as authority. Tier `i` charges `prices_usd_micro[i]` once the presale has
+    /// sold `sold_thresholds[i]` tokens. Empty lists disable tiered pricing.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetPriceTiers context
+    /// - `sold_thresholds`: Tier thresholds in token base units, strictly ascending
+    /// - `prices_usd_micro`: Tier prices in micro-USD, one per threshold
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidAmount` if the lists differ in length, exceed
+    ///   `presale::MAX_PRICE_TIERS`, are not ascending, or contain a zero price
+    pub fn queue_set_price_tiers(
+        ctx: Context<QueueSetPriceTiers>,
+        sold_thresholds: Vec<u64>,
+        prices_usd_micro: Vec<u64>,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            sold_thresholds.len() == prices_usd_micro.len()
+                && sold_thresholds.len() <= presale::MAX_PRICE_TIERS,
+            GovernanceError::InvalidAmount
+        );
+        require!(
+            prices_usd_micro.iter().all(|&price| price > 0)
+                && sold_thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetPriceTiers, clock.unix_timestamp);
+
+        let mut data = Vec::with_capacity(sold_thresholds.len() * 16);
+        for (threshold, price) in sold_thresholds.iter().zip(prices_usd_micro.iter()) {
+            data.extend_from_slice(&threshold.to_le_bytes());
+            data.extend_from_slice(&price.to_le_bytes());
+        }
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetPriceTiers;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set {} price tiers), will execute after {}",
+            tx_id,
+            sold_thresholds.len(),
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to set the presale per-transaction purchase limits
+    ///
+    /// On execution, calls the presale program's `set_max_per_transaction` and
+    /// `set_min_purchase_amount`, replacing both limits together.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetPurchaseLimits context
+    /// - `min_purchase_amount`: Minimum tokens per purchase in base units (0 = no minimum)
+    /// - `max_per_transaction`: Maximum tokens per purchase in base units (0 = unlimited)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::PresaleProgramNotSet` if presale program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidAmount` if the minimum exceeds the maximum
+    pub fn queue_set_purchase_limits(
+        ctx: Context<QueueSetPurchaseLimits>,
+        min_purchase_amount: u64,
+        max_per_transaction: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        if max_per_transaction > 0 {
+            require!(
+                min_purchase_amount <= max_per_transaction,
+                GovernanceError::InvalidAmount
+            );
+        }
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetPurchaseLimits, clock.unix_timestamp);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&min_purchase_amount.to_le_bytes());
+        data.extend_from_slice(&max_per_transaction.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetPurchaseLimits;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = governance_state.presale_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set purchase limits: min {}, max per tx {}), will execute after {}",
+            tx_id,
+            min_purchase_amount,
+            max_per_transaction,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to withdraw to treasury
+    /// `amount == u64::MAX` sweeps the entire vault balance at execution time
+    pub fn queue_withdraw_to_treasury(
+        ctx: Context<QueueWithdrawToTreasury>,
+        amount: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.presale_program_set,
+            GovernanceError::PresaleProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        // Validate amount is greater than 0
+        require!(
+            amount > 0,
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::WithdrawToTreasury;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (withdraw to treasury: {}), will execute after {}",
+            tx_id,
+            amount,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to burn tokens from a token account
+    ///
+    /// Creates a queued transaction that burns `amount` tokens from `from_token_account`
+    /// via CPI to the token program. Used for clawback of compromised supply without
+    /// requiring an EOA holding the token authority key.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueBurnTokens context (requires authorized signer)
+    /// - `from_token_account`: Token account to burn from
+    /// - `amount`: Amount of tokens to burn (must be > 0)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not configured
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidAccount` if from_token_account is default
+    /// - `GovernanceError::InvalidAmount` if amount is 0
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    ///
+    /// # Events
+    /// - Emits `BurnQueued` with the token account, amount and transaction ID
+    pub fn queue_burn_tokens(
+        ctx: Context<QueueBurnTokens>,
+        from_token_account: Pubkey,
+        amount: u64,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        require!(
+            from_token_account != Pubkey::default(),
+            GovernanceError::InvalidAccount
+        );
+        require!(
+            amount > 0,
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&from_token_account.to_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::BurnTokens;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = from_token_account;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+        emit!(BurnQueued {
+            from: from_token_account,
+            amount,
+            tx_id,
+        });
+
+        msg!(
+            "Transaction {} queued (burn {} tokens from {}), will execute after {}",
+            tx_id,
+            amount,
+            from_token_account,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to seize tokens from a blacklisted holder
+    ///
+    /// Creates a queued transaction that moves `amount` tokens from
+    /// `from_token_account` to `to_token_account` via the token program's
+    /// `governance_transfer`, e.g. to satisfy a legal custody request. The token
+    /// program rejects the transfer unless the source owner is blacklisted.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSeize context (requires authorized signer)
+    /// - `from_token_account`: Token account to seize from
+    /// - `to_token_account`: Custody token account to receive the tokens
+    /// - `amount`: Amount of tokens to move (must be > 0)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not configured
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidAccount` if either account is default or they are the same
+    /// - `GovernanceError::InvalidAmount` if amount is 0
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    pub fn queue_seize(
+        ctx: Context<QueueSeize>,
+        from_token_account: Pubkey,
+        to_token_account: Pubkey,
+        amount: u64,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        require!(
+            from_token_account != Pubkey::default()
+                && to_token_account != Pubkey::default()
+                && from_token_account != to_token_account,
+            GovernanceError::InvalidAccount
+        );
+        require!(
+            amount > 0,
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        // Encoded as from_token_account (32) + to_token_account (32) + amount (8)
+        let mut data = Vec::new();
+        data.extend_from_slice(&from_token_account.to_bytes());
+        data.extend_from_slice(&to_token_account.to_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::Seize;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = from_token_account;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (seize {} tokens from {} to {}), will execute after {}",
+            tx_id,
+            amount,
+            from_token_account,
+            to_token_account,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to mint tokens to a token account
+    ///
+    /// Creates a queued transaction that mints `amount` tokens to
+    /// `recipient_token_account` via CPI to the token program, so minting goes
+    /// through the same approval and cooldown flow as every other operation.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueMintTokens context (requires authorized signer)
+    /// - `recipient_token_account`: Token account to mint to
+    /// - `amount`: Amount of tokens to mint (must be > 0)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not configured
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidAccount` if recipient_token_account is default
+    /// - `GovernanceError::InvalidAmount` if amount is 0
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    ///
+    /// # Events
+    /// - Emits `MintQueued` with the token account, amount and transaction ID
+    pub fn queue_mint_tokens(
+        ctx: Context<QueueMintTokens>,
+        recipient_token_account: Pubkey,
+        amount: u64,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.token_program_at(token_program_index)?;
+        require!(
+            recipient_token_account != Pubkey::default(),
+            GovernanceError::InvalidAccount
+        );
+        require!(
+            amount > 0,
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&recipient_token_account.to_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::Mint;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = recipient_token_account;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+        emit!(MintQueued {
+            recipient: recipient_token_account,
+            amount,
+            tx_id,
+        });
+
+        msg!(
+            "Transaction {} queued (mint {} tokens to {}), will execute after {}",
+            tx_id,
+            amount,
+            recipient_token_account,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to mint tokens that vest to a beneficiary
+    ///
+    /// On execution, calls the token program's `mint_with_vesting`, which mints
+    /// `amount` into the beneficiary's vesting vault. The schedule starts when the
+    /// transaction executes, not when it is queued.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueMintWithVesting context
+    /// - `beneficiary`: Wallet allowed to claim the vested tokens
+    /// - `amount`: Tokens (base units) to lock (must be > 0)
+    /// - `cliff_seconds`: Time before anything unlocks (at most `duration_seconds`)
+    /// - `duration_seconds`: Time until everything has unlocked (must be > 0)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidAccount` if beneficiary is default
+    /// - `GovernanceError::InvalidAmount` if amount is 0
+    /// - `GovernanceError::InvalidVestingConfig` if the duration is 0 or the cliff is out of range
+    #[allow(clippy::too_many_arguments)]
+    pub fn queue_mint_with_vesting(
+        ctx: Context<QueueMintWithVesting>,
+        beneficiary: Pubkey,
+        amount: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        let token_program = governance_state.token_program_at(token_program_index)?;
+
+        // Encoded as beneficiary (32) + amount (8) + cliff_seconds (8) + duration_seconds (8)
+        let mut data = Vec::new();
+        data.extend_from_slice(&beneficiary.to_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&cliff_seconds.to_le_bytes());
+        data.extend_from_slice(&duration_seconds.to_le_bytes());
+        governance_state.validate_transaction_data(TransactionType::MintWithVesting, &data, None)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::MintWithVesting, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::MintWithVesting;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (mint {} tokens vesting to {} over {}s, cliff {}s), will execute after {}",
+            tx_id,
+            amount,
+            beneficiary,
+            duration_seconds,
+            cliff_seconds,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queue a transaction to set the token emission schedule
+    ///
+    /// On execution, calls the token program's `set_emission_schedule`. Accrual
+    /// starts at `start_time`, or at execution if that is later; a rate of 0
+    /// stops emissions.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetEmissionSchedule context
+    /// - `rate_per_second`: Tokens (base units) emitted per second
+    /// - `recipient`: Token account receiving emissions
+    /// - `start_time`: Time accrual starts
+    /// - `end_time`: Time accrual stops (must be after `start_time`)
+    /// - `token_program_index`: Token program to act on (0 = primary, see `add_token_program`)
+    /// - `description`: Free-text note for approvers (max 128 bytes)
+    ///
+    /// # Errors
+    /// - `GovernanceError::TokenProgramNotSet` if token program not set
+    /// - `GovernanceError::NotAuthorizedSigner` if initiator is not an authorized signer
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    /// - `GovernanceError::InvalidTokenProgramIndex` if no token program is registered at `token_program_index`
+    /// - `GovernanceError::InvalidEmissionSchedule` if the period is empty or the recipient is default
+    #[allow(clippy::too_many_arguments)]
+    pub fn queue_set_emission_schedule(
+        ctx: Context<QueueSetEmissionSchedule>,
+        rate_per_second: u64,
+        recipient: Pubkey,
+        start_time: i64,
+        end_time: i64,
+        token_program_index: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        let token_program = governance_state.token_program_at(token_program_index)?;
+
+        // Encoded as rate_per_second (8) + recipient (32) + start_time (8) + end_time (8)
+        let mut data = Vec::new();
+        data.extend_from_slice(&rate_per_second.to_le_bytes());
+        data.extend_from_slice(&recipient.to_bytes());
+        data.extend_from_slice(&start_time.to_le_bytes());
+        data.extend_from_slice(&end_time.to_le_bytes());
+        governance_state.validate_transaction_data(TransactionType::SetEmissionSchedule, &data, None)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp
+            + governance_state.queue_cooldown(TransactionType::SetEmissionSchedule, clock.unix_timestamp);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetEmissionSchedule;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = token_program;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = token_program_index;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (emit {} per second to {} until {}), will execute after {}",
+            tx_id,
+            rate_per_second,
+            recipient,
+            end_time,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change required approval threshold
+    ///
+    /// Creates a queued transaction that will update the minimum number of approvals
+    /// required for transaction execution. This is a critical governance parameter.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetRequiredApprovals context (requires authorized signer)
+    /// - `required`: New required approval weight (must be >= 2 and <= total signer weight)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::RequiredApprovalsTooLow` if required < 2
+    /// - `GovernanceError::RequiredApprovalsTooHigh` if required > total signer weight
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Requires authorized signer to queue
+    /// - Enforces minimum 2 approvals
+    /// - Prevents setting threshold higher than signer count
+    pub fn queue_set_required_approvals(
+        ctx: Context<QueueSetRequiredApprovals>,
+        required: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            required >= GovernanceState::MIN_REQUIRED_APPROVALS,
+            GovernanceError::RequiredApprovalsTooLow
+        );
+        require!(
+            required as u16 <= governance_state.total_signer_weight(),
+            GovernanceError::RequiredApprovalsTooHigh
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.push(required);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetRequiredApprovals;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set required approvals to {}), will execute after {}",
+            tx_id,
+            required,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change cooldown period
+    ///
+    /// Creates a queued transaction that will update the minimum cooldown period
+    /// required before transaction execution. This is a critical governance parameter.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetCooldownPeriod context (requires authorized signer)
+    /// - `period`: New cooldown period in seconds (must be >= 1800 and <= MAX_COOLDOWN_SECONDS)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::CooldownPeriodTooLow` if period < 1800 seconds
+    /// - `GovernanceError::CooldownPeriodTooHigh` if period > MAX_COOLDOWN_SECONDS
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Requires authorized signer to queue
+    /// - Enforces minimum 30-minute cooldown
+    /// - Enforces maximum cooldown limit
+    pub fn queue_set_cooldown_period(
+        ctx: Context<QueueSetCooldownPeriod>,
+        period: i64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            period >= GovernanceState::MIN_COOLDOWN_SECONDS,
+            GovernanceError::CooldownPeriodTooLow
+        );
+        require!(
+            period <= GovernanceState::MAX_COOLDOWN_SECONDS,
+            GovernanceError::CooldownPeriodTooHigh
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&period.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetCooldownPeriod;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set cooldown period to {}s), will execute after {}",
+            tx_id,
+            period,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change the high-value withdrawal policy
+    ///
+    /// Treasury withdrawals above `threshold` require `required` approvals
+    /// instead of the standard `required_approvals`, scaling the quorum to the
+    /// amount at risk.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetHighValuePolicy context (requires authorized signer)
+    /// - `threshold`: Withdrawal amount above which the higher quorum applies (0 = disabled)
+    /// - `required`: Approvals required for high-value withdrawals
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::RequiredApprovalsTooLow` if required < required_approvals
+    /// - `GovernanceError::RequiredApprovalsTooHigh` if required > number of signers
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_set_high_value_policy(
+        ctx: Context<QueueSetHighValuePolicy>,
+        threshold: u64,
+        required: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.validate_high_value_policy(threshold, required)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&threshold.to_le_bytes());
+        data.push(required);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetHighValuePolicy;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (high-value policy: {} approvals above {}), will execute after {}",
+            tx_id,
+            required,
+            threshold,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to override the required approvals for one transaction type
+    ///
+    /// Lets riskier types (e.g. treasury withdrawals) demand a larger quorum than
+    /// routine ones. Types without an override use `required_approvals`.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetTypeApprovals context (requires authorized signer)
+    /// - `tx_type`: Transaction type to configure
+    /// - `required`: Approvals required for `tx_type` (0 = remove the override)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::RequiredApprovalsTooLow` if required is below the minimum of 2
+    /// - `GovernanceError::RequiredApprovalsTooHigh` if required exceeds the signers' total weight
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_set_type_approvals(
+        ctx: Context<QueueSetTypeApprovals>,
+        tx_type: TransactionType,
+        required: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.validate_type_approvals(required)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let data = vec![tx_type as u8, required];
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetTypeApprovals;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (type approvals: {:?} = {}), will execute after {}",
+            tx_id,
+            tx_type,
+            required,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change the transaction expiry window
+    ///
+    /// Transactions must be executed within `expiry_window` seconds after their
+    /// cooldown ends, otherwise they expire and can no longer be executed.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetExpiryWindow context (requires authorized signer)
+    /// - `window`: New expiry window in seconds (between 1 and 90 days)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidExpiryWindow` if window is out of bounds
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_set_expiry_window(
+        ctx: Context<QueueSetExpiryWindow>,
+        window: i64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            (GovernanceState::MIN_EXPIRY_WINDOW_SECONDS..=GovernanceState::MAX_EXPIRY_WINDOW_SECONDS)
+                .contains(&window),
+            GovernanceError::InvalidExpiryWindow
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&window.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetExpiryWindow;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set expiry window to {}s), will execute after {}",
+            tx_id,
+            window,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change the approval cooldown
+    ///
+    /// Approvals are rejected until `approval_cooldown` seconds after a transaction
+    /// is queued, separately from the execution cooldown.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetApprovalCooldown context (requires authorized signer)
+    /// - `cooldown`: New approval cooldown in seconds (0 to disable, at most 1 day)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidCooldownPeriod` if cooldown is out of bounds
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_set_approval_cooldown(
+        ctx: Context<QueueSetApprovalCooldown>,
+        cooldown: i64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            (0..=GovernanceState::MAX_APPROVAL_COOLDOWN_SECONDS).contains(&cooldown),
+            GovernanceError::InvalidCooldownPeriod
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&cooldown.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetApprovalCooldown;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set approval cooldown to {}s), will execute after {}",
+            tx_id,
+            cooldown,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change the keeper reward
+    ///
+    /// Whoever executes a ready transaction and passes themselves as `executor`
+    /// is paid `keeper_reward` lamports from the governance PDA's balance above
+    /// rent exemption. The reserve is funded by transferring SOL to the PDA.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetKeeperReward context (requires authorized signer)
+    /// - `reward`: Lamports paid per execution (0 to disable, at most `MAX_KEEPER_REWARD_LAMPORTS`)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidAmount` if reward exceeds the cap
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    pub fn queue_set_keeper_reward(
+        ctx: Context<QueueSetKeeperReward>,
+        reward: u64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            reward <= GovernanceState::MAX_KEEPER_REWARD_LAMPORTS,
+            GovernanceError::InvalidAmount
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&reward.to_le_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetKeeperReward;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set keeper reward to {} lamports), will execute after {}",
+            tx_id,
+            reward,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to add a multisig signer
+    ///
+    /// Creates a queued transaction that appends `signer` to the authorized signer
+    /// list. Used to rotate in a replacement for a lost or compromised key.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueAddSigner context (requires authorized signer)
+    /// - `signer`: Address to add as an authorized signer
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidAccount` if signer is default
+    /// - `GovernanceError::DuplicateSigners` if signer is already authorized
+    /// - `GovernanceError::TooManySigners` if signer list is full
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Goes through the normal approval + cooldown flow
+    /// - Limits are re-checked at execution time
+    pub fn queue_add_signer(
+        ctx: Context<QueueAddSigner>,
+        signer: Pubkey,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            signer != Pubkey::default(),
+            GovernanceError::InvalidAccount
+        );
+        require!(
+            !governance_state.is_primary_signer(&signer),
+            GovernanceError::DuplicateSigners
+        );
+        require!(
+            governance_state.signers.len() < GovernanceState::MAX_SIGNERS,
+            GovernanceError::TooManySigners
+        );
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&signer.to_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::AddSigner;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = signer;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (add signer: {}), will execute after {}",
+            tx_id,
+            signer,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to remove a multisig signer
+    ///
+    /// Creates a queued transaction that removes `signer` from the authorized
+    /// signer list.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueRemoveSigner context (requires authorized signer)
+    /// - `signer`: Authorized signer to remove
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::SignerNotFound` if signer is not authorized
+    /// - `GovernanceError::RequiredApprovalsTooHigh` if removal would leave fewer
+    ///   signers than required approvals
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Goes through the normal approval + cooldown flow
+    /// - Limits are re-checked at execution time
+    pub fn queue_remove_signer(
+        ctx: Context<QueueRemoveSigner>,
+        signer: Pubkey,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        require!(
+            governance_state.is_primary_signer(&signer),
+            GovernanceError::SignerNotFound
+        );
+        governance_state.validate_signer_removal(&signer)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&signer.to_bytes());
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::RemoveSigner;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = signer;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (remove signer: {}), will execute after {}",
+            tx_id,
+            signer,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to designate backup signers
+    ///
+    /// Backups become authorized signers only after no primary signer has approved
+    /// or executed a transaction for `activation_period` seconds. Intended for
+    /// governance continuity if the primary multisig loses its keys.
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetBackupSigners context (requires authorized signer)
+    /// - `backup_signers`: Backup addresses (empty to remove all backups)
+    /// - `activation_period`: Primary inactivity, in seconds, before backups activate
+    ///   (`MIN_BACKUP_ACTIVATION_SECONDS` to `MAX_BACKUP_ACTIVATION_SECONDS`)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::InvalidBackupSigners` if the list is too long, has duplicates,
+    ///   primary signers or the default address, is too small to reach the quorum, or
+    ///   the activation period is out of range
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Goes through the normal approval + cooldown flow (no bootstrap exemption)
+    /// - Limits are re-checked at execution time
+    pub fn queue_set_backup_signers(
+        ctx: Context<QueueSetBackupSigners>,
+        backup_signers: Vec<Pubkey>,
+        activation_period: i64,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.validate_backup_signers(&backup_signers, activation_period)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        // Encoded as activation_period (8) + 32 bytes per backup signer
+        let mut data = Vec::new();
+        data.extend_from_slice(&activation_period.to_le_bytes());
+        for backup in backup_signers.iter() {
+            data.extend_from_slice(&backup.to_bytes());
+        }
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetBackupSigners;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = Pubkey::default();
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set {} backup signers), will execute after {}",
+            tx_id,
+            backup_signers.len(),
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Queues a transaction to change a signer's voting weight
+    ///
+    /// Creates a queued transaction that sets how much `signer`'s approval counts
+    /// toward `required_approvals` (e.g. a foundation seat worth 2).
+    ///
+    /// # Parameters
+    /// - `ctx`: QueueSetSignerWeight context (requires authorized signer)
+    /// - `signer`: Authorized signer whose weight changes
+    /// - `weight`: New voting weight (must be >= 1)
+    /// - `description`: Context for approvers (max 128 bytes)
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Transaction ID if queued successfully
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::SignerNotFound` if signer is not authorized
+    /// - `GovernanceError::InvalidSignerWeight` if weight is 0
+    /// - `GovernanceError::RequiredApprovalsTooHigh` if the total weight would drop
+    ///   below the required approvals
+    /// - `GovernanceError::DescriptionTooLong` if description exceeds 128 bytes
+    ///
+    /// # Security
+    /// - Goes through the normal approval + cooldown flow
+    /// - Limits are re-checked at execution time
+    pub fn queue_set_signer_weight(
+        ctx: Context<QueueSetSignerWeight>,
+        signer: Pubkey,
+        weight: u8,
+        description: String,
+    ) -> Result<u64> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        // Enforce multisig at queue step
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.initiator.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            description.len() <= Transaction::MAX_DESCRIPTION_LEN,
+            GovernanceError::DescriptionTooLong
+        );
+        governance_state.validate_signer_weight(&signer, weight)?;
+
+        let tx_id = governance_state.next_transaction_id;
+        governance_state.next_transaction_id += 1;
+
+        let clock = Clock::get()?;
+        let execute_after = clock.unix_timestamp + governance_state.cooldown_period;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&signer.to_bytes());
+        data.push(weight);
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.id = tx_id;
+        transaction.tx_type = TransactionType::SetSignerWeight;
+        transaction.status = TransactionStatus::Pending;
+        transaction.initiator = ctx.accounts.initiator.key();
+        transaction.target = signer;
+        transaction.data = data;
+        transaction.timestamp = clock.unix_timestamp;
+        transaction.execute_after = execute_after;
+        transaction.expires_at = execute_after + governance_state.effective_expiry_window();
+        transaction.approval_count = 0;
+        transaction.approval_weight = 0;
+        transaction.approvals = vec![];
+        transaction.rejection_reason = String::new();
+        transaction.rejector = Pubkey::default();
+        transaction.description = description;
+        transaction.token_program_index = 0;
+
+        emit!(TransactionQueued {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+            initiator: transaction.initiator,
+            execute_after,
+        });
+
+        msg!(
+            "Transaction {} queued (set signer weight: {} = {}), will execute after {}",
+            tx_id,
+            signer,
+            weight,
+            execute_after
+        );
+        Ok(tx_id)
+    }
+
+    /// Approve a transaction
+    /// Approves a queued transaction
+    ///
+    /// Adds the caller's approval, weighted by their signer weight, to a queued
+    /// transaction. When the summed weight meets the required_approvals threshold,
+    /// the transaction can be executed after the cooldown period expires.
+    ///
+    /// # Parameters
+    /// - `ctx`: ApproveTransaction context (requires authorized signer)
+    /// - `tx_id`: The transaction ID to approve
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if approval is added
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is not authorized
+    /// - `GovernanceError::TransactionNotFound` if transaction doesn't exist
+    /// - `GovernanceError::TransactionAlreadyExecuted` if transaction already executed
+    /// - `GovernanceError::AlreadyApproved` if signer already approved
+    /// - `GovernanceError::ApprovalTooEarly` if the approval cooldown has not elapsed
+    ///
+    /// # Security
+    /// - Reentrancy protection (checks status before modification)
+    /// - Prevents duplicate approvals
+    /// - Only authorized signers can approve (backups only once active)
+    /// - Approvals by primary signers refresh `last_activity`
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>, tx_id: u64) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(
+            transaction.id == tx_id,
+            GovernanceError::InvalidTransactionId
+        );
+        // Reentrancy guard - check transaction not already executed
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            GovernanceError::TransactionNotPending
+        );
+        require!(
+            !transaction.has_approved(ctx.accounts.approver.key()),
+            GovernanceError::AlreadyApproved
+        );
+        // Only authorized signers can approve
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.approver.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        // Give honest signers time to review (and reject) before approvals can accumulate
+        require!(
+            Clock::get()?.unix_timestamp >= transaction.timestamp + governance_state.approval_cooldown,
+            GovernanceError::ApprovalTooEarly
+        );
+
+        let weight = governance_state.signer_weight(&ctx.accounts.approver.key());
+        transaction.add_approval(ctx.accounts.approver.key(), weight);
+
+        if !governance_state.is_primary_signer(&ctx.accounts.approver.key()) {
+            emit!(BackupSignerApproved {
+                tx_id,
+                signer: ctx.accounts.approver.key(),
+                last_activity: governance_state.last_activity,
+            });
+        }
+        let now = Clock::get()?.unix_timestamp;
+        governance_state.record_activity(&ctx.accounts.approver.key(), now);
+
+        emit!(TransactionApproved {
+            id: tx_id,
+            approver: ctx.accounts.approver.key(),
+            approval_count: transaction.approval_count,
+            approval_weight: transaction.approval_weight,
+        });
+
+        msg!(
+            "Transaction {} approved by {} with weight {} ({} of {} required)",
+            tx_id,
+            ctx.accounts.approver.key(),
+            weight,
+            transaction.effective_approval_weight(),
+            governance_state.required_approvals_for(transaction.tx_type)
+        );
+
+        // Execution should only occur via execute_transaction after cooldown expires
+        // Do not auto-execute or check cooldown here
+
+        Ok(())
+    }
+
+    /// Revokes the caller's approval on a pending transaction
+    ///
+    /// Lets a signer who approved retract before execution, e.g. after learning
+    /// new information. The signer's weight is removed from the approval tally.
+    ///
+    /// # Parameters
+    /// - `ctx`: ApproveTransaction context
+    /// - `tx_id`: The transaction ID to revoke approval on
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the approval is removed
+    ///
+    /// # Errors
+    /// - `GovernanceError::InvalidTransactionId` if tx_id does not match
+    /// - `GovernanceError::TransactionNotPending` if transaction is not pending
+    /// - `GovernanceError::NotApproved` if the caller has not approved the transaction
+    ///
+    /// # Events
+    /// - Emits `ApprovalRevoked`
+    pub fn revoke_approval(ctx: Context<ApproveTransaction>, tx_id: u64) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        let transaction = &mut ctx.accounts.transaction;
+        let signer = ctx.accounts.approver.key();
+
+        require!(
+            transaction.id == tx_id,
+            GovernanceError::InvalidTransactionId
+        );
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            GovernanceError::TransactionNotPending
+        );
+
+        let weight = governance_state.signer_weight(&signer);
+        require!(
+            transaction.remove_approval(signer, weight),
+            GovernanceError::NotApproved
+        );
+
+        emit!(ApprovalRevoked {
+            tx_id,
+            signer,
+            remaining: transaction.approval_count,
+        });
+
+        msg!(
+            "Approval on transaction {} revoked by {} ({} approvals remaining)",
+            tx_id,
+            signer,
+            transaction.approval_count
+        );
+
+        Ok(())
+    }
+
+    /// Reject a transaction
+    pub fn reject_transaction(
+        ctx: Context<RejectTransaction>,
+        tx_id: u64,
+        reason: String,
+    ) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        let transaction = &mut ctx.accounts.transaction;
+
+        // Enforce multisig - only authorized signers can reject
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.approver.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+
+        require!(
+            transaction.id == tx_id,
+            GovernanceError::InvalidTransactionId
+        );
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            GovernanceError::TransactionNotPending
+        );
+        require!(!reason.is_empty(), GovernanceError::EmptyRejectionReason);
+        // Limit reason length to prevent log overflow
+        require!(
+            reason.len() <= 256,
+            GovernanceError::EmptyRejectionReason
+        );
+
+        transaction.status = TransactionStatus::Rejected;
+        transaction.rejection_reason = reason.clone();
+        transaction.rejector = ctx.accounts.approver.key();
+
+        emit!(TransactionRejected {
+            id: tx_id,
+            rejector: ctx.accounts.approver.key(),
+            reason: reason.clone(),
+        });
+
+        msg!(
+            "Transaction {} rejected by {}: {}",
+            tx_id,
+            ctx.accounts.approver.key(),
+            reason
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending transaction before anyone has approved it
+    ///
+    /// Lets the initiator (or any authorized signer) withdraw a mistakenly queued
+    /// transaction. The transaction account is closed and rent is refunded to the
+    /// original initiator.
+    ///
+    /// # Parameters
+    /// - `ctx`: CancelTransaction context
+    /// - `tx_id`: The transaction ID to cancel
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if transaction is cancelled
+    ///
+    /// # Errors
+    /// - `GovernanceError::NotAuthorizedSigner` if caller is neither the initiator nor a signer
+    /// - `GovernanceError::InvalidTransactionId` if tx_id does not match
+    /// - `GovernanceError::TransactionNotPending` if transaction is not pending
+    /// - `GovernanceError::TransactionHasApprovals` if any signer has already approved
+    ///
+    /// # Events
+    /// - Emits `TransactionCancelled`
+    pub fn cancel_transaction(ctx: Context<CancelTransaction>, tx_id: u64) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        let transaction = &mut ctx.accounts.transaction;
+        let canceller = ctx.accounts.canceller.key();
+
+        require!(
+            canceller == transaction.initiator || governance_state.is_authorized_signer(&canceller),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            transaction.id == tx_id,
+            GovernanceError::InvalidTransactionId
+        );
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            GovernanceError::TransactionNotPending
+        );
+        require!(
+            transaction.approval_count == 0,
+            GovernanceError::TransactionHasApprovals
+        );
+
+        // Account is closed to the initiator after this instruction
+        transaction.status = TransactionStatus::Cancelled;
+
+        emit!(TransactionCancelled {
+            tx_id,
+            tx_type: transaction.tx_type,
+            cancelled_by: canceller,
+        });
+
+        msg!("Transaction {} cancelled by {}", tx_id, canceller);
+
+        Ok(())
+    }
+
+    /// Execute a transaction (if cooldown expired and approved)
+    /// Executes a queued transaction after cooldown
+    ///
+    /// Executes a transaction that has received sufficient approvals and passed
+    /// the cooldown period. Performs actual CPI calls to apply state changes.
+    ///
+    /// # Parameters
+    /// - `ctx`: ExecuteTransaction context with all required accounts for CPI
+    /// - `tx_id`: The transaction ID to execute
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if transaction is executed
+    ///
+    /// # Errors
+    /// - `GovernanceError::TransactionNotFound` if transaction doesn't exist
+    /// - `GovernanceError::TransactionAlreadyExecuted` if already executed
+    /// - `GovernanceError::InsufficientApprovals` if not enough approvals
+    /// - `GovernanceError::CooldownNotExpired` if cooldown period hasn't passed
+    /// - `GovernanceError::TransactionExpired` if the expiry window has passed
+    ///
+    /// # Security
+    /// - Reentrancy protection (marks as executed immediately)
+    /// - Enforces cooldown period
+    /// - Validates approval count before execution
+    /// - Performs actual CPI calls to apply changes
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTransaction<'info>>,
+        tx_id: u64,
+    ) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(
+            transaction.id == tx_id,
+            GovernanceError::InvalidTransactionId
+        );
+        // Reentrancy guard - check transaction not already executed
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            GovernanceError::TransactionNotPending
+        );
+        // Mark as executing immediately to prevent reentrancy
+        transaction.status = TransactionStatus::Executed;
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= transaction.execute_after,
+            GovernanceError::CooldownNotExpired
+        );
+        // Transactions queued before expiry tracking have expires_at == 0 and never expire
+        require!(
+            transaction.expires_at == 0 || clock.unix_timestamp <= transaction.expires_at,
+            GovernanceError::TransactionExpired
+        );
+        // Weighted quorum, but never fewer than two distinct approvers
+        require!(
+            transaction.approval_count >= GovernanceState::MIN_REQUIRED_APPROVALS,
+            GovernanceError::InsufficientApprovals
+        );
+        require!(
+            transaction.effective_approval_weight()
+                >= governance_state.required_approvals_for(transaction.tx_type) as u16,
+            GovernanceError::InsufficientApprovals
+        );
+        if let Some(executor) = &ctx.accounts.executor {
+            governance_state.record_activity(&executor.key(), clock.unix_timestamp);
+        }
+        // Token transactions must CPI into the token program chosen at queue time
+        if transaction.tx_type.targets_token_program() {
+            require!(
+                ctx.accounts.token_program_program.key()
+                    == governance_state.token_program_at(transaction.token_program_index)?,
+                GovernanceError::InvalidTokenProgramIndex
+            );
+        }
+        // Reject data that doesn't match the encoding of its type before decoding it
+        require!(
+            transaction.tx_type.is_valid_data_len(transaction.data.len()),
+            GovernanceError::InvalidDataLength
+        );
+
+        // Execute real CPI calls based on transaction type
+        match transaction.tx_type {
+            TransactionType::Unpause => {
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetEmergencyPause {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_emergency_pause(cpi_ctx, false)?;
+                msg!("Transaction {} executed: Unpause", tx_id);
+            }
+            TransactionType::SetGovernanceOpsDuringPause => {
+                if transaction.data.is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let value = transaction.data[0] != 0;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetEmergencyPause {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_governance_ops_during_pause(cpi_ctx, value)?;
+                msg!("Transaction {} executed: SetGovernanceOpsDuringPause = {}", tx_id, value);
+            }
+            TransactionType::SetSellTrackerMode => {
+                if transaction.data.is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let mode = if transaction.data[0] != 0 {
+                    spl_project::SellTrackerMode::PerTokenAccount
+                } else {
+                    spl_project::SellTrackerMode::PerWallet
+                };
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetSellLimitsFrozen {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_sell_tracker_mode(cpi_ctx, mode)?;
+                msg!("Transaction {} executed: SetSellTrackerMode = {:?}", tx_id, mode);
+            }
+            TransactionType::ToggleWhitelistMode => {
+                if transaction.data.is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let enabled = transaction.data[0] != 0;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::ToggleWhitelistMode {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::toggle_whitelist_mode(cpi_ctx, enabled)?;
+                if enabled {
+                    emit!(WhitelistModeEnabled {
+                        by: transaction.initiator,
+                    });
+                } else {
+                    emit!(WhitelistModeDisabled { tx_id });
+                }
+                msg!("Transaction {} executed: ToggleWhitelistMode = {}", tx_id, enabled);
+            }
+            TransactionType::Blacklist => {
+                if transaction.data.len() < 33 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let value = transaction.data[32] != 0;
+
+                // Verify target account matches
+                require!(
+                    account_pubkey == ctx.accounts.target_account.key(),
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetBlacklist {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    blacklist: ctx.accounts.blacklist_account.to_account_info(),
+                    account: ctx.accounts.target_account.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_blacklist(cpi_ctx, account_pubkey, value)?;
+                msg!("Transaction {} executed: Blacklist {} = {}", tx_id, account_pubkey, value);
+            }
+            TransactionType::NoSellLimit => {
+                if transaction.data.len() < 33 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let value = transaction.data[32] != 0;
+
+                // Verify target account matches
+                require!(
+                    account_pubkey == ctx.accounts.target_account.key(),
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetNoSellLimit {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    no_sell_limit: ctx.accounts.no_sell_limit_account.to_account_info(),
+                    account: ctx.accounts.target_account.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_no_sell_limit(
+                    cpi_ctx,
+                    account_pubkey,
+                    value,
+                    transaction.no_sell_limit_expiry(),
+                )?;
+                msg!("Transaction {} executed: NoSellLimit {} = {}", tx_id, account_pubkey, value);
+            }
+            TransactionType::Restrict => {
+                if transaction.data.len() < 33 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let account_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let value = transaction.data[32] != 0;
+
+                // Verify target account matches
+                require!(
+                    account_pubkey == ctx.accounts.target_account.key(),
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetRestricted {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    restricted: ctx.accounts.restricted_account.to_account_info(),
+                    account: ctx.accounts.target_account.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_restricted(cpi_ctx, account_pubkey, value)?;
+                msg!("Transaction {} executed: Restrict {} = {}", tx_id, account_pubkey, value);
+            }
+            TransactionType::Pair => {
+                if transaction.data.len() < 33 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let pool_pubkey = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let value = transaction.data[32] != 0;
+
+                // Verify pool address matches
+                require!(
+                    pool_pubkey == ctx.accounts.pool_address.key(),
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetLiquidityPool {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    liquidity_pool: ctx.accounts.liquidity_pool_account.to_account_info(),
+                    pool: ctx.accounts.pool_address.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_liquidity_pool(cpi_ctx, pool_pubkey, value)?;
+                msg!("Transaction {} executed: LiquidityPool {} = {}", tx_id, pool_pubkey, value);
+            }
+            TransactionType::PairBatch => {
+                // remaining_accounts holds [liquidity_pool PDA, pool] for each entry, in order
+                let entries = transaction.data.chunks_exact(33);
+                if !entries.remainder().is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                require!(
+                    ctx.remaining_accounts.len() == entries.len() * 2,
+                    GovernanceError::InvalidBatchAccounts
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let pool_count = entries.len();
+                for (entry, accounts) in entries.zip(ctx.remaining_accounts.chunks(2)) {
+                    let pool_pubkey = Pubkey::try_from_slice(&entry[0..32])
+                        .map_err(|_| GovernanceError::InvalidDataLength)?;
+                    let value = entry[32] != 0;
+                    let (liquidity_pool_info, pool_info) = (&accounts[0], &accounts[1]);
+
+                    let (liquidity_pool_pda, _) = Pubkey::find_program_address(
+                        &[b"liquiditypool", pool_pubkey.as_ref()],
+                        &spl_project::ID,
+                    );
+                    require!(
+                        pool_info.key() == pool_pubkey && liquidity_pool_info.key() == liquidity_pool_pda,
+                        GovernanceError::InvalidBatchAccounts
+                    );
+
+                    let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                    let cpi_accounts = spl_project::cpi::accounts::SetLiquidityPool {
+                        state: ctx.accounts.state_pda.to_account_info(),
+                        liquidity_pool: liquidity_pool_info.clone(),
+                        pool: pool_info.clone(),
+                        governance: ctx.accounts.governance_state.to_account_info(),
+                        payer: ctx.accounts.payer.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                    spl_project::cpi::set_liquidity_pool(cpi_ctx, pool_pubkey, value)?;
+                }
+                msg!("Transaction {} executed: PairBatch ({} pools)", tx_id, pool_count);
+            }
+            TransactionType::BlacklistBatch => {
+                // remaining_accounts holds [blacklist PDA, account] for each entry, in order
+                let entries = transaction.data.chunks_exact(33);
+                if !entries.remainder().is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                require!(
+                    ctx.remaining_accounts.len() == entries.len() * 2,
+                    GovernanceError::InvalidBatchAccounts
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let count = entries.len();
+                for (entry, accounts) in entries.zip(ctx.remaining_accounts.chunks(2)) {
+                    let account_pubkey = Pubkey::try_from_slice(&entry[0..32])
+                        .map_err(|_| GovernanceError::InvalidDataLength)?;
+                    let value = entry[32] != 0;
+                    let (blacklist_info, account_info) = (&accounts[0], &accounts[1]);
+
+                    let (blacklist_pda, _) = Pubkey::find_program_address(
+                        &[b"blacklist", account_pubkey.as_ref()],
+                        &spl_project::ID,
+                    );
+                    require!(
+                        account_info.key() == account_pubkey && blacklist_info.key() == blacklist_pda,
+                        GovernanceError::InvalidBatchAccounts
+                    );
+
+                    let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                    let cpi_accounts = spl_project::cpi::accounts::SetBlacklist {
+                        state: ctx.accounts.state_pda.to_account_info(),
+                        blacklist: blacklist_info.clone(),
+                        account: account_info.clone(),
+                        governance: ctx.accounts.governance_state.to_account_info(),
+                        payer: ctx.accounts.payer.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                    spl_project::cpi::set_blacklist(cpi_ctx, account_pubkey, value)?;
+                }
+
+                emit!(BlacklistBatchExecuted {
+                    count: count as u8,
+                    tx_id,
+                });
+                msg!("Transaction {} executed: BlacklistBatch ({} addresses)", tx_id, count);
+            }
+            TransactionType::SetRequiredApprovals => {
+                if transaction.data.len() < 1 {
+                    return Err(GovernanceError::InvalidRequiredApprovals.into());
+                }
+                let required = transaction.data[0];
+                require!(
+                    required >= GovernanceState::MIN_REQUIRED_APPROVALS,
+                    GovernanceError::RequiredApprovalsTooLow
+                );
+                require!(
+                    required as u16 <= governance_state.total_signer_weight(),
+                    GovernanceError::RequiredApprovalsTooHigh
+                );
+                emit!(RequiredApprovalsChanged {
+                    old: governance_state.required_approvals,
+                    new: required,
+                    queued: true,
+                });
+                governance_state.required_approvals = required;
+                msg!(
+                    "Transaction {} executed: SetRequiredApprovals = {}",
+                    tx_id,
+                    required
+                );
+            }
+            TransactionType::SetCooldownPeriod => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidCooldownPeriod.into());
+                }
+                let period = i64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidCooldownPeriod)?,
+                );
+                require!(
+                    period >= GovernanceState::MIN_COOLDOWN_SECONDS,
+                    GovernanceError::CooldownPeriodTooLow
+                );
+                require!(
+                    period <= GovernanceState::MAX_COOLDOWN_SECONDS,
+                    GovernanceError::CooldownPeriodTooHigh
+                );
+                emit!(CooldownPeriodChanged {
+                    old: governance_state.cooldown_period,
+                    new: period,
+                    queued: true,
+                });
+                governance_state.cooldown_period = period;
+                msg!(
+                    "Transaction {} executed: SetCooldownPeriod = {}",
+                    tx_id,
+                    period
+                );
+            }
+            TransactionType::AddSigner => {
+                if transaction.data.len() < 32 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let signer = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                // Re-validate against current state (may have changed since queue)
+                require!(
+                    !governance_state.is_primary_signer(&signer),
+                    GovernanceError::DuplicateSigners
+                );
+                require!(
+                    governance_state.signers.len() < GovernanceState::MAX_SIGNERS,
+                    GovernanceError::TooManySigners
+                );
+                governance_state.normalize_signer_weights();
+                governance_state.signers.push(signer);
+                governance_state.signer_weights.push(1);
+                // A promoted backup is no longer a backup
+                governance_state.backup_signers.retain(|backup| *backup != signer);
+                emit!(SignerAdded {
+                    signer,
+                    signer_count: governance_state.signers.len() as u8,
+                });
+                msg!("Transaction {} executed: AddSigner = {}", tx_id, signer);
+            }
+            TransactionType::RemoveSigner => {
+                if transaction.data.len() < 32 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let signer = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                // Re-validate against current state (may have changed since queue)
+                let index = governance_state
+                    .signers
+                    .iter()
+                    .position(|s| *s == signer)
+                    .ok_or(GovernanceError::SignerNotFound)?;
+                governance_state.validate_signer_removal(&signer)?;
+                governance_state.normalize_signer_weights();
+                governance_state.signers.remove(index);
+                governance_state.signer_weights.remove(index);
+                emit!(SignerRemoved {
+                    signer,
+                    signer_count: governance_state.signers.len() as u8,
+                });
+                msg!("Transaction {} executed: RemoveSigner = {}", tx_id, signer);
+            }
+            TransactionType::SetBridgeAddress => {
+                if transaction.data.len() < 32 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let bridge_address = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetBridgeAddress {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_bridge_address(cpi_ctx, bridge_address)?;
+                msg!("Transaction {} executed: SetBridgeAddress = {}", tx_id, bridge_address);
+            }
+            TransactionType::SetBondAddress => {
+                if transaction.data.len() < 32 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let bond_address = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetBondAddress {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_bond_address(cpi_ctx, bond_address)?;
+                msg!("Transaction {} executed: SetBondAddress = {}", tx_id, bond_address);
+            }
+            TransactionType::SetTreasuryAddress => {
+                if transaction.data.len() < 32 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let treasury_address = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::SetTreasuryAddress {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    authority: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::set_treasury_address(cpi_ctx, treasury_address)?;
+                msg!("Transaction {} executed: SetTreasuryAddress = {}", tx_id, treasury_address);
+            }
+            TransactionType::SetMaxSupply => {
+                if transaction.data.len() < 10 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let cap_value = u64::from_le_bytes(
+                    transaction.data[1..9]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                let cap = if transaction.data[0] != 0 { Some(cap_value) } else { None };
+                let confirm_unlimited = transaction.data[9] != 0;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetMaxSupply {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_max_supply(cpi_ctx, cap, confirm_unlimited)?;
+                msg!("Transaction {} executed: SetMaxSupply = {:?}", tx_id, cap);
+            }
+            TransactionType::SetTransferFee => {
+                if transaction.data.len() < 36 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let fee_bps = u16::from_le_bytes(
+                    transaction.data[0..2]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                let burn = transaction.data[2] != 0;
+                let treasury_address = Pubkey::try_from_slice(&transaction.data[3..35])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let whitelist_exempt = transaction.data[35] != 0;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetPoolRestriction {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_transfer_fee(cpi_ctx, fee_bps, burn, treasury_address, whitelist_exempt)?;
+                msg!("Transaction {} executed: SetTransferFee = {} bps", tx_id, fee_bps);
+            }
+            TransactionType::SetCircuitBreaker => {
+                if transaction.data.len() < 3 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let enabled = transaction.data[0] != 0;
+                let threshold_bps = u16::from_le_bytes(
+                    transaction.data[1..3]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetPoolRestriction {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_circuit_breaker(cpi_ctx, enabled, threshold_bps)?;
+                msg!(
+                    "Transaction {} executed: SetCircuitBreaker = {} at {} bps",
+                    tx_id,
+                    enabled,
+                    threshold_bps
+                );
+            }
+            TransactionType::SetVestingConfig => {
+                if transaction.data.len() < 17 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let enabled = transaction.data[0] != 0;
+                let cliff_seconds = u64::from_le_bytes(
+                    transaction.data[1..9]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                let duration_seconds = u64::from_le_bytes(
+                    transaction.data[9..17]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::AdminOnly {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    admin: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::set_vesting_config(cpi_ctx, enabled, cliff_seconds, duration_seconds)?;
+                msg!(
+                    "Transaction {} executed: SetVestingConfig = enabled {}, cliff {}s, duration {}s",
+                    tx_id,
+                    enabled,
+                    cliff_seconds,
+                    duration_seconds
+                );
+            }
+            TransactionType::SetPurchaseLimits => {
+                if transaction.data.len() < 16 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let min_purchase_amount = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                let max_per_transaction = u64::from_le_bytes(
+                    transaction.data[8..16]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let limits_ctx = || {
+                    CpiContext::new_with_signer(
+                        ctx.accounts.presale_program_program.to_account_info(),
+                        presale::cpi::accounts::UpdatePurchaseLimits {
+                            presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                            authority: ctx.accounts.governance_state.to_account_info(),
+                        },
+                        signer_seeds,
+                    )
+                };
+                // Clear the maximum first so the new minimum is never checked
+                // against the old maximum
+                presale::cpi::set_max_per_transaction(limits_ctx(), 0)?;
+                presale::cpi::set_min_purchase_amount(limits_ctx(), min_purchase_amount)?;
+                presale::cpi::set_max_per_transaction(limits_ctx(), max_per_transaction)?;
+                msg!(
+                    "Transaction {} executed: SetPurchaseLimits = min {}, max per tx {}",
+                    tx_id,
+                    min_purchase_amount,
+                    max_per_transaction
+                );
+            }
+            TransactionType::UpdatePresaleCap => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let new_cap = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Re-check against current sales; they may have grown since queuing
+                let presale_state = {
+                    let data = ctx.accounts.presale_state_pda.try_borrow_data()?;
+                    presale::PresaleState::try_deserialize(&mut &data[..])?
+                };
+                require!(
+                    new_cap == 0 || new_cap >= presale_state.cap_progress(),
+                    GovernanceError::InvalidAmount
+                );
+                let old_cap = presale_state.max_presale_cap;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::UpdatePresaleCap {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    authority: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::update_presale_cap(cpi_ctx, new_cap)?;
+
+                emit!(PresaleCapUpdateExecuted { old_cap, new_cap });
+                msg!(
+                    "Transaction {} executed: UpdatePresaleCap {} -> {}",
+                    tx_id,
+                    old_cap,
+                    new_cap
+                );
+            }
+            TransactionType::UpdateMaxPerUser => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let new_max = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Re-check against the current cap; it may have changed since queuing
+                let presale_state = {
+                    let data = ctx.accounts.presale_state_pda.try_borrow_data()?;
+                    presale::PresaleState::try_deserialize(&mut &data[..])?
+                };
+                require!(
+                    new_max == 0
+                        || presale_state.max_presale_cap == 0
+                        || presale_state.cap_in_usd
+                        || new_max <= presale_state.max_presale_cap,
+                    GovernanceError::InvalidAmount
+                );
+                let old_max = presale_state.max_per_user;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::UpdateMaxPerUser {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    authority: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::update_max_per_user(cpi_ctx, new_max)?;
+
+                emit!(MaxPerUserUpdateExecuted { old_max, new_max });
+                msg!(
+                    "Transaction {} executed: UpdateMaxPerUser {} -> {}",
+                    tx_id,
+                    old_max,
+                    new_max
+                );
+            }
+            TransactionType::SetPriceTiers => {
+                let chunks = transaction.data.chunks_exact(16);
+                if !chunks.remainder().is_empty() {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let tiers = chunks
+                    .map(|chunk| -> Result<presale::PriceTier> {
+                        Ok(presale::PriceTier {
+                            sold_threshold: u64::from_le_bytes(
+                                chunk[0..8]
+                                    .try_into()
+                                    .map_err(|_| GovernanceError::InvalidDataLength)?,
+                            ),
+                            price_usd_micro: u64::from_le_bytes(
+                                chunk[8..16]
+                                    .try_into()
+                                    .map_err(|_| GovernanceError::InvalidDataLength)?,
+                            ),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let tier_count = tiers.len();
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::AdminOnly {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    admin: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::set_price_tiers(cpi_ctx, tiers)?;
+                msg!("Transaction {} executed: SetPriceTiers ({} tiers)", tx_id, tier_count);
+            }
+            TransactionType::StartPresale => {
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::AdminOnly {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    admin: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::start_presale(cpi_ctx)?;
+                msg!("Transaction {} executed: StartPresale", tx_id);
+            }
+            TransactionType::StopPresale => {
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::AdminOnly {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    admin: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::stop_presale(cpi_ctx)?;
+                msg!("Transaction {} executed: StopPresale", tx_id);
+            }
+            TransactionType::UpdateTokenPrice => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let new_price_usd_micro = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::SetTokenPriceUsd {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    authority: ctx.accounts.governance_state.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::set_token_price_usd(cpi_ctx, new_price_usd_micro)?;
+                msg!(
+                    "Transaction {} executed: UpdateTokenPrice = {} micro-USD",
+                    tx_id,
+                    new_price_usd_micro
+                );
+            }
+            TransactionType::WithdrawToTreasury => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidAccount.into());
+                }
+                let amount = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+
+                // High-value withdrawals need the larger quorum
+                require!(
+                    transaction.effective_approval_weight()
+                        >= governance_state.withdrawal_required_approvals(amount) as u16,
+                    GovernanceError::InsufficientApprovals
+                );
+
+                let presale_allowed_token = ctx.accounts.presale_allowed_token.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
+                let cpi_accounts = presale::cpi::accounts::WithdrawToTreasury {
+                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
+                    authority: ctx.accounts.governance_state.to_account_info(),
+                    presale_payment_vault_pda: ctx.accounts.presale_payment_vault_pda.to_account_info(),
+                    presale_payment_vault: ctx.accounts.presale_payment_vault.to_account_info(),
+                    treasury_token_account: ctx.accounts.treasury_token_account.to_account_info(),
+                    payment_token_mint: ctx.accounts.payment_token_mint.to_account_info(),
+                    allowed_token: presale_allowed_token.to_account_info(),
+                    token_program: ctx.accounts.spl_token_program.to_account_info(),
+                    associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                    // Fee collector's token account, passed as the first remaining account
+                    // while the presale charges a withdrawal fee
+                    fee_collector_token_account: ctx.remaining_accounts.first().cloned(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                presale::cpi::withdraw_to_treasury(cpi_ctx, amount)?;
+                msg!("Transaction {} executed: WithdrawToTreasury = {}", tx_id, amount);
+            }
+            TransactionType::BurnTokens => {
+                if transaction.data.len() < 40 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let from_token_account = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let amount = u64::from_le_bytes(
+                    transaction.data[32..40]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+
+                let token_mint = ctx.accounts.token_mint.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let burn_from_account = ctx.accounts.burn_from_account.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                // Verify the passed account matches the one that was queued
+                require!(
+                    burn_from_account.key() == from_token_account,
+                    GovernanceError::InvalidAccount
+                );
+                require!(amount > 0, GovernanceError::InvalidAmount);
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::BurnTokens {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    from: burn_from_account.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    token_program: ctx.accounts.spl_token_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::burn_tokens(cpi_ctx, amount)?;
+                msg!("Transaction {} executed: BurnTokens {} from {}", tx_id, amount, from_token_account);
+            }
+            TransactionType::Mint => {
+                if transaction.data.len() < 40 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let recipient_token_account = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let amount = u64::from_le_bytes(
+                    transaction.data[32..40]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+
+                let token_mint = ctx.accounts.token_mint.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let mint_to_account = ctx.accounts.mint_to_account.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let recipient_blacklist = ctx.accounts.recipient_blacklist.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                // Verify the passed account matches the one that was queued
+                require!(
+                    mint_to_account.key() == recipient_token_account,
+                    GovernanceError::InvalidAccount
+                );
+                // Verify the blacklist PDA belongs to the recipient's owner so it can't be spoofed
+                let recipient_owner = {
+                    let data = mint_to_account.try_borrow_data()?;
+                    require!(data.len() >= 64, GovernanceError::InvalidAccount);
+                    Pubkey::try_from_slice(&data[32..64])
+                        .map_err(|_| GovernanceError::InvalidAccount)?
+                };
+                let (expected_blacklist, _) = Pubkey::find_program_address(
+                    &[b"blacklist", recipient_owner.as_ref()],
+                    &ctx.accounts.token_program_program.key(),
+                );
+                require!(
+                    recipient_blacklist.key() == expected_blacklist,
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::MintTokens {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: mint_to_account.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    recipient_blacklist: recipient_blacklist.to_account_info(),
+                    token_program: ctx.accounts.spl_token_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::mint_tokens(cpi_ctx, amount)?;
+                msg!("Transaction {} executed: Mint {} to {}", tx_id, amount, recipient_token_account);
+            }
+            TransactionType::MintWithVesting => {
+                if transaction.data.len() < 56 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let beneficiary = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let amount = u64::from_le_bytes(
+                    transaction.data[32..40]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+                let cliff_seconds = i64::from_le_bytes(
+                    transaction.data[40..48]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+                let duration_seconds = i64::from_le_bytes(
+                    transaction.data[48..56]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+
+                // The vesting PDAs are derived from the beneficiary by the token program
+                let token_mint = ctx.accounts.token_mint.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let vesting_schedule = ctx.accounts.vesting_schedule.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let vesting_vault = ctx.accounts.vesting_vault.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::MintWithVesting {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    vesting_schedule: vesting_schedule.to_account_info(),
+                    vesting_vault: vesting_vault.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    token_program: ctx.accounts.spl_token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::mint_with_vesting(cpi_ctx, beneficiary, amount, cliff_seconds, duration_seconds)?;
+                msg!(
+                    "Transaction {} executed: MintWithVesting {} to {} over {}s",
+                    tx_id,
+                    amount,
+                    beneficiary,
+                    duration_seconds
+                );
+            }
+            TransactionType::SetEmissionSchedule => {
+                if transaction.data.len() < 56 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let rate_per_second = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+                let recipient = Pubkey::try_from_slice(&transaction.data[8..40])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let start_time = i64::from_le_bytes(
+                    transaction.data[40..48]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+                let end_time = i64::from_le_bytes(
+                    transaction.data[48..56]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidAccount)?,
+                );
+
+                let emission_schedule = ctx.accounts.emission_schedule.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::SetEmissionSchedule {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    emission_schedule: emission_schedule.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::set_emission_schedule(cpi_ctx, rate_per_second, recipient, start_time, end_time)?;
+                msg!(
+                    "Transaction {} executed: SetEmissionSchedule {} per second to {} until {}",
+                    tx_id,
+                    rate_per_second,
+                    recipient,
+                    end_time
+                );
+            }
+            TransactionType::Seize => {
+                if transaction.data.len() < 72 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let from_token_account = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let to_token_account = Pubkey::try_from_slice(&transaction.data[32..64])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let amount = u64::from_le_bytes(
+                    transaction.data[64..72]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+
+                let token_mint = ctx.accounts.token_mint.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let seize_from_account = ctx.accounts.burn_from_account.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let seize_to_account = ctx.accounts.mint_to_account.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                let source_blacklist = ctx.accounts.source_blacklist.as_ref()
+                    .ok_or(GovernanceError::InvalidAccount)?;
+                // Verify the passed accounts match the ones that were queued
+                require!(
+                    seize_from_account.key() == from_token_account
+                        && seize_to_account.key() == to_token_account,
+                    GovernanceError::InvalidAccount
+                );
+
+                // Get bump before mutable borrow
+                let bump = governance_state.bump;
+                let cpi_program = ctx.accounts.token_program_program.to_account_info();
+                let cpi_accounts = spl_project::cpi::accounts::GovernanceTransfer {
+                    state: ctx.accounts.state_pda.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    from: seize_from_account.to_account_info(),
+                    to: seize_to_account.to_account_info(),
+                    source_blacklist: source_blacklist.to_account_info(),
+                    governance: ctx.accounts.governance_state.to_account_info(),
+                    token_program: ctx.accounts.spl_token_program.to_account_info(),
+                };
+                // Sign with governance state PDA
+                let governance_seeds = &[b"governance".as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                spl_project::cpi::governance_transfer(cpi_ctx, amount)?;
+                msg!(
+                    "Transaction {} executed: Seize {} from {} to {}",
+                    tx_id,
+                    amount,
+                    from_token_account,
+                    to_token_account
+                );
+            }
+            TransactionType::SetSignerWeight => {
+                if transaction.data.len() < 33 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let signer = Pubkey::try_from_slice(&transaction.data[0..32])
+                    .map_err(|_| GovernanceError::InvalidAccount)?;
+                let weight = transaction.data[32];
+                // Re-validate against current state (may have changed since queue)
+                governance_state.validate_signer_weight(&signer, weight)?;
+                governance_state.normalize_signer_weights();
+                let index = governance_state
+                    .signers
+                    .iter()
+                    .position(|s| *s == signer)
+                    .ok_or(GovernanceError::SignerNotFound)?;
+                governance_state.signer_weights[index] = weight;
+                emit!(SignerWeightChanged {
+                    signer,
+                    weight,
+                    total_weight: governance_state.total_signer_weight(),
+                });
+                msg!("Transaction {} executed: SetSignerWeight {} = {}", tx_id, signer, weight);
+            }
+            TransactionType::SetExpiryWindow => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let window = i64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                require!(
+                    (GovernanceState::MIN_EXPIRY_WINDOW_SECONDS..=GovernanceState::MAX_EXPIRY_WINDOW_SECONDS)
+                        .contains(&window),
+                    GovernanceError::InvalidExpiryWindow
+                );
+                governance_state.expiry_window = window;
+                msg!("Transaction {} executed: SetExpiryWindow = {}s", tx_id, window);
+            }
+            TransactionType::SetApprovalCooldown => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let cooldown = i64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                require!(
+                    (0..=GovernanceState::MAX_APPROVAL_COOLDOWN_SECONDS).contains(&cooldown),
+                    GovernanceError::InvalidCooldownPeriod
+                );
+                governance_state.approval_cooldown = cooldown;
+                msg!("Transaction {} executed: SetApprovalCooldown = {}s", tx_id, cooldown);
+            }
+            TransactionType::SetKeeperReward => {
+                if transaction.data.len() < 8 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let reward = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                require!(
+                    reward <= GovernanceState::MAX_KEEPER_REWARD_LAMPORTS,
+                    GovernanceError::InvalidAmount
+                );
+                governance_state.keeper_reward = reward;
+                msg!("Transaction {} executed: SetKeeperReward = {} lamports", tx_id, reward);
+            }
+            TransactionType::SetHighValuePolicy => {
+                if transaction.data.len() < 9 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let threshold = u64::from_le_bytes(
+                    transaction.data[0..8]
+                        .try_into()
+                        .map_err(|_| GovernanceError::InvalidDataLength)?,
+                );
+                let required = transaction.data[8];
+                // Re-validate: signer set or base quorum may have changed since queueing
+                governance_state.validate_high_value_policy(threshold, required)?;
+                governance_state.high_value_threshold = threshold;
+                governance_state.high_value_required_approvals = required;
+                msg!(
+                    "Transaction {} executed: SetHighValuePolicy = {} approvals above {}",
+                    tx_id,
+                    required,
+                    threshold
+                );
+            }
+            TransactionType::SetTypeApprovals => {
+                if transaction.data.len() < 2 {
+                    return Err(GovernanceError::InvalidDataLength.into());
+                }
+                let target_type = TransactionType::try_from_slice(&transaction.data[0..1])
+                    .map_err(|_| GovernanceError::InvalidDataLength)?;
+                let required = transaction.data[1];
+                // Re-validate: the signer set may have changed since queueing
+                governance_state.validate_type_approvals(required)?;
+                governance_state.set_type_approvals(target_type, required)?;
+                msg!(
+                    "Transaction {} executed: SetTypeApprovals {:?} = {}",
+                    tx_id,
+                    target_type,
+                    required
+                );
+            }
+            TransactionType::SetBackupSigners => {
+                let (backup_signers, activation_period) =
+                    GovernanceState::decode_backup_signers(&transaction.data)?;
+                // Re-validate: the signer set or quorum may have changed since queueing
+                governance_state.validate_backup_signers(&backup_signers, activation_period)?;
+                governance_state.backup_activation_period = if backup_signers.is_empty() {
+                    0
+                } else {
+                    activation_period
+                };
+                governance_state.backup_signers = backup_signers;
+                emit!(BackupSignersChanged {
+                    backup_signers: governance_state.backup_signers.clone(),
+                    activation_period: governance_state.backup_activation_period,
+                });
+                msg!(
+                    "Transaction {} executed: SetBackupSigners = {} after {}s inactivity",
+                    tx_id,
+                    governance_state.backup_signers.len(),
+                    governance_state.backup_activation_period
+                );
+            }
+        }
+
+        // Transaction status already set to Executed at start for reentrancy protection
+        emit!(TransactionExecuted {
+            id: tx_id,
+            tx_type: transaction.tx_type,
+        });
+        msg!("Transaction {} executed successfully", tx_id);
+
+        // Pay the keeper from the governance PDA's lamports above rent exemption;
+        // an underfunded reserve skips the reward rather than blocking execution
+        let keeper_reward = ctx.accounts.governance_state.keeper_reward;
+        if let (true, Some(executor)) = (keeper_reward > 0, ctx.accounts.executor.as_ref()) {
+            let reserve = ctx.accounts.governance_state.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(reserve.data_len());
+            if reserve.lamports().saturating_sub(rent_floor) >= keeper_reward {
+                **reserve.try_borrow_mut_lamports()? -= keeper_reward;
+                **executor.to_account_info().try_borrow_mut_lamports()? += keeper_reward;
+                emit!(KeeperRewarded {
+                    tx_id,
+                    executor: executor.key(),
+                    amount: keeper_reward,
+                });
+            } else {
+                msg!("Keeper reward skipped: reserve holds less than {} lamports", keeper_reward);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dry-run the execute-time parameter checks for a transaction
+    ///
+    /// Decodes `data` exactly as `execute_transaction` would for `tx_type` and applies
+    /// the same validation against current governance state, without writing anything.
+    /// UpdatePresaleCap and UpdateMaxPerUser are also checked against the presale state
+    /// when its PDA is passed as the first remaining account.
+    ///
+    /// # Parameters
+    /// - `ctx`: ValidateTransactionParams context (optionally the presale state PDA)
+    /// - `tx_type`: Transaction type the data is encoded for
+    /// - `data`: Encoded transaction data, as stored in `Transaction::data`
+    ///
+    /// # Errors
+    /// - Whichever error `execute_transaction` would raise for these parameters
+    /// - `GovernanceError::InvalidAccount` if the remaining account isn't the presale state PDA
+    ///
+    /// # Events
+    /// - Emits `TransactionValidated` with the failure reason. A failed validation also
+    ///   fails the instruction, so the event is only observable through simulation logs.
+    pub fn validate_transaction_params(
+        ctx: Context<ValidateTransactionParams>,
+        tx_type: TransactionType,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        let presale_state = match ctx.remaining_accounts.first() {
+            Some(info) => {
+                let (expected, _) = Pubkey::find_program_address(
+                    &[b"presale_state"],
+                    &governance_state.presale_program,
+                );
+                require!(
+                    governance_state.presale_program_set
+                        && info.key() == expected
+                        && *info.owner == governance_state.presale_program,
+                    GovernanceError::InvalidAccount
+                );
+                let account_data = info.try_borrow_data()?;
+                Some(presale::PresaleState::try_deserialize(&mut &account_data[..])?)
+            }
+            None => None,
+        };
+
+        let result =
+            governance_state.validate_transaction_data(tx_type, &data, presale_state.as_ref());
+        emit!(TransactionValidated {
+            tx_type,
+            is_valid: result.is_ok(),
+            reason: result.as_ref().err().map(|err| err.to_string()),
+        });
+        result?;
+        msg!("Transaction parameters valid: {:?}", tx_type);
+        Ok(())
+    }
+
+    /// Execute several ready transactions in one instruction
+    ///
+    /// Clears a backlog of approved address-flag transactions (Blacklist, NoSellLimit,
+    /// Restrict) in a single call. Each transaction is passed through remaining accounts
+    /// as `[transaction, target_account, flag_account]`, in the same order as `tx_ids`.
+    /// Transactions that aren't ready are skipped rather than failing the whole batch.
+    ///
+    /// # Parameters
+    /// - `ctx`: ExecuteTransactionsBatch context, plus three remaining accounts per transaction
+    /// - `tx_ids`: Transaction IDs to execute (max 5, bounded by compute)
+    ///
+    /// # Returns
+    /// - `Result<u8>`: Number of transactions executed
+    ///
+    /// # Errors
+    /// - `GovernanceError::InvalidBatchSize` if `tx_ids` is empty or longer than 5
+    /// - `GovernanceError::InvalidBatchAccounts` if remaining accounts don't match `tx_ids`
+    ///
+    /// # Security
+    /// - Same cooldown, expiry and approval checks as `execute_transaction`
+    /// - Each transaction is marked executed and persisted before its CPI
+    /// - Other transaction types are skipped and must go through `execute_transaction`
+    pub fn execute_transactions_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTransactionsBatch<'info>>,
+        tx_ids: Vec<u64>,
+    ) -> Result<u8> {
+        require!(
+            !tx_ids.is_empty() && tx_ids.len() <= GovernanceState::MAX_BATCH_EXECUTE,
+            GovernanceError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == tx_ids.len() * 3,
+            GovernanceError::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let bump = ctx.accounts.governance_state.bump;
+        let governance_seeds = &[b"governance".as_ref(), &[bump]];
+        let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+        let mut executed: u8 = 0;
+
+        for (&tx_id, accounts) in tx_ids.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            let (tx_info, target_info, flag_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+            let mut transaction = match Account::<Transaction>::try_from(tx_info) {
+                Ok(transaction) if transaction.id == tx_id && tx_info.is_writable => transaction,
+                _ => {
+                    msg!("Transaction {} skipped: invalid transaction account", tx_id);
+                    continue;
+                }
+            };
+            if !matches!(
+                transaction.tx_type,
+                TransactionType::Blacklist | TransactionType::NoSellLimit | TransactionType::Restrict
+            ) {
+                msg!("Transaction {} skipped: type not batchable", tx_id);
+                continue;
+            }
+            if !transaction.tx_type.is_valid_data_len(transaction.data.len()) {
+                msg!("Transaction {} skipped: invalid data length", tx_id);
+                continue;
+            }
+            if transaction.token_program_index != 0 {
+                msg!("Transaction {} skipped: not for the primary token program", tx_id);
+                continue;
+            }
+            let required_approvals = ctx.accounts.governance_state.required_approvals_for(transaction.tx_type);
+            if !transaction.is_executable(clock.unix_timestamp, required_approvals) {
+                msg!("Transaction {} skipped: not ready", tx_id);
+                continue;
+            }
+            if transaction.data.len() < 33 {
+                msg!("Transaction {} skipped: invalid data", tx_id);
+                continue;
+            }
+            let account_pubkey = match Pubkey::try_from_slice(&transaction.data[0..32]) {
+                Ok(account_pubkey) if account_pubkey == target_info.key() => account_pubkey,
+                _ => {
+                    msg!("Transaction {} skipped: target account mismatch", tx_id);
+                    continue;
+                }
+            };
+            let value = transaction.data[32] != 0;
+
+            // Persist executed status before the CPI, as the reentrancy guard
+            transaction.status = TransactionStatus::Executed;
+            transaction.exit(&crate::ID)?;
+
+            let cpi_program = ctx.accounts.token_program_program.to_account_info();
+            let state = ctx.accounts.state_pda.to_account_info();
+            let governance = ctx.accounts.governance_state.to_account_info();
+            let payer = ctx.accounts.payer.to_account_info();
+            let system_program = ctx.accounts.system_program.to_account_info();
+            match transaction.tx_type {
+                TransactionType::Blacklist => {
+                    let cpi_accounts = spl_project::cpi::accounts::SetBlacklist {
+                        state,
+                        blacklist: flag_info.clone(),
+                        account: target_info.clone(),
+                        governance,
+                        payer,
+                        system_program,
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                    spl_project::cpi::set_blacklist(cpi_ctx, account_pubkey, value)?;
+                }
+                TransactionType::NoSellLimit => {
+                    let cpi_accounts = spl_project::cpi::accounts::SetNoSellLimit {
+                        state,
+                        no_sell_limit: flag_info.clone(),
+                        account: target_info.clone(),
+                        governance,
+                        payer,
+                        system_program,
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                    spl_project::cpi::set_no_sell_limit(
+                        cpi_ctx,
+                        account_pubkey,
+                        value,
+                        transaction.no_sell_limit_expiry(),
+                    )?;
+                }
+                _ => {
+                    let cpi_accounts = spl_project::cpi::accounts::SetRestricted {
+                        state,
+                        restricted: flag_info.clone(),
+                        account: target_info.clone(),
+                        governance,
+                        payer,
+                        system_program,
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                    spl_project::cpi::set_restricted(cpi_ctx, account_pubkey, value)?;
+                }
+            }
+
+            emit!(TransactionExecuted {
+                id: tx_id,
+                tx_type: transaction.tx_type,
+            });
+            msg!("Transaction {} executed: {:?} {} = {}", tx_id, transaction.tx_type, account_pubkey, value);
+            executed += 1;
+        }
+
+        msg!("Batch executed {} of {} transactions", executed, tx_ids.len());
+        Ok(executed)
+    }
+
+    /// Set required approvals (REMOVED - must use queued transaction)
+    /// This function is kept for backwards compatibility but should not be used.
+    /// Use queue_set_required_approvals instead.
+    /// DEPRECATED: Direct setter bypasses queue mechanism
+    /// Use queue_set_required_approvals instead
+    pub fn set_required_approvals(ctx: Context<SetRequiredApprovals>, required: u8) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        
+        require!(
+            required >= GovernanceState::MIN_REQUIRED_APPROVALS,
+            GovernanceError::RequiredApprovalsTooLow
+        );
+        require!(
+            governance_state.authority == ctx.accounts.authority.key(),
+            GovernanceError::Unauthorized
+        );
+        require!(
+            required as u16 <= governance_state.total_signer_weight(),
+            GovernanceError::RequiredApprovalsTooHigh
+        );
+        emit!(RequiredApprovalsChanged {
+            old: governance_state.required_approvals,
+            new: required,
+            queued: false,
+        });
+        governance_state.required_approvals = required;
+        msg!("Required approvals set to {} (DEPRECATED: use queue mechanism)", required);
+        Ok(())
+    }
+
+    /// DEPRECATED: Direct setter bypasses queue mechanism
+    /// Use queue_set_cooldown_period instead
+    pub fn set_cooldown_period(ctx: Context<SetCooldownPeriod>, period: i64) -> Result<()> {
+        let governance_state = &mut ctx.accounts.governance_state;
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        
+        require!(
+            period >= GovernanceState::MIN_COOLDOWN_SECONDS,
+            GovernanceError::CooldownPeriodTooLow
+        );
+        require!(
+            governance_state.authority == ctx.accounts.authority.key(),
+            GovernanceError::Unauthorized
+        );
+        emit!(CooldownPeriodChanged {
+            old: governance_state.cooldown_period,
+            new: period,
+            queued: false,
+        });
+        governance_state.cooldown_period = period;
+        msg!("Cooldown period set to {} seconds (DEPRECATED: use queue mechanism)", period);
+        Ok(())
+    }
+
+    /// Grant a role
+    pub fn grant_role(ctx: Context<GrantRole>, role: u8, account: Pubkey) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+
+        require!(governance_state.is_authorized_signer(&ctx.accounts.authority.key()), GovernanceError::NotAuthorizedSigner);
+
+        require!(account != ctx.accounts.authority.key(), GovernanceError::Unauthorized);
+
+        let role_account = &mut ctx.accounts.role_account;
+        role_account.account = account;
+        role_account.role = role;
+        role_account.has_role = true;
+        emit!(RoleChanged {
+            account,
+            role,
+            granted: true,
+            by: ctx.accounts.authority.key(),
+        });
+        msg!("Role {} granted to {} by {}", role, account, ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Revoke a role
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: u8, account: Pubkey) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+
+        require!(governance_state.is_authorized_signer(&ctx.accounts.authority.key()), GovernanceError::NotAuthorizedSigner);
+
+        let role_account = &mut ctx.accounts.role_account;
+        require!(
+            role_account.account == account,
+            GovernanceError::InvalidAccount
+        );
+        require!(role_account.role == role, GovernanceError::InvalidRole);
+        role_account.has_role = false;
+        emit!(RoleChanged {
+            account,
+            role,
+            granted: false,
+            by: ctx.accounts.authority.key(),
+        });
+        msg!("Role {} revoked from {} by {}", role, account, ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Emergency pause (1 signer allowed, no cooldown)
+    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        // Allow any authorized signer to pause
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+
+        // Call token program's set_emergency_pause via CPI
+        // The governance PDA must sign, not the individual authority
+        let cpi_program = ctx.accounts.token_program_program.to_account_info();
+        let cpi_accounts = spl_project::cpi::accounts::SetEmergencyPause {
+            state: ctx.accounts.state_pda.to_account_info(),
+            governance: ctx.accounts.governance_state.to_account_info(),
+        };
+        let governance_seeds = &[b"governance".as_ref(), &[governance_state.bump]];
+        let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        spl_project::cpi::set_emergency_pause(cpi_ctx, true)?;
+
+        emit!(EmergencyPauseActivated {
+            by: ctx.accounts.authority.key(),
+        });
+        msg!(
+            "Emergency pause activated by {}",
+            ctx.accounts.authority.key()
+        );
+        Ok(())
+    }
+
+    /// Emergency whitelist mode (1 signer allowed, no cooldown)
+    ///
+    /// Restricting transfers is the safe direction, so any authorized signer may
+    /// turn whitelist mode on immediately. Turning it off requires
+    /// `queue_toggle_whitelist_mode`.
+    pub fn emergency_enable_whitelist_mode(ctx: Context<EmergencyEnableWhitelistMode>) -> Result<()> {
+        let governance_state = &ctx.accounts.governance_state;
+        require!(
+            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
+            GovernanceError::NotAuthorizedSigner
+        );
+        require!(
+            governance_state.token_program_set,
+            GovernanceError::TokenProgramNotSet
+        );
+
+        // The governance PDA must sign, not the individual authority
+        let cpi_program = ctx.accounts.token_program_program.to_account_info();
+        let cpi_accounts = spl_project::cpi::accounts::ToggleWhitelistMode {
+            state: ctx.accounts.state_pda.to_account_info(),
+            governance: ctx.accounts.governance_state.to_account_info(),
+        };
+        let governance_seeds = &[b"governance".as_ref(), &[governance_state.bump]];
+        let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        spl_project::cpi::toggle_whitelist_mode(cpi_ctx, true)?;
+
+        emit!(WhitelistModeEnabled {
+            by: ctx.accounts.authority.key(),
+        });
+        msg!(
+            "Whitelist mode enabled by {}",
+            ctx.accounts.authority.key()
+        );
+        Ok(())
+    }
+}
+
+// Account Structures
+
+#[account]
+pub struct GovernanceState {
+    pub authority: Pubkey,
+    pub required_approvals: u8,
+    pub cooldown_period: i64, // in seconds (90 minutes = 5400)
+    pub next_transaction_id: u64,
+    pub token_program: Pubkey,
+    pub token_program_set: bool,
+    pub presale_program: Pubkey,
+    pub presale_program_set: bool,
+    pub bump: u8,
+    pub signers: Vec<Pubkey>, // Authorized signers (max 10)
+    pub bootstrap_ends_at: i64, // Setup transactions skip cooldown until this time (fixed at init)
+    pub bootstrap_transactions_remaining: u8, // Remaining cooldown-exempt setup transactions
+    pub high_value_threshold: u64, // Withdrawals above this need high_value_required_approvals (0 = disabled)
+    pub high_value_required_approvals: u8,
+    pub expiry_window: i64, // Seconds after execute_after during which a transaction can execute
+    pub signer_weights: Vec<u8>, // Voting weight per signer, aligned with `signers` (missing = 1)
+    pub approval_cooldown: i64, // Seconds after queuing before approvals are accepted (0 = none)
+    pub keeper_reward: u64, // Lamports paid to the executor of a transaction (0 = none)
+    pub type_approvals: Vec<TypeApproval>, // Per-type overrides of required_approvals (max MAX_TYPE_APPROVALS)
+    pub last_activity: i64, // Last approval or execution by a primary signer
+    pub backup_signers: Vec<Pubkey>, // Authorized only after backup_activation_period of primary inactivity
+    pub backup_activation_period: i64, // Seconds of inactivity before backups activate (0 = no backups)
+    pub token_programs: Vec<Pubkey>, // Managed token programs by index, [0] = token_program (max MAX_TOKEN_PROGRAMS)
+}
+
+impl GovernanceState {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 32 + 1 + 32 + 1 + 1 + 4 + (32 * 10) + 8 + 1 + 8 + 1 + 8 + 4 + 10 + 8 + 8 + (4 + TypeApproval::LEN * Self::MAX_TYPE_APPROVALS) + 8 + 4 + (32 * Self::MAX_BACKUP_SIGNERS) + 8 + 4 + (32 * Self::MAX_TOKEN_PROGRAMS); // discriminator + fields + vec overhead + max 10 signers + bootstrap fields + high-value policy + expiry window + signer weights + approval cooldown + keeper reward + type approvals + last activity + backup signers + activation period + token programs
+    pub const MIN_REQUIRED_APPROVALS: u8 = 2;
+    pub const MIN_COOLDOWN_SECONDS: i64 = 1800; // 30 minutes
+    pub const MAX_COOLDOWN_SECONDS: i64 = 2592000; // 30 days
+    pub const MAX_SIGNERS: usize = 10;
+    pub const MAX_BOOTSTRAP_WINDOW_SECONDS: i64 = 86400; // 24 hours
+    pub const MAX_BOOTSTRAP_TRANSACTIONS: u8 = 20;
+    pub const DEFAULT_EXPIRY_WINDOW_SECONDS: i64 = 1209600; // 14 days
+    pub const MIN_EXPIRY_WINDOW_SECONDS: i64 = 86400; // 1 day
+    pub const MAX_EXPIRY_WINDOW_SECONDS: i64 = 7776000; // 90 days
+    pub const MAX_APPROVAL_COOLDOWN_SECONDS: i64 = 86400; // 1 day, the minimum expiry window, so transactions stay approvable
+    pub const MAX_BATCH_EXECUTE: usize = 5; // Bounded by per-instruction compute
+    pub const MAX_POOL_BATCH: usize = 8; // Pools per PairBatch transaction (33 bytes each in data)
+    pub const MAX_BLACKLIST_BATCH: usize = 20; // Addresses per BlacklistBatch transaction (33 bytes each in data)
+    pub const MAX_KEEPER_REWARD_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
+    pub const MAX_TYPE_APPROVALS: usize = 16;
+    pub const MAX_BACKUP_SIGNERS: usize = 5;
+    pub const MIN_BACKUP_ACTIVATION_SECONDS: i64 = 7776000; // 90 days
+    pub const MAX_BACKUP_ACTIVATION_SECONDS: i64 = 31536000; // 365 days
+    pub const MAX_TOKEN_PROGRAMS: usize = 5; // Including the primary token program
+
+    /// Token program registered at `index` (0 = primary `token_program`)
+    pub fn token_program_at(&self, index: u8) -> Result<Pubkey> {
+        let program = if index == 0 {
+            Some(self.token_program)
+        } else {
+            self.token_programs.get(index as usize).copied()
+        };
+        match program {
+            Some(program) if program != Pubkey::default() => Ok(program),
+            _ => Err(GovernanceError::InvalidTokenProgramIndex.into()),
+        }
+    }
+
+    /// Primary signers, plus backup signers once `backups_active`
+    pub fn is_authorized_signer(&self, signer: &Pubkey) -> bool {
+        self.is_primary_signer(signer)
+            || (self.backup_signers.contains(signer)
+                && Clock::get().is_ok_and(|clock| self.backups_active(clock.unix_timestamp)))
+    }
+
+    pub fn is_primary_signer(&self, signer: &Pubkey) -> bool {
+        self.signers.contains(signer)
+    }
+
+    /// Whether primary signers have been inactive long enough for backups to act.
+    /// State created before activity tracking reads `last_activity == 0` and never activates.
+    pub fn backups_active(&self, now: i64) -> bool {
+        !self.backup_signers.is_empty()
+            && self.backup_activation_period > 0
+            && self.last_activity > 0
+            && now.saturating_sub(self.last_activity) >= self.backup_activation_period
+    }
+
+    /// Refreshes the inactivity heartbeat; only primary signers count
+    pub fn record_activity(&mut self, signer: &Pubkey, now: i64) {
+        if self.is_primary_signer(signer) {
+            self.last_activity = now;
+        }
+    }
+
+    /// Backups must be distinct non-signers, enough to reach every quorum at weight 1
+    pub fn validate_backup_signers(&self, backup_signers: &[Pubkey], activation_period: i64) -> Result<()> {
+        if backup_signers.is_empty() {
+            return Ok(());
+        }
+        require!(
+            backup_signers.len() <= Self::MAX_BACKUP_SIGNERS
+                && backup_signers.len()
+                    >= self.max_required_approvals().max(Self::MIN_REQUIRED_APPROVALS) as usize,
+            GovernanceError::InvalidBackupSigners
+        );
+        require!(
+            (Self::MIN_BACKUP_ACTIVATION_SECONDS..=Self::MAX_BACKUP_ACTIVATION_SECONDS)
+                .contains(&activation_period),
+            GovernanceError::InvalidBackupSigners
+        );
+        for (index, backup) in backup_signers.iter().enumerate() {
+            require!(
+                *backup != Pubkey::default()
+                    && !self.is_primary_signer(backup)
+                    && !backup_signers[..index].contains(backup),
+                GovernanceError::InvalidBackupSigners
+            );
+        }
+        Ok(())
+    }
+
+    /// Decodes SetBackupSigners data: activation_period (8) + 32 bytes per backup
+    pub fn decode_backup_signers(data: &[u8]) -> Result<(Vec<Pubkey>, i64)> {
+        require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+        let activation_period = i64::from_le_bytes(
+            data[0..8]
+                .try_into()
+                .map_err(|_| GovernanceError::InvalidDataLength)?,
+        );
+        let entries = data[8..].chunks_exact(32);
+        require!(
+            entries.remainder().is_empty(),
+            GovernanceError::InvalidDataLength
+        );
+        let backup_signers = entries
+            .map(|entry| Pubkey::try_from_slice(entry).map_err(|_| GovernanceError::InvalidAccount.into()))
+            .collect::<Result<Vec<Pubkey>>>()?;
+        Ok((backup_signers, activation_period))
+    }
+
+    /// Voting weight of `signer` (0 if not a signer; state created before
+    /// weights existed has no entries and every signer counts as 1; backups count as 1)
+    pub fn signer_weight(&self, signer: &Pubkey) -> u8 {
+        match self.signers.iter().position(|s| s == signer) {
+            Some(index) => self.signer_weights.get(index).copied().unwrap_or(1),
+            None if self.backup_signers.contains(signer) => 1,
+            None => 0,
+        }
+    }
+
+    /// Summed voting weight of all signers
+    pub fn total_signer_weight(&self) -> u16 {
+        self.signers
+            .iter()
+            .map(|signer| self.signer_weight(signer) as u16)
+            .sum()
+    }
+
+    /// Pads `signer_weights` with the default weight so it lines up with `signers`
+    pub fn normalize_signer_weights(&mut self) {
+        self.signer_weights.resize(self.signers.len(), 1);
+    }
+
+    /// Removing `signer` must leave enough weight for every quorum and at least
+    /// two distinct signers
+    pub fn validate_signer_removal(&self, signer: &Pubkey) -> Result<()> {
+        let remaining_weight = self.total_signer_weight() - self.signer_weight(signer) as u16;
+        require!(
+            remaining_weight >= self.max_required_approvals() as u16,
+            GovernanceError::RequiredApprovalsTooHigh
+        );
+        require!(
+            self.signers.len() > GovernanceState::MIN_REQUIRED_APPROVALS as usize,
+            GovernanceError::RequiredApprovalsTooHigh
+        );
+        Ok(())
+    }
+
+    /// Weight must be non-zero and keep the total weight at or above every quorum
+    pub fn validate_signer_weight(&self, signer: &Pubkey, weight: u8) -> Result<()> {
+        require!(
+            self.is_primary_signer(signer),
+            GovernanceError::SignerNotFound
+        );
+        require!(weight > 0, GovernanceError::InvalidSignerWeight);
+        let new_total = self.total_signer_weight() - self.signer_weight(signer) as u16 + weight as u16;
+        require!(
+            new_total >= self.max_required_approvals() as u16,
+            GovernanceError::RequiredApprovalsTooHigh
+        );
+        Ok(())
+    }
+
+    /// Expiry window to apply to new transactions (state created before the
+    /// field existed reads 0 and falls back to the default)
+    pub fn effective_expiry_window(&self) -> i64 {
+        if self.expiry_window > 0 {
+            self.expiry_window
+        } else {
+            Self::DEFAULT_EXPIRY_WINDOW_SECONDS
+        }
+    }
+
+    /// Approvals needed to execute a transaction of `tx_type` (its override, if set)
+    pub fn required_approvals_for(&self, tx_type: TransactionType) -> u8 {
+        self.type_approvals
+            .iter()
+            .find(|entry| entry.tx_type == tx_type)
+            .map_or(self.required_approvals, |entry| entry.required)
+    }
+
+    /// Approvals needed to execute a treasury withdrawal of `amount`
+    pub fn withdrawal_required_approvals(&self, amount: u64) -> u8 {
+        let base = self.required_approvals_for(TransactionType::WithdrawToTreasury);
+        if self.high_value_threshold > 0 && amount > self.high_value_threshold {
+            self.high_value_required_approvals.max(base)
+        } else {
+            base
+        }
+    }
+
+    /// Highest approval count any transaction can require; the signer set must stay above it
+    pub fn max_required_approvals(&self) -> u8 {
+        let base = self
+            .type_approvals
+            .iter()
+            .map(|entry| entry.required)
+            .fold(self.required_approvals, u8::max);
+        if self.high_value_threshold > 0 {
+            self.high_value_required_approvals.max(base)
+        } else {
+            base
+        }
+    }
+
+    /// Per-type quorum must be at least two and reachable by the current signers
+    /// (0 removes the override)
+    pub fn validate_type_approvals(&self, required: u8) -> Result<()> {
+        if required > 0 {
+            require!(
+                required >= Self::MIN_REQUIRED_APPROVALS,
+                GovernanceError::RequiredApprovalsTooLow
+            );
+            require!(
+                required as u16 <= self.total_signer_weight(),
+                GovernanceError::RequiredApprovalsTooHigh
+            );
+        }
+        Ok(())
+    }
+
+    /// Sets, replaces or (with `required == 0`) removes the override for `tx_type`
+    pub fn set_type_approvals(&mut self, tx_type: TransactionType, required: u8) -> Result<()> {
+        let existing = self.type_approvals.iter().position(|entry| entry.tx_type == tx_type);
+        match (existing, required) {
+            (Some(index), 0) => {
+                self.type_approvals.remove(index);
+            }
+            (Some(index), _) => self.type_approvals[index].required = required,
+            (None, 0) => {}
+            (None, _) => {
+                require!(
+                    self.type_approvals.len() < Self::MAX_TYPE_APPROVALS,
+                    GovernanceError::TooManyTypeApprovals
+                );
+                self.type_approvals.push(TypeApproval { tx_type, required });
+            }
+        }
+        Ok(())
+    }
+
+    /// High-value quorum must sit between the base quorum and the signer count
+    pub fn validate_high_value_policy(&self, threshold: u64, required: u8) -> Result<()> {
+        if threshold > 0 {
+            require!(
+                required >= self.required_approvals,
+                GovernanceError::RequiredApprovalsTooLow
+            );
+            require!(
+                required as u16 <= self.total_signer_weight(),
+                GovernanceError::RequiredApprovalsTooHigh
+            );
+        }
+        Ok(())
+    }
+
+    /// Execute-time parameter checks for `tx_type`, shared with `validate_transaction_params`
+    ///
+    /// Mirrors the decoding and re-validation done in `execute_transaction`. Checks that
+    /// depend on the presale state are skipped when `presale_state` is `None`.
+    pub fn validate_transaction_data(
+        &self,
+        tx_type: TransactionType,
+        data: &[u8],
+        presale_state: Option<&presale::PresaleState>,
+    ) -> Result<()> {
+        let read_u64 = |range: std::ops::Range<usize>| -> Result<u64> {
+            Ok(u64::from_le_bytes(
+                data[range]
+                    .try_into()
+                    .map_err(|_| GovernanceError::InvalidDataLength)?,
+            ))
+        };
+        let read_pubkey = |range: std::ops::Range<usize>| -> Result<Pubkey> {
+            Ok(Pubkey::try_from_slice(&data[range]).map_err(|_| GovernanceError::InvalidAccount)?)
+        };
+
+        match tx_type {
+            TransactionType::Unpause
+            | TransactionType::StartPresale
+            | TransactionType::StopPresale => {}
+            TransactionType::Blacklist
+            | TransactionType::NoSellLimit
+            | TransactionType::Restrict
+            | TransactionType::Pair => {
+                require!(data.len() >= 33, GovernanceError::InvalidAccount);
+                read_pubkey(0..32)?;
+            }
+            TransactionType::PairBatch | TransactionType::BlacklistBatch => {
+                let entries = data.chunks_exact(33);
+                require!(
+                    entries.remainder().is_empty(),
+                    GovernanceError::InvalidDataLength
+                );
+                let max_entries = if tx_type == TransactionType::PairBatch {
+                    Self::MAX_POOL_BATCH
+                } else {
+                    Self::MAX_BLACKLIST_BATCH
+                };
+                require!(
+                    entries.len() > 0 && entries.len() <= max_entries,
+                    GovernanceError::InvalidBatchSize
+                );
+            }
+            TransactionType::SetRequiredApprovals => {
+                require!(!data.is_empty(), GovernanceError::InvalidRequiredApprovals);
+                require!(
+                    data[0] >= Self::MIN_REQUIRED_APPROVALS,
+                    GovernanceError::RequiredApprovalsTooLow
+                );
+                require!(
+                    data[0] as u16 <= self.total_signer_weight(),
+                    GovernanceError::RequiredApprovalsTooHigh
+                );
+            }
+            TransactionType::SetCooldownPeriod => {
+                require!(data.len() >= 8, GovernanceError::InvalidCooldownPeriod);
+                let period = read_u64(0..8)? as i64;
+                require!(
+                    period >= Self::MIN_COOLDOWN_SECONDS,
                     GovernanceError::CooldownPeriodTooLow
                 );
                 require!(
-                    period <= GovernanceState::MAX_COOLDOWN_SECONDS,
+                    period <= Self::MAX_COOLDOWN_SECONDS,
                     GovernanceError::CooldownPeriodTooHigh
                 );
-                governance_state.cooldown_period = period;
-                msg!(
-                    "Transaction {} executed: SetCooldownPeriod = {}",
-                    tx_id,
-                    period
+            }
+            TransactionType::AddSigner => {
+                require!(data.len() >= 32, GovernanceError::InvalidDataLength);
+                let signer = read_pubkey(0..32)?;
+                require!(
+                    !self.is_primary_signer(&signer),
+                    GovernanceError::DuplicateSigners
+                );
+                require!(
+                    self.signers.len() < Self::MAX_SIGNERS,
+                    GovernanceError::TooManySigners
+                );
+            }
+            TransactionType::RemoveSigner => {
+                require!(data.len() >= 32, GovernanceError::InvalidDataLength);
+                let signer = read_pubkey(0..32)?;
+                require!(
+                    self.is_primary_signer(&signer),
+                    GovernanceError::SignerNotFound
+                );
+                self.validate_signer_removal(&signer)?;
+            }
+            TransactionType::SetBridgeAddress
+            | TransactionType::SetBondAddress
+            | TransactionType::SetTreasuryAddress => {
+                require!(data.len() >= 32, GovernanceError::InvalidAccount);
+                read_pubkey(0..32)?;
+            }
+            TransactionType::SetMaxSupply => {
+                require!(data.len() >= 10, GovernanceError::InvalidDataLength);
+            }
+            TransactionType::SetTransferFee => {
+                require!(data.len() >= 36, GovernanceError::InvalidDataLength);
+                let fee_bps = u16::from_le_bytes([data[0], data[1]]);
+                let burn = data[2] != 0;
+                let treasury_address = read_pubkey(3..35)?;
+                require!(
+                    fee_bps <= spl_project::TokenState::MAX_TRANSFER_FEE_BPS
+                        && (fee_bps == 0 || burn || treasury_address != Pubkey::default()),
+                    GovernanceError::InvalidTransferFee
+                );
+            }
+            TransactionType::SetVestingConfig => {
+                require!(data.len() >= 17, GovernanceError::InvalidDataLength);
+            }
+            TransactionType::SetPurchaseLimits => {
+                require!(data.len() >= 16, GovernanceError::InvalidDataLength);
+            }
+            TransactionType::UpdatePresaleCap => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+                let new_cap = read_u64(0..8)?;
+                if let Some(presale_state) = presale_state {
+                    require!(
+                        new_cap == 0 || new_cap >= presale_state.cap_progress(),
+                        GovernanceError::InvalidAmount
+                    );
+                }
+            }
+            TransactionType::UpdateMaxPerUser => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+                let new_max = read_u64(0..8)?;
+                if let Some(presale_state) = presale_state {
+                    require!(
+                        new_max == 0
+                            || presale_state.max_presale_cap == 0
+                            || presale_state.cap_in_usd
+                            || new_max <= presale_state.max_presale_cap,
+                        GovernanceError::InvalidAmount
+                    );
+                }
+            }
+            TransactionType::SetPriceTiers => {
+                require!(
+                    data.chunks_exact(16).remainder().is_empty(),
+                    GovernanceError::InvalidDataLength
+                );
+            }
+            TransactionType::UpdateTokenPrice => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+            }
+            TransactionType::WithdrawToTreasury => {
+                require!(data.len() >= 8, GovernanceError::InvalidAccount);
+            }
+            TransactionType::BurnTokens | TransactionType::Mint => {
+                require!(data.len() >= 40, GovernanceError::InvalidDataLength);
+                read_pubkey(0..32)?;
+                require!(read_u64(32..40)? > 0, GovernanceError::InvalidAmount);
+            }
+            TransactionType::MintWithVesting => {
+                require!(data.len() >= 56, GovernanceError::InvalidDataLength);
+                require!(read_pubkey(0..32)? != Pubkey::default(), GovernanceError::InvalidAccount);
+                require!(read_u64(32..40)? > 0, GovernanceError::InvalidAmount);
+                let cliff_seconds = read_u64(40..48)? as i64;
+                let duration_seconds = read_u64(48..56)? as i64;
+                require!(
+                    duration_seconds > 0 && (0..=duration_seconds).contains(&cliff_seconds),
+                    GovernanceError::InvalidVestingConfig
+                );
+            }
+            TransactionType::SetEmissionSchedule => {
+                require!(data.len() >= 56, GovernanceError::InvalidDataLength);
+                let recipient = read_pubkey(8..40)?;
+                let start_time = read_u64(40..48)? as i64;
+                let end_time = read_u64(48..56)? as i64;
+                require!(
+                    end_time > start_time && recipient != Pubkey::default(),
+                    GovernanceError::InvalidEmissionSchedule
+                );
+            }
+            TransactionType::SetSignerWeight => {
+                require!(data.len() >= 33, GovernanceError::InvalidDataLength);
+                let signer = read_pubkey(0..32)?;
+                self.validate_signer_weight(&signer, data[32])?;
+            }
+            TransactionType::SetExpiryWindow => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+                let window = read_u64(0..8)? as i64;
+                require!(
+                    (Self::MIN_EXPIRY_WINDOW_SECONDS..=Self::MAX_EXPIRY_WINDOW_SECONDS)
+                        .contains(&window),
+                    GovernanceError::InvalidExpiryWindow
+                );
+            }
+            TransactionType::SetApprovalCooldown => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+                let cooldown = read_u64(0..8)? as i64;
+                require!(
+                    (0..=Self::MAX_APPROVAL_COOLDOWN_SECONDS).contains(&cooldown),
+                    GovernanceError::InvalidCooldownPeriod
+                );
+            }
+            TransactionType::SetKeeperReward => {
+                require!(data.len() >= 8, GovernanceError::InvalidDataLength);
+                require!(
+                    read_u64(0..8)? <= Self::MAX_KEEPER_REWARD_LAMPORTS,
+                    GovernanceError::InvalidAmount
+                );
+            }
+            TransactionType::SetHighValuePolicy => {
+                require!(data.len() >= 9, GovernanceError::InvalidDataLength);
+                self.validate_high_value_policy(read_u64(0..8)?, data[8])?;
+            }
+            TransactionType::SetTypeApprovals => {
+                require!(data.len() >= 2, GovernanceError::InvalidDataLength);
+                TransactionType::try_from_slice(&data[0..1])
+                    .map_err(|_| GovernanceError::InvalidDataLength)?;
+                self.validate_type_approvals(data[1])?;
+            }
+            TransactionType::SetGovernanceOpsDuringPause
+            | TransactionType::ToggleWhitelistMode
+            | TransactionType::SetSellTrackerMode => {
+                require!(!data.is_empty(), GovernanceError::InvalidDataLength);
+            }
+            TransactionType::SetCircuitBreaker => {
+                require!(data.len() >= 3, GovernanceError::InvalidDataLength);
+                let threshold_bps = u16::from_le_bytes([data[1], data[2]]);
+                require!(
+                    (spl_project::TokenState::MIN_CIRCUIT_BREAKER_BPS..=10_000).contains(&threshold_bps),
+                    GovernanceError::InvalidCircuitBreaker
                 );
             }
-            TransactionType::SetBridgeAddress => {
-                if transaction.data.len() < 32 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let bridge_address = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
+            TransactionType::Seize => {
+                require!(data.len() >= 72, GovernanceError::InvalidDataLength);
+                read_pubkey(0..32)?;
+                read_pubkey(32..64)?;
+                require!(read_u64(64..72)? > 0, GovernanceError::InvalidAmount);
+            }
+            TransactionType::SetBackupSigners => {
+                let (backup_signers, activation_period) = Self::decode_backup_signers(data)?;
+                self.validate_backup_signers(&backup_signers, activation_period)?;
+            }
+        }
+        // Trailing bytes are rejected at execution too
+        require!(
+            tx_type.is_valid_data_len(data.len()),
+            GovernanceError::InvalidDataLength
+        );
+        Ok(())
+    }
+
+    /// Whether blacklisting `account` would lock out the protocol's own accounts:
+    /// the governance PDA, or the presale state and SOL vault PDAs once linked.
+    /// The token program also rejects its configured protected addresses.
+    pub fn is_protected_address(&self, governance_key: &Pubkey, account: &Pubkey) -> bool {
+        if account == governance_key {
+            return true;
+        }
+        if !self.presale_program_set {
+            return false;
+        }
+        let (presale_state, _) =
+            Pubkey::find_program_address(&[b"presale_state"], &self.presale_program);
+        let (sol_vault, _) = Pubkey::find_program_address(
+            &[b"presale_sol_vault", presale_state.as_ref()],
+            &self.presale_program,
+        );
+        *account == presale_state || *account == sol_vault
+    }
+
+    /// Returns the cooldown to apply to a newly queued transaction.
+    /// Setup-type transactions queued during the bootstrap window consume one
+    /// bootstrap slot and skip the cooldown; everything else uses the full period.
+    /// The slot is spent at queue time: it is not returned if the transaction is
+    /// later cancelled, rejected or left to expire.
+    pub fn queue_cooldown(&mut self, tx_type: TransactionType, now: i64) -> i64 {
+        if tx_type.is_setup_type()
+            && self.bootstrap_transactions_remaining > 0
+            && now <= self.bootstrap_ends_at
+        {
+            self.bootstrap_transactions_remaining -= 1;
+            msg!(
+                "Bootstrap mode: cooldown waived ({} exemptions remaining)",
+                self.bootstrap_transactions_remaining
+            );
+            return 0;
+        }
+        self.cooldown_period
+    }
+}
+
+#[account]
+pub struct Transaction {
+    pub id: u64,
+    pub tx_type: TransactionType,
+    pub status: TransactionStatus,
+    pub initiator: Pubkey,
+    pub target: Pubkey,
+    pub data: Vec<u8>, // Encoded parameters
+    pub timestamp: i64,
+    pub execute_after: i64,
+    pub approval_count: u8,
+    pub approvals: Vec<Pubkey>, // Max primary + backup signers
+    pub rejection_reason: String,
+    pub rejector: Pubkey,
+    pub expires_at: i64, // Execution rejected after this time (0 = never expires)
+    pub description: String, // Initiator's note for approvers (max 128 bytes)
+    pub approval_weight: u16, // Summed signer weight of `approvals`
+    pub token_program_index: u8, // Token program the transaction acts on (see GovernanceState::token_program_at)
+}
+
+impl Transaction {
+    pub const MAX_LEN: usize =
+        8 + 8 + 1 + 1 + 32 + 32 + 4 + Self::MAX_DATA_LEN + 8 + 8 + 1 + 4
+            + (32 * (GovernanceState::MAX_SIGNERS + GovernanceState::MAX_BACKUP_SIGNERS)) + 4 + (256) + 32 + 8
+            + 4 + Self::MAX_DESCRIPTION_LEN + 2 + 1;
+    pub const MAX_DESCRIPTION_LEN: usize = 128;
+    pub const MAX_DATA_LEN: usize = 660; // Fits a full BlacklistBatch (20 * 33 bytes)
+
+    pub fn has_approved(&self, approver: Pubkey) -> bool {
+        self.approvals.contains(&approver)
+    }
+
+    /// Expiry appended to NoSellLimit data (bytes 33..41); `None` for a permanent exemption
+    pub fn no_sell_limit_expiry(&self) -> Option<i64> {
+        self.data
+            .get(33..41)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(i64::from_le_bytes)
+    }
+
+    pub fn add_approval(&mut self, approver: Pubkey, weight: u8) {
+        if !self.approvals.contains(&approver) {
+            self.approvals.push(approver);
+            self.approval_count += 1;
+            self.approval_weight = self.approval_weight.saturating_add(weight as u16);
+        }
+    }
+
+    /// Removes an approval added by `add_approval`; returns false if the
+    /// approver had not approved
+    pub fn remove_approval(&mut self, approver: Pubkey, weight: u8) -> bool {
+        match self.approvals.iter().position(|a| *a == approver) {
+            Some(index) => {
+                self.approvals.remove(index);
+                self.approval_count -= 1;
+                self.approval_weight = self.approval_weight.saturating_sub(weight as u16);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Approval weight to compare against the quorum; transactions approved before
+    /// weights existed have no recorded weight and count one per approval
+    pub fn effective_approval_weight(&self) -> u16 {
+        self.approval_weight.max(self.approval_count as u16)
+    }
+
+    /// Whether the transaction is pending, past its cooldown, not expired and
+    /// approved by at least two signers carrying the required weight
+    pub fn is_executable(&self, now: i64, required_approvals: u8) -> bool {
+        self.status == TransactionStatus::Pending
+            && now >= self.execute_after
+            && (self.expires_at == 0 || now <= self.expires_at)
+            && self.approval_count >= GovernanceState::MIN_REQUIRED_APPROVALS
+            && self.effective_approval_weight() >= required_approvals as u16
+    }
+}
+
+#[account]
+pub struct Role {
+    pub account: Pubkey,
+    pub role: u8,
+    pub has_role: bool,
+}
+
+impl Role {
+    pub const LEN: usize = 8 + 32 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum TransactionType {
+    Unpause,
+    Blacklist,
+    NoSellLimit,
+    Restrict,
+    Pair,
+    SetRequiredApprovals,
+    SetCooldownPeriod,
+    SetBridgeAddress,
+    SetBondAddress,
+    SetTreasuryAddress,
+    WithdrawToTreasury,
+    BurnTokens,
+    AddSigner,
+    RemoveSigner,
+    SetHighValuePolicy,
+    SetExpiryWindow,
+    Mint,
+    SetSignerWeight,
+    SetVestingConfig,
+    SetMaxSupply,
+    UpdateTokenPrice,
+    SetPurchaseLimits,
+    StartPresale,
+    StopPresale,
+    UpdatePresaleCap,
+    UpdateMaxPerUser,
+    SetApprovalCooldown,
+    SetPriceTiers,
+    PairBatch,
+    SetKeeperReward,
+    BlacklistBatch,
+    SetTypeApprovals,
+    SetTransferFee,
+    SetBackupSigners,
+    Seize,
+    SetCircuitBreaker,
+    SetGovernanceOpsDuringPause,
+    ToggleWhitelistMode,
+    MintWithVesting,
+    SetEmissionSchedule,
+    SetSellTrackerMode,
+}
+
+impl TransactionType {
+    /// Whether `len` is a data length the queue instruction for this type can produce
+    ///
+    /// Fixed-size encodings must match exactly. Batch, price tier and backup signer
+    /// data is variable-length and checked by its own decoder.
+    pub fn is_valid_data_len(&self, len: usize) -> bool {
+        match self {
+            TransactionType::Unpause
+            | TransactionType::StartPresale
+            | TransactionType::StopPresale => len == 0,
+            TransactionType::SetRequiredApprovals
+            | TransactionType::SetGovernanceOpsDuringPause
+            | TransactionType::ToggleWhitelistMode
+            | TransactionType::SetSellTrackerMode => len == 1,
+            TransactionType::SetTypeApprovals => len == 2,
+            TransactionType::SetCircuitBreaker => len == 3,
+            TransactionType::SetCooldownPeriod
+            | TransactionType::UpdatePresaleCap
+            | TransactionType::UpdateMaxPerUser
+            | TransactionType::UpdateTokenPrice
+            | TransactionType::WithdrawToTreasury
+            | TransactionType::SetExpiryWindow
+            | TransactionType::SetApprovalCooldown
+            | TransactionType::SetKeeperReward => len == 8,
+            TransactionType::SetHighValuePolicy => len == 9,
+            TransactionType::SetMaxSupply => len == 10,
+            TransactionType::SetPurchaseLimits => len == 16,
+            TransactionType::SetVestingConfig => len == 17,
+            TransactionType::AddSigner
+            | TransactionType::RemoveSigner
+            | TransactionType::SetBridgeAddress
+            | TransactionType::SetBondAddress
+            | TransactionType::SetTreasuryAddress => len == 32,
+            TransactionType::Blacklist
+            | TransactionType::Restrict
+            | TransactionType::Pair
+            | TransactionType::SetSignerWeight => len == 33,
+            // Time-bounded grants append an 8-byte expiry
+            TransactionType::NoSellLimit => len == 33 || len == 41,
+            TransactionType::SetTransferFee => len == 36,
+            TransactionType::BurnTokens | TransactionType::Mint => len == 40,
+            TransactionType::MintWithVesting | TransactionType::SetEmissionSchedule => len == 56,
+            TransactionType::Seize => len == 72,
+            TransactionType::PairBatch
+            | TransactionType::BlacklistBatch
+            | TransactionType::SetPriceTiers
+            | TransactionType::SetBackupSigners => true,
+        }
+    }
+
+    /// Initial configuration transactions eligible for the bootstrap cooldown exemption
+    pub fn is_setup_type(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::Blacklist
+                | TransactionType::NoSellLimit
+                | TransactionType::Restrict
+                | TransactionType::Pair
+                | TransactionType::SetBridgeAddress
+                | TransactionType::SetBondAddress
+                | TransactionType::SetTreasuryAddress
+        )
+    }
+
+    /// Transactions executed by CPI into a token program
+    pub fn targets_token_program(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::Unpause
+                | TransactionType::Blacklist
+                | TransactionType::BlacklistBatch
+                | TransactionType::NoSellLimit
+                | TransactionType::Restrict
+                | TransactionType::Pair
+                | TransactionType::PairBatch
+                | TransactionType::SetBridgeAddress
+                | TransactionType::SetBondAddress
+                | TransactionType::SetMaxSupply
+                | TransactionType::SetTransferFee
+                | TransactionType::SetGovernanceOpsDuringPause
+                | TransactionType::ToggleWhitelistMode
+                | TransactionType::SetCircuitBreaker
+                | TransactionType::BurnTokens
+                | TransactionType::Seize
+                | TransactionType::Mint
+                | TransactionType::MintWithVesting
+                | TransactionType::SetEmissionSchedule
+                | TransactionType::SetSellTrackerMode
+        )
+    }
+}
+
+/// One entry of a PairBatch transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PoolFlag {
+    pub pool: Pubkey,
+    pub is_pool: bool,
+}
+
+/// Required approvals override for one transaction type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TypeApproval {
+    pub tx_type: TransactionType,
+    pub required: u8,
+}
+
+impl TypeApproval {
+    pub const LEN: usize = 1 + 1; // tx_type + required
+}
+
+/// One entry of a BlacklistBatch transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct BlacklistFlag {
+    pub account: Pubkey,
+    pub blacklisted: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TransactionStatus {
+    Pending,
+    Rejected,
+    Executed,
+    Cancelled,
+}
+
+// Role constants
+// pub const ADMIN_ROLE: u8 = 1;
+// pub const SIGNER_ROLE: u8 = 2;
+// pub const APPROVER_ROLE: u8 = 3;
+// pub const MANAGER_ROLE: u8 = 4;
+
+// Error codes
+#[error_code]
+pub enum GovernanceError {
+    #[msg("Token program not set")]
+    TokenProgramNotSet,
+    #[msg("Token program already set")]
+    TokenProgramAlreadySet,
+    #[msg("No token program is registered at this index, or it is the primary program")]
+    InvalidTokenProgramIndex,
+    #[msg("Token program is already registered")]
+    DuplicateTokenProgram,
+    #[msg("Too many token programs registered")]
+    TooManyTokenPrograms,
+    #[msg("Presale program not set")]
+    PresaleProgramNotSet,
+    #[msg("Presale program already set")]
+    PresaleProgramAlreadySet,
+    #[msg("Invalid transaction ID")]
+    InvalidTransactionId,
+    #[msg("Transaction not pending")]
+    TransactionNotPending,
+    #[msg("Already approved")]
+    AlreadyApproved,
+    #[msg("Cooldown not expired")]
+    CooldownNotExpired,
+    #[msg("Insufficient approvals")]
+    InsufficientApprovals,
+    #[msg("Empty rejection reason")]
+    EmptyRejectionReason,
+    #[msg("Invalid required approvals")]
+    InvalidRequiredApprovals,
+    #[msg("Invalid cooldown period")]
+    InvalidCooldownPeriod,
+    #[msg("Cooldown period too low")]
+    CooldownPeriodTooLow,
+    #[msg("Cooldown period too high")]
+    CooldownPeriodTooHigh,
+    #[msg("Invalid account")]
+    InvalidAccount,
+    #[msg("Invalid role")]
+    InvalidRole,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Not an authorized signer")]
+    NotAuthorizedSigner,
+    #[msg("Required approvals must be at least 2")]
+    RequiredApprovalsTooLow,
+    #[msg("Required approvals exceeds signer count")]
+    RequiredApprovalsTooHigh,
+    #[msg("Duplicate signers in signer list")]
+    DuplicateSigners,
+    #[msg("Invalid data length")]
+    InvalidDataLength,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Invalid bootstrap window or transaction count")]
+    InvalidBootstrapConfig,
+    #[msg("Maximum number of signers reached")]
+    TooManySigners,
+    #[msg("Signer not found")]
+    SignerNotFound,
+    #[msg("Transaction already has approvals")]
+    TransactionHasApprovals,
+    #[msg("Transaction has expired")]
+    TransactionExpired,
+    #[msg("Invalid expiry window")]
+    InvalidExpiryWindow,
+    #[msg("Description too long (max 128 bytes)")]
+    DescriptionTooLong,
+    #[msg("Signer weight must be at least 1")]
+    InvalidSignerWeight,
+    #[msg("Batch must contain between 1 and 5 transactions")]
+    InvalidBatchSize,
+    #[msg("Remaining accounts don't match the batch")]
+    InvalidBatchAccounts,
+    #[msg("Invalid vesting cliff or duration")]
+    InvalidVestingConfig,
+    #[msg("Removing the supply cap requires confirmation")]
+    MaxSupplyRemovalNotConfirmed,
+    #[msg("Approval cooldown has not elapsed since the transaction was queued")]
+    ApprovalTooEarly,
+    #[msg("Signer has not approved this transaction")]
+    NotApproved,
+    #[msg("Address is protected and cannot be blacklisted")]
+    CannotBlacklistProtected,
+    #[msg("Too many per-type approval overrides")]
+    TooManyTypeApprovals,
+    #[msg("Transfer fee is above the maximum or has no destination")]
+    InvalidTransferFee,
+    #[msg("Invalid backup signer set or activation period")]
+    InvalidBackupSigners,
+    #[msg("Exemption expiry must be after the cooldown and only set when granting")]
+    InvalidExemptionExpiry,
+    #[msg("Circuit breaker threshold must be between 50 and 10000 bps")]
+    InvalidCircuitBreaker,
+    #[msg("Emission schedule must end after it starts and have a recipient")]
+    InvalidEmissionSchedule,
+}
+
+// Context structures
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GovernanceState::LEN,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGovernanceState<'info> {
+    /// CHECK: Legacy GovernanceState that may not fit the current layout; owner and
+    /// discriminator are verified in migrate_governance_state
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTokenProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump,
+        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageTokenPrograms<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueUnpause<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetBlacklist<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetNoSellLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetRestricted<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetLiquidityPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetLiquidityPools<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetBlacklistBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub approver: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RejectTransaction<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTransaction<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        bump,
+        has_one = initiator,
+        close = initiator
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Original initiator of the transaction; receives the rent refund (validated by has_one)
+    #[account(mut)]
+    pub initiator: UncheckedAccount<'info>,
+
+    pub canceller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Token program state PDA
+    #[account(mut)]
+    pub state_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program to CPI into; must match the transaction's token_program_index
+    /// for token transactions (validated in function)
+    #[account(executable)]
+    pub token_program_program: UncheckedAccount<'info>,
+
+    /// CHECK: Presale program state PDA (for treasury and vesting operations)
+    #[account(mut)]
+    pub presale_state_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Presale program
+    pub presale_program_program: Program<'info, presale::program::Presale>,
+
+    /// CHECK: Presale payment vault PDA (for withdrawals)
+    pub presale_payment_vault_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Presale payment vault ATA
+    #[account(mut)]
+    pub presale_payment_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Treasury token account ATA
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Payment token mint
+    pub payment_token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token program (for withdrawals)
+    pub spl_token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Associated token program
+    pub associated_token_program: UncheckedAccount<'info>,
+
+    /// CHECK: System program (needed for CPI account creation)
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Payer for CPI account creation (governance state)
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    // Optional accounts for Blacklist, NoSellLimit, Restrict, Pair transactions
+    /// CHECK: Blacklist account (for Blacklist transaction)
+    #[account(mut)]
+    pub blacklist_account: UncheckedAccount<'info>,
+
+    /// CHECK: Account being blacklisted/restricted/etc (for Blacklist, NoSellLimit, Restrict transactions)
+    pub target_account: UncheckedAccount<'info>,
+
+    /// CHECK: NoSellLimit account (for NoSellLimit transaction)
+    #[account(mut)]
+    pub no_sell_limit_account: UncheckedAccount<'info>,
+
+    /// CHECK: Restricted account (for Restrict transaction)
+    #[account(mut)]
+    pub restricted_account: UncheckedAccount<'info>,
+
+    /// CHECK: LiquidityPool account (for Pair transaction)
+    #[account(mut)]
+    pub liquidity_pool_account: UncheckedAccount<'info>,
+
+    /// CHECK: Pool address (for Pair transaction)
+    pub pool_address: UncheckedAccount<'info>,
+
+    /// CHECK: Token mint (for BurnTokens/Mint transactions, validated by token program)
+    #[account(mut)]
+    pub token_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token account to burn from (for BurnTokens transaction, validated against queued data)
+    #[account(mut)]
+    pub burn_from_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token account to mint to (for Mint transaction, validated against queued data)
+    #[account(mut)]
+    pub mint_to_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Recipient owner's blacklist PDA (for Mint transaction, validated in function)
+    pub recipient_blacklist: Option<UncheckedAccount<'info>>,
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetBridgeAddress {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_bridge_address(cpi_ctx, bridge_address)?;
-                msg!("Transaction {} executed: SetBridgeAddress = {}", tx_id, bridge_address);
-            }
-            TransactionType::SetBondAddress => {
-                if transaction.data.len() < 32 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let bond_address = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
+    /// CHECK: Source owner's blacklist PDA (for Seize transaction, validated by token program).
+    /// Seize uses `burn_from_account` as the source and `mint_to_account` as the destination.
+    pub source_blacklist: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Presale AllowedToken PDA for `payment_token_mint` (for WithdrawToTreasury, validated by presale program)
+    pub presale_allowed_token: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Beneficiary's VestingSchedule PDA (for MintWithVesting, validated by token program)
+    #[account(mut)]
+    pub vesting_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Beneficiary's vesting vault PDA (for MintWithVesting, validated by token program)
+    #[account(mut)]
+    pub vesting_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: EmissionSchedule PDA (for SetEmissionSchedule, validated by token program)
+    #[account(mut)]
+    pub emission_schedule: Option<UncheckedAccount<'info>>,
+
+    /// Keeper executing the transaction; receives `keeper_reward` when set
+    #[account(mut)]
+    pub executor: Option<Signer<'info>>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransactionsBatch<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    /// CHECK: Token program state PDA
+    #[account(mut)]
+    pub state_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Token program program
+    pub token_program_program: Program<'info, spl_project::program::SplProject>,
+
+    /// Payer for CPI account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateTransactionParams<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequiredApprovals<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump,
+        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCooldownPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump,
+        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump,
+        // constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Role::LEN,
+        seeds = [b"role", account.key().as_ref()],
+        bump
+    )]
+    pub role_account: Account<'info, Role>,
+
+    /// CHECK: Account to grant role to
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump,
+        // constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        mut,
+        seeds = [b"role", account.key().as_ref()],
+        bump
+    )]
+    pub role_account: Account<'info, Role>,
+
+    /// CHECK: Account to revoke role from
+    pub account: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetRequiredApprovals<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetCooldownPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetBridgeAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetBondAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueSetTreasuryAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.token_program_program.to_account_info();
-                let cpi_accounts = spl_project::cpi::accounts::SetBondAddress {
-                    state: ctx.accounts.state_pda.to_account_info(),
-                    governance: ctx.accounts.governance_state.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                spl_project::cpi::set_bond_address(cpi_ctx, bond_address)?;
-                msg!("Transaction {} executed: SetBondAddress = {}", tx_id, bond_address);
-            }
-            TransactionType::SetTreasuryAddress => {
-                if transaction.data.len() < 32 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let treasury_address = Pubkey::try_from_slice(&transaction.data[0..32])
-                    .map_err(|_| GovernanceError::InvalidAccount)?;
+#[derive(Accounts)]
+pub struct QueueSetMaxSupply<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
-                let cpi_accounts = presale::cpi::accounts::SetTreasuryAddress {
-                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
-                    authority: ctx.accounts.governance_state.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                presale::cpi::set_treasury_address(cpi_ctx, treasury_address)?;
-                msg!("Transaction {} executed: SetTreasuryAddress = {}", tx_id, treasury_address);
-            }
-            TransactionType::WithdrawToTreasury => {
-                if transaction.data.len() < 8 {
-                    return Err(GovernanceError::InvalidAccount.into());
-                }
-                let amount = u64::from_le_bytes(
-                    transaction.data[0..8]
-                        .try_into()
-                        .map_err(|_| GovernanceError::InvalidAccount)?,
-                );
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-                // Get bump before mutable borrow
-                let bump = governance_state.bump;
-                let cpi_program = ctx.accounts.presale_program_program.to_account_info();
-                let cpi_accounts = presale::cpi::accounts::WithdrawToTreasury {
-                    presale_state: ctx.accounts.presale_state_pda.to_account_info(),
-                    authority: ctx.accounts.governance_state.to_account_info(),
-                    presale_payment_vault_pda: ctx.accounts.presale_payment_vault_pda.to_account_info(),
-                    presale_payment_vault: ctx.accounts.presale_payment_vault.to_account_info(),
-                    treasury_token_account: ctx.accounts.treasury_token_account.to_account_info(),
-                    payment_token_mint: ctx.accounts.payment_token_mint.to_account_info(),
-                    token_program: ctx.accounts.spl_token_program.to_account_info(),
-                    associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-                };
-                // Sign with governance state PDA
-                let governance_seeds = &[b"governance".as_ref(), &[bump]];
-                let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-                presale::cpi::withdraw_to_treasury(cpi_ctx, amount)?;
-                msg!("Transaction {} executed: WithdrawToTreasury = {}", tx_id, amount);
-            }
-        }
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-        // Transaction status already set to Executed at start for reentrancy protection
-        msg!("Transaction {} executed successfully", tx_id);
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
 
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct QueueSetTransferFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-    /// Set required approvals (REMOVED - must use queued transaction)
-    /// This function is kept for backwards compatibility but should not be used.
-    /// Use queue_set_required_approvals instead.
-    /// DEPRECATED: Direct setter bypasses queue mechanism
-    /// Use queue_set_required_approvals instead
-    pub fn set_required_approvals(ctx: Context<SetRequiredApprovals>, required: u8) -> Result<()> {
-        let governance_state = &mut ctx.accounts.governance_state;
-        require!(
-            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
-            GovernanceError::NotAuthorizedSigner
-        );
-        
-        require!(
-            required >= GovernanceState::MIN_REQUIRED_APPROVALS,
-            GovernanceError::RequiredApprovalsTooLow
-        );
-        require!(
-            governance_state.authority == ctx.accounts.authority.key(),
-            GovernanceError::Unauthorized
-        );
-        require!(
-            required <= governance_state.signers.len() as u8,
-            GovernanceError::RequiredApprovalsTooHigh
-        );
-        governance_state.required_approvals = required;
-        msg!("Required approvals set to {} (DEPRECATED: use queue mechanism)", required);
-        Ok(())
-    }
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-    /// DEPRECATED: Direct setter bypasses queue mechanism
-    /// Use queue_set_cooldown_period instead
-    pub fn set_cooldown_period(ctx: Context<SetCooldownPeriod>, period: i64) -> Result<()> {
-        let governance_state = &mut ctx.accounts.governance_state;
-        require!(
-            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
-            GovernanceError::NotAuthorizedSigner
-        );
-        
-        require!(
-            period >= GovernanceState::MIN_COOLDOWN_SECONDS,
-            GovernanceError::CooldownPeriodTooLow
-        );
-        require!(
-            governance_state.authority == ctx.accounts.authority.key(),
-            GovernanceError::Unauthorized
-        );
-        governance_state.cooldown_period = period;
-        msg!("Cooldown period set to {} seconds (DEPRECATED: use queue mechanism)", period);
-        Ok(())
-    }
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-    /// Grant a role
-    pub fn grant_role(ctx: Context<GrantRole>, role: u8, account: Pubkey) -> Result<()> {
-        let governance_state = &ctx.accounts.governance_state;
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
 
-        require!(governance_state.is_authorized_signer(&ctx.accounts.authority.key()), GovernanceError::NotAuthorizedSigner);
+#[derive(Accounts)]
+pub struct QueueSetCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-        require!(account != ctx.accounts.authority.key(), GovernanceError::Unauthorized);
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-        let role_account = &mut ctx.accounts.role_account;
-        role_account.account = account;
-        role_account.role = role;
-        role_account.has_role = true;
-        msg!("Role {} granted to {} by {}", role, account, ctx.accounts.authority.key());
-        Ok(())
-    }
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-    /// Revoke a role
-    pub fn revoke_role(ctx: Context<RevokeRole>, role: u8, account: Pubkey) -> Result<()> {
-        let governance_state = &ctx.accounts.governance_state;
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
 
-        require!(governance_state.is_authorized_signer(&ctx.accounts.authority.key()), GovernanceError::NotAuthorizedSigner);
+#[derive(Accounts)]
+pub struct QueueSetGovernanceOpsDuringPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-        let role_account = &mut ctx.accounts.role_account;
-        require!(
-            role_account.account == account,
-            GovernanceError::InvalidAccount
-        );
-        require!(role_account.role == role, GovernanceError::InvalidRole);
-        role_account.has_role = false;
-        msg!("Role {} revoked from {} by {}", role, account, ctx.accounts.authority.key());
-        Ok(())
-    }
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-    /// Emergency pause (1 signer allowed, no cooldown)
-    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
-        let governance_state = &ctx.accounts.governance_state;
-        // Allow any authorized signer to pause
-        require!(
-            governance_state.is_authorized_signer(&ctx.accounts.authority.key()),
-            GovernanceError::NotAuthorizedSigner
-        );
-        require!(
-            governance_state.token_program_set,
-            GovernanceError::TokenProgramNotSet
-        );
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-        // Call token program's set_emergency_pause via CPI
-        // The governance PDA must sign, not the individual authority
-        let cpi_program = ctx.accounts.token_program_program.to_account_info();
-        let cpi_accounts = spl_project::cpi::accounts::SetEmergencyPause {
-            state: ctx.accounts.state_pda.to_account_info(),
-            governance: ctx.accounts.governance_state.to_account_info(),
-        };
-        let governance_seeds = &[b"governance".as_ref(), &[governance_state.bump]];
-        let signer_seeds: &[&[&[u8]]] = &[governance_seeds];
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct QueueToggleWhitelistMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-        spl_project::cpi::set_emergency_pause(cpi_ctx, true)?;
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-        msg!(
-            "Emergency pause activated by {}",
-            ctx.accounts.authority.key()
-        );
-        Ok(())
-    }
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
-// Account Structures
+#[derive(Accounts)]
+pub struct QueueSetVestingConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-#[account]
-pub struct GovernanceState {
-    pub authority: Pubkey,
-    pub required_approvals: u8,
-    pub cooldown_period: i64, // in seconds (90 minutes = 5400)
-    pub next_transaction_id: u64,
-    pub token_program: Pubkey,
-    pub token_program_set: bool,
-    pub presale_program: Pubkey,
-    pub presale_program_set: bool,
-    pub bump: u8,
-    pub signers: Vec<Pubkey>, // Authorized signers (max 10)
-}
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-impl GovernanceState {
-    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 32 + 1 + 32 + 1 + 1 + 4 + (32 * 10); // discriminator + fields + vec overhead + max 10 signers
-    pub const MIN_REQUIRED_APPROVALS: u8 = 2;
-    pub const MIN_COOLDOWN_SECONDS: i64 = 1800; // 30 minutes
-    pub const MAX_COOLDOWN_SECONDS: i64 = 2592000; // 30 days
-    pub const MAX_SIGNERS: usize = 10;
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-    pub fn is_authorized_signer(&self, signer: &Pubkey) -> bool {
-        self.signers.contains(signer)
-    }
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
-#[account]
-pub struct Transaction {
-    pub id: u64,
-    pub tx_type: TransactionType,
-    pub status: TransactionStatus,
-    pub initiator: Pubkey,
-    pub target: Pubkey,
-    pub data: Vec<u8>, // Encoded parameters
-    pub timestamp: i64,
-    pub execute_after: i64,
-    pub approval_count: u8,
-    pub approvals: Vec<Pubkey>, // Max 10 approvers
-    pub rejection_reason: String,
-    pub rejector: Pubkey,
-}
+#[derive(Accounts)]
+pub struct QueueUpdatePresaleCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-impl Transaction {
-    pub const MAX_LEN: usize =
-        8 + 8 + 1 + 1 + 32 + 32 + 4 + (256) + 8 + 8 + 1 + 4 + (32 * 10) + 4 + (256) + 32;
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-    pub fn has_approved(&self, approver: Pubkey) -> bool {
-        self.approvals.contains(&approver)
-    }
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-    pub fn add_approval(&mut self, approver: Pubkey) {
-        if !self.approvals.contains(&approver) {
-            self.approvals.push(approver);
-            self.approval_count += 1;
-        }
-    }
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
-#[account]
-pub struct Role {
-    pub account: Pubkey,
-    pub role: u8,
-    pub has_role: bool,
-}
+#[derive(Accounts)]
+pub struct QueueUpdateMaxPerUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-impl Role {
-    pub const LEN: usize = 8 + 32 + 1 + 1;
-}
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
-pub enum TransactionType {
-    Unpause,
-    Blacklist,
-    NoSellLimit,
-    Restrict,
-    Pair,
-    SetRequiredApprovals,
-    SetCooldownPeriod,
-    SetBridgeAddress,
-    SetBondAddress,
-    SetTreasuryAddress,
-    WithdrawToTreasury,
-}
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum TransactionStatus {
-    Pending,
-    Rejected,
-    Executed,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
-// Role constants
-// pub const ADMIN_ROLE: u8 = 1;
-// pub const SIGNER_ROLE: u8 = 2;
-// pub const APPROVER_ROLE: u8 = 3;
-// pub const MANAGER_ROLE: u8 = 4;
+#[derive(Accounts)]
+pub struct QueueSetPriceTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
 
-// Error codes
-#[error_code]
-pub enum GovernanceError {
-    #[msg("Token program not set")]
-    TokenProgramNotSet,
-    #[msg("Token program already set")]
-    TokenProgramAlreadySet,
-    #[msg("Presale program not set")]
-    PresaleProgramNotSet,
-    #[msg("Presale program already set")]
-    PresaleProgramAlreadySet,
-    #[msg("Invalid transaction ID")]
-    InvalidTransactionId,
-    #[msg("Transaction not pending")]
-    TransactionNotPending,
-    #[msg("Already approved")]
-    AlreadyApproved,
-    #[msg("Cooldown not expired")]
-    CooldownNotExpired,
-    #[msg("Insufficient approvals")]
-    InsufficientApprovals,
-    #[msg("Empty rejection reason")]
-    EmptyRejectionReason,
-    #[msg("Invalid required approvals")]
-    InvalidRequiredApprovals,
-    #[msg("Invalid cooldown period")]
-    InvalidCooldownPeriod,
-    #[msg("Cooldown period too low")]
-    CooldownPeriodTooLow,
-    #[msg("Cooldown period too high")]
-    CooldownPeriodTooHigh,
-    #[msg("Invalid account")]
-    InvalidAccount,
-    #[msg("Invalid role")]
-    InvalidRole,
-    #[msg("Unauthorized")]
-    Unauthorized,
-    #[msg("Not an authorized signer")]
-    NotAuthorizedSigner,
-    #[msg("Required approvals must be at least 2")]
-    RequiredApprovalsTooLow,
-    #[msg("Required approvals exceeds signer count")]
-    RequiredApprovalsTooHigh,
-    #[msg("Duplicate signers in signer list")]
-    DuplicateSigners,
-    #[msg("Invalid data length")]
-    InvalidDataLength,
-    #[msg("Invalid amount")]
-    InvalidAmount,
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
-// Context structures
+#[derive(Accounts)]
+pub struct QueueStartPresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct QueueStopPresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
     #[account(
         init,
-        payer = authority,
-        space = 8 + GovernanceState::LEN,
-        seeds = [b"governance"],
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub governance_state: Account<'info, GovernanceState>,
+    pub transaction: Account<'info, Transaction>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub initiator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct SetTokenProgram<'info> {
+pub struct QueueSetPurchaseLimits<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
-        bump = governance_state.bump,
-        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+        bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
-    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct QueueUnpause<'info> {
+pub struct QueueUpdateTokenPrice<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1641,6 +8154,14 @@ pub struct QueueUnpause<'info> {
     )]
     pub transaction: Account<'info, Transaction>,
 
+    // Presale state, read for the current price reported in `PriceUpdateQueued`
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        seeds::program = governance_state.presale_program
+    )]
+    pub presale_state: Account<'info, presale::PresaleState>,
+
     #[account(mut)]
     pub initiator: Signer<'info>,
 
@@ -1649,7 +8170,7 @@ pub struct QueueUnpause<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetBlacklist<'info> {
+pub struct QueueWithdrawToTreasury<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1674,7 +8195,7 @@ pub struct QueueSetBlacklist<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetNoSellLimit<'info> {
+pub struct QueueBurnTokens<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1699,7 +8220,7 @@ pub struct QueueSetNoSellLimit<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetRestricted<'info> {
+pub struct QueueSeize<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1724,7 +8245,7 @@ pub struct QueueSetRestricted<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetLiquidityPool<'info> {
+pub struct QueueMintTokens<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1749,45 +8270,57 @@ pub struct QueueSetLiquidityPool<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ApproveTransaction<'info> {
+pub struct QueueMintWithVesting<'info> {
     #[account(
+        mut,
         seeds = [b"governance"],
         bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
     #[account(
-        mut,
-        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
     pub transaction: Account<'info, Transaction>,
 
-    pub approver: Signer<'info>,
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
+    pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct RejectTransaction<'info> {
+pub struct QueueSetEmissionSchedule<'info> {
     #[account(
+        mut,
         seeds = [b"governance"],
         bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
     #[account(
-        mut,
-        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
     pub transaction: Account<'info, Transaction>,
 
-    pub approver: Signer<'info>,
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct ExecuteTransaction<'info> {
+pub struct QueueSetSellTrackerMode<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1796,158 +8329,98 @@ pub struct ExecuteTransaction<'info> {
     pub governance_state: Account<'info, GovernanceState>,
 
     #[account(
-        mut,
-        seeds = [b"transaction", &transaction.id.to_le_bytes()],
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
     pub transaction: Account<'info, Transaction>,
 
-    /// CHECK: Token program state PDA
-    #[account(mut)]
-    pub state_pda: UncheckedAccount<'info>,
-
-    /// CHECK: Token program
-    pub token_program: UncheckedAccount<'info>,
-
-    /// CHECK: Token program program
-    pub token_program_program: Program<'info, spl_project::program::SplProject>,
-
-    /// CHECK: Presale program state PDA (for treasury operations)
-    pub presale_state_pda: UncheckedAccount<'info>,
-
-    /// CHECK: Presale program
-    pub presale_program_program: Program<'info, presale::program::Presale>,
-
-    /// CHECK: Presale payment vault PDA (for withdrawals)
-    pub presale_payment_vault_pda: UncheckedAccount<'info>,
-
-    /// CHECK: Presale payment vault ATA
-    #[account(mut)]
-    pub presale_payment_vault: UncheckedAccount<'info>,
-
-    /// CHECK: Treasury token account ATA
     #[account(mut)]
-    pub treasury_token_account: UncheckedAccount<'info>,
-
-    /// CHECK: Payment token mint
-    pub payment_token_mint: UncheckedAccount<'info>,
-
-    /// CHECK: SPL Token program (for withdrawals)
-    pub spl_token_program: UncheckedAccount<'info>,
-
-    /// CHECK: Associated token program
-    pub associated_token_program: UncheckedAccount<'info>,
+    pub initiator: Signer<'info>,
 
-    /// CHECK: System program (needed for CPI account creation)
     pub system_program: Program<'info, System>,
-
-    /// CHECK: Payer for CPI account creation (governance state)
-    #[account(mut)]
-    pub payer: UncheckedAccount<'info>,
-
-    // Optional accounts for Blacklist, NoSellLimit, Restrict, Pair transactions
-    /// CHECK: Blacklist account (for Blacklist transaction)
-    #[account(mut)]
-    pub blacklist_account: UncheckedAccount<'info>,
-
-    /// CHECK: Account being blacklisted/restricted/etc (for Blacklist, NoSellLimit, Restrict transactions)
-    pub target_account: UncheckedAccount<'info>,
-
-    /// CHECK: NoSellLimit account (for NoSellLimit transaction)
-    #[account(mut)]
-    pub no_sell_limit_account: UncheckedAccount<'info>,
-
-    /// CHECK: Restricted account (for Restrict transaction)
-    #[account(mut)]
-    pub restricted_account: UncheckedAccount<'info>,
-
-    /// CHECK: LiquidityPool account (for Pair transaction)
-    #[account(mut)]
-    pub liquidity_pool_account: UncheckedAccount<'info>,
-
-    /// CHECK: Pool address (for Pair transaction)
-    pub pool_address: UncheckedAccount<'info>,
-
     pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct SetRequiredApprovals<'info> {
+pub struct QueueSetHighValuePolicy<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
-        bump = governance_state.bump,
-        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+        bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SetCooldownPeriod<'info> {
     #[account(
-        mut,
-        seeds = [b"governance"],
-        bump = governance_state.bump,
-        constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub governance_state: Account<'info, GovernanceState>,
+    pub transaction: Account<'info, Transaction>,
 
-    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct GrantRole<'info> {
+pub struct QueueSetTypeApprovals<'info> {
     #[account(
+        mut,
         seeds = [b"governance"],
-        bump = governance_state.bump,
-        // constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+        bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = 8 + Role::LEN,
-        seeds = [b"role", account.key().as_ref()],
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub role_account: Account<'info, Role>,
-
-    /// CHECK: Account to grant role to
-    pub account: UncheckedAccount<'info>,
+    pub transaction: Account<'info, Transaction>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub initiator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct RevokeRole<'info> {
+pub struct QueueSetExpiryWindow<'info> {
     #[account(
+        mut,
         seeds = [b"governance"],
-        bump = governance_state.bump,
-        // constraint = governance_state.authority == authority.key() @ GovernanceError::Unauthorized
+        bump = governance_state.bump
     )]
     pub governance_state: Account<'info, GovernanceState>,
 
     #[account(
-        mut,
-        seeds = [b"role", account.key().as_ref()],
+        init,
+        payer = initiator,
+        space = 8 + Transaction::MAX_LEN,
+        seeds = [b"transaction", governance_state.next_transaction_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub role_account: Account<'info, Role>,
+    pub transaction: Account<'info, Transaction>,
 
-    /// CHECK: Account to revoke role from
-    pub account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub initiator: Signer<'info>,
 
-    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct QueueSetRequiredApprovals<'info> {
+pub struct QueueSetApprovalCooldown<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1972,7 +8445,7 @@ pub struct QueueSetRequiredApprovals<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetCooldownPeriod<'info> {
+pub struct QueueSetKeeperReward<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -1997,7 +8470,7 @@ pub struct QueueSetCooldownPeriod<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetBridgeAddress<'info> {
+pub struct QueueAddSigner<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -2022,7 +8495,7 @@ pub struct QueueSetBridgeAddress<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetBondAddress<'info> {
+pub struct QueueSetBackupSigners<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -2047,7 +8520,7 @@ pub struct QueueSetBondAddress<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueSetTreasuryAddress<'info> {
+pub struct QueueSetSignerWeight<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -2072,7 +8545,7 @@ pub struct QueueSetTreasuryAddress<'info> {
 }
 
 #[derive(Accounts)]
-pub struct QueueWithdrawToTreasury<'info> {
+pub struct QueueRemoveSigner<'info> {
     #[account(
         mut,
         seeds = [b"governance"],
@@ -2129,3 +8602,193 @@ pub struct EmergencyPause<'info> {
 
     pub authority: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct EmergencyEnableWhitelistMode<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance_state.bump
+    )]
+    pub governance_state: Account<'info, GovernanceState>,
+
+    /// CHECK: Token program state PDA
+    #[account(mut)]
+    pub state_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program program
+    pub token_program_program: Program<'info, spl_project::program::SplProject>,
+
+    pub authority: Signer<'info>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_transaction() -> Transaction {
+        Transaction {
+            id: 1,
+            tx_type: TransactionType::Unpause,
+            status: TransactionStatus::Pending,
+            initiator: Pubkey::new_unique(),
+            target: Pubkey::new_unique(),
+            data: vec![],
+            timestamp: 0,
+            execute_after: 0,
+            approval_count: 0,
+            approvals: vec![],
+            rejection_reason: String::new(),
+            rejector: Pubkey::default(),
+            expires_at: 0,
+            description: String::new(),
+            approval_weight: 0,
+            token_program_index: 0,
+        }
+    }
+
+    #[test]
+    fn add_approval_counts_each_signer_once() {
+        let mut tx = pending_transaction();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        tx.add_approval(first, 1);
+        tx.add_approval(second, 2);
+        assert_eq!(tx.approvals, vec![first, second]);
+        assert_eq!(tx.approval_count, 2);
+        assert_eq!(tx.approval_weight, 3);
+
+        // Approving twice changes nothing
+        tx.add_approval(first, 1);
+        assert_eq!(tx.approval_count, 2);
+        assert_eq!(tx.approval_weight, 3);
+        assert!(tx.has_approved(first) && tx.has_approved(second));
+    }
+
+    #[test]
+    fn remove_approval_undoes_add_approval() {
+        let mut tx = pending_transaction();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        tx.add_approval(first, 1);
+        tx.add_approval(second, 1);
+
+        assert!(tx.remove_approval(first, 1));
+        assert_eq!(tx.approvals, vec![second]);
+        assert_eq!(tx.approval_count, 1);
+        assert_eq!(tx.approval_weight, 1);
+        assert!(!tx.has_approved(first));
+
+        // The signer can approve again after revoking
+        tx.add_approval(first, 1);
+        assert_eq!(tx.approval_count, 2);
+        assert_eq!(tx.approval_weight, 2);
+    }
+
+    #[test]
+    fn remove_approval_ignores_signers_that_never_approved() {
+        let mut tx = pending_transaction();
+        let approver = Pubkey::new_unique();
+        assert!(!tx.remove_approval(approver, 1));
+        assert_eq!(tx.approval_count, 0);
+        assert_eq!(tx.approval_weight, 0);
+
+        tx.add_approval(approver, 2);
+        assert!(!tx.remove_approval(Pubkey::new_unique(), 2));
+        assert_eq!(tx.approvals, vec![approver]);
+        assert_eq!(tx.approval_count, 1);
+        assert_eq!(tx.approval_weight, 2);
+    }
+
+    #[test]
+    fn remove_approval_subtracts_the_signer_weight() {
+        let mut tx = pending_transaction();
+        let (heavy, light) = (Pubkey::new_unique(), Pubkey::new_unique());
+        tx.add_approval(heavy, 3);
+        tx.add_approval(light, 1);
+        assert_eq!(tx.effective_approval_weight(), 4);
+        assert!(tx.is_executable(0, 4));
+
+        assert!(tx.remove_approval(heavy, 3));
+        assert_eq!(tx.approval_count, 1);
+        assert_eq!(tx.approval_weight, 1);
+        assert_eq!(tx.effective_approval_weight(), 1);
+        assert!(!tx.is_executable(0, 2));
+
+        // Removing the last approval leaves no weight behind
+        assert!(tx.remove_approval(light, 1));
+        assert_eq!(tx.approval_count, 0);
+        assert_eq!(tx.approval_weight, 0);
+    }
+
+    const COOLDOWN: i64 = 1800;
+    const BOOTSTRAP_ENDS_AT: i64 = 10_000;
+
+    fn bootstrapping_state(bootstrap_transactions: u8) -> GovernanceState {
+        GovernanceState {
+            authority: Pubkey::new_unique(),
+            required_approvals: 2,
+            cooldown_period: COOLDOWN,
+            next_transaction_id: 1,
+            token_program: Pubkey::new_unique(),
+            token_program_set: true,
+            presale_program: Pubkey::default(),
+            presale_program_set: false,
+            bump: 255,
+            signers: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            bootstrap_ends_at: BOOTSTRAP_ENDS_AT,
+            bootstrap_transactions_remaining: bootstrap_transactions,
+            high_value_threshold: 0,
+            high_value_required_approvals: 0,
+            expiry_window: 0,
+            signer_weights: vec![],
+            approval_cooldown: 0,
+            keeper_reward: 0,
+            type_approvals: vec![],
+            last_activity: 0,
+            backup_signers: vec![],
+            backup_activation_period: 0,
+            token_programs: vec![],
+        }
+    }
+
+    #[test]
+    fn bootstrap_waives_cooldown_for_setup_transactions_in_the_window() {
+        let mut state = bootstrapping_state(2);
+        assert_eq!(state.queue_cooldown(TransactionType::Blacklist, BOOTSTRAP_ENDS_AT - 100), 0);
+        assert_eq!(state.bootstrap_transactions_remaining, 1);
+        // The window end is inclusive
+        assert_eq!(state.queue_cooldown(TransactionType::SetTreasuryAddress, BOOTSTRAP_ENDS_AT), 0);
+        assert_eq!(state.bootstrap_transactions_remaining, 0);
+    }
+
+    #[test]
+    fn bootstrap_ends_when_the_count_runs_out() {
+        let mut state = bootstrapping_state(1);
+        assert_eq!(state.queue_cooldown(TransactionType::Restrict, 0), 0);
+        assert_eq!(state.queue_cooldown(TransactionType::Restrict, 0), COOLDOWN);
+        assert_eq!(state.bootstrap_transactions_remaining, 0);
+    }
+
+    #[test]
+    fn bootstrap_ends_when_the_window_closes() {
+        let mut state = bootstrapping_state(5);
+        assert_eq!(state.queue_cooldown(TransactionType::Pair, BOOTSTRAP_ENDS_AT + 1), COOLDOWN);
+        // Unused exemptions are not spent after the window
+        assert_eq!(state.bootstrap_transactions_remaining, 5);
+    }
+
+    #[test]
+    fn bootstrap_never_waives_cooldown_for_other_transactions() {
+        let mut state = bootstrapping_state(5);
+        for tx_type in [TransactionType::Mint, TransactionType::Unpause, TransactionType::SetRequiredApprovals] {
+            assert_eq!(state.queue_cooldown(tx_type, 0), COOLDOWN);
+        }
+        assert_eq!(state.bootstrap_transactions_remaining, 5);
+
+        // A state initialized without bootstrap mode uses the cooldown from the start
+        let mut disabled = bootstrapping_state(0);
+        assert_eq!(disabled.queue_cooldown(TransactionType::Blacklist, 0), COOLDOWN);
+    }
+}
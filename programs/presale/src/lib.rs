@@ -4,6 +4,7 @@
 //! - Multi-token payment support (USDC, USDT, etc.)
 //! - Native SOL payment support
 //! - Presale caps (total and per-user limits)
+//! - Multi-round sales (seed, private, public) with per-round price and cap
 //! - Blacklist enforcement
 //! - Emergency pause integration
 //! - Treasury management
@@ -23,9 +24,10 @@
 //! 3. Start Presale: Activate presale for purchases
 //! 4. Buy: Users purchase tokens with allowed payment tokens or native SOL
 //! 5. Withdraw: Admin withdraws funds to treasury
+//! 6. Refund: If the presale is marked Failed, buyers return tokens and reclaim payment
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
 use chainlink_solana::v2::read_feed_v2;
 
@@ -40,7 +42,17 @@ declare_id!("7LkwkH3TpyhvCuVBEecFYbYk1T7c66qoYa2UpR9Q8LQj");
 // Constants for token account layout offsets
 pub const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
 pub const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+pub const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+// COption tags of the delegate and close authority (4-byte tag + 32-byte pubkey)
+pub const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+pub const TOKEN_ACCOUNT_CLOSE_AUTHORITY_OFFSET: usize = 129;
+pub const TOKEN_ACCOUNT_BASE_LEN: usize = 165;
 pub const TOKEN_STATE_EMERGENCY_PAUSED_OFFSET: usize = 41; // discriminator(8) + authority(32) + bump(1) = 41
+// Mint decimals offset, shared by legacy SPL Token and Token-2022: mint_authority(36) + supply(8) = 44
+pub const MINT_DECIMALS_OFFSET: usize = 44;
+// Mint is_initialized flag follows decimals; base mint layout is 82 bytes
+pub const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+pub const MINT_BASE_LEN: usize = 82;
 
 // Chainlink SOL/USD Price Feed Addresses
 // Mainnet: CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt
@@ -48,7 +60,7 @@ pub const TOKEN_STATE_EMERGENCY_PAUSED_OFFSET: usize = 41; // discriminator(8) +
 // Chainlink OCR2 Program ID: HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny
 
 // Production feed verification: we hardcode ONLY the Chainlink OCR2 program ID.
-// Exact mainnet/devnet feed addresses are enforced off-chain in clients.
+// The exact feed address is stored per presale (`oracle_feed`) and set by the authority.
 pub const CHAINLINK_PROGRAM_ID: Pubkey =
     anchor_lang::solana_program::pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
 
@@ -59,8 +71,53 @@ pub const SOL_DECIMALS: u8 = 9;
 pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
 // Token has 8 decimals (allows up to 184 billion supply with u64)
 pub const TOKEN_DECIMALS: u8 = 8;
-// Staleness threshold: 3600 seconds (1 hour) - price feed should be updated within this time
+// Default staleness threshold: 3600 seconds (1 hour) - price feed should be updated within this time.
+// Each presale can tighten or loosen it within the bounds below (`price_staleness_threshold`)
 pub const PRICE_FEED_STALENESS_THRESHOLD_SECONDS: i64 = 3600;
+pub const MIN_PRICE_STALENESS_THRESHOLD_SECONDS: i64 = 60;
+pub const MAX_PRICE_STALENESS_THRESHOLD_SECONDS: i64 = 86400;
+// Grace buffer around the threshold: prices within it emit `PriceNearStale`, and prices
+// up to this far past the threshold are still accepted (avoids flapping at the boundary)
+pub const PRICE_FEED_STALENESS_GRACE_SECONDS: i64 = 60;
+// Sentinel withdrawal amount: sweep the entire vault balance (computed on-chain)
+pub const WITHDRAW_ALL: u64 = u64::MAX;
+// Upper bound on the referral bonus percentage
+pub const MAX_REFERRAL_BONUS_PERCENT: u8 = 20;
+// Upper bound on the vesting duration (10 years)
+pub const MAX_VESTING_DURATION_SECONDS: u64 = 315_360_000;
+// Default cap on the number of simultaneously allowed payment tokens
+pub const DEFAULT_MAX_ALLOWED_TOKENS: u8 = 10;
+// Maximum buyers per set_buyer_whitelist_batch call (bounded by transaction size)
+pub const MAX_WHITELIST_BATCH: usize = 10;
+// Maximum number of price tiers in PresaleState::price_tiers
+pub const MAX_PRICE_TIERS: usize = 8;
+// Maximum Merkle proof length accepted by buy/buy_with_sol (a tree of 2^20 buyers)
+pub const MAX_MERKLE_PROOF_LEN: usize = 20;
+// Native Ed25519 signature verification program
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+// Payment mint under which SOL purchases are tracked in `RaisedTracker`
+pub const SOL_PAYMENT_SENTINEL: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+// Pyth price account (v2) layout, see pyth-sdk-solana `PriceAccount`. Parsed by hand
+// because pyth-sdk-solana pins solana-program <= 1.16, which conflicts with Anchor 0.31.
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+pub const PYTH_VERSION_2: u32 = 2;
+pub const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const PYTH_STATUS_TRADING: u32 = 1;
+pub const PYTH_EXPO_OFFSET: usize = 20;
+pub const PYTH_TIMESTAMP_OFFSET: usize = 96;
+pub const PYTH_AGG_PRICE_OFFSET: usize = 208;
+pub const PYTH_AGG_CONF_OFFSET: usize = 216;
+pub const PYTH_AGG_STATUS_OFFSET: usize = 224;
+// Pyth confidence interval must be within 2% of the price
+pub const PYTH_MAX_CONFIDENCE_BPS: u64 = 200;
+
+// Maximum treasury withdrawal fee (10%)
+pub const MAX_WITHDRAWAL_FEE_BPS: u16 = 1_000;
+
+// Maximum purchase code discount and bonus (50% each)
+pub const MAX_PURCHASE_CODE_DISCOUNT_BPS: u16 = 5_000;
+pub const MAX_PURCHASE_CODE_BONUS_BPS: u16 = 5_000;
 
 #[event]
 pub struct TreasuryWithdrawn {
@@ -79,6 +136,262 @@ pub struct PresaleStopped {}
 #[event]
 pub struct PresalePaused {}
 
+#[event]
+pub struct PresaleFailed {}
+
+#[event]
+pub struct PurchaseCodeCreated {
+    pub code_hash: [u8; 32],
+    pub discount_bps: u16,
+    pub bonus_bps: u16,
+    pub max_uses: u32,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct PurchaseCodeRedeemed {
+    pub code_hash: [u8; 32],
+    pub buyer: Pubkey,
+    pub uses: u32,
+}
+
+#[event]
+pub struct RoundCreated {
+    pub round_id: u8,
+    pub token_price_usd_micro: u64,
+    pub cap: u64,
+}
+
+#[event]
+pub struct RoundActivated {
+    pub round_id: u8,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub round_id: u8,
+    pub tokens_sold: u64,
+}
+
+#[event]
+pub struct RoundAdvanced {
+    pub from_round_id: Option<u8>,
+    pub to_round_id: u8,
+}
+
+#[event]
+pub struct VestingScheduleCreated {
+    pub buyer: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct OracleTypeChanged {
+    pub oracle_type: OracleType,
+    pub feed: Pubkey,
+}
+
+#[event]
+pub struct OracleBackupFeedChanged {
+    pub feed: Pubkey,
+}
+
+#[event]
+pub struct PriceStalenessThresholdChanged {
+    pub old_threshold: i64,
+    pub new_threshold: i64,
+}
+
+#[event]
+pub struct WithdrawalFeeChanged {
+    pub fee_bps: u16,
+    pub fee_collector: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalFeeCharged {
+    pub fee_amount: u64,
+    pub fee_collector: Pubkey,
+    pub treasury_amount: u64,
+}
+
+#[event]
+pub struct SwapProgramChanged {
+    pub old_swap_program: Pubkey,
+    pub new_swap_program: Pubkey,
+}
+
+#[event]
+pub struct TreasurySwapped {
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub output_mint: Pubkey,
+    pub amount_out: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct BackupFeedUsed {
+    pub primary: Pubkey,
+    pub backup: Pubkey,
+}
+
+#[event]
+pub struct ClaimModeChanged {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct TokensClaimed {
+    pub buyer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct ReferralRecorded {
+    pub referrer: Pubkey,
+    pub buyer: Pubkey,
+    pub bonus: u64,
+}
+
+#[event]
+pub struct PurchaseRecorded {
+    pub buyer: Pubkey,
+    pub index: u32,
+    pub tokens: u64,
+    pub paid: u64,
+    pub is_sol: bool,
+    pub price_usd_micro: u64, // Effective (average) token price of this purchase
+    pub unique_buyers: u64, // Distinct buyers so far, including this one
+}
+
+#[event]
+pub struct PriceTiersUpdated {
+    pub tiers: Vec<PriceTier>,
+}
+
+#[event]
+pub struct PriceNearStale {
+    pub price_age: i64,
+    pub threshold: i64,
+}
+
+#[event]
+pub struct SlippageProtectionTriggered {
+    pub buyer: Pubkey,
+    pub expected_min: u64,
+    pub actual_received: u64,
+}
+
+#[event]
+pub struct WhitelistPhaseChanged {
+    pub active: bool,
+}
+
+#[event]
+pub struct BuyerWhitelistChanged {
+    pub buyer: Pubkey,
+    pub is_allowed: bool,
+}
+
+#[event]
+pub struct MerkleWhitelistRootChanged {
+    pub root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct SolvencyChecked {
+    pub solvent: bool,
+    pub shortfall: u64,
+}
+
+#[event]
+pub struct PresaleFinalized {
+    pub end_time: i64,
+    pub tokens_sold: u64,
+    pub total_raised: u64,
+}
+
+#[event]
+pub struct PurchaseLimitsChanged {
+    pub min_purchase_amount: u64,
+    pub max_per_transaction: u64,
+}
+
+#[event]
+pub struct PresaleResultsFinalized {
+    pub total_tokens_sold: u64,
+    pub total_raised_usd_micro: u64,
+    pub unique_buyers: u64,
+    pub final_price: u64,
+    pub end_timestamp: i64,
+}
+
+#[event]
+pub struct PaymentTokenAllowed {
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct PaymentTokenDisallowed {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct RoundReset {
+    pub round_number: u64, // Round that was archived
+    pub total_tokens_sold: u64,
+    pub total_raised_usd_micro: u64,
+    pub unique_buyers: u64,
+}
+
+#[event]
+pub struct PresaleStats {
+    pub status: PresaleStatus,
+    pub total_tokens_sold: u64,
+    pub total_raised_usd_micro: u64, // SOL and payment-token purchases valued at their token price
+    pub token_price_usd_micro: u64,
+    pub remaining_cap: Option<u64>, // None = no presale cap
+    pub percent_complete_bps: u16,
+    pub active_since: Option<i64>,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub is_sol: bool,
+}
+
+#[event]
+pub struct UserPurchaseClosed {
+    pub buyer: Pubkey,
+    pub records_closed: u32,
+}
+
+#[event]
+pub struct AdminChangeProposed {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
 #[program]
 pub mod presale {
     use super::*;
@@ -154,6 +467,27 @@ pub mod presale {
         presale_state.max_per_user = 0; // 0 = unlimited
         presale_state.token_price_usd_micro = token_price_usd_micro;
         presale_state.bump = ctx.bumps.presale_state;
+        presale_state.active_round_id = None;
+        presale_state.total_raised_usd_micro = 0;
+        presale_state.unique_buyers = 0;
+        presale_state.min_purchase_amount = 0; // 0 = no minimum
+        presale_state.max_per_transaction = 0; // 0 = unlimited
+        presale_state.end_timestamp = None; // None = no end time
+        presale_state.start_timestamp = None; // None = no start time
+        presale_state.total_claimed = 0;
+        presale_state.whitelist_phase_active = false; // open to all non-blacklisted buyers
+        presale_state.referral_enabled = false;
+        presale_state.referral_bonus_percent = 0;
+        presale_state.referral_bonus_outstanding = 0;
+        presale_state.referral_bonus_total = 0;
+        presale_state.withdrawal_fee_bps = 0; // no withdrawal fee
+        presale_state.price_staleness_threshold = PRICE_FEED_STALENESS_THRESHOLD_SECONDS;
+        presale_state.fee_collector = Pubkey::default();
+        presale_state.swap_program = Pubkey::default(); // withdraw_and_swap disabled
+        presale_state.claim_mode = false; // tokens delivered at purchase time
+        presale_state.pending_admin = None;
+        presale_state.allowed_token_count = 0;
+        presale_state.max_allowed_tokens = DEFAULT_MAX_ALLOWED_TOKENS;
         
         msg!("Presale initialized with admin: {}, token_program: {}, token_price_usd_micro: {}", admin, token_program, token_price_usd_micro);
         Ok(())
@@ -165,6 +499,10 @@ pub mod presale {
     /// It replaces the old tokens_per_sol field with token_price_usd_micro.
     /// This is a one-time migration for existing deployments.
     ///
+    /// Fields added after an account was created read as zero: a zero
+    /// `price_staleness_threshold` falls back to `PRICE_FEED_STALENESS_THRESHOLD_SECONDS`,
+    /// and a zero `oracle_feed` rejects `buy_with_sol` until `set_oracle_type` pins the feed.
+    ///
     /// # Parameters
     /// - `ctx`: MigratePresaleState context (requires authority)
     /// - `token_price_usd_micro`: Token price in micro-USD (e.g., 1000 = $0.001 per token)
@@ -325,10 +663,86 @@ pub mod presale {
                 ctx.accounts.authority.key()
             );
         }
+
+        // Accounts created before total_claimed existed delivered every sold token
+        // at purchase time, so seed total_claimed from total_tokens_sold.
+        // Bytes past the old end are zeroed, so both Option fields serialize as None (1 byte).
+        const TOTAL_TOKENS_SOLD_OFFSET: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 1;
+        const TOTAL_CLAIMED_OFFSET: usize = TOKEN_PRICE_USD_MICRO_OFFSET + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 1;
+        // price + bump + active_round_id + total_raised_usd_micro + unique_buyers + min_purchase_amount + max_per_transaction + end_timestamp
+        if account_len < TOTAL_CLAIMED_OFFSET + 8 {
+            let total_tokens_sold: [u8; 8] = account_data_mut
+                [TOTAL_TOKENS_SOLD_OFFSET..TOTAL_TOKENS_SOLD_OFFSET + 8]
+                .try_into()
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            account_data_mut[TOTAL_CLAIMED_OFFSET..TOTAL_CLAIMED_OFFSET + 8]
+                .copy_from_slice(&total_tokens_sold);
+        }
         
         Ok(())
     }
 
+    /// Migrates a buyer's UserPurchase account created before the refund fields existed
+    ///
+    /// Accounts created by earlier versions hold only `buyer` and `total_purchased`, so
+    /// they no longer deserialize and every instruction taking the buyer's
+    /// `user_purchase` fails until the account is grown. The account is reallocated to
+    /// `8 + UserPurchase::LEN` with the new fields zero-filled: no SOL or payment tokens
+    /// are on record for refunds, nothing is owed, and the purchases count toward round 0.
+    /// Anyone can migrate an account; the payer funds the additional rent.
+    ///
+    /// # Parameters
+    /// - `ctx`: MigrateUserPurchase context
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidAccount` if `user_purchase` is not an initialized UserPurchase
+    ///   PDA of this program
+    pub fn migrate_user_purchase(ctx: Context<MigrateUserPurchase>) -> Result<()> {
+        let user_purchase = ctx.accounts.user_purchase.to_account_info();
+        require!(
+            user_purchase.owner == &crate::ID,
+            PresaleError::InvalidAccount
+        );
+        {
+            let data = user_purchase.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *UserPurchase::DISCRIMINATOR,
+                PresaleError::InvalidAccount
+            );
+        }
+
+        let new_size = 8 + UserPurchase::LEN;
+        if user_purchase.data_len() >= new_size {
+            msg!("UserPurchase already migrated");
+            return Ok(());
+        }
+
+        let new_minimum_balance = Rent::get()?.minimum_balance(new_size);
+        let current_lamports = user_purchase.lamports();
+        if current_lamports < new_minimum_balance {
+            let additional_lamports = new_minimum_balance
+                .checked_sub(current_lamports)
+                .ok_or(PresaleError::Overflow)?;
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: user_purchase.clone(),
+                    },
+                ),
+                additional_lamports,
+            )?;
+        }
+
+        // Zero-fill the new fields
+        #[allow(deprecated)]
+        user_purchase.realloc(new_size, true)?;
+
+        msg!("UserPurchase for {} migrated to {} bytes", ctx.accounts.buyer.key(), new_size);
+        Ok(())
+    }
+
     // Transfer authority to governance PDA (one-time operation)
     pub fn set_governance(ctx: Context<SetGovernance>, new_authority: Pubkey) -> Result<()> {
         let presale_state = &mut ctx.accounts.presale_state;
@@ -359,6 +773,81 @@ pub mod presale {
         Ok(())
     }
 
+    /// Proposes a new presale admin (step one of a two-step rotation)
+    ///
+    /// The change only takes effect once `new_admin` signs `accept_admin_change`,
+    /// so a mistyped key can never lock the admin out. Proposing again replaces
+    /// any pending proposal.
+    ///
+    /// # Parameters
+    /// - `ctx`: ProposeAdminChange context (requires admin or governance)
+    /// - `new_admin`: Address that must accept the role
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not the admin or governance
+    /// - `PresaleError::InvalidAccount` if `new_admin` is the default pubkey
+    ///
+    /// # Events
+    /// - Emits `AdminChangeProposed`
+    pub fn propose_admin_change(ctx: Context<ProposeAdminChange>, new_admin: Pubkey) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.admin == ctx.accounts.authority.key()
+                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
+            PresaleError::Unauthorized
+        );
+        require!(
+            new_admin != Pubkey::default(),
+            PresaleError::InvalidAccount
+        );
+
+        presale_state.pending_admin = Some(new_admin);
+
+        emit!(AdminChangeProposed {
+            old_admin: presale_state.admin,
+            new_admin,
+        });
+        msg!("Admin change proposed from {:?} to {:?}", presale_state.admin, new_admin);
+        Ok(())
+    }
+
+    /// Accepts a pending admin change (step two of a two-step rotation)
+    ///
+    /// Must be signed by the proposed admin. Until governance is set, the
+    /// presale authority moves to the new admin as well.
+    ///
+    /// # Parameters
+    /// - `ctx`: AcceptAdminChange context (requires the pending admin's signature)
+    ///
+    /// # Errors
+    /// - `PresaleError::NoPendingAdmin` if no change has been proposed
+    /// - `PresaleError::Unauthorized` if signer is not the pending admin
+    ///
+    /// # Events
+    /// - Emits `AdminChanged`
+    pub fn accept_admin_change(ctx: Context<AcceptAdminChange>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        let new_admin = presale_state.pending_admin.ok_or(PresaleError::NoPendingAdmin)?;
+        require!(
+            ctx.accounts.signer.key() == new_admin,
+            PresaleError::Unauthorized
+        );
+
+        let old_admin = presale_state.admin;
+        presale_state.admin = new_admin;
+        if !presale_state.governance_set {
+            presale_state.authority = new_admin;
+        }
+        presale_state.pending_admin = None;
+
+        emit!(AdminChanged {
+            old_admin,
+            new_admin,
+        });
+        msg!("Admin changed from {:?} to {:?}", old_admin, new_admin);
+        Ok(())
+    }
+
     // Set the token program address (can be called by admin or governance)
     pub fn set_token_program(
         ctx: Context<SetTokenProgram>,
@@ -446,6 +935,7 @@ pub mod presale {
     /// # Errors
     /// - `PresaleError::Unauthorized` if caller is not admin
     /// - `PresaleError::InvalidStatus` if presale is not Active
+    /// - `PresaleError::PresaleEnded` if the end timestamp has passed (use `finalize_presale`)
     ///
     /// # Events
     /// - Emits `PresaleStopped`
@@ -462,6 +952,12 @@ pub mod presale {
             presale_state.status == PresaleStatus::Active,
             PresaleError::InvalidStatus
         );
+
+        // After the end time the presale is closed via finalize_presale instead
+        require!(
+            !presale_state.has_ended(Clock::get()?.unix_timestamp),
+            PresaleError::PresaleEnded
+        );
         
         presale_state.status = PresaleStatus::Stopped;
         
@@ -509,231 +1005,486 @@ pub mod presale {
         Ok(())
     }
 
-    // Admin function to allow a payment token (USDC, USDT, etc.)
-    pub fn allow_payment_token(
-        ctx: Context<AllowPaymentToken>,
-        payment_token_mint: Pubkey,
-    ) -> Result<()> {
-        let allowed_token = &mut ctx.accounts.allowed_token;
-        allowed_token.payment_token_mint = payment_token_mint;
-        allowed_token.is_allowed = true;
-        allowed_token.presale_state = ctx.accounts.presale_state.key();
-        
-        msg!("Payment token allowed: {}", payment_token_mint);
+    /// Marks the presale as failed, enabling buyer refunds
+    ///
+    /// Changes presale status to Failed (e.g. soft cap not reached). This is a
+    /// terminal state: purchases and treasury withdrawals are disabled and buyers
+    /// can reclaim their payment via `claim_refund_sol` / `claim_refund_payment_token`.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin authority)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if presale is marked failed
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not admin or governance
+    /// - `PresaleError::InvalidStatus` if presale is not Active, Paused or Stopped
+    ///
+    /// # Events
+    /// - Emits `PresaleFailed`
+    pub fn fail_presale(ctx: Context<AdminOnly>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status == PresaleStatus::Active
+                || presale_state.status == PresaleStatus::Paused
+                || presale_state.status == PresaleStatus::Stopped,
+            PresaleError::InvalidStatus
+        );
+
+        presale_state.status = PresaleStatus::Failed;
+
+        // Emit event
+        emit!(PresaleFailed {});
+
+        msg!("Presale marked as failed, refunds enabled");
         Ok(())
     }
 
-    // Admin function to disallow a payment token
-    pub fn disallow_payment_token(
-        ctx: Context<DisallowPaymentToken>,
-    ) -> Result<()> {
-        let allowed_token = &mut ctx.accounts.allowed_token;
-        allowed_token.is_allowed = false;
-        
-        msg!("Payment token disallowed");
+    /// Switches between whitelist-only and open purchase mode
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `active`: true = only whitelisted buyers may purchase, false = open to all
+    ///
+    /// # Events
+    /// - Emits `WhitelistPhaseChanged`
+    pub fn toggle_whitelist_phase(ctx: Context<AdminOnly>, active: bool) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.whitelist_phase_active = active;
+
+        emit!(WhitelistPhaseChanged { active });
+
+        msg!("Whitelist phase {}", if active { "activated" } else { "deactivated" });
         Ok(())
     }
 
-    /// Allows users to buy presale tokens with allowed payment tokens
+    /// Sets the Merkle root of whitelisted buyers
     ///
-    /// Transfers payment tokens from buyer to presale vault and transfers presale
-    /// tokens from presale vault to buyer. Enforces all security checks including
-    /// blacklist, presale caps, and emergency pause.
+    /// During the whitelist phase, a buyer without an `AllowedBuyer` entry may purchase
+    /// by passing a proof of inclusion in this tree, so large whitelists need no
+    /// per-buyer accounts. An all-zero root clears it.
     ///
     /// # Parameters
-    /// - `ctx`: Buy context with all required accounts
-    /// - `amount`: Amount of payment tokens to spend (in payment token's base units)
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `root`: Merkle root over `sha256(buyer)` leaves with sorted-pair hashing
     ///
-    /// # Returns
-    /// - `Result<()>`: Success if purchase completes
+    /// # Events
+    /// - Emits `MerkleWhitelistRootChanged`
+    pub fn set_merkle_whitelist_root(ctx: Context<AdminOnly>, root: [u8; 32]) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.merkle_whitelist_root = if root == [0u8; 32] { None } else { Some(root) };
+
+        emit!(MerkleWhitelistRootChanged {
+            root: presale_state.merkle_whitelist_root,
+        });
+
+        msg!(
+            "Merkle whitelist root {}",
+            if presale_state.merkle_whitelist_root.is_some() { "set" } else { "cleared" }
+        );
+        Ok(())
+    }
+
+    /// Adds or removes a buyer from the presale whitelist
+    ///
+    /// # Parameters
+    /// - `ctx`: SetBuyerWhitelist context (requires admin or governance authority)
+    /// - `buyer`: Buyer wallet address
+    /// - `value`: true = allowed during whitelist phase, false = not allowed
+    ///
+    /// # Events
+    /// - Emits `BuyerWhitelistChanged`
+    pub fn set_buyer_whitelist(
+        ctx: Context<SetBuyerWhitelist>,
+        buyer: Pubkey,
+        value: bool,
+    ) -> Result<()> {
+        let allowed_buyer = &mut ctx.accounts.allowed_buyer;
+        allowed_buyer.account = buyer;
+        allowed_buyer.is_allowed = value;
+
+        emit!(BuyerWhitelistChanged {
+            buyer,
+            is_allowed: value,
+        });
+
+        msg!("Buyer {} whitelist status: {}", buyer, value);
+        Ok(())
+    }
+
+    /// Adds or removes several buyers from the presale whitelist at once
+    ///
+    /// `remaining_accounts` must hold each buyer's `AllowedBuyer` PDA (writable),
+    /// in the same order as `buyers`. Missing entries are created, paid for by `admin`.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetBuyerWhitelistBatch context (requires admin or governance authority)
+    /// - `buyers`: Buyer wallet addresses (1 to `MAX_WHITELIST_BATCH`)
+    /// - `value`: true = allowed during whitelist phase, false = not allowed
     ///
     /// # Errors
-    /// - `PresaleError::PresaleNotActive` if presale is not active
-    /// - `PresaleError::TokenEmergencyPaused` if token program is paused
-    /// - `PresaleError::BuyerBlacklisted` if buyer is blacklisted
-    /// - `PresaleError::PaymentTokenNotAllowed` if payment token not whitelisted
-    /// - `PresaleError::PresaleCapExceeded` if purchase exceeds total cap
-    /// - `PresaleError::PerUserLimitExceeded` if purchase exceeds per-user limit
+    /// - `PresaleError::InvalidBatchSize` if `buyers` is empty, too long, or does not
+    ///   match the number of remaining accounts
+    /// - `PresaleError::InvalidAccount` if an account is not the buyer's `AllowedBuyer` PDA
     ///
-    /// # Security
-    /// - Blacklist check before purchase
-    /// - Emergency pause check
-    /// - Presale cap enforcement
-    /// - Per-user limit enforcement
-    /// - Manual token account validation for safety
-    pub fn buy(
-        ctx: Context<Buy>,
-        amount: u64, // Amount of payment tokens to spend
+    /// # Events
+    /// - Emits `BuyerWhitelistChanged` for each buyer
+    pub fn set_buyer_whitelist_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetBuyerWhitelistBatch<'info>>,
+        buyers: Vec<Pubkey>,
+        value: bool,
     ) -> Result<()> {
-        let presale_state = &ctx.accounts.presale_state;
-        
-        // Check if presale is active
         require!(
-            presale_state.status == PresaleStatus::Active,
-            PresaleError::PresaleNotActive
+            !buyers.is_empty()
+                && buyers.len() <= MAX_WHITELIST_BATCH
+                && buyers.len() == ctx.remaining_accounts.len(),
+            PresaleError::InvalidBatchSize
         );
 
-        // Check token program emergency pause
-        // Deserialize token state manually to check emergency_paused
-        let token_state_data = ctx.accounts.token_state.try_borrow_data()?;
-        if token_state_data.len() > TOKEN_STATE_EMERGENCY_PAUSED_OFFSET {
-            let emergency_paused = token_state_data[TOKEN_STATE_EMERGENCY_PAUSED_OFFSET] != 0;
-            require!(
-                !emergency_paused,
-                PresaleError::TokenEmergencyPaused
+        let presale_state_key = ctx.accounts.presale_state.key();
+        let space = 8 + AllowedBuyer::LEN;
+        for (buyer, entry_info) in buyers.iter().zip(ctx.remaining_accounts.iter()) {
+            let (expected_entry, bump) = Pubkey::find_program_address(
+                &[b"allowed_buyer", presale_state_key.as_ref(), buyer.as_ref()],
+                ctx.program_id,
             );
-        }
+            require!(entry_info.key() == expected_entry, PresaleError::InvalidAccount);
 
-        // Check if buyer is blacklisted
-        if ctx.accounts.buyer_blacklist.key() != Pubkey::default() {
-            let blacklist_data = ctx.accounts.buyer_blacklist.try_borrow_data()?;
-            if blacklist_data.len() >= 41 {
-                // Account discriminator (8) + account Pubkey (32) + is_blacklisted bool (1) = offset 40
-                let is_blacklisted = blacklist_data[40] != 0;
-                require!(!is_blacklisted, PresaleError::BuyerBlacklisted);
+            if entry_info.owner == ctx.program_id {
+                // Existing entry: only the discriminator check is needed before overwriting
+                AllowedBuyer::try_deserialize(&mut &entry_info.try_borrow_data()?[..])?;
+            } else {
+                let seeds: &[&[u8]] = &[
+                    b"allowed_buyer",
+                    presale_state_key.as_ref(),
+                    buyer.as_ref(),
+                    &[bump],
+                ];
+                let signer = &[seeds];
+                let cpi_accounts = anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: entry_info.clone(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    cpi_accounts,
+                    signer,
+                );
+                anchor_lang::system_program::create_account(
+                    cpi_ctx,
+                    Rent::get()?.minimum_balance(space),
+                    space as u64,
+                    ctx.program_id,
+                )?;
             }
-        }
-        
-        // Check if payment token is allowed
-        let allowed_token = &ctx.accounts.allowed_token;
-        require!(
-            allowed_token.is_allowed,
-            PresaleError::PaymentTokenNotAllowed
-        );
 
-        // Validate token account mints match (manual validation)
-        let buyer_payment_data = ctx.accounts.buyer_payment_token_account.try_borrow_data()?;
-        require!(buyer_payment_data.len() >= 32, PresaleError::PaymentTokenNotAllowed);
-        let buyer_payment_mint = Pubkey::try_from_slice(&buyer_payment_data[0..32])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        require!(
-            buyer_payment_mint == ctx.accounts.payment_token_mint.key(),
-            PresaleError::PaymentTokenNotAllowed
-        );
-        
-        let buyer_token_data = ctx.accounts.buyer_token_account.try_borrow_data()?;
-        require!(buyer_token_data.len() >= 32, PresaleError::PaymentTokenNotAllowed);
-        let buyer_token_mint = Pubkey::try_from_slice(&buyer_token_data[0..32])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        require!(
-            buyer_token_mint == presale_state.presale_token_mint,
-            PresaleError::PaymentTokenNotAllowed
-        );
+            let entry = AllowedBuyer {
+                account: *buyer,
+                is_allowed: value,
+            };
+            entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
 
-        // Calculate tokens to receive (1:1 ratio - you can modify this)
-        let tokens_to_receive = amount; // Adjust based on your pricing logic
+            emit!(BuyerWhitelistChanged {
+                buyer: *buyer,
+                is_allowed: value,
+            });
+        }
 
-        // Check presale cap
-        if presale_state.max_presale_cap > 0 {
-            let new_total = presale_state
-                .total_tokens_sold
-                .checked_add(tokens_to_receive)
-                .ok_or(PresaleError::Overflow)?;
+        msg!("Set whitelist status {} for {} buyers", value, buyers.len());
+        Ok(())
+    }
+
+    // Admin function to allow a payment token (USDC, USDT, etc.)
+    pub fn allow_payment_token(
+        ctx: Context<AllowPaymentToken>,
+        payment_token_mint: Pubkey,
+    ) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        let allowed_token = &mut ctx.accounts.allowed_token;
+        // Only count tokens that are newly allowed (re-allowing is a no-op for the count)
+        if !allowed_token.is_allowed {
             require!(
-                new_total <= presale_state.max_presale_cap,
-                PresaleError::PresaleCapExceeded
+                presale_state.allowed_token_count < presale_state.effective_max_allowed_tokens(),
+                PresaleError::TooManyPaymentTokens
             );
+            presale_state.allowed_token_count = presale_state
+                .allowed_token_count
+                .checked_add(1)
+                .ok_or(PresaleError::Overflow)?;
         }
+        allowed_token.payment_token_mint = payment_token_mint;
+        allowed_token.is_allowed = true;
+        allowed_token.presale_state = presale_state.key();
+        allowed_token.decimals = validate_payment_mint(&ctx.accounts.payment_token_mint_account)?;
 
-        // Check per-user limit
-        if presale_state.max_per_user > 0 {
-            let user_purchase = &mut ctx.accounts.user_purchase;
-            let new_user_total = user_purchase.total_purchased
-                .checked_add(tokens_to_receive)
-                .ok_or(PresaleError::Overflow)?;
-            require!(
-                new_user_total <= presale_state.max_per_user,
-                PresaleError::PerUserLimitExceeded
-            );
+        emit!(PaymentTokenAllowed {
+            mint: payment_token_mint,
+            decimals: allowed_token.decimals,
+        });
+        
+        msg!("Payment token allowed: {}", payment_token_mint);
+        Ok(())
+    }
+
+    // Admin function to disallow a payment token
+    pub fn disallow_payment_token(
+        ctx: Context<DisallowPaymentToken>,
+    ) -> Result<()> {
+        let allowed_token = &mut ctx.accounts.allowed_token;
+        if allowed_token.is_allowed {
+            let presale_state = &mut ctx.accounts.presale_state;
+            presale_state.allowed_token_count = presale_state.allowed_token_count.saturating_sub(1);
         }
+        allowed_token.is_allowed = false;
 
-        // Validate payment vault (manual validation)
-        let payment_vault_data = ctx.accounts.presale_payment_vault.try_borrow_data()?;
-        require!(payment_vault_data.len() >= 64, PresaleError::PaymentTokenNotAllowed);
-        let payment_vault_mint = Pubkey::try_from_slice(&payment_vault_data[0..32])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        let payment_vault_owner = Pubkey::try_from_slice(&payment_vault_data[32..64])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        require!(
-            payment_vault_mint == ctx.accounts.payment_token_mint.key(),
-            PresaleError::PaymentTokenNotAllowed
-        );
+        emit!(PaymentTokenDisallowed {
+            mint: allowed_token.payment_token_mint,
+        });
+        
+        msg!("Payment token disallowed");
+        Ok(())
+    }
+
+    /// Sets the maximum number of payment tokens that may be allowed at once
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `max_allowed_tokens`: New limit (0 restores `DEFAULT_MAX_ALLOWED_TOKENS`)
+    ///
+    /// # Errors
+    /// - `PresaleError::TooManyPaymentTokens` if more tokens are already allowed than the new limit
+    pub fn set_max_allowed_tokens(ctx: Context<AdminOnly>, max_allowed_tokens: u8) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.max_allowed_tokens = max_allowed_tokens;
         require!(
-            payment_vault_owner == ctx.accounts.presale_payment_vault_pda.key(),
-            PresaleError::PaymentTokenNotAllowed
+            presale_state.allowed_token_count <= presale_state.effective_max_allowed_tokens(),
+            PresaleError::TooManyPaymentTokens
         );
 
-        // Transfer payment tokens from buyer to presale vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.buyer_payment_token_account.to_account_info(),
-            to: ctx.accounts.presale_payment_vault.to_account_info(),
-            authority: ctx.accounts.buyer.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        msg!("Max allowed payment tokens set to {}", presale_state.effective_max_allowed_tokens());
+        Ok(())
+    }
 
-        // Validate presale token vault (manual validation)
-        let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
-        require!(presale_token_vault_data.len() >= 64, PresaleError::PaymentTokenNotAllowed);
-        let presale_token_vault_mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        let presale_token_vault_owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
-            .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
-        require!(
-            presale_token_vault_mint == presale_state.presale_token_mint,
-            PresaleError::PaymentTokenNotAllowed
-        );
+    /// Allows users to buy presale tokens with allowed payment tokens
+    ///
+    /// Transfers payment tokens from buyer to presale vault and transfers presale
+    /// tokens from presale vault to buyer. Enforces all security checks including
+    /// blacklist, presale caps, and emergency pause.
+    ///
+    /// # Parameters
+    /// - `ctx`: Buy context with all required accounts
+    /// - `amount`: Amount of payment tokens to spend (in payment token's base units).
+    ///   Payment tokens are valued at $1 each and converted at the effective token price
+    /// - `round_id`: Presale round; must be the active round (required while one is active)
+    ///   and `presale_round` must be provided. The round's cap and time window are enforced
+    ///   in addition to the global cap. A purchase that would cross the round cap is
+    ///   rejected, not split. Once the round has less than `min_purchase_amount` left it is
+    ///   sold out, and `next_presale_round` (the next round's PDA, possibly uninitialized)
+    ///   must be passed so the next round is activated
+    /// - `referrer`: Optional referrer; requires `referral_account` and `referrer_blacklist`.
+    ///   Buyer and referrer each earn `referral_bonus_percent` of the purchased tokens
+    /// - `merkle_proof`: Proof of the buyer's inclusion in `merkle_whitelist_root`
+    ///   (only checked during the whitelist phase for buyers without an `AllowedBuyer` entry)
+    /// - `allow_partial`: When the order would exceed the presale cap, spend only as much
+    ///   of `amount` as fits instead of failing
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Presale tokens purchased (excluding referral bonus)
+    ///
+    /// # Errors
+    /// - `PresaleError::PresaleNotActive` if presale is not active
+    /// - `PresaleError::PresaleNotStarted` if the start timestamp has not been reached
+    /// - `PresaleError::PresaleEnded` if the end timestamp has passed
+    /// - `PresaleError::BuyerNotWhitelisted` if whitelist phase is active and buyer is not approved
+    /// - `PresaleError::InvalidMerkleProof` if a Merkle root is set and the proof does not verify
+    /// - `PresaleError::InvalidRound` / `RoundNotActive` / `RoundCapExceeded` for round violations
+    /// - `PresaleError::NextRoundRequired` if the purchase sells out the round without `next_presale_round`
+    /// - `PresaleError::TokenEmergencyPaused` if token program is paused
+    /// - `PresaleError::BuyerBlacklisted` if buyer is blacklisted
+    /// - `PresaleError::InvalidAccount` if `buyer_blacklist` is not the buyer's blacklist PDA
+    /// - `PresaleError::PaymentTokenNotAllowed` if payment token not whitelisted
+    /// - `PresaleError::PresaleCapExceeded` if purchase exceeds total cap (with `allow_partial`,
+    ///   only if the cap is already reached)
+    /// - `PresaleError::PerUserLimitExceeded` if purchase plus referral bonus exceeds per-user limit
+    /// - `PresaleError::VestingAccountRequired` if vesting is enabled and `vesting_account` is missing
+    ///
+    /// # Security
+    /// - Blacklist check before purchase
+    /// - Emergency pause check
+    /// - Presale cap enforcement
+    /// - Per-user limit enforcement
+    /// - Manual token account validation for safety
+    pub fn buy(
+        ctx: Context<Buy>,
+        amount: u64, // Amount of payment tokens to spend
+        round_id: Option<u8>,
+        referrer: Option<Pubkey>,
+        merkle_proof: Vec<[u8; 32]>,
+        allow_partial: bool,
+    ) -> Result<u64> {
+        process_buy(ctx, amount, round_id, referrer, &merkle_proof, None, allow_partial, None)
+    }
+
+    /// Buys presale tokens against an allocation signed off-chain
+    ///
+    /// For KYC'd sales, the `allocation_authority` signs `buyer || max_allocation`
+    /// (32-byte pubkey followed by the u64 little-endian) with ed25519. The transaction
+    /// must include an Ed25519 program instruction verifying that signature
+    /// immediately before this instruction. The buyer's total purchased tokens are
+    /// capped at `max_allocation`, and the signed allocation stands in for the
+    /// whitelist during the whitelist phase.
+    ///
+    /// # Parameters
+    /// - `ctx`: Buy context; `instructions_sysvar` is required, and `presale_round` must be
+    ///   the active round while one is active
+    /// - `amount`: Amount of payment tokens to spend (in payment token's base units)
+    /// - `max_allocation`: Signed cap on the buyer's total purchased tokens (base units)
+    /// - `signature`: The allocation authority's ed25519 signature
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Presale tokens purchased (excluding referral bonus)
+    ///
+    /// # Errors
+    /// - `PresaleError::AllocationAuthorityNotSet` if no allocation authority is configured
+    /// - `PresaleError::InvalidAllocationSignature` if the preceding Ed25519 instruction does
+    ///   not verify exactly this buyer, allocation, signature and authority
+    /// - `PresaleError::AllocationExceeded` if the purchase would exceed `max_allocation`
+    /// - Any error returned by `buy`
+    pub fn buy_with_allocation(
+        ctx: Context<Buy>,
+        amount: u64,
+        max_allocation: u64,
+        signature: [u8; 64],
+    ) -> Result<u64> {
+        let allocation_authority = ctx.accounts.presale_state.allocation_authority;
         require!(
-            presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
-            PresaleError::PaymentTokenNotAllowed
+            allocation_authority != Pubkey::default(),
+            PresaleError::AllocationAuthorityNotSet
         );
+        let instructions_sysvar = ctx
+            .accounts
+            .instructions_sysvar
+            .as_ref()
+            .ok_or(PresaleError::InvalidAllocationSignature)?;
 
-        // Transfer presale tokens from presale vault to buyer
-        let seeds = &[
-            b"presale_token_vault_pda",
-            presale_state.presale_token_mint.as_ref(),
-            &[ctx.bumps.presale_token_vault_pda],
-        ];
-        let signer = &[&seeds[..]];
+        let mut message = [0u8; 40];
+        message[..32].copy_from_slice(ctx.accounts.buyer.key().as_ref());
+        message[32..].copy_from_slice(&max_allocation.to_le_bytes());
+        verify_ed25519_instruction(instructions_sysvar, &allocation_authority, &message, &signature)?;
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.presale_token_vault.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, tokens_to_receive)?;
+        let round_id = ctx.accounts.presale_state.active_round_id;
+        process_buy(ctx, amount, round_id, None, &[], Some(max_allocation), false, None)
+    }
 
-        // Update state
-        let presale_state = &mut ctx.accounts.presale_state;
-        presale_state.total_tokens_sold = presale_state
-            .total_tokens_sold
-            .checked_add(tokens_to_receive)
-            .ok_or(PresaleError::Overflow)?;
-        presale_state.total_raised = presale_state
-            .total_raised
-            .checked_add(amount)
-            .ok_or(PresaleError::Overflow)?;
+    /// Buys presale tokens with a promotional purchase code
+    ///
+    /// Only the code's sha256 hash is stored, so unused codes cannot be read off the
+    /// chain. The code itself is passed in the clear, though: the first redemption
+    /// reveals it in the instruction data, and anyone can reuse it from there until
+    /// `max_uses` is reached. Codes meant for a single buyer should have `max_uses = 1`.
+    /// The code's discount lowers the effective token price and its bonus adds tokens
+    /// on top; both count as purchased tokens for caps and limits.
+    ///
+    /// # Parameters
+    /// - `ctx`: Buy context; `purchase_code` must be the code's `PurchaseCode` PDA, and
+    ///   `presale_round` the active round while one is active
+    /// - `amount`: Amount of payment tokens to spend (in payment token's base units)
+    /// - `code`: The purchase code preimage
+    ///
+    /// # Returns
+    /// - `Result<u64>`: Presale tokens purchased, including the code's discount and bonus
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidPurchaseCode` if `purchase_code` is missing or its hash does not match `code`
+    /// - `PresaleError::PurchaseCodeExpired` if the code has expired
+    /// - `PresaleError::PurchaseCodeExhausted` if the code has no uses left
+    /// - Any error returned by `buy`
+    ///
+    /// # Events
+    /// - Emits `PurchaseCodeRedeemed`
+    pub fn buy_with_code(ctx: Context<Buy>, amount: u64, code: Vec<u8>) -> Result<u64> {
+        let round_id = ctx.accounts.presale_state.active_round_id;
+        process_buy(ctx, amount, round_id, None, &[], None, false, Some(&code))
+    }
 
-        // Update user purchase tracker
-        let user_purchase = &mut ctx.accounts.user_purchase;
-        if user_purchase.buyer == Pubkey::default() {
-            user_purchase.buyer = ctx.accounts.buyer.key();
-            user_purchase.total_purchased = 0;
-        }
-        user_purchase.total_purchased = user_purchase
-            .total_purchased
-            .checked_add(tokens_to_receive)
-            .ok_or(PresaleError::Overflow)?;
+    /// Creates a time-limited purchase code redeemable through `buy_with_code`
+    ///
+    /// Only the sha256 hash of the code is submitted, so creating a code does not
+    /// reveal it.
+    ///
+    /// # Parameters
+    /// - `ctx`: CreatePurchaseCode context (requires admin or governance authority)
+    /// - `code_hash`: sha256 of the code
+    /// - `discount_bps`: Price discount in basis points (max `MAX_PURCHASE_CODE_DISCOUNT_BPS`)
+    /// - `bonus_bps`: Bonus tokens in basis points of the purchase (max `MAX_PURCHASE_CODE_BONUS_BPS`)
+    /// - `max_uses`: Number of purchases the code can be redeemed for
+    /// - `expires_at`: Unix timestamp after which the code is rejected
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not admin or governance
+    /// - `PresaleError::InvalidPurchaseCode` if the terms are out of bounds, `max_uses`
+    ///   is zero or `expires_at` is not in the future
+    ///
+    /// # Events
+    /// - Emits `PurchaseCodeCreated`
+    pub fn create_purchase_code(
+        ctx: Context<CreatePurchaseCode>,
+        code_hash: [u8; 32],
+        discount_bps: u16,
+        bonus_bps: u16,
+        max_uses: u32,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(
+            discount_bps <= MAX_PURCHASE_CODE_DISCOUNT_BPS
+                && bonus_bps <= MAX_PURCHASE_CODE_BONUS_BPS
+                && (discount_bps > 0 || bonus_bps > 0)
+                && max_uses > 0
+                && expires_at > Clock::get()?.unix_timestamp,
+            PresaleError::InvalidPurchaseCode
+        );
+
+        let purchase_code = &mut ctx.accounts.purchase_code;
+        purchase_code.presale_state = ctx.accounts.presale_state.key();
+        purchase_code.code_hash = code_hash;
+        purchase_code.discount_bps = discount_bps;
+        purchase_code.bonus_bps = bonus_bps;
+        purchase_code.max_uses = max_uses;
+        purchase_code.uses = 0;
+        purchase_code.expires_at = expires_at;
+        purchase_code.bump = ctx.bumps.purchase_code;
 
+        emit!(PurchaseCodeCreated {
+            code_hash,
+            discount_bps,
+            bonus_bps,
+            max_uses,
+            expires_at,
+        });
         msg!(
-            "Buy successful: {} tokens for {} payment tokens",
-            tokens_to_receive,
-            amount
+            "Purchase code created: {} bps discount, {} bps bonus, {} uses, expires at {}",
+            discount_bps,
+            bonus_bps,
+            max_uses,
+            expires_at
         );
+        Ok(())
+    }
 
+    /// Sets the key whose ed25519 signatures authorize `buy_with_allocation`
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `allocation_authority`: Signing key (`Pubkey::default()` disables allocation purchases)
+    pub fn set_allocation_authority(
+        ctx: Context<AdminOnly>,
+        allocation_authority: Pubkey,
+    ) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.allocation_authority = allocation_authority;
+        msg!("Allocation authority set to {}", allocation_authority);
         Ok(())
     }
 
@@ -746,21 +1497,47 @@ pub mod presale {
     /// # Parameters
     /// - `ctx`: BuyWithSol context with all required accounts
     /// - `sol_amount`: Amount of SOL to spend (in lamports)
+    /// - `round_id`: Presale round (required while one is active); when set, the round's
+    ///   price is used instead of the global `token_price_usd_micro` and the round's
+    ///   cap/window are enforced. Rounds advance as in `buy`
+    /// - `min_tokens_out`: Minimum tokens the buyer accepts at the oracle price at
+    ///   execution (0 disables slippage protection)
+    /// - `merkle_proof`: Proof of the buyer's inclusion in `merkle_whitelist_root`
+    ///   (only checked during the whitelist phase for buyers without an `AllowedBuyer` entry)
+    /// - `allow_partial`: When the order would exceed the presale cap, spend only as many
+    ///   lamports as fit instead of failing; `min_tokens_out` is scaled down to match
     ///
     /// # Returns
-    /// - `Result<()>`: Success if purchase completes
+    /// - `Result<u64>`: Presale tokens purchased (excluding referral bonus)
     ///
     /// # Errors
     /// - `PresaleError::PresaleNotActive` if presale is not active
+    /// - `PresaleError::PresaleNotStarted` if the start timestamp has not been reached
+    /// - `PresaleError::PresaleEnded` if the end timestamp has passed
+    /// - `PresaleError::BuyerNotWhitelisted` if whitelist phase is active and buyer is not approved
+    /// - `PresaleError::InvalidMerkleProof` if a Merkle root is set and the proof does not verify
+    /// - `PresaleError::InvalidRound` / `RoundNotActive` / `RoundCapExceeded` for round violations
+    /// - `PresaleError::NextRoundRequired` if the purchase sells out the round without `next_presale_round`
     /// - `PresaleError::TokenEmergencyPaused` if token program is paused
     /// - `PresaleError::BuyerBlacklisted` if buyer is blacklisted
-    /// - `PresaleError::PresaleCapExceeded` if purchase exceeds total cap
-    /// - `PresaleError::PerUserLimitExceeded` if purchase exceeds per-user limit
+    /// - `PresaleError::InvalidAccount` if `buyer_blacklist` is not the buyer's blacklist PDA
+    /// - `PresaleError::OracleFeedNotSet` if no SOL/USD feed has been configured
+    /// - `PresaleError::InvalidOracleFeed` if `chainlink_feed` (or the backup) is not the configured feed
+    /// - `PresaleError::StalePrice` if the SOL/USD price (and any backup feed's) is stale
+    /// - `PresaleError::SlippageExceeded` if fewer than `min_tokens_out` tokens would be received
+    /// - `PresaleError::PresaleCapExceeded` if purchase exceeds total cap (with `allow_partial`,
+    ///   only if the cap is already reached)
+    /// - `PresaleError::PerUserLimitExceeded` if purchase plus referral bonus exceeds per-user limit
     /// - `PresaleError::InvalidAmount` if amount is 0 or exceeds buyer balance
     pub fn buy_with_sol(
         ctx: Context<BuyWithSol>,
         sol_amount: u64, // Amount of SOL to spend (in lamports)
-    ) -> Result<()> {
+        round_id: Option<u8>,
+        referrer: Option<Pubkey>,
+        min_tokens_out: u64,
+        merkle_proof: Vec<[u8; 32]>,
+        allow_partial: bool,
+    ) -> Result<u64> {
         let presale_state = &ctx.accounts.presale_state;
         
         // Check if presale is active
@@ -769,6 +1546,11 @@ pub mod presale {
             PresaleError::PresaleNotActive
         );
 
+        // Reject purchases outside the sale window, even while Active
+        let now = Clock::get()?.unix_timestamp;
+        require!(presale_state.has_started(now), PresaleError::PresaleNotStarted);
+        require!(!presale_state.has_ended(now), PresaleError::PresaleEnded);
+
         // Validate amount
         require!(
             sol_amount > 0,
@@ -781,6 +1563,15 @@ pub mod presale {
             PresaleError::InvalidAmount
         );
 
+        // Purchase totals are tracked per round; start a fresh tally on the buyer's first purchase of this round
+        ctx.accounts.user_purchase.start_round(presale_state.round_number);
+
+        // Refunds are tracked per payment method, so a buyer must stick to one
+        require!(
+            ctx.accounts.user_purchase.payment_amount_paid == 0,
+            PresaleError::PaymentMethodMismatch
+        );
+
         // Check token program emergency pause - scope the borrow
         let emergency_paused = {
             let token_state_data = ctx.accounts.token_state.try_borrow_data()?;
@@ -795,71 +1586,87 @@ pub mod presale {
             PresaleError::TokenEmergencyPaused
         );
 
-        // Check if buyer is blacklisted - scope the borrow
-        if ctx.accounts.buyer_blacklist.key() != Pubkey::default() {
-            let is_blacklisted = {
-                let blacklist_data = ctx.accounts.buyer_blacklist.try_borrow_data()?;
-                if blacklist_data.len() >= 41 {
-                    blacklist_data[40] != 0
-                } else {
-                    false
-                }
-            }; // Borrow dropped here
-            require!(!is_blacklisted, PresaleError::BuyerBlacklisted);
-        }
+        // Check if buyer is blacklisted
+        check_buyer_blacklist(presale_state, ctx.accounts.buyer.key(), &ctx.accounts.buyer_blacklist)?;
 
-        // Read SOL/USD price from Chainlink oracle using SDK v2
+        // Whitelist phase: only approved buyers may purchase
+        presale_state.check_buyer_whitelist(
+            ctx.accounts.buyer_whitelist.as_deref(),
+            ctx.accounts.buyer.key(),
+            &merkle_proof,
+        )?;
+
+        // Only the configured feed may price the purchase; any other account owned by
+        // the oracle program (e.g. a BTC/USD feed) would misprice it
         let feed = &ctx.accounts.chainlink_feed;
-        let feed_data = read_feed_v2(
-            feed.try_borrow_data()?,
-            feed.owner.to_bytes(),
-        )
-        .map_err(|_| PresaleError::InvalidPrice)?;
-        
-        // Get the latest round data (price + timestamp)
-        let round = feed_data
-            .latest_round_data()
-            .ok_or(PresaleError::InvalidPrice)?;
-        
-        let sol_price_usd = round.answer; // Price with 8 decimals (e.g., 140_00000000 = $140)
-        
-        // Validate price is positive
         require!(
-            sol_price_usd > 0,
-            PresaleError::InvalidPrice
+            presale_state.oracle_feed != Pubkey::default(),
+            PresaleError::OracleFeedNotSet
         );
-        
-        // Optional: Check that the feed uses the expected decimals (8)
-        let decimals = feed_data.decimals();
         require!(
-            decimals == CHAINLINK_DECIMALS,
-            PresaleError::InvalidPrice
+            feed.key() == presale_state.oracle_feed,
+            PresaleError::InvalidOracleFeed
         );
-        
-        // Check for stale price using round timestamp
+
+        // Read SOL/USD price (8 decimals) and its publish time from the configured oracle
+        let primary_price = match presale_state.oracle_type {
+            OracleType::Chainlink => read_chainlink_price(feed)?,
+            OracleType::Pyth => read_pyth_price(feed)?,
+        };
+
+        // A stale or non-positive Chainlink price falls back to the configured backup feed
+        // when it is passed
         let current_timestamp = Clock::get()?.unix_timestamp;
-        // round.timestamp is u32, convert to i64 to match unix_timestamp type
+        let staleness_threshold = presale_state.effective_staleness_threshold();
+        let (sol_price_usd, price_timestamp) = match ctx.accounts.chainlink_feed_backup.as_ref() {
+            Some(backup)
+                if presale_state.oracle_type == OracleType::Chainlink
+                    && !is_usable_price(primary_price, current_timestamp, staleness_threshold) =>
+            {
+                require!(
+                    presale_state.oracle_feed_backup != Pubkey::default()
+                        && backup.key() == presale_state.oracle_feed_backup
+                        && backup.key() != feed.key(),
+                    PresaleError::InvalidOracleFeed
+                );
+                let backup_price = read_chainlink_price(backup)?;
+                require!(
+                    is_usable_price(backup_price, current_timestamp, staleness_threshold),
+                    PresaleError::StalePrice
+                );
+                emit!(BackupFeedUsed {
+                    primary: feed.key(),
+                    backup: backup.key(),
+                });
+                backup_price
+            }
+            _ => primary_price,
+        };
+
+        // Validate price is positive
+        require!(
+            sol_price_usd > 0,
+            PresaleError::InvalidPrice
+        );
+
+        // Check for stale price using the publish timestamp
         let price_age = current_timestamp
-            .checked_sub(round.timestamp.into())
+            .checked_sub(price_timestamp)
             .ok_or(PresaleError::InvalidPrice)?;
         
         require!(
-            price_age <= PRICE_FEED_STALENESS_THRESHOLD_SECONDS,
+            price_age <= staleness_threshold + PRICE_FEED_STALENESS_GRACE_SECONDS,
             PresaleError::StalePrice
         );
+        // Warn monitors when the feed is at the staleness boundary
+        if price_age > staleness_threshold - PRICE_FEED_STALENESS_GRACE_SECONDS {
+            emit!(PriceNearStale {
+                price_age,
+                threshold: staleness_threshold,
+            });
+        }
         
-        // Production security: Verify feed owner is Chainlink OCR2 program.
-        // We do NOT hardcode specific feed addresses on-chain; instead, we rely on:
-        // - Owner verification (must be Chainlink OCR2 program)
-        // - Decimals check (must be 8)
-        // - Positive price
-        // - Staleness check
-        require!(
-            feed.owner == &CHAINLINK_PROGRAM_ID,
-            PresaleError::InvalidPrice
-        );
-        
-        // Calculate tokens to receive using Chainlink price
+        // Calculate tokens to receive using the oracle price
         // Formula: 
         // 1. Convert SOL amount to USD: sol_usd = (sol_amount * sol_price_usd) / (10^8 * 10^9)
         // 2. Calculate tokens: tokens = sol_usd / token_price_usd
@@ -867,9 +1674,23 @@ pub mod presale {
         // Simplified: tokens = (sol_amount * sol_price_usd * 10^6) / (token_price_usd_micro * 10^8 * 10^9)
         // Further simplified: tokens = (sol_amount * sol_price_usd) / (token_price_usd_micro * 10^11)
         
+        // While a round is active, purchases must price against it
+        require!(
+            presale_state.active_round_id.is_none() || round_id == presale_state.active_round_id,
+            PresaleError::InvalidRound
+        );
+
+        // Select the round price if a round is given, otherwise the global price
+        let token_price_usd_micro = match round_id {
+            Some(_) => ctx.accounts.presale_round.as_ref()
+                .ok_or(PresaleError::InvalidRound)?
+                .token_price_usd_micro,
+            None => presale_state.token_price_usd_micro,
+        };
+
         // Validate token_price_usd_micro is set
         require!(
-            presale_state.token_price_usd_micro > 0,
+            token_price_usd_micro > 0,
             PresaleError::InvalidAmount
         );
 
@@ -893,52 +1714,127 @@ pub mod presale {
         // 5. Simplified: tokens_base = (sol_amount * sol_price_usd * 10^6) / (token_price_usd_micro * 10^9)
         //    tokens_base = (sol_amount * sol_price_usd * 10^6) / (token_price_usd_micro * 10^9)
         
-        let tokens_to_receive_u128 = (sol_amount as u128)
-            .checked_mul(sol_price_usd_u128)
-            .ok_or(PresaleError::Overflow)?
-            .checked_mul(1_000_000u128) // Convert to micro-USD (10^6)
-            .ok_or(PresaleError::Overflow)?
-            .checked_mul(10u128.pow(TOKEN_DECIMALS as u32)) // 10^8 for token base units
-            .ok_or(PresaleError::Overflow)?
-            .checked_div(
-                (presale_state.token_price_usd_micro as u128)
-                    .checked_mul(10u128.pow(SOL_DECIMALS as u32)) // 10^9 for SOL decimals
-                    .ok_or(PresaleError::Overflow)?
-                    .checked_mul(10u128.pow(CHAINLINK_DECIMALS as u32)) // 10^8 for Chainlink decimals
-                    .ok_or(PresaleError::Overflow)?
-            )
-            .ok_or(PresaleError::Overflow)?;
+        // Quote a SOL amount: tokens, effective price, USD value and referral bonus
+        let quote = |sol_amount: u64| -> Result<(u64, u64, u64, u64)> {
+            // Price tiers apply to the presale price only; a round keeps its flat price
+            let tiered = round_id.is_none() && !presale_state.price_tiers.is_empty();
+            let sol_usd_micro = (sol_amount as u128)
+                .checked_mul(sol_price_usd_u128)
+                .ok_or(PresaleError::Overflow)?
+                .checked_mul(1_000_000u128)
+                .ok_or(PresaleError::Overflow)?
+                .checked_div(
+                    10u128
+                        .pow(SOL_DECIMALS as u32)
+                        .checked_mul(10u128.pow(CHAINLINK_DECIMALS as u32))
+                        .ok_or(PresaleError::Overflow)?,
+                )
+                .ok_or(PresaleError::Overflow)?;
+            require!(sol_usd_micro <= u64::MAX as u128, PresaleError::Overflow);
+            let sol_usd_micro = sol_usd_micro as u64;
 
-        require!(
-            tokens_to_receive_u128 <= u64::MAX as u128,
-            PresaleError::Overflow
-        );
+            let tokens_to_receive_u128 = (sol_amount as u128)
+                .checked_mul(sol_price_usd_u128)
+                .ok_or(PresaleError::Overflow)?
+                .checked_mul(1_000_000u128) // Convert to micro-USD (10^6)
+                .ok_or(PresaleError::Overflow)?
+                .checked_mul(10u128.pow(TOKEN_DECIMALS as u32)) // 10^8 for token base units
+                .ok_or(PresaleError::Overflow)?
+                .checked_div(
+                    (token_price_usd_micro as u128)
+                        .checked_mul(10u128.pow(SOL_DECIMALS as u32)) // 10^9 for SOL decimals
+                        .ok_or(PresaleError::Overflow)?
+                        .checked_mul(10u128.pow(CHAINLINK_DECIMALS as u32)) // 10^8 for Chainlink decimals
+                        .ok_or(PresaleError::Overflow)?
+                )
+                .ok_or(PresaleError::Overflow)?;
 
-        let tokens_to_receive = tokens_to_receive_u128 as u64;
-        
-        // Validate tokens_to_receive is greater than 0
-        require!(
-            tokens_to_receive > 0,
-            PresaleError::InvalidAmount
-        );
+            require!(
+                tokens_to_receive_u128 <= u64::MAX as u128,
+                PresaleError::Overflow
+            );
 
-        // Check presale cap
-        if presale_state.max_presale_cap > 0 {
-            let new_total = presale_state
-                .total_tokens_sold
-                .checked_add(tokens_to_receive)
-                .ok_or(PresaleError::Overflow)?;
+            let (tokens_to_receive, effective_price_usd_micro) = if tiered {
+                presale_state.quote_tokens(sol_usd_micro)?
+            } else {
+                (tokens_to_receive_u128 as u64, token_price_usd_micro)
+            };
+        
+            // Validate tokens_to_receive is greater than 0
             require!(
-                new_total <= presale_state.max_presale_cap,
-                PresaleError::PresaleCapExceeded
+                tokens_to_receive > 0,
+                PresaleError::InvalidAmount
             );
+
+            // USD value of this purchase at the effective token price
+            let purchase_usd_micro = if tiered {
+                sol_usd_micro
+            } else {
+                PresaleState::tokens_to_usd_micro(tokens_to_receive, token_price_usd_micro)?
+            };
+
+            // Referral bonus is paid on top of the purchased tokens
+            let referral_bonus = validate_referral(
+                presale_state,
+                referrer,
+                ctx.accounts.buyer.key(),
+                ctx.accounts.referral_account.as_deref().map(|account| &**account),
+                ctx.accounts.referrer_blacklist.as_ref(),
+                tokens_to_receive,
+            )?;
+            Ok((tokens_to_receive, effective_price_usd_micro, purchase_usd_micro, referral_bonus))
+        };
+
+        // A partial fill spends only as much SOL as fits under the presale cap
+        let requested_sol_amount = sol_amount;
+        let sol_amount = if allow_partial {
+            presale_state.fit_to_remaining_cap(sol_amount, |sol_amount| {
+                let (tokens, _, usd_micro, bonus) = quote(sol_amount)?;
+                presale_state.presale_cap_usage(tokens, bonus, usd_micro)
+            })?
+        } else {
+            sol_amount
+        };
+        let (tokens_to_receive, effective_price_usd_micro, purchase_usd_micro, referral_bonus) =
+            quote(sol_amount)?;
+
+        // A partial fill only has to meet the slippage bound pro rata
+        let min_tokens_out = if sol_amount < requested_sol_amount {
+            ((min_tokens_out as u128) * (sol_amount as u128) / (requested_sol_amount as u128)) as u64
+        } else {
+            min_tokens_out
+        };
+
+        // Slippage protection against oracle moves between signing and execution.
+        // The event stays in the failed transaction's logs, so clients can also
+        // read the actual amount from a simulation.
+        if tokens_to_receive < min_tokens_out {
+            emit!(SlippageProtectionTriggered {
+                buyer: ctx.accounts.buyer.key(),
+                expected_min: min_tokens_out,
+                actual_received: tokens_to_receive,
+            });
+            return Err(PresaleError::SlippageExceeded.into());
+        }
+
+        // Enforce round window and cap if a round is selected
+        if let Some(id) = round_id {
+            let round = ctx.accounts.presale_round.as_ref().ok_or(PresaleError::InvalidRound)?;
+            round.check_purchase(presale_state, id, tokens_to_receive, current_timestamp)?;
         }
 
-        // Check per-user limit
+        // Enforce per-purchase minimum and maximum
+        presale_state.check_purchase_amount(tokens_to_receive)?;
+
+        let tokens_delivered = tokens_to_receive
+            .checked_add(referral_bonus)
+            .ok_or(PresaleError::Overflow)?;
+
+        // Check per-user limit; the buyer's referral bonus counts towards it
         if presale_state.max_per_user > 0 {
             let user_purchase = &mut ctx.accounts.user_purchase;
             let new_user_total = user_purchase.total_purchased
-                .checked_add(tokens_to_receive)
+                .checked_add(tokens_delivered)
                 .ok_or(PresaleError::Overflow)?;
             require!(
                 new_user_total <= presale_state.max_per_user,
@@ -946,6 +1842,9 @@ pub mod presale {
             );
         }
 
+        // Check presale cap, counting the buyer's and referrer's bonuses as sold tokens
+        presale_state.check_presale_cap(tokens_to_receive, referral_bonus, purchase_usd_micro)?;
+
         // Extract values we need before borrowing
         let presale_token_mint = presale_state.presale_token_mint;
         let presale_token_vault_pda_bump = ctx.bumps.presale_token_vault_pda;
@@ -981,43 +1880,167 @@ pub mod presale {
         );
 
         // Transfer presale tokens from presale vault to buyer
-        let seeds = &[
-            b"presale_token_vault_pda",
-            presale_token_mint.as_ref(),
-            &[presale_token_vault_pda_bump],
-        ];
-        let signer = &[&seeds[..]];
+        // (with vesting enabled they stay in the vault until claim_vested_tokens,
+        // in claim mode until claim_tokens)
+        if !presale_state.vesting_enabled && !presale_state.claim_mode {
+            let seeds = &[
+                b"presale_token_vault_pda",
+                presale_token_mint.as_ref(),
+                &[presale_token_vault_pda_bump],
+            ];
+            let signer = &[&seeds[..]];
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.presale_token_vault.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, tokens_to_receive)?;
+            let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.presale_token_vault.to_account_info(),
+                mint: ctx.accounts.presale_token_mint.to_account_info(),
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, tokens_delivered, decimals)?;
+        }
 
         // Update state (now we can mutably borrow)
         let presale_state = &mut ctx.accounts.presale_state;
         presale_state.total_tokens_sold = presale_state
             .total_tokens_sold
-            .checked_add(tokens_to_receive)
+            .checked_add(tokens_delivered)
             .ok_or(PresaleError::Overflow)?;
         presale_state.total_raised = presale_state
             .total_raised
             .checked_add(sol_amount)
             .ok_or(PresaleError::Overflow)?;
+        if presale_state.vesting_enabled {
+            let vesting_account = ctx.accounts.vesting_account.as_mut()
+                .ok_or(PresaleError::VestingAccountRequired)?;
+            let created = vesting_account.record_purchase(
+                ctx.accounts.buyer.key(),
+                tokens_delivered,
+                presale_state,
+                Clock::get()?.unix_timestamp,
+                ctx.bumps.vesting_account.ok_or(PresaleError::VestingAccountRequired)?,
+            )?;
+            if created {
+                emit!(VestingScheduleCreated {
+                    buyer: vesting_account.buyer,
+                    total_amount: vesting_account.total_amount,
+                    start_time: vesting_account.start_time,
+                    cliff_time: vesting_account.cliff_time,
+                    end_time: vesting_account.end_time,
+                });
+            }
+        } else if !presale_state.claim_mode {
+            // Tokens are delivered at purchase time, so they are claimed immediately
+            presale_state.total_claimed = presale_state
+                .total_claimed
+                .checked_add(tokens_delivered)
+                .ok_or(PresaleError::Overflow)?;
+        }
+        presale_state.total_raised_usd_micro = presale_state
+            .total_raised_usd_micro
+            .checked_add(purchase_usd_micro)
+            .ok_or(PresaleError::Overflow)?;
 
         // Update user purchase tracker
         let user_purchase = &mut ctx.accounts.user_purchase;
         if user_purchase.buyer == Pubkey::default() {
             user_purchase.buyer = ctx.accounts.buyer.key();
             user_purchase.total_purchased = 0;
+            presale_state.unique_buyers = presale_state
+                .unique_buyers
+                .checked_add(1)
+                .ok_or(PresaleError::Overflow)?;
         }
         user_purchase.total_purchased = user_purchase
             .total_purchased
-            .checked_add(tokens_to_receive)
+            .checked_add(tokens_delivered)
+            .ok_or(PresaleError::Overflow)?;
+        if presale_state.claim_mode && !presale_state.vesting_enabled {
+            user_purchase.owed = user_purchase
+                .owed
+                .checked_add(tokens_delivered)
+                .ok_or(PresaleError::Overflow)?;
+        }
+        user_purchase.sol_paid = user_purchase
+            .sol_paid
+            .checked_add(sol_amount)
+            .ok_or(PresaleError::Overflow)?;
+
+        // Append this purchase to the buyer's on-chain history
+        let purchase_index = user_purchase.purchase_count;
+        user_purchase.purchase_count = purchase_index
+            .checked_add(1)
             .ok_or(PresaleError::Overflow)?;
+        let purchase_record = &mut ctx.accounts.purchase_record;
+        purchase_record.buyer = ctx.accounts.buyer.key();
+        purchase_record.purchase_index = purchase_index;
+        purchase_record.amount_tokens = tokens_delivered;
+        purchase_record.amount_paid = sol_amount;
+        purchase_record.payment_is_sol = true;
+        purchase_record.payment_mint = Pubkey::default();
+        purchase_record.timestamp = Clock::get()?.unix_timestamp;
+
+        // SOL totals, in lamports
+        ctx.accounts.raised_tracker.record(
+            presale_state.key(),
+            SOL_PAYMENT_SENTINEL,
+            sol_amount,
+            purchase_usd_micro,
+        )?;
+
+        emit!(PurchaseRecorded {
+            buyer: ctx.accounts.buyer.key(),
+            index: purchase_index,
+            tokens: tokens_delivered,
+            paid: sol_amount,
+            is_sol: true,
+            price_usd_micro: effective_price_usd_micro,
+            unique_buyers: presale_state.unique_buyers,
+        });
+
+        // Credit the referrer's matching bonus
+        if referral_bonus > 0 {
+            let referral_account = ctx.accounts.referral_account.as_mut()
+                .ok_or(PresaleError::InvalidReferrer)?;
+            referral_account.total_referrals = referral_account
+                .total_referrals
+                .checked_add(1)
+                .ok_or(PresaleError::Overflow)?;
+            referral_account.bonus_tokens_earned = referral_account
+                .bonus_tokens_earned
+                .checked_add(referral_bonus)
+                .ok_or(PresaleError::Overflow)?;
+            presale_state.referral_bonus_outstanding = presale_state
+                .referral_bonus_outstanding
+                .checked_add(referral_bonus)
+                .ok_or(PresaleError::Overflow)?;
+            presale_state.referral_bonus_total = presale_state
+                .referral_bonus_total
+                .checked_add(referral_bonus)
+                .ok_or(PresaleError::Overflow)?;
+
+            emit!(ReferralRecorded {
+                referrer: referral_account.referrer,
+                buyer: ctx.accounts.buyer.key(),
+                bonus: referral_bonus,
+            });
+        }
+
+        // Update round tracker, moving on to the next round once this one sells out
+        if round_id.is_some() {
+            if let Some(round) = ctx.accounts.presale_round.as_mut() {
+                round.tokens_sold = round
+                    .tokens_sold
+                    .checked_add(tokens_to_receive)
+                    .ok_or(PresaleError::Overflow)?;
+                round.advance_if_sold_out(
+                    presale_state,
+                    ctx.accounts.next_presale_round.as_ref(),
+                )?;
+            }
+        }
 
         msg!(
             "Buy with SOL successful: {} tokens for {} lamports",
@@ -1025,7 +2048,7 @@ pub mod presale {
             sol_amount
         );
 
-        Ok(())
+        Ok(tokens_to_receive)
     }
 
     /// Sets the token rate (tokens per SOL)
@@ -1114,7 +2137,7 @@ pub mod presale {
     ///
     /// # Parameters
     /// - `ctx`: WithdrawToTreasury context with all required accounts
-    /// - `amount`: Amount of payment tokens to withdraw (must be > 0)
+    /// - `amount`: Amount of payment tokens to withdraw (must be > 0, `WITHDRAW_ALL` = entire vault balance)
     ///
     /// # Returns
     /// - `Result<()>`: Success if withdrawal completes
@@ -1123,9 +2146,12 @@ pub mod presale {
     /// - `PresaleError::Unauthorized` if caller is not admin or governance
     /// - `PresaleError::TreasuryNotSet` if treasury address not configured
     /// - `PresaleError::InvalidAmount` if amount is 0 or exceeds vault balance
+    /// - `PresaleError::InvalidFeeCollector` if a withdrawal fee is set and the fee
+    ///   collector's token account is missing or invalid
     ///
     /// # Events
-    /// - Emits `TreasuryWithdrawn` with amount and treasury address
+    /// - Emits `TreasuryWithdrawn` with the amount sent to the treasury
+    /// - Emits `WithdrawalFeeCharged` when a withdrawal fee is taken
     ///
     /// # Security
     /// - Requires admin or governance authority
@@ -1148,6 +2174,19 @@ pub mod presale {
             presale_state.treasury_address != Pubkey::default(),
             PresaleError::TreasuryNotSet
         );
+
+        // Funds in a failed presale (or one stopped below its soft cap) are reserved for buyer refunds
+        require!(
+            !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
+
+        // Only mints that were ever allowed for this presale can be withdrawn
+        check_known_payment_token(
+            &ctx.accounts.presale_state.key(),
+            &ctx.accounts.payment_token_mint.key(),
+            &ctx.accounts.allowed_token,
+        )?;
         
         // Validate treasury token account (manual validation)
         let treasury_token_data = ctx.accounts.treasury_token_account.try_borrow_data()?;
@@ -1193,11 +2232,28 @@ pub mod presale {
         let vault_balance = u64::from_le_bytes(
             payment_vault_data[64..72].try_into().map_err(|_| PresaleError::InvalidAmount)?
         );
+        let amount = if amount == WITHDRAW_ALL { vault_balance } else { amount };
         require!(
-            vault_balance >= amount,
+            amount > 0 && vault_balance >= amount,
             PresaleError::InvalidAmount
         );
-        
+
+        let fee_amount = presale_state.withdrawal_fee(amount)?;
+        let treasury_amount = amount
+            .checked_sub(fee_amount)
+            .ok_or(PresaleError::Overflow)?;
+        if fee_amount > 0 {
+            let fee_token_account = ctx.accounts.fee_collector_token_account
+                .as_ref()
+                .ok_or(PresaleError::InvalidFeeCollector)?;
+            let fee_token_data = fee_token_account.try_borrow_data()?;
+            require!(fee_token_data.len() >= 64, PresaleError::InvalidFeeCollector);
+            require!(
+                fee_token_data[0..32] == ctx.accounts.payment_token_mint.key().to_bytes()
+                    && fee_token_data[32..64] == presale_state.fee_collector.to_bytes(),
+                PresaleError::InvalidFeeCollector
+            );
+        }
         
         // Transfer from PDA vault to treasury
         let presale_state_key = presale_state.key();
@@ -1210,25 +2266,47 @@ pub mod presale {
         ];
         let signer = &[&seeds[..]];
         
-        let cpi_accounts = Transfer {
+        let decimals = mint_decimals(&ctx.accounts.payment_token_mint)?;
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.presale_payment_vault.to_account_info(),
+            mint: ctx.accounts.payment_token_mint.to_account_info(),
             to: ctx.accounts.treasury_token_account.to_account_info(),
             authority: ctx.accounts.presale_payment_vault_pda.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, amount)?;
+        token_interface::transfer_checked(cpi_ctx, treasury_amount, decimals)?;
+
+        // Fee collector's share (account validated above)
+        if let (true, Some(fee_token_account)) = (fee_amount > 0, &ctx.accounts.fee_collector_token_account) {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.presale_payment_vault.to_account_info(),
+                mint: ctx.accounts.payment_token_mint.to_account_info(),
+                to: fee_token_account.to_account_info(),
+                authority: ctx.accounts.presale_payment_vault_pda.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, fee_amount, decimals)?;
+
+            emit!(WithdrawalFeeCharged {
+                fee_amount,
+                fee_collector: presale_state.fee_collector,
+                treasury_amount,
+            });
+        }
         
         // Emit event
         emit!(TreasuryWithdrawn {
-            amount,
+            amount: treasury_amount,
             treasury: presale_state.treasury_address,
         });
 
         msg!(
-            "Withdrew {} payment tokens to treasury: {}",
-            amount,
-            presale_state.treasury_address
+            "Withdrew {} payment tokens to treasury: {} (fee: {})",
+            treasury_amount,
+            presale_state.treasury_address,
+            fee_amount
         );
         
         Ok(())
@@ -1241,7 +2319,8 @@ pub mod presale {
     ///
     /// # Parameters
     /// - `ctx`: WithdrawSolToTreasury context with all required accounts
-    /// - `amount`: Amount of SOL to withdraw in lamports (must be > 0)
+    /// - `amount`: Amount of SOL to withdraw in lamports (must be > 0, `WITHDRAW_ALL` = entire
+    ///   balance above the vault's rent-exempt minimum)
     ///
     /// # Returns
     /// - `Result<()>`: Success if withdrawal completes
@@ -1250,15 +2329,19 @@ pub mod presale {
     /// - `PresaleError::Unauthorized` if caller is not admin or governance
     /// - `PresaleError::TreasuryNotSet` if treasury address not configured
     /// - `PresaleError::InvalidAmount` if amount is 0 or exceeds vault balance
+    /// - `PresaleError::WouldBreakRentExemption` if the vault would drop below the rent-exempt minimum
+    /// - `PresaleError::InvalidFeeCollector` if a withdrawal fee is set and the fee
+    ///   collector account is missing
     ///
     /// # Events
-    /// - Emits `TreasuryWithdrawn` with amount and treasury address
+    /// - Emits `TreasuryWithdrawn` with the amount sent to the treasury
+    /// - Emits `WithdrawalFeeCharged` when a withdrawal fee is taken
     ///
     /// # Security
     /// - Requires admin or governance authority
     /// - Validates treasury address is set
     /// - Validates amount is positive
-    /// - Checks vault has sufficient balance
+    /// - Checks vault has sufficient balance and stays rent-exempt
     pub fn withdraw_sol_to_treasury(
         ctx: Context<WithdrawSolToTreasury>,
         amount: u64,
@@ -1275,6 +2358,12 @@ pub mod presale {
             presale_state.treasury_address != Pubkey::default(),
             PresaleError::TreasuryNotSet
         );
+
+        // Funds in a failed presale (or one stopped below its soft cap) are reserved for buyer refunds
+        require!(
+            !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
         
         // Validate amount is greater than 0
         require!(
@@ -1282,11 +2371,35 @@ pub mod presale {
             PresaleError::InvalidAmount
         );
         
+        // The vault must stay rent-exempt so it is never reaped between purchases
+        let rent_floor = Rent::get()?.minimum_balance(0);
+        let vault_lamports = ctx.accounts.sol_vault.lamports();
+
+        // Sweep everything above the rent-exempt minimum
+        let amount = if amount == WITHDRAW_ALL {
+            vault_lamports.saturating_sub(rent_floor)
+        } else {
+            amount
+        };
+
         // Check vault has enough SOL
         require!(
-            ctx.accounts.sol_vault.lamports() >= amount,
+            amount > 0 && vault_lamports >= amount,
             PresaleError::InvalidAmount
         );
+        require!(
+            vault_lamports - amount >= rent_floor,
+            PresaleError::WouldBreakRentExemption
+        );
+
+        let fee_amount = presale_state.withdrawal_fee(amount)?;
+        let treasury_amount = amount
+            .checked_sub(fee_amount)
+            .ok_or(PresaleError::Overflow)?;
+        require!(
+            fee_amount == 0 || ctx.accounts.fee_collector.is_some(),
+            PresaleError::InvalidFeeCollector
+        );
         
         // Transfer SOL from vault to treasury using system program
         let presale_state_key = presale_state.key();
@@ -1303,390 +2416,3421 @@ pub mod presale {
         };
         let cpi_program = ctx.accounts.system_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        anchor_lang::system_program::transfer(cpi_ctx, treasury_amount)?;
+
+        // Fee collector's share (address checked by the account constraint)
+        if let (true, Some(fee_collector)) = (fee_amount > 0, &ctx.accounts.fee_collector) {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: fee_collector.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            anchor_lang::system_program::transfer(cpi_ctx, fee_amount)?;
+
+            emit!(WithdrawalFeeCharged {
+                fee_amount,
+                fee_collector: presale_state.fee_collector,
+                treasury_amount,
+            });
+        }
         
         // Emit event
         emit!(TreasuryWithdrawn {
-            amount,
+            amount: treasury_amount,
             treasury: presale_state.treasury_address,
         });
 
         msg!(
-            "Withdrew {} lamports to treasury: {}",
-            amount,
-            presale_state.treasury_address
+            "Withdrew {} lamports to treasury: {} (fee: {})",
+            treasury_amount,
+            presale_state.treasury_address,
+            fee_amount
         );
         
         Ok(())
     }
 
-    /// Withdraws unsold presale tokens from presale vault to destination
+    /// Tops the SOL vault up to its rent-exempt minimum
     ///
-    /// Transfers unsold presale tokens from the presale token vault to the configured
-    /// treasury address or a specified destination. Can be called by admin or governance.
-    /// Typically called after the presale has ended to recover unsold tokens.
+    /// Helper for existing deployments whose vault was drained below the rent
+    /// floor before withdrawals enforced it. Anyone can pay; does nothing if the
+    /// vault is already rent-exempt.
     ///
     /// # Parameters
-    /// - `ctx`: WithdrawUnsoldTokens context with all required accounts
-    /// - `amount`: Amount of presale tokens to withdraw (must be > 0)
+    /// - `ctx`: FundSolVaultRent context
     ///
     /// # Returns
-    /// - `Result<()>`: Success if withdrawal completes
+    /// - `Result<()>`: Success if the vault is rent-exempt afterwards
+    pub fn fund_sol_vault_rent(ctx: Context<FundSolVaultRent>) -> Result<()> {
+        let rent_floor = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_floor.saturating_sub(ctx.accounts.sol_vault.lamports());
+
+        if shortfall == 0 {
+            msg!("SOL vault already rent-exempt");
+            return Ok(());
+        }
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: ctx.accounts.sol_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, shortfall)?;
+
+        msg!("Funded SOL vault with {} lamports to reach rent exemption", shortfall);
+        Ok(())
+    }
+
+    /// Claims a SOL refund from a failed presale (or one stopped below its soft cap)
+    ///
+    /// Returns the buyer's purchased presale tokens to the presale token vault and
+    /// transfers the lamports they paid back from the SOL vault. Each purchase
+    /// record can only be refunded once.
+    ///
+    /// # Parameters
+    /// - `ctx`: ClaimRefundSol context with all required accounts
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if refund completes
     ///
     /// # Errors
-    /// - `PresaleError::Unauthorized` if caller is not admin or governance
-    /// - `PresaleError::TreasuryNotSet` if treasury address not configured and destination is treasury
-    /// - `PresaleError::InvalidAmount` if amount is 0 or exceeds vault balance
+    /// - `PresaleError::InvalidStatus` if presale is not Failed or stopped below its soft cap
+    /// - `PresaleError::AlreadyRefunded` if buyer was already refunded
+    /// - `PresaleError::NothingToRefund` if buyer paid no SOL
+    /// - `PresaleError::InvalidAmount` if SOL vault cannot cover the refund
     ///
     /// # Events
-    /// - Emits `TreasuryWithdrawn` with amount and destination address
-    ///
-    /// # Security
-    /// - Requires admin or governance authority
-    /// - Validates destination token account
-    /// - Validates amount is positive
-    /// - Checks vault has sufficient balance
-    pub fn withdraw_unsold_tokens(
-        ctx: Context<WithdrawUnsoldTokens>,
-        amount: u64,
-    ) -> Result<()> {
+    /// - Emits `RefundClaimed` with `is_sol = true`
+    pub fn claim_refund_sol(ctx: Context<ClaimRefundSol>) -> Result<()> {
         let presale_state = &ctx.accounts.presale_state;
-        
-        require!(
-            presale_state.authority == ctx.accounts.authority.key() 
-                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
-            PresaleError::Unauthorized
-        );
-        
-        // Validate amount is greater than 0
+
         require!(
-            amount > 0,
-            PresaleError::InvalidAmount
+            presale_state.refunds_open(),
+            PresaleError::InvalidStatus
         );
-        
-        // Validate destination token account (manual validation)
-        let destination_token_data = ctx.accounts.destination_token_account.try_borrow_data()?;
-        require!(destination_token_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
-        let destination_token_mint = Pubkey::try_from_slice(&destination_token_data[0..32])
-            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
-        let destination_token_owner = Pubkey::try_from_slice(&destination_token_data[32..64])
-            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+
+        let user_purchase = &ctx.accounts.user_purchase;
         require!(
-            destination_token_mint == presale_state.presale_token_mint,
-            PresaleError::InvalidTreasuryAccount
+            user_purchase.round_number == presale_state.round_number,
+            PresaleError::NothingToRefund
         );
+        require!(!user_purchase.refunded, PresaleError::AlreadyRefunded);
+        require!(user_purchase.sol_paid > 0, PresaleError::NothingToRefund);
+
+        let refund_amount = user_purchase.sol_paid;
+        // Unclaimed vesting and claim-mode tokens never left the vault, so only
+        // delivered tokens are returned
+        let tokens_to_return = user_purchase.total_purchased
+            .saturating_sub(
+                ctx.accounts.vesting_account.as_ref().map_or(0, |vesting| vesting.remaining()),
+            )
+            .saturating_sub(user_purchase.owed);
+
         require!(
-            destination_token_owner == ctx.accounts.destination.key(),
-            PresaleError::InvalidTreasuryAccount
+            ctx.accounts.sol_vault.lamports() >= refund_amount,
+            PresaleError::InvalidAmount
         );
 
-        // Validate presale token vault (manual validation)
-        let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
-        require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
-        let presale_token_vault_mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
-            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
-        let presale_token_vault_owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
-            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        // Validate presale token vault (manual validation) - scope the borrow
+        let (presale_token_vault_mint, presale_token_vault_owner) = {
+            let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+            require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidAccount);
+            let mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            let owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            (mint, owner)
+        }; // Borrow dropped here
         require!(
             presale_token_vault_mint == presale_state.presale_token_mint,
-            PresaleError::InvalidTreasuryAccount
+            PresaleError::InvalidAccount
         );
         require!(
             presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
-            PresaleError::InvalidTreasuryAccount
-        );
-        
-        // Check withdrawal balance (ensure vault has enough)
-        // Token account layout: mint (0-32), owner (32-64), amount (64-72)
-        require!(presale_token_vault_data.len() >= 72, PresaleError::InvalidAmount);
-        let vault_balance = u64::from_le_bytes(
-            presale_token_vault_data[64..72].try_into().map_err(|_| PresaleError::InvalidAmount)?
-        );
-        require!(
-            vault_balance >= amount,
-            PresaleError::InvalidAmount
+            PresaleError::InvalidAccount
         );
-        
-        // Transfer from PDA vault to destination
-        let presale_token_mint = presale_state.presale_token_mint;
-        let seeds = &[
-            b"presale_token_vault_pda",
-            presale_token_mint.as_ref(),
-            &[ctx.bumps.presale_token_vault_pda],
+
+        // Return purchased presale tokens from buyer to presale vault
+        if tokens_to_return > 0 {
+            let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.buyer_token_account.to_account_info(),
+                mint: ctx.accounts.presale_token_mint.to_account_info(),
+                to: ctx.accounts.presale_token_vault.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, tokens_to_return, decimals)?;
+        }
+
+        // Transfer SOL from vault back to buyer
+        let presale_state_key = presale_state.key();
+        let seeds = &[
+            b"presale_sol_vault",
+            presale_state_key.as_ref(),
+            &[ctx.bumps.sol_vault],
         ];
         let signer = &[&seeds[..]];
-        
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.presale_token_vault.to_account_info(),
-            to: ctx.accounts.destination_token_account.to_account_info(),
-            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            to: ctx.accounts.buyer.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_program = ctx.accounts.system_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, amount)?;
-        
+        anchor_lang::system_program::transfer(cpi_ctx, refund_amount)?;
+
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        user_purchase.refunded = true;
+        user_purchase.owed = 0;
+
         // Emit event
-        emit!(TreasuryWithdrawn {
-            amount,
-            treasury: ctx.accounts.destination.key(),
+        emit!(RefundClaimed {
+            buyer: ctx.accounts.buyer.key(),
+            amount: refund_amount,
+            is_sol: true,
         });
 
         msg!(
-            "Withdrew {} unsold presale tokens to destination: {}",
-            amount,
-            ctx.accounts.destination.key()
+            "Refunded {} lamports to {} ({} tokens returned)",
+            refund_amount,
+            ctx.accounts.buyer.key(),
+            tokens_to_return
         );
-        
+
         Ok(())
     }
 
-    /// Update maximum presale cap
-    /// Allows authority (admin or governance) to adjust the total presale cap after initialization
+    /// Claims a payment token refund from a failed presale (or one stopped below its soft cap)
+    ///
+    /// Returns the buyer's purchased presale tokens to the presale token vault and
+    /// transfers the payment tokens they paid back from the payment vault of the
+    /// mint recorded in their purchase. Each purchase record can only be refunded once.
     ///
     /// # Parameters
-    /// - `ctx`: UpdatePresaleCap context (requires authority)
-    /// - `new_cap`: New maximum presale cap in payment token base units
+    /// - `ctx`: ClaimRefundPaymentToken context with all required accounts
     ///
     /// # Returns
-    /// - `Result<()>`: Success if cap is updated
+    /// - `Result<()>`: Success if refund completes
     ///
     /// # Errors
-    /// - `PresaleError::Unauthorized` if caller is not authority
-    /// - `PresaleError::InvalidAmount` if new cap < current raised amount
-    /// - `PresaleError::InvalidStatus` if presale has stopped
+    /// - `PresaleError::InvalidStatus` if presale is not Failed or stopped below its soft cap
+    /// - `PresaleError::AlreadyRefunded` if buyer was already refunded
+    /// - `PresaleError::NothingToRefund` if buyer paid no payment tokens
+    /// - `PresaleError::PaymentTokenNotAllowed` if vault or buyer account mints do not match
     ///
-    /// # Security
-    /// - Only authority (admin or governance) can update caps
-    /// - Cannot set cap below already raised amount
-    /// - Cannot update after presale is stopped (but can update when paused)
-    pub fn update_presale_cap(ctx: Context<UpdatePresaleCap>, new_cap: u64) -> Result<()> {
-        let presale_state = &mut ctx.accounts.presale_state;
-        
-        // Verify authority (admin or governance)
+    /// # Events
+    /// - Emits `RefundClaimed` with `is_sol = false`
+    pub fn claim_refund_payment_token(ctx: Context<ClaimRefundPaymentToken>) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
         require!(
-            presale_state.authority == ctx.accounts.authority.key() 
-                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
-            PresaleError::Unauthorized
+            presale_state.refunds_open(),
+            PresaleError::InvalidStatus
         );
-        
-        // Validate new cap is reasonable (0 = unlimited is allowed)
-        // If setting a limit, it must be greater than already raised
-        if new_cap > 0 {
-            require!(
-                new_cap >= presale_state.total_raised,
-                PresaleError::InvalidAmount
-            );
-        }
-        
-        // Cannot update if presale is stopped (but paused is okay)
+
+        let user_purchase = &ctx.accounts.user_purchase;
         require!(
-            presale_state.status != PresaleStatus::Stopped,
-            PresaleError::InvalidStatus
+            user_purchase.round_number == presale_state.round_number,
+            PresaleError::NothingToRefund
         );
-        
-        let old_cap = presale_state.max_presale_cap;
-        presale_state.max_presale_cap = new_cap;
-        
+        require!(!user_purchase.refunded, PresaleError::AlreadyRefunded);
+        require!(user_purchase.payment_amount_paid > 0, PresaleError::NothingToRefund);
+
+        let refund_amount = user_purchase.payment_amount_paid;
+        // Unclaimed vesting and claim-mode tokens never left the vault, so only
+        // delivered tokens are returned
+        let tokens_to_return = user_purchase.total_purchased
+            .saturating_sub(
+                ctx.accounts.vesting_account.as_ref().map_or(0, |vesting| vesting.remaining()),
+            )
+            .saturating_sub(user_purchase.owed);
+
+        // Validate payment vault (manual validation) - scope the borrow
+        let (payment_vault_mint, payment_vault_owner) = {
+            let payment_vault_data = ctx.accounts.presale_payment_vault.try_borrow_data()?;
+            require!(payment_vault_data.len() >= 64, PresaleError::PaymentTokenNotAllowed);
+            let mint = Pubkey::try_from_slice(&payment_vault_data[0..32])
+                .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+            let owner = Pubkey::try_from_slice(&payment_vault_data[32..64])
+                .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+            (mint, owner)
+        }; // Borrow dropped here
+        require!(
+            payment_vault_mint == ctx.accounts.payment_token_mint.key(),
+            PresaleError::PaymentTokenNotAllowed
+        );
+        require!(
+            payment_vault_owner == ctx.accounts.presale_payment_vault_pda.key(),
+            PresaleError::PaymentTokenNotAllowed
+        );
+
+        // Validate buyer payment token account mint - scope the borrow
+        let buyer_payment_mint = {
+            let buyer_payment_data = ctx.accounts.buyer_payment_token_account.try_borrow_data()?;
+            require!(buyer_payment_data.len() >= 32, PresaleError::PaymentTokenNotAllowed);
+            Pubkey::try_from_slice(&buyer_payment_data[0..32])
+                .map_err(|_| PresaleError::PaymentTokenNotAllowed)?
+        }; // Borrow dropped here
+        require!(
+            buyer_payment_mint == ctx.accounts.payment_token_mint.key(),
+            PresaleError::PaymentTokenNotAllowed
+        );
+
+        // Validate presale token vault (manual validation) - scope the borrow
+        let (presale_token_vault_mint, presale_token_vault_owner) = {
+            let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+            require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidAccount);
+            let mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            let owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            (mint, owner)
+        }; // Borrow dropped here
+        require!(
+            presale_token_vault_mint == presale_state.presale_token_mint,
+            PresaleError::InvalidAccount
+        );
+        require!(
+            presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
+            PresaleError::InvalidAccount
+        );
+
+        // Return purchased presale tokens from buyer to presale vault
+        if tokens_to_return > 0 {
+            let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.buyer_token_account.to_account_info(),
+                mint: ctx.accounts.presale_token_mint.to_account_info(),
+                to: ctx.accounts.presale_token_vault.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, tokens_to_return, decimals)?;
+        }
+
+        // Transfer payment tokens from vault back to buyer
+        let presale_state_key = presale_state.key();
+        let payment_token_mint_key = ctx.accounts.payment_token_mint.key();
+        let seeds = &[
+            b"presale_payment_vault_pda",
+            presale_state_key.as_ref(),
+            payment_token_mint_key.as_ref(),
+            &[ctx.bumps.presale_payment_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint_decimals(&ctx.accounts.payment_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_payment_vault.to_account_info(),
+            mint: ctx.accounts.payment_token_mint.to_account_info(),
+            to: ctx.accounts.buyer_payment_token_account.to_account_info(),
+            authority: ctx.accounts.presale_payment_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, refund_amount, decimals)?;
+
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        user_purchase.refunded = true;
+        user_purchase.owed = 0;
+
+        // Emit event
+        emit!(RefundClaimed {
+            buyer: ctx.accounts.buyer.key(),
+            amount: refund_amount,
+            is_sol: false,
+        });
+
         msg!(
-            "Presale cap updated from {} to {} by authority {}",
-            old_cap,
-            new_cap,
-            ctx.accounts.authority.key()
+            "Refunded {} payment tokens to {} ({} tokens returned)",
+            refund_amount,
+            ctx.accounts.buyer.key(),
+            tokens_to_return
         );
-        
+
         Ok(())
     }
 
-    /// Update maximum contribution per user
-    /// Allows authority (admin or governance) to adjust the per-user contribution limit after initialization
+    /// Closes the buyer's purchase tracker once the presale has ended
+    ///
+    /// Returns the rent of the `UserPurchase` PDA and of every `PurchaseRecord` PDA
+    /// indexed by it to the buyer. The records are closed together with the tracker
+    /// because their seeds are derived from `purchase_count`, which restarts at zero
+    /// if the tracker is recreated.
+    ///
+    /// All `purchase_count` records must be passed in `remaining_accounts`, ordered by
+    /// purchase index. Closing the tracker gives up any refund should the presale be
+    /// marked as failed afterwards.
     ///
     /// # Parameters
-    /// - `ctx`: UpdateMaxPerUser context (requires authority)
-    /// - `new_max`: New maximum contribution per user in payment token base units
+    /// - `ctx`: CloseUserPurchase context (requires the buyer's signature)
     ///
     /// # Returns
-    /// - `Result<()>`: Success if max is updated
+    /// - `Result<()>`: Success if the tracker and its records are closed
     ///
     /// # Errors
-    /// - `PresaleError::Unauthorized` if caller is not authority
-    /// - `PresaleError::InvalidAmount` if new max exceeds presale cap (when cap is set)
-    /// - `PresaleError::InvalidStatus` if presale has stopped
+    /// - `PresaleError::InvalidStatus` if presale is not Stopped or refunds are open
+    /// - `PresaleError::TokensNotClaimed` if claim-mode tokens are still owed
+    /// - `PresaleError::InvalidAccount` if the purchase records passed do not match
     ///
-    /// # Security
-    /// - Only authority (admin or governance) can update limits
-    /// - Must be less than or equal to total presale cap (if cap is set)
-    /// - Cannot update after presale is stopped (but paused is okay)
-    pub fn update_max_per_user(ctx: Context<UpdateMaxPerUser>, new_max: u64) -> Result<()> {
-        let presale_state = &mut ctx.accounts.presale_state;
-        
-        // Verify authority (admin or governance)
+    /// # Events
+    /// - Emits `UserPurchaseClosed`
+    pub fn close_user_purchase<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseUserPurchase<'info>>,
+    ) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
         require!(
-            presale_state.authority == ctx.accounts.authority.key() 
-                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
-            PresaleError::Unauthorized
+            presale_state.status == PresaleStatus::Stopped && !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
         );
-        
-        // Validate new max is reasonable (0 = unlimited is allowed)
-        // If both max_per_user and max_presale_cap are set, max_per_user must be <= max_presale_cap
-        if new_max > 0 && presale_state.max_presale_cap > 0 {
-            require!(
-                new_max <= presale_state.max_presale_cap,
-                PresaleError::InvalidAmount
-            );
-        }
-        
-        // Cannot update if presale is stopped (but paused is okay)
+
         require!(
-            presale_state.status != PresaleStatus::Stopped,
-            PresaleError::InvalidStatus
+            ctx.accounts.user_purchase.owed == 0,
+            PresaleError::TokensNotClaimed
         );
-        
-        let old_max = presale_state.max_per_user;
-        presale_state.max_per_user = new_max;
-        
+
+        let purchase_count = ctx.accounts.user_purchase.purchase_count;
+        require!(
+            ctx.remaining_accounts.len() == purchase_count as usize,
+            PresaleError::InvalidAccount
+        );
+
+        let presale_state_key = presale_state.key();
+        let buyer_key = ctx.accounts.buyer.key();
+        let buyer_info = ctx.accounts.buyer.to_account_info();
+        for (index, record_info) in ctx.remaining_accounts.iter().enumerate() {
+            let (expected, _) = Pubkey::find_program_address(
+                &[
+                    b"purchase_record",
+                    presale_state_key.as_ref(),
+                    buyer_key.as_ref(),
+                    &(index as u32).to_le_bytes(),
+                ],
+                ctx.program_id,
+            );
+            require!(record_info.key() == expected, PresaleError::InvalidAccount);
+
+            let record: Account<'info, PurchaseRecord> = Account::try_from(record_info)?;
+            record.close(buyer_info.clone())?;
+        }
+
+        // Emit event
+        emit!(UserPurchaseClosed {
+            buyer: buyer_key,
+            records_closed: purchase_count,
+        });
+
         msg!(
-            "Max per user updated from {} to {} by authority {}",
-            old_max,
-            new_max,
-            ctx.accounts.authority.key()
+            "Closed purchase tracker for {} ({} purchase records)",
+            buyer_key,
+            purchase_count
         );
-        
+
         Ok(())
     }
 
-    /// Update both presale cap and max per user atomically
-    /// Allows authority (admin or governance) to adjust both limits in a single transaction
+    /// Withdraws unsold presale tokens from presale vault to destination
+    ///
+    /// Transfers unsold presale tokens from the presale token vault to the configured
+    /// treasury address or a specified destination. Can be called by admin or governance.
+    /// Typically called after the presale has ended to recover unsold tokens.
     ///
     /// # Parameters
-    /// - `ctx`: UpdatePresaleLimits context (requires authority)
-    /// - `new_presale_cap`: New maximum presale cap (optional, None = no change)
-    /// - `new_max_per_user`: New maximum per user (optional, None = no change)
+    /// - `ctx`: WithdrawUnsoldTokens context with all required accounts
+    /// - `amount`: Amount of presale tokens to withdraw (must be > 0)
     ///
     /// # Returns
-    /// - `Result<()>`: Success if limits are updated
+    /// - `Result<()>`: Success if withdrawal completes
     ///
     /// # Errors
-    /// - `PresaleError::Unauthorized` if caller is not authority
-    /// - `PresaleError::InvalidAmount` if validation fails
-    /// - `PresaleError::InvalidStatus` if presale has stopped
+    /// - `PresaleError::Unauthorized` if caller is not admin or governance
+    /// - `PresaleError::TreasuryNotSet` if treasury address not configured and destination is treasury
+    /// - `PresaleError::InvalidAmount` if amount is 0 or exceeds vault balance
+    /// - `PresaleError::VaultInsolvent` if the withdrawal would leave outstanding allocations uncovered
+    ///
+    /// # Events
+    /// - Emits `TreasuryWithdrawn` with amount and destination address
     ///
     /// # Security
-    /// - Atomic update ensures consistency
-    /// - All validations applied
-    /// - Cannot update after presale is stopped
-    pub fn update_presale_limits(
-        ctx: Context<UpdatePresaleLimits>,
-        new_presale_cap: Option<u64>,
-        new_max_per_user: Option<u64>,
+    /// - Requires admin or governance authority
+    /// - Validates destination token account
+    /// - Validates amount is positive
+    /// - Checks vault has sufficient balance
+    pub fn withdraw_unsold_tokens(
+        ctx: Context<WithdrawUnsoldTokens>,
+        amount: u64,
     ) -> Result<()> {
-        let presale_state = &mut ctx.accounts.presale_state;
+        let presale_state = &ctx.accounts.presale_state;
         
-        // Verify authority (admin or governance)
         require!(
             presale_state.authority == ctx.accounts.authority.key() 
                 || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
             PresaleError::Unauthorized
         );
         
-        // Cannot update if presale is stopped (but paused is okay)
+        // Validate amount is greater than 0
         require!(
-            presale_state.status != PresaleStatus::Stopped,
-            PresaleError::InvalidStatus
+            amount > 0,
+            PresaleError::InvalidAmount
         );
         
-        // Track the effective cap for validation
-        let mut effective_cap = presale_state.max_presale_cap;
-        
-        // Update presale cap if provided
-        if let Some(new_cap) = new_presale_cap {
-            // If setting a limit (not 0), it must be >= already raised
-            if new_cap > 0 {
-                require!(
-                    new_cap >= presale_state.total_raised,
-                    PresaleError::InvalidAmount
-                );
-            }
-            
-            let old_cap = presale_state.max_presale_cap;
-            presale_state.max_presale_cap = new_cap;
-            effective_cap = new_cap;
-            
-            msg!("Presale cap updated from {} to {}", old_cap, new_cap);
+        // Validate destination token account (manual validation)
+        let destination_token_data = ctx.accounts.destination_token_account.try_borrow_data()?;
+        require!(destination_token_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+        let destination_token_mint = Pubkey::try_from_slice(&destination_token_data[0..32])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        let destination_token_owner = Pubkey::try_from_slice(&destination_token_data[32..64])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        require!(
+            destination_token_mint == presale_state.presale_token_mint,
+            PresaleError::InvalidTreasuryAccount
+        );
+        require!(
+            destination_token_owner == ctx.accounts.destination.key(),
+            PresaleError::InvalidTreasuryAccount
+        );
+
+        // Validate presale token vault (manual validation)
+        let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+        require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+        let presale_token_vault_mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        let presale_token_vault_owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        require!(
+            presale_token_vault_mint == presale_state.presale_token_mint,
+            PresaleError::InvalidTreasuryAccount
+        );
+        require!(
+            presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
+            PresaleError::InvalidTreasuryAccount
+        );
+        
+        // Check withdrawal balance (ensure vault has enough)
+        // Token account layout: mint (0-32), owner (32-64), amount (64-72)
+        require!(presale_token_vault_data.len() >= 72, PresaleError::InvalidAmount);
+        let vault_balance = u64::from_le_bytes(
+            presale_token_vault_data[64..72].try_into().map_err(|_| PresaleError::InvalidAmount)?
+        );
+        require!(
+            vault_balance >= amount,
+            PresaleError::InvalidAmount
+        );
+        // Unsold means unsold: never touch tokens still owed to buyers
+        require!(
+            vault_balance - amount >= presale_state.outstanding_allocations(),
+            PresaleError::VaultInsolvent
+        );
+        
+        // Transfer from PDA vault to destination
+        let presale_token_mint = presale_state.presale_token_mint;
+        let seeds = &[
+            b"presale_token_vault_pda",
+            presale_token_mint.as_ref(),
+            &[ctx.bumps.presale_token_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_token_vault.to_account_info(),
+            mint: ctx.accounts.presale_token_mint.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
+        
+        // Emit event
+        emit!(TreasuryWithdrawn {
+            amount,
+            treasury: ctx.accounts.destination.key(),
+        });
+
+        msg!(
+            "Withdrew {} unsold presale tokens to destination: {}",
+            amount,
+            ctx.accounts.destination.key()
+        );
+        
+        Ok(())
+    }
+
+    /// Verifies the presale token vault can cover all outstanding allocations
+    ///
+    /// Checks `vault_balance >= total_tokens_sold - total_claimed` so operators can
+    /// detect a vault that can no longer honor buyer claims before a claim fails.
+    /// Permissionless and read-only; intended to be run after any withdrawal.
+    ///
+    /// # Parameters
+    /// - `ctx`: VerifySolvency context
+    ///
+    /// # Returns
+    /// - `Result<bool>`: Whether the vault is solvent
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidTreasuryAccount` if the vault account is invalid
+    ///
+    /// # Events
+    /// - Emits `SolvencyChecked` with the shortfall (0 when solvent)
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<bool> {
+        let presale_state = &ctx.accounts.presale_state;
+
+        // Validate presale token vault (manual validation)
+        let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+        require!(presale_token_vault_data.len() >= 72, PresaleError::InvalidTreasuryAccount);
+        let presale_token_vault_mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        let presale_token_vault_owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+        require!(
+            presale_token_vault_mint == presale_state.presale_token_mint,
+            PresaleError::InvalidTreasuryAccount
+        );
+        require!(
+            presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
+            PresaleError::InvalidTreasuryAccount
+        );
+        // Token account layout: mint (0-32), owner (32-64), amount (64-72)
+        let vault_balance = u64::from_le_bytes(
+            presale_token_vault_data[64..72].try_into().map_err(|_| PresaleError::InvalidTreasuryAccount)?
+        );
+
+        let outstanding = presale_state.outstanding_allocations();
+        let shortfall = outstanding.saturating_sub(vault_balance);
+        let solvent = shortfall == 0;
+
+        emit!(SolvencyChecked { solvent, shortfall });
+
+        msg!(
+            "Solvency check: vault {} / outstanding {} (shortfall {})",
+            vault_balance,
+            outstanding,
+            shortfall
+        );
+        Ok(solvent)
+    }
+
+    /// Update maximum presale cap
+    /// Allows authority (admin or governance) to adjust the total presale cap after initialization
+    ///
+    /// # Parameters
+    /// - `ctx`: UpdatePresaleCap context (requires authority)
+    /// - `new_cap`: New maximum presale cap in payment token base units
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if cap is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidAmount` if new cap < current raised amount
+    /// - `PresaleError::InvalidStatus` if presale has stopped
+    ///
+    /// # Security
+    /// - Only authority (admin or governance) can update caps
+    /// - Cannot set cap below already raised amount
+    /// - Cannot update after presale is stopped (but can update when paused)
+    pub fn update_presale_cap(ctx: Context<UpdatePresaleCap>, new_cap: u64) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        
+        // Verify authority (admin or governance)
+        require!(
+            presale_state.authority == ctx.accounts.authority.key() 
+                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
+            PresaleError::Unauthorized
+        );
+        
+        // Validate new cap is reasonable (0 = unlimited is allowed)
+        // If setting a limit, it must be greater than already raised
+        if new_cap > 0 {
+            require!(
+                new_cap >= presale_state.cap_progress(),
+                PresaleError::InvalidAmount
+            );
         }
         
-        // Update max per user if provided
-        if let Some(new_max) = new_max_per_user {
-            // If both limits are set (not 0), max_per_user must be <= cap
-            if new_max > 0 && effective_cap > 0 {
-                require!(
-                    new_max <= effective_cap,
-                    PresaleError::InvalidAmount
-                );
-            }
-            
-            let old_max = presale_state.max_per_user;
-            presale_state.max_per_user = new_max;
-            
-            msg!("Max per user updated from {} to {}", old_max, new_max);
+        // Cannot update if presale is stopped (but paused is okay)
+        require!(
+            presale_state.status != PresaleStatus::Stopped,
+            PresaleError::InvalidStatus
+        );
+        
+        let old_cap = presale_state.max_presale_cap;
+        presale_state.max_presale_cap = new_cap;
+        
+        msg!(
+            "Presale cap updated from {} to {} by authority {}",
+            old_cap,
+            new_cap,
+            ctx.accounts.authority.key()
+        );
+        
+        Ok(())
+    }
+
+    /// Update maximum contribution per user
+    /// Allows authority (admin or governance) to adjust the per-user contribution limit after initialization
+    ///
+    /// # Parameters
+    /// - `ctx`: UpdateMaxPerUser context (requires authority)
+    /// - `new_max`: New maximum contribution per user in payment token base units
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if max is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidAmount` if new max exceeds presale cap (when cap is set)
+    /// - `PresaleError::InvalidStatus` if presale has stopped
+    ///
+    /// # Security
+    /// - Only authority (admin or governance) can update limits
+    /// - Must be less than or equal to total presale cap (if cap is set)
+    /// - Cannot update after presale is stopped (but paused is okay)
+    pub fn update_max_per_user(ctx: Context<UpdateMaxPerUser>, new_max: u64) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        
+        // Verify authority (admin or governance)
+        require!(
+            presale_state.authority == ctx.accounts.authority.key() 
+                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
+            PresaleError::Unauthorized
+        );
+        
+        // Validate new max is reasonable (0 = unlimited is allowed)
+        // If both max_per_user and max_presale_cap are set, max_per_user must be <= max_presale_cap
+        // (only comparable while the cap is counted in tokens)
+        if new_max > 0 && presale_state.max_presale_cap > 0 && !presale_state.cap_in_usd {
+            require!(
+                new_max <= presale_state.max_presale_cap,
+                PresaleError::InvalidAmount
+            );
         }
         
+        // Cannot update if presale is stopped (but paused is okay)
+        require!(
+            presale_state.status != PresaleStatus::Stopped,
+            PresaleError::InvalidStatus
+        );
+        
+        let old_max = presale_state.max_per_user;
+        presale_state.max_per_user = new_max;
+        
         msg!(
-            "Presale limits updated by authority {}",
+            "Max per user updated from {} to {} by authority {}",
+            old_max,
+            new_max,
             ctx.accounts.authority.key()
         );
         
         Ok(())
     }
+
+    /// Update both presale cap and max per user atomically
+    /// Allows authority (admin or governance) to adjust both limits in a single transaction
+    ///
+    /// # Parameters
+    /// - `ctx`: UpdatePresaleLimits context (requires authority)
+    /// - `new_presale_cap`: New maximum presale cap (optional, None = no change)
+    /// - `new_max_per_user`: New maximum per user (optional, None = no change)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if limits are updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidAmount` if validation fails
+    /// - `PresaleError::InvalidStatus` if presale has stopped
+    ///
+    /// # Security
+    /// - Atomic update ensures consistency
+    /// - All validations applied
+    /// - Cannot update after presale is stopped
+    pub fn update_presale_limits(
+        ctx: Context<UpdatePresaleLimits>,
+        new_presale_cap: Option<u64>,
+        new_max_per_user: Option<u64>,
+    ) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        
+        // Verify authority (admin or governance)
+        require!(
+            presale_state.authority == ctx.accounts.authority.key() 
+                || (presale_state.governance_set && presale_state.governance == ctx.accounts.authority.key()),
+            PresaleError::Unauthorized
+        );
+        
+        // Cannot update if presale is stopped (but paused is okay)
+        require!(
+            presale_state.status != PresaleStatus::Stopped,
+            PresaleError::InvalidStatus
+        );
+        
+        // Track the effective cap for validation
+        let mut effective_cap = presale_state.max_presale_cap;
+        
+        // Update presale cap if provided
+        if let Some(new_cap) = new_presale_cap {
+            // If setting a limit (not 0), it must be >= already raised
+            if new_cap > 0 {
+                require!(
+                    new_cap >= presale_state.cap_progress(),
+                    PresaleError::InvalidAmount
+                );
+            }
+            
+            let old_cap = presale_state.max_presale_cap;
+            presale_state.max_presale_cap = new_cap;
+            effective_cap = new_cap;
+            
+            msg!("Presale cap updated from {} to {}", old_cap, new_cap);
+        }
+        
+        // Update max per user if provided
+        if let Some(new_max) = new_max_per_user {
+            // If both limits are set (not 0), max_per_user must be <= cap
+            // (only comparable while the cap is counted in tokens)
+            if new_max > 0 && effective_cap > 0 && !presale_state.cap_in_usd {
+                require!(
+                    new_max <= effective_cap,
+                    PresaleError::InvalidAmount
+                );
+            }
+            
+            let old_max = presale_state.max_per_user;
+            presale_state.max_per_user = new_max;
+            
+            msg!("Max per user updated from {} to {}", old_max, new_max);
+        }
+        
+        msg!(
+            "Presale limits updated by authority {}",
+            ctx.accounts.authority.key()
+        );
+        
+        Ok(())
+    }
+
+    /// Set the minimum purchase amount
+    /// Rejects dust purchases that waste rent and pollute analytics
+    ///
+    /// # Parameters
+    /// - `ctx`: UpdatePurchaseLimits context (requires authority)
+    /// - `min_amount`: Minimum tokens per purchase in base units (0 = no minimum)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if minimum is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidAmount` if minimum exceeds max per transaction (when set)
+    ///
+    /// # Events
+    /// - Emits `PurchaseLimitsChanged`
+    pub fn set_min_purchase_amount(ctx: Context<UpdatePurchaseLimits>, min_amount: u64) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        if min_amount > 0 && presale_state.max_per_transaction > 0 {
+            require!(
+                min_amount <= presale_state.max_per_transaction,
+                PresaleError::InvalidAmount
+            );
+        }
+
+        let old_min = presale_state.min_purchase_amount;
+        presale_state.min_purchase_amount = min_amount;
+
+        emit!(PurchaseLimitsChanged {
+            min_purchase_amount: presale_state.min_purchase_amount,
+            max_per_transaction: presale_state.max_per_transaction,
+        });
+
+        msg!("Min purchase amount updated from {} to {}", old_min, min_amount);
+        Ok(())
+    }
+
+    /// Set the maximum tokens a single purchase may buy
+    /// Distinct from `max_per_user`; limits whale front-running within one transaction
+    ///
+    /// # Parameters
+    /// - `ctx`: UpdatePurchaseLimits context (requires authority)
+    /// - `max_amount`: Maximum tokens per purchase in base units (0 = unlimited)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if maximum is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidAmount` if maximum is below the minimum purchase amount
+    ///
+    /// # Events
+    /// - Emits `PurchaseLimitsChanged`
+    pub fn set_max_per_transaction(ctx: Context<UpdatePurchaseLimits>, max_amount: u64) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        if max_amount > 0 {
+            require!(
+                max_amount >= presale_state.min_purchase_amount,
+                PresaleError::InvalidAmount
+            );
+        }
+
+        let old_max = presale_state.max_per_transaction;
+        presale_state.max_per_transaction = max_amount;
+
+        emit!(PurchaseLimitsChanged {
+            min_purchase_amount: presale_state.min_purchase_amount,
+            max_per_transaction: presale_state.max_per_transaction,
+        });
+
+        msg!("Max per transaction updated from {} to {}", old_max, max_amount);
+        Ok(())
+    }
+
+    /// Set the presale end timestamp
+    /// Purchases are rejected once the end time has passed
+    ///
+    /// # Parameters
+    /// - `ctx`: SetEndTimestamp context (requires authority)
+    /// - `ts`: Unix timestamp after which the presale ends (None = no end time)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if end timestamp is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidTimestamp` if timestamp is not in the future or not after the start time
+    /// - `PresaleError::InvalidStatus` if presale has stopped
+    pub fn set_end_timestamp(ctx: Context<SetEndTimestamp>, ts: Option<i64>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+
+        if let Some(end) = ts {
+            require!(
+                end > Clock::get()?.unix_timestamp,
+                PresaleError::InvalidTimestamp
+            );
+            if let Some(start) = presale_state.start_timestamp {
+                require!(start < end, PresaleError::InvalidTimestamp);
+            }
+        }
+
+        presale_state.end_timestamp = ts;
+
+        msg!("Presale end timestamp set to {:?}", ts);
+        Ok(())
+    }
+
+    /// Set the presale start timestamp
+    /// Purchases are rejected before the start time, even while Active, so a sale
+    /// can be scheduled by starting it ahead of time
+    ///
+    /// # Parameters
+    /// - `ctx`: SetEndTimestamp context (requires authority)
+    /// - `ts`: Unix timestamp from which purchases are accepted (None = no start time)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if start timestamp is updated
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidTimestamp` if timestamp is not before the end time
+    /// - `PresaleError::InvalidStatus` if presale has stopped
+    pub fn set_start_timestamp(ctx: Context<SetEndTimestamp>, ts: Option<i64>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+
+        if let (Some(start), Some(end)) = (ts, presale_state.end_timestamp) {
+            require!(start < end, PresaleError::InvalidTimestamp);
+        }
+
+        presale_state.start_timestamp = ts;
+
+        msg!("Presale start timestamp set to {:?}", ts);
+        Ok(())
+    }
+
+    /// Finalizes the presale once its end time has passed
+    ///
+    /// Permissionless: anyone can call this after `end_timestamp` to move the
+    /// presale to Stopped, so closing does not depend on the admin being online.
+    ///
+    /// # Parameters
+    /// - `ctx`: FinalizePresale context
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if presale is finalized
+    ///
+    /// # Errors
+    /// - `PresaleError::PresaleNotEnded` if no end time is set or it has not passed
+    /// - `PresaleError::InvalidStatus` if presale is not Active or Paused
+    ///
+    /// # Events
+    /// - Emits `PresaleFinalized`
+    pub fn finalize_presale(ctx: Context<FinalizePresale>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status == PresaleStatus::Active
+                || presale_state.status == PresaleStatus::Paused,
+            PresaleError::InvalidStatus
+        );
+
+        let end_time = presale_state.end_timestamp.ok_or(PresaleError::PresaleNotEnded)?;
+        require!(
+            presale_state.has_ended(Clock::get()?.unix_timestamp),
+            PresaleError::PresaleNotEnded
+        );
+
+        presale_state.status = PresaleStatus::Stopped;
+
+        emit!(PresaleFinalized {
+            end_time,
+            tokens_sold: presale_state.total_tokens_sold,
+            total_raised: presale_state.total_raised,
+        });
+
+        msg!("Presale finalized by {}", ctx.accounts.caller.key());
+        Ok(())
+    }
+
+    /// Configures the referral program
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `enabled`: Whether purchases may name a referrer
+    /// - `bonus_percent`: Bonus paid to both buyer and referrer, as a percentage of purchased tokens
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidAmount` if bonus_percent exceeds MAX_REFERRAL_BONUS_PERCENT
+    pub fn set_referral_config(
+        ctx: Context<AdminOnly>,
+        enabled: bool,
+        bonus_percent: u8,
+    ) -> Result<()> {
+        require!(
+            bonus_percent <= MAX_REFERRAL_BONUS_PERCENT,
+            PresaleError::InvalidAmount
+        );
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.referral_enabled = enabled;
+        presale_state.referral_bonus_percent = bonus_percent;
+
+        msg!("Referral program enabled: {}, bonus: {}%", enabled, bonus_percent);
+        Ok(())
+    }
+
+    /// Registers the caller as a referrer
+    ///
+    /// Creates the caller's `ReferralAccount` PDA, which buyers pass to `buy` /
+    /// `buy_with_sol` together with the referrer address.
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        let referral_account = &mut ctx.accounts.referral_account;
+        referral_account.referrer = ctx.accounts.referrer.key();
+        referral_account.total_referrals = 0;
+        referral_account.bonus_tokens_earned = 0;
+        referral_account.bonus_tokens_claimed = 0;
+        referral_account.bump = ctx.bumps.referral_account;
+
+        msg!("Referrer registered: {}", referral_account.referrer);
+        Ok(())
+    }
+
+    /// Claims earned referral bonus tokens from the presale token vault
+    ///
+    /// # Parameters
+    /// - `ctx`: ClaimReferralBonus context (referrer must sign)
+    ///
+    /// # Errors
+    /// - `PresaleError::NoReferralBonus` if there is nothing to claim
+    /// - `PresaleError::InvalidTreasuryAccount` if vault or destination accounts are invalid
+    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+        let referral_account = &ctx.accounts.referral_account;
+        let claimable = referral_account
+            .bonus_tokens_earned
+            .saturating_sub(referral_account.bonus_tokens_claimed);
+        require!(claimable > 0, PresaleError::NoReferralBonus);
+
+        let presale_token_mint = ctx.accounts.presale_state.presale_token_mint;
+
+        // Validate presale token vault (manual validation)
+        {
+            let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+            require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.presale_token_vault_pda.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Validate referrer token account (manual validation)
+        {
+            let referrer_token_data = ctx.accounts.referrer_token_account.try_borrow_data()?;
+            require!(referrer_token_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&referrer_token_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&referrer_token_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.referrer.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Transfer bonus tokens from presale vault to referrer
+        let seeds = &[
+            b"presale_token_vault_pda",
+            presale_token_mint.as_ref(),
+            &[ctx.bumps.presale_token_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_token_vault.to_account_info(),
+            mint: ctx.accounts.presale_token_mint.to_account_info(),
+            to: ctx.accounts.referrer_token_account.to_account_info(),
+            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, claimable, decimals)?;
+
+        let referral_account = &mut ctx.accounts.referral_account;
+        referral_account.bonus_tokens_claimed = referral_account.bonus_tokens_earned;
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.referral_bonus_outstanding = presale_state
+            .referral_bonus_outstanding
+            .saturating_sub(claimable);
+
+        msg!("Referrer {} claimed {} bonus tokens", ctx.accounts.referrer.key(), claimable);
+        Ok(())
+    }
+
+    /// Configures vesting for future purchases
+    ///
+    /// While enabled, purchased tokens are held in the presale vault and released
+    /// linearly over `duration_seconds` from the buyer's first purchase, with nothing
+    /// claimable before `cliff_seconds`. Existing vesting schedules are not changed.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `enabled`: Whether purchases are vested instead of delivered immediately
+    /// - `cliff_seconds`: Time after the first purchase before any tokens can be claimed
+    /// - `duration_seconds`: Total vesting duration (must be > 0 and >= cliff_seconds)
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if presale is stopped or failed
+    /// - `PresaleError::InvalidVestingConfig` if the cliff or duration is invalid
+    pub fn set_vesting_config(
+        ctx: Context<AdminOnly>,
+        enabled: bool,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+    ) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+        if enabled {
+            require!(
+                duration_seconds > 0
+                    && duration_seconds <= MAX_VESTING_DURATION_SECONDS
+                    && cliff_seconds <= duration_seconds,
+                PresaleError::InvalidVestingConfig
+            );
+        }
+
+        presale_state.vesting_enabled = enabled;
+        presale_state.vesting_cliff_seconds = cliff_seconds;
+        presale_state.vesting_duration_seconds = duration_seconds;
+
+        msg!(
+            "Vesting enabled: {}, cliff: {}s, duration: {}s",
+            enabled,
+            cliff_seconds,
+            duration_seconds
+        );
+        Ok(())
+    }
+
+    /// Enables or disables claim mode for the presale
+    ///
+    /// In claim mode `buy` and `buy_with_sol` only record the purchased tokens as
+    /// `UserPurchase.owed`; buyers receive them with `claim_tokens` once the presale
+    /// has stopped, so the vault does not need to be funded while the sale runs.
+    /// Vesting, when enabled, takes precedence. Can only be changed before the first sale.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `enabled`: `true` to defer delivery until `claim_tokens`
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if presale has started or tokens were already sold
+    ///
+    /// # Events
+    /// - Emits `ClaimModeChanged`
+    pub fn set_claim_mode(ctx: Context<AdminOnly>, enabled: bool) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.status == PresaleStatus::NotStarted
+                && presale_state.total_tokens_sold == 0,
+            PresaleError::InvalidStatus
+        );
+
+        presale_state.claim_mode = enabled;
+
+        emit!(ClaimModeChanged { enabled });
+        msg!("Claim mode enabled: {}", enabled);
+        Ok(())
+    }
+
+    /// Delivers the presale tokens a buyer is owed from claim-mode purchases
+    ///
+    /// # Parameters
+    /// - `ctx`: ClaimTokens context (buyer must sign)
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if presale is not Stopped or refunds are open
+    /// - `PresaleError::NothingToClaim` if the buyer is owed nothing
+    /// - `PresaleError::InvalidTreasuryAccount` if vault or destination accounts are invalid
+    ///
+    /// # Events
+    /// - Emits `TokensClaimed` with the delivered amount
+    pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+        require!(
+            presale_state.status == PresaleStatus::Stopped && !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
+
+        let owed = ctx.accounts.user_purchase.owed;
+        require!(owed > 0, PresaleError::NothingToClaim);
+
+        let presale_token_mint = presale_state.presale_token_mint;
+
+        // Validate presale token vault (manual validation)
+        {
+            let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+            require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.presale_token_vault_pda.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Validate buyer token account (manual validation)
+        {
+            let buyer_token_data = ctx.accounts.buyer_token_account.try_borrow_data()?;
+            require!(buyer_token_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&buyer_token_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&buyer_token_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.buyer.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Transfer owed tokens from presale vault to buyer
+        let seeds = &[
+            b"presale_token_vault_pda",
+            presale_token_mint.as_ref(),
+            &[ctx.bumps.presale_token_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_token_vault.to_account_info(),
+            mint: ctx.accounts.presale_token_mint.to_account_info(),
+            to: ctx.accounts.buyer_token_account.to_account_info(),
+            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, owed, decimals)?;
+
+        ctx.accounts.user_purchase.owed = 0;
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.total_claimed = presale_state
+            .total_claimed
+            .checked_add(owed)
+            .ok_or(PresaleError::Overflow)?;
+
+        emit!(TokensClaimed {
+            buyer: ctx.accounts.buyer.key(),
+            amount: owed,
+        });
+
+        msg!("Buyer {} claimed {} tokens", ctx.accounts.buyer.key(), owed);
+        Ok(())
+    }
+
+    /// Selects whether `max_presale_cap` counts tokens sold or micro-USD raised
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `cap_in_usd`: `true` to compare the cap against `total_raised_usd_micro`
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidAmount` if the current cap is already exceeded in the new unit
+    /// - `PresaleError::InvalidStatus` if presale is stopped or failed
+    pub fn set_cap_in_usd(ctx: Context<AdminOnly>, cap_in_usd: bool) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+
+        presale_state.cap_in_usd = cap_in_usd;
+        if presale_state.max_presale_cap > 0 {
+            require!(
+                presale_state.max_presale_cap >= presale_state.cap_progress(),
+                PresaleError::InvalidAmount
+            );
+        }
+
+        msg!("Presale cap denominated in {}", if cap_in_usd { "micro-USD" } else { "tokens" });
+        Ok(())
+    }
+
+    /// Sets the soft cap below which a stopped presale is refunded
+    ///
+    /// If the presale stops with `total_raised_usd_micro` below the soft cap, buyers
+    /// can claim refunds exactly as if it had been marked Failed.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `soft_cap`: Minimum raise in micro-USD (0 disables the soft cap)
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if presale is stopped or failed
+    pub fn set_soft_cap(ctx: Context<AdminOnly>, soft_cap: u64) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        // Fixed once the outcome is decided, so buyers can't lose a refund right
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+
+        presale_state.soft_cap = soft_cap;
+        msg!("Presale soft cap set to {} micro-USD", soft_cap);
+        Ok(())
+    }
+
+    /// Sets the price tiers that raise the token price as sales progress
+    ///
+    /// Each tier's price applies once `total_tokens_sold` reaches its threshold;
+    /// below the first threshold `token_price_usd_micro` applies. Purchases within a
+    /// round keep the round's flat price. An empty list disables tiered pricing.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `tiers`: Up to `MAX_PRICE_TIERS` tiers with strictly ascending thresholds
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidPriceTiers` if tiers are too many, unordered, or have a zero price
+    /// - `PresaleError::InvalidStatus` if presale is stopped or failed
+    ///
+    /// # Events
+    /// - Emits `PriceTiersUpdated`
+    pub fn set_price_tiers(ctx: Context<AdminOnly>, tiers: Vec<PriceTier>) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.status != PresaleStatus::Stopped
+                && presale_state.status != PresaleStatus::Failed,
+            PresaleError::InvalidStatus
+        );
+        PresaleState::validate_price_tiers(&tiers)?;
+
+        presale_state.price_tiers = tiers;
+
+        emit!(PriceTiersUpdated {
+            tiers: presale_state.price_tiers.clone(),
+        });
+        msg!("Presale price tiers set ({} tiers)", presale_state.price_tiers.len());
+        Ok(())
+    }
+
+    /// Selects the token program (legacy SPL Token or Token-2022) used for vault transfers
+    ///
+    /// Payment tokens must use the same token program as the presale token. Can only
+    /// be changed before the presale starts, since existing vaults belong to one program.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `token_program_id`: `anchor_spl::token::ID` or `anchor_spl::token_2022::ID`
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if the presale has already started
+    /// - `PresaleError::InvalidTokenProgram` if the program is not a known token program
+    pub fn set_token_program_id(ctx: Context<AdminOnly>, token_program_id: Pubkey) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.status == PresaleStatus::NotStarted,
+            PresaleError::InvalidStatus
+        );
+        require!(
+            token_program_id == anchor_spl::token::ID
+                || token_program_id == anchor_spl::token_2022::ID,
+            PresaleError::InvalidTokenProgram
+        );
+
+        presale_state.token_program_id = token_program_id;
+        msg!("Presale token program set to {}", token_program_id);
+        Ok(())
+    }
+
+    /// Selects the oracle used to price SOL purchases
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `oracle_type`: Chainlink (OCR2 feed) or Pyth (v2 price account)
+    /// - `new_feed`: SOL/USD feed account `buy_with_sol` must use. Required for Pyth;
+    ///   for Chainlink, `Pubkey::default()` leaves SOL purchases disabled
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidPrice` if Pyth is selected without a feed
+    pub fn set_oracle_type(
+        ctx: Context<AdminOnly>,
+        oracle_type: OracleType,
+        new_feed: Pubkey,
+    ) -> Result<()> {
+        // Pyth accounts have no fixed owner across clusters, so the feed must be pinned
+        if oracle_type == OracleType::Pyth {
+            require!(new_feed != Pubkey::default(), PresaleError::InvalidPrice);
+        }
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.oracle_type = oracle_type;
+        presale_state.oracle_feed = new_feed;
+
+        emit!(OracleTypeChanged {
+            oracle_type,
+            feed: new_feed,
+        });
+        msg!("Oracle set to {:?} with feed {}", oracle_type, new_feed);
+        Ok(())
+    }
+
+    /// Sets the backup Chainlink feed `buy_with_sol` may fall back to
+    ///
+    /// The backup is read only when the primary Chainlink price is stale or
+    /// non-positive. `Pubkey::default()` disables the fallback.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `backup_feed`: Backup SOL/USD feed account
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidPrice` if the backup equals the primary feed
+    pub fn set_oracle_backup_feed(ctx: Context<AdminOnly>, backup_feed: Pubkey) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            backup_feed == Pubkey::default() || backup_feed != presale_state.oracle_feed,
+            PresaleError::InvalidPrice
+        );
+        presale_state.oracle_feed_backup = backup_feed;
+
+        emit!(OracleBackupFeedChanged { feed: backup_feed });
+        msg!("Oracle backup feed set to {}", backup_feed);
+        Ok(())
+    }
+
+    /// Sets how old a SOL/USD price may be before `buy_with_sol` rejects it
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `threshold_seconds`: Maximum price age, between `MIN_PRICE_STALENESS_THRESHOLD_SECONDS`
+    ///   and `MAX_PRICE_STALENESS_THRESHOLD_SECONDS`
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStalenessThreshold` if the threshold is out of bounds
+    ///
+    /// # Events
+    /// - Emits `PriceStalenessThresholdChanged`
+    pub fn set_price_staleness_threshold(ctx: Context<AdminOnly>, threshold_seconds: i64) -> Result<()> {
+        require!(
+            (MIN_PRICE_STALENESS_THRESHOLD_SECONDS..=MAX_PRICE_STALENESS_THRESHOLD_SECONDS)
+                .contains(&threshold_seconds),
+            PresaleError::InvalidStalenessThreshold
+        );
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        let old_threshold = presale_state.effective_staleness_threshold();
+        presale_state.price_staleness_threshold = threshold_seconds;
+
+        emit!(PriceStalenessThresholdChanged {
+            old_threshold,
+            new_threshold: threshold_seconds,
+        });
+        msg!("Price staleness threshold set to {} seconds", threshold_seconds);
+        Ok(())
+    }
+
+    /// Sets the fee taken from treasury withdrawals and the account that receives it
+    ///
+    /// The fee is `amount * fee_bps / 10_000` of each `withdraw_to_treasury` and
+    /// `withdraw_sol_to_treasury` call; the rest goes to the treasury. A fee of 0
+    /// disables the split.
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `fee_bps`: Fee in basis points (max `MAX_WITHDRAWAL_FEE_BPS`)
+    /// - `fee_collector`: Wallet receiving the fee (owner of the fee token accounts)
+    ///
+    /// # Errors
+    /// - `PresaleError::WithdrawalFeeTooHigh` if `fee_bps` exceeds `MAX_WITHDRAWAL_FEE_BPS`
+    /// - `PresaleError::InvalidFeeCollector` if a fee is set without a fee collector
+    pub fn set_withdrawal_fee(
+        ctx: Context<AdminOnly>,
+        fee_bps: u16,
+        fee_collector: Pubkey,
+    ) -> Result<()> {
+        require!(
+            fee_bps <= MAX_WITHDRAWAL_FEE_BPS,
+            PresaleError::WithdrawalFeeTooHigh
+        );
+        require!(
+            fee_bps == 0 || fee_collector != Pubkey::default(),
+            PresaleError::InvalidFeeCollector
+        );
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.withdrawal_fee_bps = fee_bps;
+        presale_state.fee_collector = fee_collector;
+
+        emit!(WithdrawalFeeChanged { fee_bps, fee_collector });
+        msg!("Withdrawal fee set to {} bps, collector: {}", fee_bps, fee_collector);
+        Ok(())
+    }
+
+    /// Sets the DEX program `withdraw_and_swap` may route treasury swaps through
+    ///
+    /// # Parameters
+    /// - `ctx`: AdminOnly context (requires admin or governance authority)
+    /// - `swap_program`: Swap venue program id, or the default pubkey to disable swaps
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidSwapProgram` if the program is this program, the system
+    ///   program or the presale's token program
+    ///
+    /// # Events
+    /// - Emits `SwapProgramChanged`
+    pub fn set_swap_program(ctx: Context<AdminOnly>, swap_program: Pubkey) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+
+        require!(
+            swap_program != crate::ID
+                && swap_program != anchor_lang::system_program::ID
+                && swap_program != presale_state.effective_token_program_id(),
+            PresaleError::InvalidSwapProgram
+        );
+
+        let old_swap_program = presale_state.swap_program;
+        presale_state.swap_program = swap_program;
+
+        emit!(SwapProgramChanged {
+            old_swap_program,
+            new_swap_program: swap_program,
+        });
+        msg!("Swap program set to {}", swap_program);
+        Ok(())
+    }
+
+    /// Withdraws payment tokens and swaps them into another token for the treasury
+    ///
+    /// Takes the withdrawal fee (if set) like `withdraw_to_treasury`, then invokes the
+    /// configured swap program with `swap_data` and `remaining_accounts`, with the
+    /// payment vault PDA signing. The swap output must land in `treasury_token_account`.
+    ///
+    /// The swap venue is trusted only as far as these post-swap checks go: the vault
+    /// may lose at most the swapped amount, must still be owned by the vault PDA with
+    /// no delegate or close authority, and the treasury must receive at least `min_out`.
+    ///
+    /// # Parameters
+    /// - `ctx`: WithdrawAndSwap context with all required accounts
+    /// - `amount`: Payment tokens to withdraw (must be > 0, `WITHDRAW_ALL` = entire vault balance)
+    /// - `min_out`: Minimum output tokens the treasury must receive (must be > 0)
+    /// - `swap_data`: Instruction data for the swap program
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the swap completes
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not admin or governance
+    /// - `PresaleError::TreasuryNotSet` if treasury address not configured
+    /// - `PresaleError::SwapProgramNotSet` if no swap program is configured
+    /// - `PresaleError::InvalidSwapProgram` if `swap_program` is not the configured venue
+    /// - `PresaleError::InvalidStatus` if refunds are open
+    /// - `PresaleError::InvalidAmount` if amount or `min_out` is 0, or amount exceeds vault balance
+    /// - `PresaleError::InvalidTreasuryAccount` if the vault or treasury accounts are invalid
+    /// - `PresaleError::InvalidFeeCollector` if a withdrawal fee is set and the fee
+    ///   collector's token account is missing or invalid
+    /// - `PresaleError::SwapValidationFailed` if the swap took too much from the vault or
+    ///   left it delegated, re-owned or closable
+    /// - `PresaleError::SlippageExceeded` if the treasury received less than `min_out`
+    ///
+    /// # Events
+    /// - Emits `WithdrawalFeeCharged` when a withdrawal fee is taken
+    /// - Emits `TreasurySwapped` with the amounts in and out
+    pub fn withdraw_and_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawAndSwap<'info>>,
+        amount: u64,
+        min_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
+        require!(
+            presale_state.treasury_address != Pubkey::default(),
+            PresaleError::TreasuryNotSet
+        );
+        require!(
+            presale_state.swap_program != Pubkey::default(),
+            PresaleError::SwapProgramNotSet
+        );
+        require!(
+            ctx.accounts.swap_program.key() == presale_state.swap_program
+                && ctx.accounts.swap_program.executable,
+            PresaleError::InvalidSwapProgram
+        );
+
+        // Funds in a failed presale (or one stopped below its soft cap) are reserved for buyer refunds
+        require!(
+            !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
+        require!(min_out > 0, PresaleError::InvalidAmount);
+
+        let payment_token_mint_key = ctx.accounts.payment_token_mint.key();
+        let vault_pda_key = ctx.accounts.presale_payment_vault_pda.key();
+        let (vault_mint, vault_owner, vault_balance) =
+            read_token_account(&ctx.accounts.presale_payment_vault)?;
+        require!(
+            vault_mint == payment_token_mint_key && vault_owner == vault_pda_key,
+            PresaleError::InvalidTreasuryAccount
+        );
+        let (output_mint, output_owner, output_before) =
+            read_token_account(&ctx.accounts.treasury_token_account)?;
+        require!(
+            output_owner == presale_state.treasury_address && output_mint != payment_token_mint_key,
+            PresaleError::InvalidTreasuryAccount
+        );
+
+        let amount = if amount == WITHDRAW_ALL { vault_balance } else { amount };
+        require!(
+            amount > 0 && vault_balance >= amount,
+            PresaleError::InvalidAmount
+        );
+
+        let fee_amount = presale_state.withdrawal_fee(amount)?;
+        let swap_amount = amount
+            .checked_sub(fee_amount)
+            .ok_or(PresaleError::Overflow)?;
+
+        let presale_state_key = presale_state.key();
+        let seeds = &[
+            b"presale_payment_vault_pda",
+            presale_state_key.as_ref(),
+            payment_token_mint_key.as_ref(),
+            &[ctx.bumps.presale_payment_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        if fee_amount > 0 {
+            let fee_token_account = ctx.accounts.fee_collector_token_account
+                .as_ref()
+                .ok_or(PresaleError::InvalidFeeCollector)?;
+            let (fee_mint, fee_owner, _) = read_token_account(fee_token_account)
+                .map_err(|_| PresaleError::InvalidFeeCollector)?;
+            require!(
+                fee_mint == payment_token_mint_key && fee_owner == presale_state.fee_collector,
+                PresaleError::InvalidFeeCollector
+            );
+
+            let decimals = mint_decimals(&ctx.accounts.payment_token_mint)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.presale_payment_vault.to_account_info(),
+                mint: ctx.accounts.payment_token_mint.to_account_info(),
+                to: fee_token_account.to_account_info(),
+                authority: ctx.accounts.presale_payment_vault_pda.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, fee_amount, decimals)?;
+
+            emit!(WithdrawalFeeCharged {
+                fee_amount,
+                fee_collector: presale_state.fee_collector,
+                treasury_amount: swap_amount,
+            });
+        }
+
+        // Only the vault PDA signs for the swap; no other signer privilege is forwarded
+        let swap_ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: ctx.accounts.swap_program.key(),
+            accounts: ctx.remaining_accounts
+                .iter()
+                .map(|account| anchor_lang::solana_program::instruction::AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.key() == vault_pda_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: swap_data,
+        };
+        anchor_lang::solana_program::program::invoke_signed(
+            &swap_ix,
+            ctx.remaining_accounts,
+            signer,
+        )?;
+
+        // The vault must have lost at most the swapped amount and still be fully
+        // controlled by the vault PDA
+        let vault_before_swap = vault_balance
+            .checked_sub(fee_amount)
+            .ok_or(PresaleError::Overflow)?;
+        let amount_in = {
+            let (_, vault_owner_after, vault_after) =
+                read_token_account(&ctx.accounts.presale_payment_vault)?;
+            let vault_data = ctx.accounts.presale_payment_vault.try_borrow_data()?;
+            require!(
+                vault_owner_after == vault_pda_key
+                    && vault_data[TOKEN_ACCOUNT_DELEGATE_OFFSET..TOKEN_ACCOUNT_DELEGATE_OFFSET + 4] == [0u8; 4]
+                    && vault_data[TOKEN_ACCOUNT_CLOSE_AUTHORITY_OFFSET..TOKEN_ACCOUNT_CLOSE_AUTHORITY_OFFSET + 4] == [0u8; 4],
+                PresaleError::SwapValidationFailed
+            );
+            vault_before_swap.saturating_sub(vault_after)
+        };
+        require!(amount_in <= swap_amount, PresaleError::SwapValidationFailed);
+
+        let (_, output_owner_after, output_after) =
+            read_token_account(&ctx.accounts.treasury_token_account)?;
+        require!(
+            output_owner_after == presale_state.treasury_address,
+            PresaleError::SwapValidationFailed
+        );
+        let amount_out = output_after.saturating_sub(output_before);
+        require!(amount_out >= min_out, PresaleError::SlippageExceeded);
+
+        emit!(TreasurySwapped {
+            input_mint: payment_token_mint_key,
+            amount_in,
+            output_mint,
+            amount_out,
+            treasury: presale_state.treasury_address,
+        });
+
+        msg!(
+            "Swapped {} payment tokens into {} of {} for treasury: {}",
+            amount_in,
+            amount_out,
+            output_mint,
+            presale_state.treasury_address
+        );
+        Ok(())
+    }
+
+    /// Claims presale tokens vested so far from the presale token vault
+    ///
+    /// # Parameters
+    /// - `ctx`: ClaimVestedTokens context (buyer must sign)
+    ///
+    /// # Errors
+    /// - `PresaleError::InvalidStatus` if presale has failed (buyers claim refunds instead)
+    /// - `PresaleError::VestingCliffNotReached` if called before the cliff
+    /// - `PresaleError::NothingToClaim` if no newly vested tokens are available
+    /// - `PresaleError::InvalidTreasuryAccount` if vault or destination accounts are invalid
+    ///
+    /// # Events
+    /// - Emits `VestedTokensClaimed` with the claimed and remaining amounts
+    pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
+        require!(
+            !ctx.accounts.presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let vesting_account = &ctx.accounts.vesting_account;
+        require!(
+            now >= vesting_account.cliff_time,
+            PresaleError::VestingCliffNotReached
+        );
+        let claimable = vesting_account
+            .vested_amount(now)?
+            .saturating_sub(vesting_account.claimed_amount);
+        require!(claimable > 0, PresaleError::NothingToClaim);
+
+        let presale_token_mint = ctx.accounts.presale_state.presale_token_mint;
+
+        // Validate presale token vault (manual validation)
+        {
+            let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+            require!(presale_token_vault_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.presale_token_vault_pda.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Validate buyer token account (manual validation)
+        {
+            let buyer_token_data = ctx.accounts.buyer_token_account.try_borrow_data()?;
+            require!(buyer_token_data.len() >= 64, PresaleError::InvalidTreasuryAccount);
+            let mint = Pubkey::try_from_slice(&buyer_token_data[0..32])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            let owner = Pubkey::try_from_slice(&buyer_token_data[32..64])
+                .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+            require!(mint == presale_token_mint, PresaleError::InvalidTreasuryAccount);
+            require!(
+                owner == ctx.accounts.buyer.key(),
+                PresaleError::InvalidTreasuryAccount
+            );
+        }
+
+        // Transfer vested tokens from presale vault to buyer
+        let seeds = &[
+            b"presale_token_vault_pda",
+            presale_token_mint.as_ref(),
+            &[ctx.bumps.presale_token_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_token_vault.to_account_info(),
+            mint: ctx.accounts.presale_token_mint.to_account_info(),
+            to: ctx.accounts.buyer_token_account.to_account_info(),
+            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, claimable, decimals)?;
+
+        let vesting_account = &mut ctx.accounts.vesting_account;
+        vesting_account.claimed_amount = vesting_account
+            .claimed_amount
+            .checked_add(claimable)
+            .ok_or(PresaleError::Overflow)?;
+        let remaining = vesting_account.remaining();
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.total_claimed = presale_state
+            .total_claimed
+            .checked_add(claimable)
+            .ok_or(PresaleError::Overflow)?;
+
+        emit!(VestedTokensClaimed {
+            buyer: ctx.accounts.buyer.key(),
+            amount: claimable,
+            remaining,
+        });
+
+        msg!("Buyer {} claimed {} vested tokens ({} remaining)", ctx.accounts.buyer.key(), claimable, remaining);
+        Ok(())
+    }
+
+    /// Creates a new presale round (e.g. seed, private, public)
+    ///
+    /// Each round has its own price, cap and optional time window. Rounds are
+    /// created inactive and must be activated with `activate_round`.
+    ///
+    /// # Parameters
+    /// - `ctx`: CreateRound context (requires admin or governance authority)
+    /// - `round_id`: Unique round identifier
+    /// - `token_price_usd_micro`: Round token price in micro-USD (must be > 0)
+    /// - `cap`: Maximum tokens sold in this round (0 = unlimited)
+    /// - `start_time`: Optional unix timestamp before which purchases are rejected
+    /// - `end_time`: Optional unix timestamp after which purchases are rejected
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidPrice` if price is 0
+    /// - `PresaleError::InvalidRound` if start_time >= end_time
+    ///
+    /// # Events
+    /// - Emits `RoundCreated`
+    pub fn create_round(
+        ctx: Context<CreateRound>,
+        round_id: u8,
+        token_price_usd_micro: u64,
+        cap: u64,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> Result<()> {
+        require!(token_price_usd_micro > 0, PresaleError::InvalidPrice);
+        if let (Some(start), Some(end)) = (start_time, end_time) {
+            require!(start < end, PresaleError::InvalidRound);
+        }
+
+        let round = &mut ctx.accounts.presale_round;
+        round.presale_state = ctx.accounts.presale_state.key();
+        round.round_id = round_id;
+        round.token_price_usd_micro = token_price_usd_micro;
+        round.cap = cap;
+        round.tokens_sold = 0;
+        round.start_time = start_time;
+        round.end_time = end_time;
+        round.is_active = false;
+        round.bump = ctx.bumps.presale_round;
+
+        emit!(RoundCreated {
+            round_id,
+            token_price_usd_micro,
+            cap,
+        });
+
+        msg!(
+            "Round {} created: price {} micro-USD, cap {}",
+            round_id,
+            token_price_usd_micro,
+            cap
+        );
+        Ok(())
+    }
+
+    /// Activates a presale round
+    ///
+    /// Only one round can be active at a time; the current active round must be
+    /// closed before another is activated.
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::RoundAlreadyActive` if another round is active
+    ///
+    /// # Events
+    /// - Emits `RoundActivated`
+    pub fn activate_round(ctx: Context<ManageRound>, round_id: u8) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        require!(
+            presale_state.active_round_id.is_none(),
+            PresaleError::RoundAlreadyActive
+        );
+
+        let round = &mut ctx.accounts.presale_round;
+        round.is_active = true;
+        presale_state.active_round_id = Some(round_id);
+
+        emit!(RoundActivated { round_id });
+
+        msg!("Round {} activated", round_id);
+        Ok(())
+    }
+
+    /// Closes a presale round
+    ///
+    /// Marks the round inactive and clears it as the active round if it was.
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::RoundNotActive` if round is not active
+    ///
+    /// # Events
+    /// - Emits `RoundClosed` with tokens sold in the round
+    pub fn close_round(ctx: Context<ManageRound>, round_id: u8) -> Result<()> {
+        let round = &mut ctx.accounts.presale_round;
+        require!(round.is_active, PresaleError::RoundNotActive);
+        round.is_active = false;
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        if presale_state.active_round_id == Some(round_id) {
+            presale_state.active_round_id = None;
+        }
+
+        emit!(RoundClosed {
+            round_id,
+            tokens_sold: round.tokens_sold,
+        });
+
+        msg!("Round {} closed with {} tokens sold", round_id, round.tokens_sold);
+        Ok(())
+    }
+
+    /// Switches the active presale round
+    ///
+    /// Unlike `activate_round`, this replaces a currently active round, which must be
+    /// passed as `active_round` so it is marked inactive. Rounds also advance on their
+    /// own when a purchase sells out the active round and passes the next one.
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::RoundAlreadyActive` if `round_id` is already the active round
+    /// - `PresaleError::InvalidRound` if `active_round` is missing or not the active round
+    ///
+    /// # Events
+    /// - Emits `RoundAdvanced`
+    pub fn set_active_round(ctx: Context<SetActiveRound>, round_id: u8) -> Result<()> {
+        let presale_state = &mut ctx.accounts.presale_state;
+        let from_round_id = presale_state.active_round_id;
+        require!(
+            from_round_id != Some(round_id),
+            PresaleError::RoundAlreadyActive
+        );
+        if let Some(active_round_id) = from_round_id {
+            let active_round = ctx.accounts.active_round.as_mut()
+                .ok_or(PresaleError::InvalidRound)?;
+            require!(
+                active_round.round_id == active_round_id,
+                PresaleError::InvalidRound
+            );
+            active_round.is_active = false;
+        }
+
+        ctx.accounts.presale_round.is_active = true;
+        presale_state.active_round_id = Some(round_id);
+
+        emit!(RoundAdvanced {
+            from_round_id,
+            to_round_id: round_id,
+        });
+
+        msg!("Active round set to {}", round_id);
+        Ok(())
+    }
+
+    /// Records an immutable snapshot of the presale's final results
+    ///
+    /// Creates a `PresaleResults` PDA capturing totals at the time the presale
+    /// concluded. The account is created once and no instruction can modify it,
+    /// giving an auditable record separate from the mutable `PresaleState`.
+    ///
+    /// # Parameters
+    /// - `ctx`: FinalizeResults context (requires admin or governance authority)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if results are recorded
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidStatus` if presale is not Stopped
+    /// - Account already in use if results were already finalized
+    ///
+    /// # Events
+    /// - Emits `PresaleResultsFinalized`
+    pub fn finalize_results(ctx: Context<FinalizeResults>) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status == PresaleStatus::Stopped,
+            PresaleError::InvalidStatus
+        );
+
+        let end_timestamp = Clock::get()?.unix_timestamp;
+
+        let results = &mut ctx.accounts.presale_results;
+        results.presale_state = presale_state.key();
+        results.total_tokens_sold = presale_state.total_tokens_sold;
+        results.total_raised_usd_micro = presale_state.total_raised_usd_micro;
+        results.unique_buyers = presale_state.unique_buyers;
+        results.final_price = presale_state.token_price_usd_micro;
+        results.end_timestamp = end_timestamp;
+        results.bump = ctx.bumps.presale_results;
+
+        emit!(PresaleResultsFinalized {
+            total_tokens_sold: results.total_tokens_sold,
+            total_raised_usd_micro: results.total_raised_usd_micro,
+            unique_buyers: results.unique_buyers,
+            final_price: results.final_price,
+            end_timestamp,
+        });
+
+        msg!(
+            "Presale results finalized: {} tokens sold, {} micro-USD raised, {} buyers",
+            results.total_tokens_sold,
+            results.total_raised_usd_micro,
+            results.unique_buyers
+        );
+        Ok(())
+    }
+
+    /// Archives the stopped presale's totals and opens a new round on the same deployment
+    ///
+    /// Writes the round's results to a `RoundArchive` PDA keyed by `round_number`,
+    /// clears the running totals, returns the presale to NotStarted and increments
+    /// `round_number`. Buyer trackers from earlier rounds are reset lazily on the
+    /// buyer's next purchase, so per-user limits apply per round.
+    ///
+    /// Every payment token vault must be passed in `remaining_accounts` (at least
+    /// `allowed_token_count` distinct mints) and be empty.
+    ///
+    /// # Parameters
+    /// - `ctx`: ResetForNewRound context (requires admin or governance authority)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the round is archived and a new round opened
+    ///
+    /// # Errors
+    /// - `PresaleError::Unauthorized` if caller is not authority
+    /// - `PresaleError::InvalidStatus` if presale is not Stopped or refunds are open
+    /// - `PresaleError::FundsNotWithdrawn` if the SOL vault, a payment vault or the
+    ///   token allocations from the round have not been settled
+    ///
+    /// # Events
+    /// - Emits `RoundReset`
+    pub fn reset_for_new_round<'info>(ctx: Context<'_, '_, 'info, 'info, ResetForNewRound<'info>>) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
+        require!(
+            presale_state.status == PresaleStatus::Stopped && !presale_state.refunds_open(),
+            PresaleError::InvalidStatus
+        );
+
+        // Every sold token and referral bonus must have been delivered
+        require!(
+            presale_state.outstanding_allocations() == 0,
+            PresaleError::FundsNotWithdrawn
+        );
+
+        // SOL proceeds must have been swept down to the rent-exempt floor
+        require!(
+            ctx.accounts.sol_vault.lamports() <= Rent::get()?.minimum_balance(0),
+            PresaleError::FundsNotWithdrawn
+        );
+
+        // Payment token vaults (token accounts owned by their vault PDA) must be empty
+        let presale_state_key = presale_state.key();
+        let mut vault_mints: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for vault in ctx.remaining_accounts.iter() {
+            let data = vault.try_borrow_data()?;
+            require!(data.len() >= 72, PresaleError::InvalidAccount);
+            let mint = Pubkey::try_from_slice(&data[0..32])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            let owner = Pubkey::try_from_slice(&data[32..64])
+                .map_err(|_| PresaleError::InvalidAccount)?;
+            let amount = u64::from_le_bytes(data[64..72].try_into().unwrap());
+            let (vault_pda, _) = Pubkey::find_program_address(
+                &[b"presale_payment_vault_pda", presale_state_key.as_ref(), mint.as_ref()],
+                ctx.program_id,
+            );
+            require!(owner == vault_pda, PresaleError::InvalidAccount);
+            require!(amount == 0, PresaleError::FundsNotWithdrawn);
+            if !vault_mints.contains(&mint) {
+                vault_mints.push(mint);
+            }
+        }
+        require!(
+            vault_mints.len() >= presale_state.allowed_token_count as usize,
+            PresaleError::FundsNotWithdrawn
+        );
+
+        let archive = &mut ctx.accounts.round_archive;
+        archive.presale_state = presale_state_key;
+        archive.round_number = presale_state.round_number;
+        archive.total_tokens_sold = presale_state.total_tokens_sold;
+        archive.total_raised = presale_state.total_raised;
+        archive.total_raised_usd_micro = presale_state.total_raised_usd_micro;
+        archive.unique_buyers = presale_state.unique_buyers;
+        archive.final_price = presale_state.token_price_usd_micro;
+        archive.end_timestamp = Clock::get()?.unix_timestamp;
+        archive.bump = ctx.bumps.round_archive;
+
+        let presale_state = &mut ctx.accounts.presale_state;
+        presale_state.total_tokens_sold = 0;
+        presale_state.total_raised = 0;
+        presale_state.total_raised_usd_micro = 0;
+        presale_state.unique_buyers = 0;
+        presale_state.total_claimed = 0;
+        presale_state.referral_bonus_total = 0;
+        presale_state.status = PresaleStatus::NotStarted;
+        presale_state.round_number = presale_state
+            .round_number
+            .checked_add(1)
+            .ok_or(PresaleError::Overflow)?;
+
+        emit!(RoundReset {
+            round_number: archive.round_number,
+            total_tokens_sold: archive.total_tokens_sold,
+            total_raised_usd_micro: archive.total_raised_usd_micro,
+            unique_buyers: archive.unique_buyers,
+        });
+
+        msg!(
+            "Round {} archived ({} tokens sold); presale reset for round {}",
+            archive.round_number,
+            archive.total_tokens_sold,
+            presale_state.round_number
+        );
+        Ok(())
+    }
+
+    /// Emits aggregated presale statistics in a single `PresaleStats` event
+    ///
+    /// Read-only; anyone may call. The USD raised is `total_raised_usd_micro`, which
+    /// values every SOL and payment-token purchase of the current round at its token
+    /// price when it was made. `total_raised` itself mixes lamports and payment-token
+    /// base units, so it cannot be valued with a single SOL/USD price.
+    ///
+    /// # Parameters
+    /// - `ctx`: GetPresaleStats context
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success once the event is emitted
+    ///
+    /// # Events
+    /// - Emits `PresaleStats`
+    pub fn get_presale_stats(ctx: Context<GetPresaleStats>) -> Result<()> {
+        let presale_state = &ctx.accounts.presale_state;
+
+        let progress = presale_state.cap_progress();
+        let (remaining_cap, percent_complete_bps) = if presale_state.max_presale_cap == 0 {
+            (None, 0)
+        } else {
+            let bps = (progress as u128 * 10_000 / presale_state.max_presale_cap as u128).min(10_000);
+            (
+                Some(presale_state.max_presale_cap.saturating_sub(progress)),
+                bps as u16,
+            )
+        };
+
+        emit!(PresaleStats {
+            status: presale_state.status,
+            total_tokens_sold: presale_state.total_tokens_sold,
+            total_raised_usd_micro: presale_state.total_raised_usd_micro,
+            token_price_usd_micro: presale_state.token_price_usd_micro,
+            remaining_cap,
+            percent_complete_bps,
+            active_since: presale_state.start_timestamp,
+        });
+        Ok(())
+    }
+}
+
+// Account Structures
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PresaleState::LEN,
+        seeds = [b"presale_state"],
+        bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePresaleState<'info> {
+    #[account(mut)]
+    /// CHECK: PDA and authority are verified manually in the function to handle old structure
+    /// Reallocation is handled manually in the function
+    pub presale_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserPurchase<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    /// CHECK: Only used to derive the UserPurchase PDA
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Legacy UserPurchase that no longer deserializes; owner and discriminator
+    /// are verified in migrate_user_purchase
+    #[account(
+        mut,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub user_purchase: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// SetGovernance - Transfer authority to governance PDA
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    pub authority: Signer<'info>,
+}
+
+// ProposeAdminChange - Nominate a new admin
+#[derive(Accounts)]
+pub struct ProposeAdminChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    pub authority: Signer<'info>,
+}
+
+// AcceptAdminChange - Pending admin takes over
+#[derive(Accounts)]
+pub struct AcceptAdminChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    pub signer: Signer<'info>,
+}
+
+// SetTokenProgram - Set token program references
+#[derive(Accounts)]
+pub struct SetTokenProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetPresaleStats<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+}
+
+#[derive(Accounts)]
+pub struct AdminOnly<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payment_token_mint: Pubkey)]
+pub struct AllowPaymentToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + AllowedToken::LEN,
+        seeds = [
+            b"allowed_token",
+            presale_state.key().as_ref(),
+            payment_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub allowed_token: Account<'info, AllowedToken>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Payment token mint account (validated in validate_payment_mint)
+    #[account(address = payment_token_mint @ PresaleError::InvalidAccount)]
+    pub payment_token_mint_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(buyer: Pubkey)]
+pub struct SetBuyerWhitelist<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + AllowedBuyer::LEN,
+        seeds = [
+            b"allowed_buyer",
+            presale_state.key().as_ref(),
+            buyer.as_ref()
+        ],
+        bump
+    )]
+    pub allowed_buyer: Account<'info, AllowedBuyer>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBuyerWhitelistBatch<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisallowPaymentToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"allowed_token",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub allowed_token: Account<'info, AllowedToken>,
+    
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Payment token mint account (for validation)
+    pub payment_token_mint: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    // Token program state to check emergency pause
+    /// CHECK: Token program state PDA (validated by constraint)
+    #[account(
+        constraint = token_state.key() == presale_state.token_program_state @ PresaleError::InvalidTokenProgramState
+    )]
+    pub token_state: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [
+            b"allowed_token",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub allowed_token: Account<'info, AllowedToken>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    /// CHECK: Buyer's payment token account (validated manually)
+    #[account(mut)]
+    pub buyer_payment_token_account: UncheckedAccount<'info>,
+
+    // PDA that will own the payment token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_payment_vault_pda",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub presale_payment_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the payment vault PDA
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_payment_vault: UncheckedAccount<'info>,
+
+    // PDA that will own the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's token account (validated manually)
+    #[account(mut)]
+    pub buyer_token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Payment token mint account (for validation)
+    pub payment_token_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + UserPurchase::LEN,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    // Per-purchase history entry, indexed by the buyer's purchase count
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + PurchaseRecord::LEN,
+        seeds = [
+            b"purchase_record",
+            presale_state.key().as_ref(),
+            buyer.key().as_ref(),
+            user_purchase.purchase_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, PurchaseRecord>>,
+    // Running totals raised in this payment token
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + RaisedTracker::LEN,
+        seeds = [b"raised_tracker", presale_state.key().as_ref(), payment_token_mint.key().as_ref()],
+        bump
+    )]
+    pub raised_tracker: Box<Account<'info, RaisedTracker>>,
+
+    /// CHECK: Buyer's blacklist PDA in the token program (validated in check_buyer_blacklist)
+    pub buyer_blacklist: UncheckedAccount<'info>,
+
+    // Optional whitelist entry (required while the whitelist phase is active)
+    #[account(
+        seeds = [b"allowed_buyer", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_whitelist: Option<Account<'info, AllowedBuyer>>,
+
+    // Referrer's referral account (required when referrer is provided)
+    #[account(
+        mut,
+        seeds = [b"referral", referral_account.referrer.as_ref()],
+        bump = referral_account.bump
+    )]
+    pub referral_account: Option<Box<Account<'info, ReferralAccount>>>,
+
+    /// CHECK: Referrer's blacklist PDA in the token program (validated in function)
+    pub referrer_blacklist: Option<UncheckedAccount<'info>>,
+
+    // Optional round account (required when round_id is provided)
+    #[account(
+        mut,
+        constraint = presale_round.presale_state == presale_state.key() @ PresaleError::InvalidRound
+    )]
+    pub presale_round: Option<Box<Account<'info, PresaleRound>>>,
+
+    /// CHECK: Next round PDA, activated when this purchase sells out `presale_round`
+    /// Required once the round sells out; uninitialized when there is no next round
+    /// (validated in advance_if_sold_out)
+    #[account(mut)]
+    pub next_presale_round: Option<UncheckedAccount<'info>>,
+
+    // Buyer's vesting schedule (required while vesting is enabled)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + VestingAccount::LEN,
+        seeds = [b"vesting", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub vesting_account: Option<Box<Account<'info, VestingAccount>>>,
+    
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar (required by buy_with_allocation, validated by address)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    // Purchase code being redeemed (required by buy_with_code, matched against the code's hash)
+    #[account(
+        mut,
+        constraint = purchase_code.presale_state == presale_state.key() @ PresaleError::InvalidPurchaseCode
+    )]
+    pub purchase_code: Option<Box<Account<'info, PurchaseCode>>>,
+}
+
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePurchaseCode<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump,
+        constraint = presale_state.authority == admin.key() 
+            || (presale_state.governance_set && presale_state.governance == admin.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PurchaseCode::LEN,
+        seeds = [b"purchase_code", presale_state.key().as_ref(), code_hash.as_ref()],
+        bump
+    )]
+    pub purchase_code: Account<'info, PurchaseCode>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawToTreasury<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    pub authority: Signer<'info>,
+    
+    // PDA that owns the payment token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_payment_vault_pda",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub presale_payment_vault_pda: UncheckedAccount<'info>,
+    
+    // ATA owned by the payment vault PDA (source)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_payment_vault: UncheckedAccount<'info>,
+
+    // Treasury token account (destination)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Payment token mint account (for validation)
+    pub payment_token_mint: UncheckedAccount<'info>,
+
+    // AllowedToken PDA for payment_token_mint (must exist, even if since disallowed)
+    /// CHECK: Validated manually so a missing PDA reports UnknownPaymentToken
+    pub allowed_token: UncheckedAccount<'info>,
+    
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // Fee collector's token account (required while a withdrawal fee is set)
+    /// CHECK: Validated manually against fee_collector and the payment mint
+    #[account(mut)]
+    pub fee_collector_token_account: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAndSwap<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key()
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    pub authority: Signer<'info>,
+
+    // PDA that owns the payment token vault ATA (signs the swap)
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_payment_vault_pda",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub presale_payment_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the payment vault PDA (swap input)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_payment_vault: UncheckedAccount<'info>,
+
+    // Treasury token account of the output mint (swap destination)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Payment token mint account (for validation)
+    pub payment_token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Swap venue, validated against presale_state.swap_program in the instruction
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // Fee collector's token account (required while a withdrawal fee is set)
+    /// CHECK: Validated manually against fee_collector and the payment mint
+    #[account(mut)]
+    pub fee_collector_token_account: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct BuyWithSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    // Token program state to check emergency pause
+    /// CHECK: Token program state PDA (validated by constraint)
+    #[account(
+        constraint = token_state.key() == presale_state.token_program_state @ PresaleError::InvalidTokenProgramState
+    )]
+    pub token_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    // PDA that owns the SOL vault
+    /// CHECK: This is a PDA that will receive SOL (created automatically on first transfer)
+    #[account(
+        mut,
+        seeds = [
+            b"presale_sol_vault",
+            presale_state.key().as_ref()
+        ],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    // PDA that will own the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's token account (validated manually)
+    #[account(mut)]
+    pub buyer_token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + UserPurchase::LEN,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    // Per-purchase history entry, indexed by the buyer's purchase count
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + PurchaseRecord::LEN,
+        seeds = [
+            b"purchase_record",
+            presale_state.key().as_ref(),
+            buyer.key().as_ref(),
+            user_purchase.purchase_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, PurchaseRecord>>,
+    // Running totals raised in SOL (tracked under SOL_PAYMENT_SENTINEL)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + RaisedTracker::LEN,
+        seeds = [b"raised_tracker", presale_state.key().as_ref(), SOL_PAYMENT_SENTINEL.as_ref()],
+        bump
+    )]
+    pub raised_tracker: Box<Account<'info, RaisedTracker>>,
+
+    /// CHECK: Buyer's blacklist PDA in the token program (validated in check_buyer_blacklist)
+    pub buyer_blacklist: UncheckedAccount<'info>,
+
+    // Optional whitelist entry (required while the whitelist phase is active)
+    #[account(
+        seeds = [b"allowed_buyer", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_whitelist: Option<Account<'info, AllowedBuyer>>,
+
+    // Referrer's referral account (required when referrer is provided)
+    #[account(
+        mut,
+        seeds = [b"referral", referral_account.referrer.as_ref()],
+        bump = referral_account.bump
+    )]
+    pub referral_account: Option<Box<Account<'info, ReferralAccount>>>,
+
+    /// CHECK: Referrer's blacklist PDA in the token program (validated in function)
+    pub referrer_blacklist: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SOL/USD price feed account (Chainlink or Pyth, per `presale_state.oracle_type`)
+    /// Must be the configured feed (validated in buy_with_sol)
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Backup Chainlink SOL/USD feed, used when the primary is stale or non-positive
+    /// Must be the configured backup feed (validated in buy_with_sol)
+    pub chainlink_feed_backup: Option<UncheckedAccount<'info>>,
+
+    // Optional round account (required when round_id is provided)
+    #[account(
+        mut,
+        constraint = presale_round.presale_state == presale_state.key() @ PresaleError::InvalidRound
+    )]
+    pub presale_round: Option<Box<Account<'info, PresaleRound>>>,
+
+    /// CHECK: Next round PDA, activated when this purchase sells out `presale_round`
+    /// Required once the round sells out; uninitialized when there is no next round
+    /// (validated in advance_if_sold_out)
+    #[account(mut)]
+    pub next_presale_round: Option<UncheckedAccount<'info>>,
+
+    // Buyer's vesting schedule (required while vesting is enabled)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + VestingAccount::LEN,
+        seeds = [b"vesting", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub vesting_account: Option<Box<Account<'info, VestingAccount>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolToTreasury<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+    
+    pub authority: Signer<'info>,
+    
+    // PDA that owns the SOL vault
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        mut,
+        seeds = [
+            b"presale_sol_vault",
+            presale_state.key().as_ref()
+        ],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    /// CHECK: Treasury wallet (validated by constraint)
+    #[account(
+        mut,
+        constraint = treasury.key() == presale_state.treasury_address @ PresaleError::InvalidTreasuryAddress
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Fee collector wallet (required while a withdrawal fee is set, validated by constraint)
+    #[account(
+        mut,
+        constraint = fee_collector.key() == presale_state.fee_collector @ PresaleError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferralAccount::LEN,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump = referral_account.bump,
+        has_one = referrer
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    pub referrer: Signer<'info>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA (source)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    // Referrer's presale token account (destination)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub referrer_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump = vesting_account.bump,
+        has_one = buyer
+    )]
+    pub vesting_account: Account<'info, VestingAccount>,
+
+    pub buyer: Signer<'info>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA (source)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    // Buyer's presale token account (destination)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-// Account Structures
+#[derive(Accounts)]
+pub struct ClaimTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        constraint = user_purchase.buyer == buyer.key() @ PresaleError::Unauthorized
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+
+    pub buyer: Signer<'info>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA (source)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    // Buyer's presale token account (destination)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct FundSolVaultRent<'info> {
     #[account(
-        init,
-        payer = payer,
-        space = 8 + PresaleState::LEN,
         seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"presale_sol_vault",
+            presale_state.key().as_ref()
+        ],
         bump
     )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnsoldTokens<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
+    )]
     pub presale_state: Account<'info, PresaleState>,
     
+    pub authority: Signer<'info>,
+    
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+    
+    // ATA owned by the presale token vault PDA (source)
+    /// CHECK: Validated manually
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    // Destination token account (where unsold tokens will be sent)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub destination_token_account: UncheckedAccount<'info>,
     
+    /// CHECK: Destination wallet (owner of destination_token_account, validated manually)
+    pub destination: UncheckedAccount<'info>,
+    
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for vault ownership validation
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA
+    /// CHECK: Validated manually
+    pub presale_token_vault: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUserPurchase<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        constraint = user_purchase.buyer == buyer.key() @ PresaleError::Unauthorized
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefundSol<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        constraint = user_purchase.buyer == buyer.key() @ PresaleError::Unauthorized
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+
+    // PDA that owns the SOL vault
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        mut,
+        seeds = [
+            b"presale_sol_vault",
+            presale_state.key().as_ref()
+        ],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for validation
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA (destination for returned tokens)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's presale token account (validated by token program)
+    #[account(mut)]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    // Buyer's vesting schedule (pass when the purchase was vested)
+    #[account(
+        seeds = [b"vesting", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump = vesting_account.bump
+    )]
+    pub vesting_account: Option<Account<'info, VestingAccount>>,
+
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct MigratePresaleState<'info> {
+pub struct ClaimRefundPaymentToken<'info> {
+    #[account(
+        seeds = [b"presale_state"],
+        bump = presale_state.bump
+    )]
+    pub presale_state: Account<'info, PresaleState>,
+
     #[account(mut)]
-    /// CHECK: PDA and authority are verified manually in the function to handle old structure
-    /// Reallocation is handled manually in the function
-    pub presale_state: UncheckedAccount<'info>,
-    
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        constraint = user_purchase.buyer == buyer.key() @ PresaleError::Unauthorized
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+
+    // PDA that owns the payment token vault ATA
+    /// CHECK: This is a PDA used for signing
+    #[account(
+        seeds = [
+            b"presale_payment_vault_pda",
+            presale_state.key().as_ref(),
+            payment_token_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub presale_payment_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the payment vault PDA (source)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_payment_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's payment token account (validated manually)
+    #[account(mut)]
+    pub buyer_payment_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Payment token mint (must match the mint recorded in the purchase)
+    #[account(
+        constraint = payment_token_mint.key() == user_purchase.payment_mint_used @ PresaleError::PaymentTokenNotAllowed
+    )]
+    pub payment_token_mint: UncheckedAccount<'info>,
+
+    // PDA that owns the presale token vault ATA
+    /// CHECK: This is a PDA used for validation
+    #[account(
+        seeds = [
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub presale_token_vault_pda: UncheckedAccount<'info>,
+
+    // ATA owned by the presale token vault PDA (destination for returned tokens)
+    /// CHECK: Validated manually
+    #[account(mut)]
+    pub presale_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's presale token account (validated by token program)
     #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    // Buyer's vesting schedule (pass when the purchase was vested)
+    #[account(
+        seeds = [b"vesting", presale_state.key().as_ref(), buyer.key().as_ref()],
+        bump = vesting_account.bump
+    )]
+    pub vesting_account: Option<Account<'info, VestingAccount>>,
+
+    /// CHECK: Presale token mint (for transfer_checked)
+    #[account(address = presale_state.presale_token_mint @ PresaleError::InvalidAccount)]
+    pub presale_token_mint: UncheckedAccount<'info>,
+
+    #[account(address = presale_state.effective_token_program_id() @ PresaleError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-// SetGovernance - Transfer authority to governance PDA
+
+
 #[derive(Accounts)]
-pub struct SetGovernance<'info> {
+pub struct UpdatePresaleCap<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump = presale_state.bump
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-
+    
     pub authority: Signer<'info>,
 }
 
-// SetTokenProgram - Set token program references
 #[derive(Accounts)]
-pub struct SetTokenProgram<'info> {
+pub struct UpdateMaxPerUser<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
@@ -1696,182 +5840,69 @@ pub struct SetTokenProgram<'info> {
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-
+    
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct AdminOnly<'info> {
+pub struct UpdatePresaleLimits<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump,
-        constraint = presale_state.authority == admin.key() 
-            || (presale_state.governance_set && presale_state.governance == admin.key())
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
     
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(payment_token_mint: Pubkey)]
-pub struct AllowPaymentToken<'info> {
+pub struct UpdatePurchaseLimits<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump,
-        constraint = presale_state.authority == admin.key() 
-            || (presale_state.governance_set && presale_state.governance == admin.key())
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
     
-    #[account(
-        init_if_needed,
-        payer = admin,
-        space = 8 + AllowedToken::LEN,
-        seeds = [
-            b"allowed_token",
-            presale_state.key().as_ref(),
-            payment_token_mint.as_ref()
-        ],
-        bump
-    )]
-    pub allowed_token: Account<'info, AllowedToken>,
-    
-    #[account(mut)]
-    pub admin: Signer<'info>,
-    
-    /// CHECK: Payment token mint account (for validation)
-    pub payment_token_mint_account: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct DisallowPaymentToken<'info> {
+pub struct SetEndTimestamp<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump,
-        constraint = presale_state.authority == admin.key() 
-            || (presale_state.governance_set && presale_state.governance == admin.key())
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
     
-    #[account(
-        mut,
-        seeds = [
-            b"allowed_token",
-            presale_state.key().as_ref(),
-            payment_token_mint.key().as_ref()
-        ],
-        bump
-    )]
-    pub allowed_token: Account<'info, AllowedToken>,
-    
-    pub admin: Signer<'info>,
-    
-    /// CHECK: Payment token mint account (for validation)
-    pub payment_token_mint: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Buy<'info> {
+pub struct FinalizePresale<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump
+        bump = presale_state.bump
     )]
     pub presale_state: Account<'info, PresaleState>,
     
-    // Token program state to check emergency pause
-    /// CHECK: Token program state PDA (validated by constraint)
-    #[account(
-        constraint = token_state.key() == presale_state.token_program_state @ PresaleError::InvalidTokenProgramState
-    )]
-    pub token_state: UncheckedAccount<'info>,
-    
-    #[account(
-        seeds = [
-            b"allowed_token",
-            presale_state.key().as_ref(),
-            payment_token_mint.key().as_ref()
-        ],
-        bump
-    )]
-    pub allowed_token: Account<'info, AllowedToken>,
-    
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-    
-    /// CHECK: Buyer's payment token account (validated manually)
-    #[account(mut)]
-    pub buyer_payment_token_account: UncheckedAccount<'info>,
-
-    // PDA that will own the payment token vault ATA
-    /// CHECK: This is a PDA used for signing
-    #[account(
-        seeds = [
-            b"presale_payment_vault_pda",
-            presale_state.key().as_ref(),
-            payment_token_mint.key().as_ref()
-        ],
-        bump
-    )]
-    pub presale_payment_vault_pda: UncheckedAccount<'info>,
-
-    // ATA owned by the payment vault PDA
-    /// CHECK: Validated manually
-    #[account(mut)]
-    pub presale_payment_vault: UncheckedAccount<'info>,
-
-    // PDA that will own the presale token vault ATA
-    /// CHECK: This is a PDA used for signing
-    #[account(
-        seeds = [
-            b"presale_token_vault_pda",
-            presale_state.presale_token_mint.as_ref()
-        ],
-        bump
-    )]
-    pub presale_token_vault_pda: UncheckedAccount<'info>,
-
-    // ATA owned by the presale token vault PDA
-    /// CHECK: Validated manually
-    #[account(mut)]
-    pub presale_token_vault: UncheckedAccount<'info>,
-
-    /// CHECK: Buyer's token account (validated manually)
-    #[account(mut)]
-    pub buyer_token_account: UncheckedAccount<'info>,
-    
-    /// CHECK: Payment token mint account (for validation)
-    pub payment_token_mint: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + UserPurchase::LEN,
-        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
-        bump
-    )]
-    pub user_purchase: Account<'info, UserPurchase>,
-
-    /// CHECK: Optional blacklist account for buyer (validated in function)
-    pub buyer_blacklist: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SetTreasuryAddress<'info> {
+pub struct SetTokenPriceUsd<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
@@ -1886,7 +5917,8 @@ pub struct SetTreasuryAddress<'info> {
 }
 
 #[derive(Accounts)]
-pub struct WithdrawToTreasury<'info> {
+#[instruction(round_id: u8)]
+pub struct CreateRound<'info> {
     #[account(
         seeds = [b"presale_state"],
         bump = presale_state.bump,
@@ -1895,113 +5927,77 @@ pub struct WithdrawToTreasury<'info> {
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
-    
-    // PDA that owns the payment token vault ATA
-    /// CHECK: This is a PDA used for signing
+
     #[account(
-        seeds = [
-            b"presale_payment_vault_pda",
-            presale_state.key().as_ref(),
-            payment_token_mint.key().as_ref()
-        ],
+        init,
+        payer = authority,
+        space = 8 + PresaleRound::LEN,
+        seeds = [b"presale_round", presale_state.key().as_ref(), &[round_id]],
         bump
     )]
-    pub presale_payment_vault_pda: UncheckedAccount<'info>,
-    
-    // ATA owned by the payment vault PDA (source)
-    /// CHECK: Validated manually
-    #[account(mut)]
-    pub presale_payment_vault: UncheckedAccount<'info>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    // Treasury token account (destination)
-    /// CHECK: Validated manually
     #[account(mut)]
-    pub treasury_token_account: UncheckedAccount<'info>,
-    
-    /// CHECK: Payment token mint account (for validation)
-    pub payment_token_mint: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct BuyWithSol<'info> {
+#[instruction(round_id: u8)]
+pub struct ManageRound<'info> {
     #[account(
         mut,
         seeds = [b"presale_state"],
-        bump
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-    
-    // Token program state to check emergency pause
-    /// CHECK: Token program state PDA (validated by constraint)
-    #[account(
-        constraint = token_state.key() == presale_state.token_program_state @ PresaleError::InvalidTokenProgramState
-    )]
-    pub token_state: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-    
-    // PDA that owns the SOL vault
-    /// CHECK: This is a PDA that will receive SOL (created automatically on first transfer)
+
     #[account(
         mut,
-        seeds = [
-            b"presale_sol_vault",
-            presale_state.key().as_ref()
-        ],
-        bump
+        seeds = [b"presale_round", presale_state.key().as_ref(), &[round_id]],
+        bump = presale_round.bump
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    // PDA that will own the presale token vault ATA
-    /// CHECK: This is a PDA used for signing
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u8)]
+pub struct SetActiveRound<'info> {
     #[account(
-        seeds = [
-            b"presale_token_vault_pda",
-            presale_state.presale_token_mint.as_ref()
-        ],
-        bump
+        mut,
+        seeds = [b"presale_state"],
+        bump = presale_state.bump,
+        constraint = presale_state.authority == authority.key() 
+            || (presale_state.governance_set && presale_state.governance == authority.key())
+            @ PresaleError::Unauthorized
     )]
-    pub presale_token_vault_pda: UncheckedAccount<'info>,
-
-    // ATA owned by the presale token vault PDA
-    /// CHECK: Validated manually
-    #[account(mut)]
-    pub presale_token_vault: UncheckedAccount<'info>,
-
-    /// CHECK: Buyer's token account (validated manually)
-    #[account(mut)]
-    pub buyer_token_account: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub presale_state: Account<'info, PresaleState>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + UserPurchase::LEN,
-        seeds = [b"user_purchase", presale_state.key().as_ref(), buyer.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"presale_round", presale_state.key().as_ref(), &[round_id]],
+        bump = presale_round.bump
     )]
-    pub user_purchase: Account<'info, UserPurchase>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    /// CHECK: Optional blacklist account for buyer (validated in function)
-    pub buyer_blacklist: UncheckedAccount<'info>,
-    
-    /// CHECK: Chainlink SOL/USD price feed account
-    /// Must be the official Chainlink feed (validated in buy_with_sol)
-    pub chainlink_feed: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
+    // Currently active round (required while one is active)
+    #[account(
+        mut,
+        constraint = active_round.presale_state == presale_state.key() @ PresaleError::InvalidRound
+    )]
+    pub active_round: Option<Account<'info, PresaleRound>>,
+
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawSolToTreasury<'info> {
+pub struct FinalizeResults<'info> {
     #[account(
         seeds = [b"presale_state"],
         bump = presale_state.bump,
@@ -2010,34 +6006,26 @@ pub struct WithdrawSolToTreasury<'info> {
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
-    
-    // PDA that owns the SOL vault
-    /// CHECK: This is a PDA used for signing
+
     #[account(
-        mut,
-        seeds = [
-            b"presale_sol_vault",
-            presale_state.key().as_ref()
-        ],
+        init,
+        payer = authority,
+        space = 8 + PresaleResults::LEN,
+        seeds = [b"presale_results", presale_state.key().as_ref()],
         bump
     )]
-    pub sol_vault: SystemAccount<'info>,
-    
-    /// CHECK: Treasury wallet (validated by constraint)
-    #[account(
-        mut,
-        constraint = treasury.key() == presale_state.treasury_address @ PresaleError::InvalidTreasuryAddress
-    )]
-    pub treasury: UncheckedAccount<'info>,
-    
+    pub presale_results: Account<'info, PresaleResults>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawUnsoldTokens<'info> {
+pub struct ResetForNewRound<'info> {
     #[account(
+        mut,
         seeds = [b"presale_state"],
         bump = presale_state.bump,
         constraint = presale_state.authority == authority.key() 
@@ -2045,125 +6033,657 @@ pub struct WithdrawUnsoldTokens<'info> {
             @ PresaleError::Unauthorized
     )]
     pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
-    
-    // PDA that owns the presale token vault ATA
-    /// CHECK: This is a PDA used for signing
+
     #[account(
+        init,
+        payer = authority,
+        space = 8 + RoundArchive::LEN,
         seeds = [
-            b"presale_token_vault_pda",
-            presale_state.presale_token_mint.as_ref()
+            b"round_archive",
+            presale_state.key().as_ref(),
+            presale_state.round_number.to_le_bytes().as_ref()
         ],
         bump
     )]
-    pub presale_token_vault_pda: UncheckedAccount<'info>,
-    
-    // ATA owned by the presale token vault PDA (source)
-    /// CHECK: Validated manually
-    #[account(mut)]
-    pub presale_token_vault: UncheckedAccount<'info>,
+    pub round_archive: Account<'info, RoundArchive>,
+
+    #[account(
+        seeds = [
+            b"presale_sol_vault",
+            presale_state.key().as_ref()
+        ],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
 
-    // Destination token account (where unsold tokens will be sent)
-    /// CHECK: Validated manually
     #[account(mut)]
-    pub destination_token_account: UncheckedAccount<'info>,
-    
-    /// CHECK: Destination wallet (owner of destination_token_account, validated manually)
-    pub destination: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// State Structures
+
+
+
+#[account]
+pub struct PresaleState {
+    pub admin: Pubkey, // Original admin (kept for reference)
+    pub authority: Pubkey, // Current authority (admin or governance PDA)
+    pub governance: Pubkey, // Governance PDA (set after set_governance)
+    pub token_program: Pubkey, // Token program address
+    pub token_program_state: Pubkey, // Token program state PDA
+    pub presale_token_mint: Pubkey,
+    pub status: PresaleStatus,
+    pub total_tokens_sold: u64,
+    pub total_raised: u64,
+    pub governance_set: bool, // Track if governance has been set
+    pub treasury_address: Pubkey, // Treasury wallet address (settable via set_treasury_address)
+    pub max_presale_cap: u64, // Maximum presale cap (0 = unlimited)
+    pub max_per_user: u64, // Maximum per user purchase (0 = unlimited)
+    pub token_price_usd_micro: u64, // Token price in micro-USD (e.g., 1000 = $0.001 per token)
+    pub bump: u8, // PDA bump
+    pub active_round_id: Option<u8>, // Currently active presale round (None = no round)
+    pub total_raised_usd_micro: u64, // Total raised valued at the effective token price (micro-USD)
+    pub unique_buyers: u64, // Number of distinct buyers
+    pub min_purchase_amount: u64, // Minimum tokens per purchase (0 = no minimum)
+    pub max_per_transaction: u64, // Maximum tokens per purchase (0 = unlimited)
+    pub end_timestamp: Option<i64>, // Purchases rejected after this time (None = no end time)
+    pub total_claimed: u64, // Tokens delivered to buyers out of total_tokens_sold
+    pub whitelist_phase_active: bool, // Only whitelisted buyers may purchase while true
+    pub referral_enabled: bool, // Whether purchases may name a referrer
+    pub referral_bonus_percent: u8, // Bonus for buyer and referrer (% of purchased tokens)
+    pub referral_bonus_outstanding: u64, // Referral bonus earned but not yet claimed
+    pub vesting_enabled: bool, // Purchases vest instead of being delivered immediately
+    pub vesting_cliff_seconds: u64, // Time after first purchase before anything is claimable
+    pub vesting_duration_seconds: u64, // Linear vesting duration from first purchase
+    pub oracle_type: OracleType, // Oracle used to price SOL purchases
+    pub oracle_feed: Pubkey, // Required SOL/USD feed account (default = not set, SOL purchases disabled)
+    pub token_program_id: Pubkey, // SPL Token or Token-2022 program for vault transfers (default = SPL Token)
+    pub soft_cap: u64, // Minimum total_raised_usd_micro for a stopped presale to succeed (0 = no soft cap)
+    pub allowed_token_count: u8, // Number of payment tokens currently allowed
+    pub max_allowed_tokens: u8, // Limit on allowed payment tokens (0 = DEFAULT_MAX_ALLOWED_TOKENS)
+    pub start_timestamp: Option<i64>, // Purchases rejected before this time (None = no start time)
+    pub cap_in_usd: bool, // If true, max_presale_cap is in micro-USD raised instead of tokens sold
+    pub round_number: u64, // Sequential sale round, incremented by reset_for_new_round
+    pub price_tiers: Vec<PriceTier>, // Price steps by total_tokens_sold, ascending (max MAX_PRICE_TIERS)
+    pub merkle_whitelist_root: Option<[u8; 32]>, // Root of a Merkle tree of whitelisted buyers (None = PDA whitelist only)
+    pub allocation_authority: Pubkey, // Signs off-chain allocations for buy_with_allocation (default = disabled)
+    pub referral_bonus_total: u64, // Referrer bonuses credited this round, counted against a token cap
+    pub oracle_feed_backup: Pubkey, // Backup Chainlink feed for stale primaries (default = no fallback)
+    pub withdrawal_fee_bps: u16, // Share of treasury withdrawals sent to fee_collector (0 = no fee)
+    pub fee_collector: Pubkey, // Receives withdrawal fees
+    pub price_staleness_threshold: i64, // Maximum SOL/USD price age in seconds (0 = PRICE_FEED_STALENESS_THRESHOLD_SECONDS)
+    pub swap_program: Pubkey, // DEX program withdraw_and_swap may route through (default = disabled)
+    pub claim_mode: bool, // Purchases are recorded as owed and delivered by claim_tokens
+    pub pending_admin: Option<Pubkey>, // Proposed admin awaiting accept_admin_change
+}
+
+impl PresaleState {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 1 + 2 + 8 + 8 + 8 + 8  + 9 + 8 + 1 + 1 + 1 + 8 + 1 + 8 + 8 + 1 + 32 + 32 + 8 + 1 + 1 + 9 + 1 + 8 + (4 + PriceTier::LEN * MAX_PRICE_TIERS) + 33 + 32 + 8 + 32 + 2 + 32 + 8 + 32 + 1 + 33; 
+    // admin + authority + governance + token_program + token_program_state + mint + status + sold + raised + governance_set + treasury_address + max_presale_cap + max_per_user + token_price_usd_micro + bump + active_round_id + total_raised_usd_micro + unique_buyers + min_purchase_amount + max_per_transaction + end_timestamp + total_claimed + whitelist_phase_active + referral_enabled + referral_bonus_percent + referral_bonus_outstanding + vesting_enabled + vesting_cliff_seconds + vesting_duration_seconds + oracle_type + oracle_feed + token_program_id + soft_cap + allowed_token_count + max_allowed_tokens + start_timestamp + cap_in_usd + round_number + price_tiers + merkle_whitelist_root + allocation_authority + referral_bonus_total + oracle_feed_backup + withdrawal_fee_bps + fee_collector + price_staleness_threshold + swap_program + claim_mode + pending_admin
+
+    /// Withdrawal fee owed to the fee collector on `amount` (0 when no fee is set)
+    pub fn withdrawal_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.withdrawal_fee_bps as u128)
+            .ok_or(PresaleError::Overflow)?
+            .checked_div(10_000)
+            .ok_or(PresaleError::Overflow)?;
+        Ok(u64::try_from(fee).map_err(|_| PresaleError::Overflow)?)
+    }
+
+    /// Rejects the purchase during the whitelist phase unless the buyer has an allowed
+    /// entry or, when a Merkle root is set, a valid proof of inclusion
+    pub fn check_buyer_whitelist(
+        &self,
+        whitelist: Option<&AllowedBuyer>,
+        buyer: Pubkey,
+        merkle_proof: &[[u8; 32]],
+    ) -> Result<()> {
+        if !self.whitelist_phase_active || whitelist.is_some_and(|entry| entry.is_allowed) {
+            return Ok(());
+        }
+        match self.merkle_whitelist_root {
+            Some(root) => {
+                require!(
+                    verify_merkle_proof(root, buyer, merkle_proof),
+                    PresaleError::InvalidMerkleProof
+                );
+                Ok(())
+            }
+            None => err!(PresaleError::BuyerNotWhitelisted),
+        }
+    }
+
+    /// Tokens owed but not yet delivered (unclaimed purchases and referral bonuses);
+    /// the presale token vault must hold at least this much
+    pub fn outstanding_allocations(&self) -> u64 {
+        self.total_tokens_sold
+            .saturating_sub(self.total_claimed)
+            .saturating_add(self.referral_bonus_outstanding)
+    }
+
+    /// Maximum SOL/USD price age; presales created before the field existed have
+    /// it zeroed and use the default threshold
+    pub fn effective_staleness_threshold(&self) -> i64 {
+        if self.price_staleness_threshold == 0 {
+            PRICE_FEED_STALENESS_THRESHOLD_SECONDS
+        } else {
+            self.price_staleness_threshold
+        }
+    }
+
+    /// Token program used for vault transfers; presales created before the field
+    /// existed have it zeroed and use legacy SPL Token
+    pub fn effective_token_program_id(&self) -> Pubkey {
+        if self.token_program_id == Pubkey::default() {
+            anchor_spl::token::ID
+        } else {
+            self.token_program_id
+        }
+    }
+
+    /// Payment token limit; presales created before the field existed have it zeroed
+    /// and use the default
+    pub fn effective_max_allowed_tokens(&self) -> u8 {
+        if self.max_allowed_tokens == 0 {
+            DEFAULT_MAX_ALLOWED_TOKENS
+        } else {
+            self.max_allowed_tokens
+        }
+    }
+
+    /// Buyers may claim refunds once the presale has failed, or has stopped
+    /// without reaching its soft cap
+    pub fn refunds_open(&self) -> bool {
+        match self.status {
+            PresaleStatus::Failed => true,
+            PresaleStatus::Stopped => self.soft_cap > 0 && self.total_raised_usd_micro < self.soft_cap,
+            _ => false,
+        }
+    }
+
+    /// Returns true once the configured start timestamp has been reached
+    pub fn has_started(&self, now: i64) -> bool {
+        !matches!(self.start_timestamp, Some(start) if now < start)
+    }
+
+    /// Returns true once the configured end timestamp has passed
+    pub fn has_ended(&self, now: i64) -> bool {
+        matches!(self.end_timestamp, Some(end) if now > end)
+    }
+
+    /// Validates a single purchase against the min/max per-transaction limits
+    pub fn check_purchase_amount(&self, tokens: u64) -> Result<()> {
+        require!(
+            tokens >= self.min_purchase_amount,
+            PresaleError::BelowMinimumPurchase
+        );
+        if self.max_per_transaction > 0 {
+            require!(
+                tokens <= self.max_per_transaction,
+                PresaleError::MaxPerTransactionExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Amount counted against `max_presale_cap`, in the cap's unit; a token cap
+    /// also covers bonuses credited to referrers
+    pub fn cap_progress(&self) -> u64 {
+        if self.cap_in_usd {
+            self.total_raised_usd_micro
+        } else {
+            self.total_tokens_sold.saturating_add(self.referral_bonus_total)
+        }
+    }
+
+    /// Amount of `max_presale_cap` a purchase uses, in the cap's unit; a token cap
+    /// counts the buyer's and referrer's bonuses as sold tokens
+    pub fn presale_cap_usage(&self, tokens: u64, referral_bonus: u64, usd_micro: u64) -> Result<u64> {
+        if self.cap_in_usd {
+            return Ok(usd_micro);
+        }
+        let bonuses = referral_bonus.checked_mul(2).ok_or(PresaleError::Overflow)?;
+        Ok(tokens.checked_add(bonuses).ok_or(PresaleError::Overflow)?)
+    }
+
+    /// Rejects a purchase that would push sales past `max_presale_cap` (0 = unlimited)
+    pub fn check_presale_cap(&self, tokens: u64, referral_bonus: u64, usd_micro: u64) -> Result<()> {
+        if self.max_presale_cap == 0 {
+            return Ok(());
+        }
+        let purchase = self.presale_cap_usage(tokens, referral_bonus, usd_micro)?;
+        let new_total = self
+            .cap_progress()
+            .checked_add(purchase)
+            .ok_or(PresaleError::Overflow)?;
+        require!(
+            new_total <= self.max_presale_cap,
+            PresaleError::PresaleCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Scales a payment down until its purchase fits under `max_presale_cap`
+    ///
+    /// `cap_usage` quotes how much of the cap a payment would use. The payment is
+    /// scaled by the share of that usage still available; tier pricing can leave the
+    /// scaled payment slightly over, so this repeats until it fits.
+    ///
+    /// # Errors
+    /// - `PresaleError::PresaleCapExceeded` if no part of the payment fits
+    pub fn fit_to_remaining_cap(
+        &self,
+        amount: u64,
+        mut cap_usage: impl FnMut(u64) -> Result<u64>,
+    ) -> Result<u64> {
+        if self.max_presale_cap == 0 {
+            return Ok(amount);
+        }
+        let remaining = self.max_presale_cap.saturating_sub(self.cap_progress());
+        require!(remaining > 0, PresaleError::PresaleCapExceeded);
+        let mut amount = amount;
+        loop {
+            let usage = cap_usage(amount)?;
+            if usage <= remaining {
+                return Ok(amount);
+            }
+            // usage > remaining, so this strictly shrinks the payment
+            amount = ((amount as u128) * (remaining as u128) / (usage as u128)) as u64;
+            require!(amount > 0, PresaleError::PresaleCapExceeded);
+        }
+    }
+
+    /// Converts a stablecoin amount (base units with `decimals`) to micro-USD at $1 per token
+    pub fn stable_to_usd_micro(amount: u64, decimals: u8) -> Result<u64> {
+        let value = (amount as u128)
+            .checked_mul(1_000_000u128)
+            .ok_or(PresaleError::Overflow)?
+            .checked_div(
+                10u128
+                    .checked_pow(decimals as u32)
+                    .ok_or(PresaleError::Overflow)?,
+            )
+            .ok_or(PresaleError::Overflow)?;
+        require!(value <= u64::MAX as u128, PresaleError::Overflow);
+        Ok(value as u64)
+    }
+
+    /// Converts micro-USD to presale token base units at `token_price_usd_micro`
+    pub fn usd_micro_to_tokens(usd_micro: u64, token_price_usd_micro: u64) -> Result<u64> {
+        let tokens = (usd_micro as u128)
+            .checked_mul(10u128.pow(TOKEN_DECIMALS as u32))
+            .ok_or(PresaleError::Overflow)?
+            .checked_div(token_price_usd_micro as u128)
+            .ok_or(PresaleError::Overflow)?;
+        require!(tokens <= u64::MAX as u128, PresaleError::Overflow);
+        Ok(tokens as u64)
+    }
+
+    /// Token price in micro-USD once `sold` tokens have been sold
+    ///
+    /// Below the first tier threshold (or with no tiers) this is `token_price_usd_micro`.
+    pub fn tier_price_at(&self, sold: u64) -> u64 {
+        self.price_tiers
+            .iter()
+            .rev()
+            .find(|tier| sold >= tier.sold_threshold)
+            .map_or(self.token_price_usd_micro, |tier| tier.price_usd_micro)
+    }
+
+    /// Converts micro-USD to tokens along the price tiers, starting at `total_tokens_sold`
+    ///
+    /// A purchase that crosses a tier threshold buys the tokens below the threshold at
+    /// the lower price and the rest at the next tier's price. Returns the tokens and
+    /// the effective (average) price in micro-USD.
+    pub fn quote_tokens(&self, usd_micro: u64) -> Result<(u64, u64)> {
+        let mut remaining = usd_micro;
+        let mut sold = self.total_tokens_sold;
+        let mut tokens: u64 = 0;
+        loop {
+            let price = self.tier_price_at(sold);
+            require!(price > 0, PresaleError::InvalidAmount);
+            let at_price = Self::usd_micro_to_tokens(remaining, price)?;
+            let next_threshold = self
+                .price_tiers
+                .iter()
+                .map(|tier| tier.sold_threshold)
+                .find(|&threshold| threshold > sold);
+            match next_threshold {
+                Some(threshold)
+                    if sold.checked_add(at_price).ok_or(PresaleError::Overflow)? > threshold =>
+                {
+                    // Buy up to the threshold at this price (rounding the cost up),
+                    // then continue with the remainder at the next tier
+                    let step = threshold - sold;
+                    let scale = 10u128.pow(TOKEN_DECIMALS as u32);
+                    let cost = (step as u128)
+                        .checked_mul(price as u128)
+                        .and_then(|v| v.checked_add(scale - 1))
+                        .ok_or(PresaleError::Overflow)?
+                        / scale;
+                    remaining = remaining
+                        .checked_sub(cost as u64)
+                        .ok_or(PresaleError::Overflow)?;
+                    tokens = tokens.checked_add(step).ok_or(PresaleError::Overflow)?;
+                    sold = threshold;
+                }
+                _ => {
+                    tokens = tokens.checked_add(at_price).ok_or(PresaleError::Overflow)?;
+                    break;
+                }
+            }
+        }
+        require!(tokens > 0, PresaleError::InvalidAmount);
+
+        let effective_price = (usd_micro as u128)
+            .checked_mul(10u128.pow(TOKEN_DECIMALS as u32))
+            .ok_or(PresaleError::Overflow)?
+            / tokens as u128;
+        require!(effective_price <= u64::MAX as u128, PresaleError::Overflow);
+        Ok((tokens, effective_price as u64))
+    }
+
+    /// Checks that tiers have strictly ascending thresholds and non-zero prices
+    pub fn validate_price_tiers(tiers: &[PriceTier]) -> Result<()> {
+        require!(tiers.len() <= MAX_PRICE_TIERS, PresaleError::InvalidPriceTiers);
+        for (i, tier) in tiers.iter().enumerate() {
+            require!(tier.price_usd_micro > 0, PresaleError::InvalidPriceTiers);
+            if i > 0 {
+                require!(
+                    tier.sold_threshold > tiers[i - 1].sold_threshold,
+                    PresaleError::InvalidPriceTiers
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a token amount (base units) to micro-USD at `token_price_usd_micro`
+    pub fn tokens_to_usd_micro(tokens: u64, token_price_usd_micro: u64) -> Result<u64> {
+        let value = (tokens as u128)
+            .checked_mul(token_price_usd_micro as u128)
+            .ok_or(PresaleError::Overflow)?
+            .checked_div(10u128.pow(TOKEN_DECIMALS as u32))
+            .ok_or(PresaleError::Overflow)?;
+        require!(value <= u64::MAX as u128, PresaleError::Overflow);
+        Ok(value as u64)
+    }
+}
+
+#[account]
+pub struct PresaleResults {
+    pub presale_state: Pubkey,
+    pub total_tokens_sold: u64,
+    pub total_raised_usd_micro: u64,
+    pub unique_buyers: u64,
+    pub final_price: u64, // Token price in micro-USD at finalization
+    pub end_timestamp: i64,
+    pub bump: u8,
+}
+
+impl PresaleResults {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1;
+    // presale_state + total_tokens_sold + total_raised_usd_micro + unique_buyers + final_price + end_timestamp + bump
+}
+
+#[account]
+pub struct RoundArchive {
+    pub presale_state: Pubkey,
+    pub round_number: u64,
+    pub total_tokens_sold: u64,
+    pub total_raised: u64,
+    pub total_raised_usd_micro: u64,
+    pub unique_buyers: u64,
+    pub final_price: u64, // Token price in micro-USD when the round was archived
+    pub end_timestamp: i64,
+    pub bump: u8,
+}
+
+impl RoundArchive {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+    // presale_state + round_number + total_tokens_sold + total_raised + total_raised_usd_micro + unique_buyers + final_price + end_timestamp + bump
+}
+
+#[account]
+pub struct PresaleRound {
+    pub presale_state: Pubkey,
+    pub round_id: u8,
+    pub token_price_usd_micro: u64, // Round token price in micro-USD
+    pub cap: u64, // Maximum tokens sold in this round (0 = unlimited)
+    pub tokens_sold: u64,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl PresaleRound {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 9 + 9 + 1 + 1;
+    // presale_state + round_id + price + cap + tokens_sold + start_time + end_time + is_active + bump
+
+    /// Validates a purchase of `tokens` against this round at time `now`
+    pub fn check_purchase(
+        &self,
+        presale_state: &PresaleState,
+        round_id: u8,
+        tokens: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            self.round_id == round_id && presale_state.active_round_id == Some(round_id),
+            PresaleError::InvalidRound
+        );
+        require!(self.is_active, PresaleError::RoundNotActive);
+        if let Some(start) = self.start_time {
+            require!(now >= start, PresaleError::RoundNotActive);
+        }
+        if let Some(end) = self.end_time {
+            require!(now <= end, PresaleError::RoundNotActive);
+        }
+        if self.cap > 0 {
+            let new_total = self
+                .tokens_sold
+                .checked_add(tokens)
+                .ok_or(PresaleError::Overflow)?;
+            require!(new_total <= self.cap, PresaleError::RoundCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Returns true once the tokens left under the cap are below the minimum purchase
+    pub fn is_sold_out(&self, min_purchase_amount: u64) -> bool {
+        self.cap > 0 && self.cap.saturating_sub(self.tokens_sold) < min_purchase_amount.max(1)
+    }
+
+    /// Hands the active status to the next round once this round sells out
+    ///
+    /// `next_round` must be the PDA of round `round_id + 1` whenever this round is
+    /// sold out. An uninitialized PDA means there is no next round, so the sold-out
+    /// round stays active and rejects further purchases until `set_active_round`.
+    pub fn advance_if_sold_out(
+        &mut self,
+        presale_state: &mut PresaleState,
+        next_round: Option<&UncheckedAccount>,
+    ) -> Result<()> {
+        if !self.is_sold_out(presale_state.min_purchase_amount) {
+            return Ok(());
+        }
+        let next_round_id = match self.round_id.checked_add(1) {
+            Some(next_round_id) => next_round_id,
+            None => return Ok(()),
+        };
+        let next_round_info = next_round.ok_or(PresaleError::NextRoundRequired)?;
+        let (expected_next_round, _) = Pubkey::find_program_address(
+            &[b"presale_round", self.presale_state.as_ref(), &[next_round_id]],
+            &crate::ID,
+        );
+        require!(
+            next_round_info.key() == expected_next_round,
+            PresaleError::InvalidRound
+        );
+        if next_round_info.data_is_empty() {
+            return Ok(());
+        }
+        require!(next_round_info.owner == &crate::ID, PresaleError::InvalidRound);
+
+        let mut next_round_data = next_round_info.try_borrow_mut_data()?;
+        let mut next_round = PresaleRound::try_deserialize(&mut &next_round_data[..])?;
+        self.is_active = false;
+        next_round.is_active = true;
+        presale_state.active_round_id = Some(next_round.round_id);
+        next_round.try_serialize(&mut &mut next_round_data[..])?;
+
+        emit!(RoundClosed {
+            round_id: self.round_id,
+            tokens_sold: self.tokens_sold,
+        });
+        emit!(RoundAdvanced {
+            from_round_id: Some(self.round_id),
+            to_round_id: next_round.round_id,
+        });
+        msg!("Round {} sold out, round {} activated", self.round_id, next_round.round_id);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PurchaseCode {
+    pub presale_state: Pubkey,
+    pub code_hash: [u8; 32], // sha256 of the code
+    pub discount_bps: u16,
+    pub bonus_bps: u16,
+    pub max_uses: u32,
+    pub uses: u32,
+    pub expires_at: i64,
+    pub bump: u8,
 }
 
+impl PurchaseCode {
+    pub const LEN: usize = 32 + 32 + 2 + 2 + 4 + 4 + 8 + 1;
+    // presale_state + code_hash + discount_bps + bonus_bps + max_uses + uses + expires_at + bump
 
+    /// Checks that `code` hashes to this code and it can still be redeemed at `now`
+    pub fn check_redeemable(&self, code: &[u8], now: i64) -> Result<()> {
+        require!(
+            anchor_lang::solana_program::hash::hash(code).to_bytes() == self.code_hash,
+            PresaleError::InvalidPurchaseCode
+        );
+        require!(now <= self.expires_at, PresaleError::PurchaseCodeExpired);
+        require!(self.uses < self.max_uses, PresaleError::PurchaseCodeExhausted);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct UpdatePresaleCap<'info> {
-    #[account(
-        mut,
-        seeds = [b"presale_state"],
-        bump = presale_state.bump,
-        constraint = presale_state.authority == authority.key() 
-            || (presale_state.governance_set && presale_state.governance == authority.key())
-            @ PresaleError::Unauthorized
-    )]
-    pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
+    /// Applies the code to a quote: the discount divides the price, the bonus adds
+    /// a share of the discounted tokens. Returns the adjusted tokens and price.
+    pub fn apply(&self, tokens: u64, price_usd_micro: u64) -> Result<(u64, u64)> {
+        let discounted = (tokens as u128)
+            .checked_mul(10_000)
+            .ok_or(PresaleError::Overflow)?
+            / (10_000 - self.discount_bps as u128);
+        let with_bonus = discounted
+            .checked_mul(10_000 + self.bonus_bps as u128)
+            .ok_or(PresaleError::Overflow)?
+            / 10_000;
+        let price = price_usd_micro as u128 * (10_000 - self.discount_bps as u128) / 10_000;
+        Ok((
+            u64::try_from(with_bonus).map_err(|_| PresaleError::Overflow)?,
+            price as u64,
+        ))
+    }
 }
 
-#[derive(Accounts)]
-pub struct UpdateMaxPerUser<'info> {
-    #[account(
-        mut,
-        seeds = [b"presale_state"],
-        bump = presale_state.bump,
-        constraint = presale_state.authority == authority.key() 
-            || (presale_state.governance_set && presale_state.governance == authority.key())
-            @ PresaleError::Unauthorized
-    )]
-    pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
+#[account]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,
+    pub total_referrals: u32,
+    pub bonus_tokens_earned: u64,
+    pub bonus_tokens_claimed: u64,
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct UpdatePresaleLimits<'info> {
-    #[account(
-        mut,
-        seeds = [b"presale_state"],
-        bump = presale_state.bump,
-        constraint = presale_state.authority == authority.key() 
-            || (presale_state.governance_set && presale_state.governance == authority.key())
-            @ PresaleError::Unauthorized
-    )]
-    pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
+impl ReferralAccount {
+    pub const LEN: usize = 32 + 4 + 8 + 8 + 1; // referrer + total_referrals + earned + claimed + bump
 }
 
-#[derive(Accounts)]
-pub struct SetTokenPriceUsd<'info> {
-    #[account(
-        mut,
-        seeds = [b"presale_state"],
-        bump = presale_state.bump,
-        constraint = presale_state.authority == authority.key() 
-            || (presale_state.governance_set && presale_state.governance == authority.key())
-            @ PresaleError::Unauthorized
-    )]
-    pub presale_state: Account<'info, PresaleState>,
-    
-    pub authority: Signer<'info>,
+#[account]
+pub struct VestingAccount {
+    pub buyer: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
 }
 
-// State Structures
+impl VestingAccount {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1; // buyer + total_amount + claimed_amount + start_time + cliff_time + end_time + bump
 
+    /// Adds purchased tokens to the schedule, starting it on the buyer's first purchase.
+    /// Returns true if the schedule was created by this call.
+    pub fn record_purchase(
+        &mut self,
+        buyer: Pubkey,
+        amount: u64,
+        presale_state: &PresaleState,
+        now: i64,
+        bump: u8,
+    ) -> Result<bool> {
+        let created = self.buyer == Pubkey::default();
+        if created {
+            // Bounded by MAX_VESTING_DURATION_SECONDS, so the casts cannot wrap
+            self.buyer = buyer;
+            self.start_time = now;
+            self.cliff_time = now
+                .checked_add(presale_state.vesting_cliff_seconds as i64)
+                .ok_or(PresaleError::Overflow)?;
+            self.end_time = now
+                .checked_add(presale_state.vesting_duration_seconds as i64)
+                .ok_or(PresaleError::Overflow)?;
+            self.bump = bump;
+        }
+        self.total_amount = self
+            .total_amount
+            .checked_add(amount)
+            .ok_or(PresaleError::Overflow)?;
+        Ok(created)
+    }
+
+    /// Tokens vested as of `now`: nothing before the cliff, then linear from start to end
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_time {
+            return Ok(0);
+        }
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+        let elapsed = now.saturating_sub(self.start_time) as u128;
+        let duration = self.end_time.saturating_sub(self.start_time) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(PresaleError::Overflow)?
+            .checked_div(duration)
+            .ok_or(PresaleError::Overflow)?;
+        Ok(vested as u64)
+    }
 
+    /// Tokens still held in the presale vault for this schedule
+    pub fn remaining(&self) -> u64 {
+        self.total_amount.saturating_sub(self.claimed_amount)
+    }
+}
 
 #[account]
-pub struct PresaleState {
-    pub admin: Pubkey, // Original admin (kept for reference)
-    pub authority: Pubkey, // Current authority (admin or governance PDA)
-    pub governance: Pubkey, // Governance PDA (set after set_governance)
-    pub token_program: Pubkey, // Token program address
-    pub token_program_state: Pubkey, // Token program state PDA
-    pub presale_token_mint: Pubkey,
-    pub status: PresaleStatus,
-    pub total_tokens_sold: u64,
-    pub total_raised: u64,
-    pub governance_set: bool, // Track if governance has been set
-    pub treasury_address: Pubkey, // Treasury wallet address (settable via set_treasury_address)
-    pub max_presale_cap: u64, // Maximum presale cap (0 = unlimited)
-    pub max_per_user: u64, // Maximum per user purchase (0 = unlimited)
-    pub token_price_usd_micro: u64, // Token price in micro-USD (e.g., 1000 = $0.001 per token)
-    pub bump: u8, // PDA bump
+pub struct AllowedBuyer {
+    pub account: Pubkey,
+    pub is_allowed: bool,
 }
 
-impl PresaleState {
-    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 1; 
-    // admin + authority + governance + token_program + token_program_state + mint + status + sold + raised + governance_set + treasury_address + max_presale_cap + max_per_user + token_price_usd_micro + bump
+impl AllowedBuyer {
+    pub const LEN: usize = 32 + 1; // account + is_allowed
 }
 
 #[account]
@@ -2171,20 +6691,104 @@ pub struct AllowedToken {
     pub presale_state: Pubkey,
     pub payment_token_mint: Pubkey,
     pub is_allowed: bool,
+    pub decimals: u8, // Payment mint decimals, used to value purchases at $1 per token
 }
 
 impl AllowedToken {
-    pub const LEN: usize = 32 + 32 + 1; // presale_state + mint + is_allowed
+    pub const LEN: usize = 32 + 32 + 1 + 1; // presale_state + mint + is_allowed + decimals
 }
 
 #[account]
 pub struct UserPurchase {
     pub buyer: Pubkey,
     pub total_purchased: u64,
+    pub sol_paid: u64, // Total lamports paid (for refunds)
+    pub payment_amount_paid: u64, // Total payment tokens paid (for refunds)
+    pub payment_mint_used: Pubkey, // Payment token mint used (default if SOL only)
+    pub refunded: bool, // Set once a refund has been claimed
+    pub purchase_count: u32, // Number of PurchaseRecord entries created for this buyer
+    pub round_number: u64, // Presale round the totals above belong to
+    pub owed: u64, // Claim-mode tokens bought but not yet delivered by claim_tokens
 }
 
 impl UserPurchase {
-    pub const LEN: usize = 32 + 8; // buyer + total_purchased
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 32 + 1 + 4 + 8 + 8; // buyer + total_purchased + sol_paid + payment_amount_paid + payment_mint_used + refunded + purchase_count + round_number + owed
+
+    /// Clears the per-round totals if they belong to an earlier round. The buyer is
+    /// reset too, so the purchase counts toward the new round's unique buyers.
+    /// `purchase_count` is kept since it indexes the buyer's PurchaseRecord PDAs,
+    /// and `owed` since unclaimed tokens from the earlier round are still due.
+    pub fn start_round(&mut self, round_number: u64) {
+        if self.round_number == round_number {
+            return;
+        }
+        self.buyer = Pubkey::default();
+        self.total_purchased = 0;
+        self.sol_paid = 0;
+        self.payment_amount_paid = 0;
+        self.payment_mint_used = Pubkey::default();
+        self.refunded = false;
+        self.round_number = round_number;
+    }
+}
+
+#[account]
+pub struct RaisedTracker {
+    pub presale_state: Pubkey,
+    pub payment_mint: Pubkey, // SOL_PAYMENT_SENTINEL for SOL purchases
+    pub total_raised: u64, // Payment token base units (lamports for SOL)
+    pub total_raised_usd_micro: u64,
+    pub purchase_count: u64,
+}
+
+impl RaisedTracker {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8; // presale_state + payment_mint + total_raised + total_raised_usd_micro + purchase_count
+
+    /// Adds one purchase to the running totals, initializing the tracker on first use
+    pub fn record(
+        &mut self,
+        presale_state: Pubkey,
+        payment_mint: Pubkey,
+        amount: u64,
+        usd_micro: u64,
+    ) -> Result<()> {
+        if self.presale_state == Pubkey::default() {
+            self.presale_state = presale_state;
+            self.payment_mint = payment_mint;
+        }
+        self.total_raised = self.total_raised.checked_add(amount).ok_or(PresaleError::Overflow)?;
+        self.total_raised_usd_micro = self
+            .total_raised_usd_micro
+            .checked_add(usd_micro)
+            .ok_or(PresaleError::Overflow)?;
+        self.purchase_count = self.purchase_count.checked_add(1).ok_or(PresaleError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PurchaseRecord {
+    pub buyer: Pubkey,
+    pub purchase_index: u32,
+    pub amount_tokens: u64,
+    pub amount_paid: u64, // Lamports or payment token base units
+    pub payment_is_sol: bool,
+    pub payment_mint: Pubkey, // Default pubkey for SOL purchases
+    pub timestamp: i64,
+}
+
+impl PurchaseRecord {
+    pub const LEN: usize = 32 + 4 + 8 + 8 + 1 + 32 + 8; // buyer + purchase_index + amount_tokens + amount_paid + payment_is_sol + payment_mint + timestamp
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceTier {
+    pub sold_threshold: u64, // Tier applies once total_tokens_sold reaches this amount
+    pub price_usd_micro: u64,
+}
+
+impl PriceTier {
+    pub const LEN: usize = 8 + 8; // sold_threshold + price_usd_micro
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -2193,6 +6797,714 @@ pub enum PresaleStatus {
     Active,
     Paused,
     Stopped,
+    Failed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleType {
+    Chainlink,
+    Pyth,
+}
+
+/// Shared body of `buy` and `buy_with_allocation`
+///
+/// `max_allocation` is the verified off-chain allocation, if any; it replaces the
+/// whitelist check and caps the buyer's total purchased tokens.
+#[allow(clippy::too_many_arguments)]
+fn process_buy(
+    ctx: Context<Buy>,
+    amount: u64,
+    round_id: Option<u8>,
+    referrer: Option<Pubkey>,
+    merkle_proof: &[[u8; 32]],
+    max_allocation: Option<u64>,
+    allow_partial: bool,
+    purchase_code: Option<&[u8]>,
+) -> Result<u64> {
+    let presale_state = &ctx.accounts.presale_state;
+    
+    // Check if presale is active
+    require!(
+        presale_state.status == PresaleStatus::Active,
+        PresaleError::PresaleNotActive
+    );
+
+    // Reject purchases outside the sale window, even while Active
+    let now = Clock::get()?.unix_timestamp;
+    require!(presale_state.has_started(now), PresaleError::PresaleNotStarted);
+    require!(!presale_state.has_ended(now), PresaleError::PresaleEnded);
+
+    // Check token program emergency pause
+    // Deserialize token state manually to check emergency_paused
+    let token_state_data = ctx.accounts.token_state.try_borrow_data()?;
+    if token_state_data.len() > TOKEN_STATE_EMERGENCY_PAUSED_OFFSET {
+        let emergency_paused = token_state_data[TOKEN_STATE_EMERGENCY_PAUSED_OFFSET] != 0;
+        require!(
+            !emergency_paused,
+            PresaleError::TokenEmergencyPaused
+        );
+    }
+
+    // Check if buyer is blacklisted
+    check_buyer_blacklist(presale_state, ctx.accounts.buyer.key(), &ctx.accounts.buyer_blacklist)?;
+
+    // Whitelist phase: only approved buyers may purchase
+    // A signed allocation already approves the buyer
+    if max_allocation.is_none() {
+        presale_state.check_buyer_whitelist(
+            ctx.accounts.buyer_whitelist.as_deref(),
+            ctx.accounts.buyer.key(),
+            merkle_proof,
+        )?;
+    }
+    
+    // Check if payment token is allowed
+    let allowed_token = &ctx.accounts.allowed_token;
+    require!(
+        allowed_token.is_allowed,
+        PresaleError::PaymentTokenNotAllowed
+    );
+
+    // Purchase totals are tracked per round; start a fresh tally on the buyer's first purchase of this round
+    ctx.accounts.user_purchase.start_round(presale_state.round_number);
+
+    // Refunds are tracked per payment method, so a buyer must stick to one
+    let user_purchase = &ctx.accounts.user_purchase;
+    require!(
+        user_purchase.sol_paid == 0
+            && (user_purchase.payment_mint_used == Pubkey::default()
+                || user_purchase.payment_mint_used == ctx.accounts.payment_token_mint.key()),
+        PresaleError::PaymentMethodMismatch
+    );
+
+    // Validate token account mints match (manual validation)
+    let buyer_payment_data = ctx.accounts.buyer_payment_token_account.try_borrow_data()?;
+    require!(buyer_payment_data.len() >= 32, PresaleError::PaymentTokenNotAllowed);
+    let buyer_payment_mint = Pubkey::try_from_slice(&buyer_payment_data[0..32])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    require!(
+        buyer_payment_mint == ctx.accounts.payment_token_mint.key(),
+        PresaleError::PaymentTokenNotAllowed
+    );
+    
+    let buyer_token_data = ctx.accounts.buyer_token_account.try_borrow_data()?;
+    require!(buyer_token_data.len() >= 32, PresaleError::PaymentTokenNotAllowed);
+    let buyer_token_mint = Pubkey::try_from_slice(&buyer_token_data[0..32])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    require!(
+        buyer_token_mint == presale_state.presale_token_mint,
+        PresaleError::PaymentTokenNotAllowed
+    );
+
+    // While a round is active, purchases must price against it
+    require!(
+        presale_state.active_round_id.is_none() || round_id == presale_state.active_round_id,
+        PresaleError::InvalidRound
+    );
+
+    // A purchase code's terms apply only when its preimage was supplied
+    let code_terms = match purchase_code {
+        Some(code) => {
+            let account = ctx.accounts.purchase_code.as_deref()
+                .ok_or(PresaleError::InvalidPurchaseCode)?;
+            account.check_redeemable(code, now)?;
+            Some(account)
+        }
+        None => None,
+    };
+
+    // Quote a payment amount: tokens, effective price, USD value and referral bonus
+    let quote = |amount: u64| -> Result<(u64, u64, u64, u64)> {
+        // Payment tokens are stablecoins valued at $1, so no oracle is involved
+        let purchase_usd_micro = PresaleState::stable_to_usd_micro(amount, allowed_token.decimals)?;
+
+        // Effective token price: the selected round's flat price, else the presale price tiers
+        let (tokens_to_receive, effective_price_usd_micro) =
+            match (round_id, ctx.accounts.presale_round.as_ref()) {
+                (Some(_), Some(round)) => {
+                    require!(
+                        round.token_price_usd_micro > 0,
+                        PresaleError::InvalidAmount
+                    );
+                    (
+                        PresaleState::usd_micro_to_tokens(purchase_usd_micro, round.token_price_usd_micro)?,
+                        round.token_price_usd_micro,
+                    )
+                }
+                _ => presale_state.quote_tokens(purchase_usd_micro)?,
+            };
+        let (tokens_to_receive, effective_price_usd_micro) = match code_terms {
+            Some(code) => code.apply(tokens_to_receive, effective_price_usd_micro)?,
+            None => (tokens_to_receive, effective_price_usd_micro),
+        };
+        require!(tokens_to_receive > 0, PresaleError::InvalidAmount);
+
+        // Referral bonus is paid on top of the purchased tokens
+        let referral_bonus = validate_referral(
+            presale_state,
+            referrer,
+            ctx.accounts.buyer.key(),
+            ctx.accounts.referral_account.as_deref().map(|account| &**account),
+            ctx.accounts.referrer_blacklist.as_ref(),
+            tokens_to_receive,
+        )?;
+        Ok((tokens_to_receive, effective_price_usd_micro, purchase_usd_micro, referral_bonus))
+    };
+
+    // A partial fill charges only as much as fits under the presale cap
+    let amount = if allow_partial {
+        presale_state.fit_to_remaining_cap(amount, |amount| {
+            let (tokens, _, usd_micro, bonus) = quote(amount)?;
+            presale_state.presale_cap_usage(tokens, bonus, usd_micro)
+        })?
+    } else {
+        amount
+    };
+    let (tokens_to_receive, effective_price_usd_micro, purchase_usd_micro, referral_bonus) =
+        quote(amount)?;
+
+    // Enforce round window and cap if a round is selected
+    if let Some(id) = round_id {
+        let round = ctx.accounts.presale_round.as_ref().ok_or(PresaleError::InvalidRound)?;
+        round.check_purchase(presale_state, id, tokens_to_receive, Clock::get()?.unix_timestamp)?;
+    }
+
+    // Enforce per-purchase minimum and maximum
+    presale_state.check_purchase_amount(tokens_to_receive)?;
+
+    let tokens_delivered = tokens_to_receive
+        .checked_add(referral_bonus)
+        .ok_or(PresaleError::Overflow)?;
+
+    // Check per-user limit; the buyer's referral bonus counts towards it
+    if presale_state.max_per_user > 0 {
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        let new_user_total = user_purchase.total_purchased
+            .checked_add(tokens_delivered)
+            .ok_or(PresaleError::Overflow)?;
+        require!(
+            new_user_total <= presale_state.max_per_user,
+            PresaleError::PerUserLimitExceeded
+        );
+    }
+
+    // Signed off-chain allocation caps the buyer's total
+    if let Some(max_allocation) = max_allocation {
+        let new_user_total = ctx.accounts.user_purchase.total_purchased
+            .checked_add(tokens_to_receive)
+            .ok_or(PresaleError::Overflow)?;
+        require!(
+            new_user_total <= max_allocation,
+            PresaleError::AllocationExceeded
+        );
+    }
+
+    // Check presale cap, counting the buyer's and referrer's bonuses as sold tokens
+    presale_state.check_presale_cap(tokens_to_receive, referral_bonus, purchase_usd_micro)?;
+
+    // Validate payment vault (manual validation)
+    let payment_vault_data = ctx.accounts.presale_payment_vault.try_borrow_data()?;
+    require!(payment_vault_data.len() >= 64, PresaleError::PaymentTokenNotAllowed);
+    let payment_vault_mint = Pubkey::try_from_slice(&payment_vault_data[0..32])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    let payment_vault_owner = Pubkey::try_from_slice(&payment_vault_data[32..64])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    require!(
+        payment_vault_mint == ctx.accounts.payment_token_mint.key(),
+        PresaleError::PaymentTokenNotAllowed
+    );
+    require!(
+        payment_vault_owner == ctx.accounts.presale_payment_vault_pda.key(),
+        PresaleError::PaymentTokenNotAllowed
+    );
+
+    // Transfer payment tokens from buyer to presale vault
+    let decimals = mint_decimals(&ctx.accounts.payment_token_mint)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.buyer_payment_token_account.to_account_info(),
+        mint: ctx.accounts.payment_token_mint.to_account_info(),
+        to: ctx.accounts.presale_payment_vault.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
+
+    // Validate presale token vault (manual validation)
+    let presale_token_vault_data = ctx.accounts.presale_token_vault.try_borrow_data()?;
+    require!(presale_token_vault_data.len() >= 64, PresaleError::PaymentTokenNotAllowed);
+    let presale_token_vault_mint = Pubkey::try_from_slice(&presale_token_vault_data[0..32])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    let presale_token_vault_owner = Pubkey::try_from_slice(&presale_token_vault_data[32..64])
+        .map_err(|_| PresaleError::PaymentTokenNotAllowed)?;
+    require!(
+        presale_token_vault_mint == presale_state.presale_token_mint,
+        PresaleError::PaymentTokenNotAllowed
+    );
+    require!(
+        presale_token_vault_owner == ctx.accounts.presale_token_vault_pda.key(),
+        PresaleError::PaymentTokenNotAllowed
+    );
+
+    // Transfer presale tokens from presale vault to buyer
+    // (with vesting enabled they stay in the vault until claim_vested_tokens,
+    // in claim mode until claim_tokens)
+    if !presale_state.vesting_enabled && !presale_state.claim_mode {
+        let seeds = &[
+            b"presale_token_vault_pda",
+            presale_state.presale_token_mint.as_ref(),
+            &[ctx.bumps.presale_token_vault_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint_decimals(&ctx.accounts.presale_token_mint)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.presale_token_vault.to_account_info(),
+            mint: ctx.accounts.presale_token_mint.to_account_info(),
+            to: ctx.accounts.buyer_token_account.to_account_info(),
+            authority: ctx.accounts.presale_token_vault_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, tokens_delivered, decimals)?;
+    }
+
+    // Update state
+    let presale_state = &mut ctx.accounts.presale_state;
+    presale_state.total_tokens_sold = presale_state
+        .total_tokens_sold
+        .checked_add(tokens_delivered)
+        .ok_or(PresaleError::Overflow)?;
+    presale_state.total_raised = presale_state
+        .total_raised
+        .checked_add(amount)
+        .ok_or(PresaleError::Overflow)?;
+    if presale_state.vesting_enabled {
+        let vesting_account = ctx.accounts.vesting_account.as_mut()
+            .ok_or(PresaleError::VestingAccountRequired)?;
+        let created = vesting_account.record_purchase(
+            ctx.accounts.buyer.key(),
+            tokens_delivered,
+            presale_state,
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.vesting_account.ok_or(PresaleError::VestingAccountRequired)?,
+        )?;
+        if created {
+            emit!(VestingScheduleCreated {
+                buyer: vesting_account.buyer,
+                total_amount: vesting_account.total_amount,
+                start_time: vesting_account.start_time,
+                cliff_time: vesting_account.cliff_time,
+                end_time: vesting_account.end_time,
+            });
+        }
+    } else if !presale_state.claim_mode {
+        // Tokens are delivered at purchase time, so they are claimed immediately
+        presale_state.total_claimed = presale_state
+            .total_claimed
+            .checked_add(tokens_delivered)
+            .ok_or(PresaleError::Overflow)?;
+    }
+    presale_state.total_raised_usd_micro = presale_state
+        .total_raised_usd_micro
+        .checked_add(purchase_usd_micro)
+        .ok_or(PresaleError::Overflow)?;
+
+    // Update user purchase tracker
+    let user_purchase = &mut ctx.accounts.user_purchase;
+    if user_purchase.buyer == Pubkey::default() {
+        user_purchase.buyer = ctx.accounts.buyer.key();
+        user_purchase.total_purchased = 0;
+        presale_state.unique_buyers = presale_state
+            .unique_buyers
+            .checked_add(1)
+            .ok_or(PresaleError::Overflow)?;
+    }
+    user_purchase.total_purchased = user_purchase
+        .total_purchased
+        .checked_add(tokens_delivered)
+        .ok_or(PresaleError::Overflow)?;
+    if presale_state.claim_mode && !presale_state.vesting_enabled {
+        user_purchase.owed = user_purchase
+            .owed
+            .checked_add(tokens_delivered)
+            .ok_or(PresaleError::Overflow)?;
+    }
+    user_purchase.payment_amount_paid = user_purchase
+        .payment_amount_paid
+        .checked_add(amount)
+        .ok_or(PresaleError::Overflow)?;
+    user_purchase.payment_mint_used = ctx.accounts.payment_token_mint.key();
+
+    // Append this purchase to the buyer's on-chain history
+    let purchase_index = user_purchase.purchase_count;
+    user_purchase.purchase_count = purchase_index
+        .checked_add(1)
+        .ok_or(PresaleError::Overflow)?;
+    let purchase_record = &mut ctx.accounts.purchase_record;
+    purchase_record.buyer = ctx.accounts.buyer.key();
+    purchase_record.purchase_index = purchase_index;
+    purchase_record.amount_tokens = tokens_delivered;
+    purchase_record.amount_paid = amount;
+    purchase_record.payment_is_sol = false;
+    purchase_record.payment_mint = ctx.accounts.payment_token_mint.key();
+    purchase_record.timestamp = Clock::get()?.unix_timestamp;
+
+    // Per-payment-token totals, in the payment token's own units
+    ctx.accounts.raised_tracker.record(
+        presale_state.key(),
+        ctx.accounts.payment_token_mint.key(),
+        amount,
+        purchase_usd_micro,
+    )?;
+
+    emit!(PurchaseRecorded {
+        buyer: ctx.accounts.buyer.key(),
+        index: purchase_index,
+        tokens: tokens_delivered,
+        paid: amount,
+        is_sol: false,
+        price_usd_micro: effective_price_usd_micro,
+        unique_buyers: presale_state.unique_buyers,
+    });
+
+    // Credit the referrer's matching bonus
+    if referral_bonus > 0 {
+        let referral_account = ctx.accounts.referral_account.as_mut()
+            .ok_or(PresaleError::InvalidReferrer)?;
+        referral_account.total_referrals = referral_account
+            .total_referrals
+            .checked_add(1)
+            .ok_or(PresaleError::Overflow)?;
+        referral_account.bonus_tokens_earned = referral_account
+            .bonus_tokens_earned
+            .checked_add(referral_bonus)
+            .ok_or(PresaleError::Overflow)?;
+        presale_state.referral_bonus_outstanding = presale_state
+            .referral_bonus_outstanding
+            .checked_add(referral_bonus)
+            .ok_or(PresaleError::Overflow)?;
+        presale_state.referral_bonus_total = presale_state
+            .referral_bonus_total
+            .checked_add(referral_bonus)
+            .ok_or(PresaleError::Overflow)?;
+
+        emit!(ReferralRecorded {
+            referrer: referral_account.referrer,
+            buyer: ctx.accounts.buyer.key(),
+            bonus: referral_bonus,
+        });
+    }
+
+    // Update round tracker, moving on to the next round once this one sells out
+    if round_id.is_some() {
+        if let Some(round) = ctx.accounts.presale_round.as_mut() {
+            round.tokens_sold = round
+                .tokens_sold
+                .checked_add(tokens_to_receive)
+                .ok_or(PresaleError::Overflow)?;
+            round.advance_if_sold_out(
+                presale_state,
+                ctx.accounts.next_presale_round.as_ref(),
+            )?;
+        }
+    }
+
+    // Count the redemption against the code's uses
+    if purchase_code.is_some() {
+        if let Some(code) = ctx.accounts.purchase_code.as_mut() {
+            code.uses = code.uses.checked_add(1).ok_or(PresaleError::Overflow)?;
+            emit!(PurchaseCodeRedeemed {
+                code_hash: code.code_hash,
+                buyer: ctx.accounts.buyer.key(),
+                uses: code.uses,
+            });
+        }
+    }
+
+    msg!(
+        "Buy successful: {} tokens for {} payment tokens",
+        tokens_to_receive,
+        amount
+    );
+
+    Ok(tokens_to_receive)
+}
+
+/// Checks that the instruction before the current one is an Ed25519 program
+/// verification of exactly `signature` by `signer` over `message`
+///
+/// The Ed25519 program has already verified the signature if the transaction got
+/// here; this only binds its inputs to the expected ones. All offsets must point
+/// into the Ed25519 instruction itself.
+fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    use anchor_lang::solana_program::sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked,
+    };
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, PresaleError::InvalidAllocationSignature);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ED25519_PROGRAM_ID,
+        PresaleError::InvalidAllocationSignature
+    );
+
+    // Header: num_signatures (u8) + padding (u8), then one 14-byte offsets struct
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, PresaleError::InvalidAllocationSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        PresaleError::InvalidAllocationSignature
+    );
+
+    let field = |offset: usize, len: usize| data.get(offset..offset + len);
+    require!(
+        field(public_key_offset, 32) == Some(signer.as_ref())
+            && field(signature_offset, 64) == Some(&signature[..])
+            && message_size == message.len()
+            && field(message_offset, message_size) == Some(message),
+        PresaleError::InvalidAllocationSignature
+    );
+    Ok(())
+}
+
+/// Verifies that `buyer` is a leaf of the Merkle tree with `root`
+///
+/// Leaves are `sha256(buyer)`; each parent is `sha256` of its two children in
+/// ascending byte order, so proofs need no left/right flags.
+fn verify_merkle_proof(root: [u8; 32], buyer: Pubkey, proof: &[[u8; 32]]) -> bool {
+    if proof.len() > MAX_MERKLE_PROOF_LEN {
+        return false;
+    }
+    let leaf = anchor_lang::solana_program::hash::hash(buyer.as_ref()).to_bytes();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (first, second) = if node <= *sibling { (&node, sibling) } else { (sibling, &node) };
+        anchor_lang::solana_program::hash::hashv(&[first, second]).to_bytes()
+    });
+    computed == root
+}
+
+/// Reads `decimals` from a legacy SPL Token or Token-2022 mint (same base layout)
+/// Reads mint, owner and amount from a token account (SPL Token or Token-2022)
+fn read_token_account(account: &AccountInfo) -> Result<(Pubkey, Pubkey, u64)> {
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= TOKEN_ACCOUNT_BASE_LEN, PresaleError::InvalidTreasuryAccount);
+    let mint = Pubkey::try_from_slice(&data[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET])
+        .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+    let owner = Pubkey::try_from_slice(&data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET])
+        .map_err(|_| PresaleError::InvalidTreasuryAccount)?;
+    let amount = u64::from_le_bytes(
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .try_into()
+            .map_err(|_| PresaleError::InvalidTreasuryAccount)?,
+    );
+    Ok((mint, owner, amount))
+}
+
+fn mint_decimals(mint: &AccountInfo) -> Result<u8> {
+    let data = mint.try_borrow_data()?;
+    require!(data.len() > MINT_DECIMALS_OFFSET, PresaleError::InvalidAccount);
+    Ok(data[MINT_DECIMALS_OFFSET])
+}
+
+/// Checks that `mint` is an initialized SPL Token or Token-2022 mint and returns its decimals
+fn validate_payment_mint(mint: &AccountInfo) -> Result<u8> {
+    require!(
+        *mint.owner == anchor_spl::token::ID || *mint.owner == anchor_spl::token_2022::ID,
+        PresaleError::InvalidPaymentMint
+    );
+    let data = mint.try_borrow_data()?;
+    require!(data.len() >= MINT_BASE_LEN, PresaleError::InvalidPaymentMint);
+    require!(data[MINT_IS_INITIALIZED_OFFSET] == 1, PresaleError::InvalidPaymentMint);
+    Ok(data[MINT_DECIMALS_OFFSET])
+}
+
+/// Reads the aggregate price from a Pyth v2 price account
+///
+/// Returns the price scaled to 8 decimals (matching Chainlink) and its publish
+/// timestamp. The aggregate must be trading and its confidence interval within
+/// `PYTH_MAX_CONFIDENCE_BPS` of the price.
+fn read_pyth_price(feed: &AccountInfo) -> Result<(i128, i64)> {
+    let data = feed.try_borrow_data()?;
+    require!(
+        data.len() >= PYTH_AGG_STATUS_OFFSET + 4,
+        PresaleError::InvalidPrice
+    );
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    require!(
+        read_u32(0) == PYTH_MAGIC
+            && read_u32(4) == PYTH_VERSION_2
+            && read_u32(8) == PYTH_ACCOUNT_TYPE_PRICE,
+        PresaleError::InvalidPrice
+    );
+    require!(
+        read_u32(PYTH_AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        PresaleError::InvalidPrice
+    );
+
+    let expo = read_u32(PYTH_EXPO_OFFSET) as i32;
+    let timestamp = read_u64(PYTH_TIMESTAMP_OFFSET) as i64;
+    let price = read_u64(PYTH_AGG_PRICE_OFFSET) as i64;
+    let conf = read_u64(PYTH_AGG_CONF_OFFSET);
+    require!(price > 0, PresaleError::InvalidPrice);
+
+    // Reject prices whose confidence interval is too wide to trust
+    require!(
+        (conf as u128) * 10_000 <= (price as u128) * PYTH_MAX_CONFIDENCE_BPS as u128,
+        PresaleError::InvalidPrice
+    );
+
+    // Rescale from 10^expo to Chainlink's 8 decimals
+    let shift = expo + CHAINLINK_DECIMALS as i32;
+    require!(shift.abs() <= 18, PresaleError::InvalidPrice);
+    let scale = 10i128.pow(shift.unsigned_abs());
+    let price_8_decimals = if shift >= 0 {
+        (price as i128).checked_mul(scale).ok_or(PresaleError::Overflow)?
+    } else {
+        (price as i128) / scale
+    };
+
+    Ok((price_8_decimals, timestamp))
+}
+
+/// Reads the SOL/USD price (8 decimals) and its publish time from a Chainlink feed
+///
+/// The feed must be owned by the Chainlink OCR2 program and report 8 decimals.
+/// The feed address, price sign and staleness are checked by the caller.
+fn read_chainlink_price(feed: &AccountInfo) -> Result<(i128, i64)> {
+    require!(feed.owner == &CHAINLINK_PROGRAM_ID, PresaleError::InvalidPrice);
+    let feed_data = read_feed_v2(feed.try_borrow_data()?, feed.owner.to_bytes())
+        .map_err(|_| PresaleError::InvalidPrice)?;
+    require!(
+        feed_data.decimals() == CHAINLINK_DECIMALS,
+        PresaleError::InvalidPrice
+    );
+    let round = feed_data
+        .latest_round_data()
+        .ok_or(PresaleError::InvalidPrice)?;
+    // round.timestamp is u32, convert to i64 to match unix_timestamp type
+    Ok((round.answer, i64::from(round.timestamp)))
+}
+
+/// Whether a (price, publish time) pair is positive and within the staleness limit
+fn is_usable_price((price, timestamp): (i128, i64), now: i64, threshold: i64) -> bool {
+    price > 0 && now.saturating_sub(timestamp) <= threshold + PRICE_FEED_STALENESS_GRACE_SECONDS
+}
+
+/// Rejects a blacklisted buyer
+///
+/// `buyer_blacklist` must be the buyer's blacklist PDA in the token program. An
+/// uninitialized PDA (no data) means the buyer was never blacklisted.
+fn check_buyer_blacklist(
+    presale_state: &PresaleState,
+    buyer: Pubkey,
+    buyer_blacklist: &UncheckedAccount,
+) -> Result<()> {
+    let (expected_blacklist, _) = Pubkey::find_program_address(
+        &[b"blacklist", buyer.as_ref()],
+        &presale_state.token_program,
+    );
+    require!(
+        buyer_blacklist.key() == expected_blacklist,
+        PresaleError::InvalidAccount
+    );
+    let blacklist_data = buyer_blacklist.try_borrow_data()?;
+    if blacklist_data.len() >= 41 {
+        // Account discriminator (8) + account Pubkey (32) + is_blacklisted bool (1) = offset 40
+        require!(blacklist_data[40] == 0, PresaleError::BuyerBlacklisted);
+    }
+    Ok(())
+}
+
+/// Checks that `allowed_token` is the AllowedToken PDA for `payment_mint`
+///
+/// The PDA is kept when a token is disallowed, so this accepts any mint that was
+/// ever allowed for the presale and rejects mints that never were.
+fn check_known_payment_token(
+    presale_state: &Pubkey,
+    payment_mint: &Pubkey,
+    allowed_token: &UncheckedAccount,
+) -> Result<()> {
+    let (expected_allowed_token, _) = Pubkey::find_program_address(
+        &[b"allowed_token", presale_state.as_ref(), payment_mint.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        allowed_token.key() == expected_allowed_token && allowed_token.owner == &crate::ID,
+        PresaleError::UnknownPaymentToken
+    );
+    let allowed_token_data = allowed_token.try_borrow_data()?;
+    let allowed = AllowedToken::try_deserialize(&mut &allowed_token_data[..])
+        .map_err(|_| PresaleError::UnknownPaymentToken)?;
+    require!(
+        allowed.payment_token_mint == *payment_mint,
+        PresaleError::UnknownPaymentToken
+    );
+    Ok(())
+}
+
+/// Validates an optional referral and returns the bonus owed to buyer and referrer
+///
+/// Returns 0 when no referrer is given. The referral account must belong to
+/// `referrer`, and `referrer_blacklist` must be the referrer's blacklist PDA in the
+/// token program.
+fn validate_referral(
+    presale_state: &PresaleState,
+    referrer: Option<Pubkey>,
+    buyer: Pubkey,
+    referral_account: Option<&ReferralAccount>,
+    referrer_blacklist: Option<&UncheckedAccount>,
+    purchased_tokens: u64,
+) -> Result<u64> {
+    let referrer = match referrer {
+        Some(referrer) => referrer,
+        None => return Ok(0),
+    };
+
+    require!(presale_state.referral_enabled, PresaleError::ReferralDisabled);
+    // Self-referral would let a buyer collect both bonuses
+    require!(referrer != buyer, PresaleError::SelfReferral);
+
+    let referral_account = referral_account.ok_or(PresaleError::InvalidReferrer)?;
+    require!(
+        referral_account.referrer == referrer,
+        PresaleError::InvalidReferrer
+    );
+
+    let referrer_blacklist = referrer_blacklist.ok_or(PresaleError::InvalidReferrer)?;
+    let (expected_blacklist, _) = Pubkey::find_program_address(
+        &[b"blacklist", referrer.as_ref()],
+        &presale_state.token_program,
+    );
+    require!(
+        referrer_blacklist.key() == expected_blacklist,
+        PresaleError::InvalidReferrer
+    );
+    let blacklist_data = referrer_blacklist.try_borrow_data()?;
+    if blacklist_data.len() >= 41 {
+        // Account discriminator (8) + account Pubkey (32) + is_blacklisted bool (1) = offset 40
+        require!(blacklist_data[40] == 0, PresaleError::ReferrerBlacklisted);
+    }
+
+    let bonus = (purchased_tokens as u128)
+        .checked_mul(presale_state.referral_bonus_percent as u128)
+        .ok_or(PresaleError::Overflow)?
+        .checked_div(100)
+        .ok_or(PresaleError::Overflow)?;
+    Ok(bonus as u64)
 }
 
 // Error Codes
@@ -2233,4 +7545,106 @@ pub enum PresaleError {
     InvalidPrice,
     #[msg("Chainlink price feed is stale (too old)")]
     StalePrice,
+    #[msg("Refund already claimed")]
+    AlreadyRefunded,
+    #[msg("Nothing to refund for this payment method")]
+    NothingToRefund,
+    #[msg("Buyer has already purchased with a different payment method")]
+    PaymentMethodMismatch,
+    #[msg("Invalid presale round")]
+    InvalidRound,
+    #[msg("Presale round is not active")]
+    RoundNotActive,
+    #[msg("Another presale round is already active")]
+    RoundAlreadyActive,
+    #[msg("Presale round cap exceeded")]
+    RoundCapExceeded,
+    #[msg("Purchase is below the minimum purchase amount")]
+    BelowMinimumPurchase,
+    #[msg("Purchase exceeds the maximum per transaction")]
+    MaxPerTransactionExceeded,
+    #[msg("Presale has ended")]
+    PresaleEnded,
+    #[msg("Presale end time has not passed")]
+    PresaleNotEnded,
+    #[msg("Invalid timestamp")]
+    InvalidTimestamp,
+    #[msg("Presale token vault cannot cover outstanding allocations")]
+    VaultInsolvent,
+    #[msg("Buyer is not whitelisted for this presale phase")]
+    BuyerNotWhitelisted,
+    #[msg("Withdrawal would leave the SOL vault below the rent-exempt minimum")]
+    WouldBreakRentExemption,
+    #[msg("Referral program is not enabled")]
+    ReferralDisabled,
+    #[msg("Buyer cannot refer themselves")]
+    SelfReferral,
+    #[msg("Invalid referrer or referral account")]
+    InvalidReferrer,
+    #[msg("Referrer is blacklisted")]
+    ReferrerBlacklisted,
+    #[msg("No referral bonus to claim")]
+    NoReferralBonus,
+    #[msg("Vesting account is required while vesting is enabled")]
+    VestingAccountRequired,
+    #[msg("Invalid vesting cliff or duration")]
+    InvalidVestingConfig,
+    #[msg("Vesting cliff has not been reached")]
+    VestingCliffNotReached,
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+    #[msg("Tokens received below the minimum accepted (slippage)")]
+    SlippageExceeded,
+    #[msg("Token program does not match the presale's token program")]
+    InvalidTokenProgram,
+    #[msg("Too many payment tokens allowed")]
+    TooManyPaymentTokens,
+    #[msg("Presale has not started yet")]
+    PresaleNotStarted,
+    #[msg("Funds from the previous round must be withdrawn first")]
+    FundsNotWithdrawn,
+    #[msg("Payment token mint is not an initialized SPL mint")]
+    InvalidPaymentMint,
+    #[msg("Invalid batch size")]
+    InvalidBatchSize,
+    #[msg("Price tiers must have ascending thresholds and non-zero prices")]
+    InvalidPriceTiers,
+    #[msg("Merkle proof does not prove the buyer is whitelisted")]
+    InvalidMerkleProof,
+    #[msg("Allocation authority is not set")]
+    AllocationAuthorityNotSet,
+    #[msg("Allocation signature does not match the buyer and allocation")]
+    InvalidAllocationSignature,
+    #[msg("Purchase exceeds the buyer's signed allocation")]
+    AllocationExceeded,
+    #[msg("Withdrawal fee exceeds the maximum")]
+    WithdrawalFeeTooHigh,
+    #[msg("Fee collector account is missing or invalid")]
+    InvalidFeeCollector,
+    #[msg("Price staleness threshold is out of bounds")]
+    InvalidStalenessThreshold,
+    #[msg("No swap program is configured")]
+    SwapProgramNotSet,
+    #[msg("Swap program is not the configured swap venue")]
+    InvalidSwapProgram,
+    #[msg("Swap moved more than the withdrawn amount or changed vault authority")]
+    SwapValidationFailed,
+    #[msg("Owed tokens must be claimed first")]
+    TokensNotClaimed,
+    #[msg("No admin change has been proposed")]
+    NoPendingAdmin,
+    #[msg("Payment token mint was never allowed for this presale")]
+    UnknownPaymentToken,
+    #[msg("Purchase code does not match or has invalid terms")]
+    InvalidPurchaseCode,
+    #[msg("Purchase code has expired")]
+    PurchaseCodeExpired,
+    #[msg("Purchase code has no uses left")]
+    PurchaseCodeExhausted,
+    #[msg("SOL/USD price feed is not configured")]
+    OracleFeedNotSet,
+    #[msg("Price feed account is not the configured feed")]
+    InvalidOracleFeed,
+    #[msg("Next round account is required once the round sells out")]
+    NextRoundRequired,
 }
\ No newline at end of file
@@ -23,11 +23,17 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::spl_token::instruction::AuthorityType;
-use anchor_spl::token::{self, Burn, MintTo, SetAuthority, Token, Transfer, TokenAccount};
+use anchor_spl::token::{self, Burn, MintTo, SetAuthority, Token, Transfer, TransferChecked, TokenAccount};
 use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
 use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+use anchor_spl::token::spl_token::state::Mint as SplMint;
 
+#[cfg(not(feature = "second-instance"))]
 declare_id!("Bp6PD8dSwGgESvbAZ6mismyDuemZ1cKZ9FC8JmNXZ9uw");
+// Test-only build deployed at a second address, so governance can be tested
+// against more than one token program (see `yarn build:second-token-program`)
+#[cfg(feature = "second-instance")]
+declare_id!("GzGwQQaXd175fdpvjfLtLfXxPKPS7jRqukUTSwKmQDdG");
 
 #[error_code]
 pub enum TokenError {
@@ -49,6 +55,52 @@ pub enum TokenError {
     IncompatibleVersion,
     #[msg(Invalid Token Account)]
     InvalidTokenAccount,
+    #[msg("Transaction deadline exceeded")]
+    DeadlineExceeded,
+    #[msg("Supply cap can only be lowered, and never below current supply")]
+    InvalidMaxSupply,
+    #[msg("Removing the supply cap requires confirmation")]
+    MaxSupplyRemovalNotConfirmed,
+    #[msg("Recipient looks like a liquidity pool that is not registered")]
+    UnknownPool,
+    #[msg("Too many DEX programs registered")]
+    TooManyDexPrograms,
+    #[msg("Token state is already at the current version")]
+    AlreadyMigrated,
+    #[msg("List account does not match the expected PDA")]
+    InvalidListAccount,
+    #[msg("Address is protected and cannot be blacklisted")]
+    CannotBlacklistProtected,
+    #[msg("Too many protected addresses registered")]
+    TooManyProtectedAddresses,
+    #[msg("Emission schedule parameters are invalid")]
+    InvalidEmissionSchedule,
+    #[msg("No scheduled emission is due")]
+    NoEmissionDue,
+    #[msg("Transfer fee is above the maximum or has no destination")]
+    InvalidTransferFee,
+    #[msg("Fee destination must be a token account of the fee treasury")]
+    InvalidFeeDestination,
+    #[msg("Exemption expiry must be in the future and only set when granting")]
+    InvalidExemptionExpiry,
+    #[msg("Sell limit exemption has not expired")]
+    ExemptionNotExpired,
+    #[msg("Mint batch is empty, too large, has a zero amount, or its accounts don't match")]
+    InvalidMintBatch,
+    #[msg("Tokens can only be seized from a blacklisted owner")]
+    SourceNotBlacklisted,
+    #[msg("Transfer exceeds the circuit breaker threshold")]
+    CircuitBreakerTriggered,
+    #[msg("Circuit breaker threshold must be between 50 and 10000 bps")]
+    InvalidCircuitBreakerThreshold,
+    #[msg("Mint decimals do not match the expected decimals")]
+    DecimalsMismatch,
+    #[msg("Vesting amount, cliff or duration is invalid")]
+    InvalidVestingSchedule,
+    #[msg("No vested tokens to claim")]
+    NothingVested,
+    #[msg("Velocity tracker must be the writable velocity PDA")]
+    InvalidVelocityTracker,
 }
 
 #[event]
@@ -57,6 +109,52 @@ pub struct TokenMinted {
     pub recipient: Pubkey,
 }
 
+#[event]
+pub struct VestingScheduleCreated {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub start_time: i64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+#[event]
+pub struct VestingClaimed {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct GovernanceOpsDuringPauseChanged {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct WhitelistModeChanged {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct CircuitBreakerChanged {
+    pub enabled: bool,
+    pub threshold_bps: u16,
+}
+
+#[event]
+pub struct CircuitBreakerTriggered {
+    pub from: Pubkey,
+    pub amount: u64,
+    pub threshold: u64,
+}
+
+#[event]
+pub struct TokensSeized {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct TokenBurned {
     pub amount: u64,
@@ -90,6 +188,13 @@ pub struct RestrictedChanged {
 pub struct NoSellLimitChanged {
     pub account: Pubkey,
     pub has_exemption: bool,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct NoSellLimitExemptionExpired {
+    pub account: Pubkey,
+    pub expired_at: i64,
 }
 
 #[event]
@@ -109,6 +214,111 @@ pub struct MintAuthorityRevoked {
     pub mint: Pubkey,
 }
 
+#[event]
+pub struct EmissionScheduleSet {
+    pub rate_per_second: u64,
+    pub recipient: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct EmissionMinted {
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub minted_until: i64,
+}
+
+#[event]
+pub struct MaxSupplyChanged {
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+#[event]
+pub struct KnownPoolsModeChanged {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SellLimitsFrozenChanged {
+    pub frozen: bool,
+}
+
+#[event]
+pub struct SellTrackerModeChanged {
+    pub mode: SellTrackerMode,
+}
+
+#[event]
+pub struct ProtectedAddressChanged {
+    pub address: Pubkey,
+    pub is_protected: bool,
+}
+
+#[event]
+pub struct TransferFeeChanged {
+    pub fee_bps: u16,
+    pub burn: bool,
+    pub treasury_address: Pubkey,
+    pub whitelist_exempt: bool,
+}
+
+#[event]
+pub struct TransferFeeCharged {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct TokensTransferred {
+    pub from_owner: Pubkey,
+    pub to_owner: Pubkey,
+    pub amount: u64,
+    pub is_pool_sale: bool,
+    /// Sender's rolling 24h sold total after this transfer (0 unless `is_pool_sale`)
+    pub sell_limit_consumed: u64,
+}
+
+#[event]
+pub struct FeeCollected {
+    pub from: Pubkey,
+    pub fee_collector: Pubkey,
+    pub fee_destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DexProgramChanged {
+    pub program_id: Pubkey,
+    pub is_dex: bool,
+}
+
+#[event]
+pub struct VelocityUpdated {
+    pub period_volume: u128,
+    pub period_transfers: u64,
+    pub period_start: i64,
+}
+
+#[event]
+pub struct VelocityTrackerReset {
+    pub by: Pubkey,
+    pub period_start: i64,
+}
+
+#[event]
+pub struct TokenStateMigrated {
+    pub authority: Pubkey,
+    pub from_version: u16,
+    pub to_version: u16,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub migrated_fields: Vec<String>,
+}
+
 #[program]
 pub mod spl_project {
     use super::*;
@@ -140,6 +350,7 @@ pub mod spl_project {
         state.authority = ctx.accounts.authority.key();
         state.bump = ctx.bumps.state;
         state.emergency_paused = false;
+        state.governance_ops_during_pause = false; // Pause blocks governance mint/burn by default
         state.sell_limit_percent = 10; // 10% sell limit
         state.sell_limit_period = 86400; // 24 hours in seconds
         state.bridge_address = Pubkey::default(); // Will be set by governance later
@@ -149,6 +360,17 @@ pub mod spl_project {
         state.max_supply = None; // No supply cap by default
         state.current_supply = 0; // Track current supply
         state.whitelist_mode = false; // Whitelist mode disabled by default
+        state.restrict_to_known_pools = false; // Unregistered pools allowed by default
+        state.dex_programs = Vec::new();
+        state.sell_limits_frozen = false; // Sell limits enforced by default
+        state.protected_addresses = Vec::new();
+        state.sell_tracker_mode = SellTrackerMode::PerWallet; // One sell limit per signing wallet
+        state.transfer_fee_bps = 0; // No transfer fee by default
+        state.transfer_fee_burn = false;
+        state.treasury_address = Pubkey::default();
+        state.fee_whitelist_exempt = false;
+        state.circuit_breaker_enabled = false; // No single-transfer limit by default
+        state.circuit_breaker_threshold_bps = 0;
         state.version = TokenState::CURRENT_VERSION;
         state.min_compatible_version = TokenState::MIN_COMPATIBLE_VERSION;
 
@@ -164,6 +386,118 @@ pub mod spl_project {
         Ok(())
     }
 
+    /// Migrates the token state to the current layout and version
+    ///
+    /// Grows the state account to the current size (zero-filling new fields) and
+    /// bumps `version` / `min_compatible_version` in a single instruction, so the
+    /// state is never left half-migrated.
+    ///
+    /// # Parameters
+    /// - `ctx`: MigrateTokenState context containing the state PDA and authority signer
+    ///
+    /// # Errors
+    /// - `TokenError::InvalidTokenAccount` if the state account is not the state PDA
+    /// - `TokenError::Unauthorized` if signer is not the state authority
+    /// - `TokenError::AlreadyMigrated` if the state is already current
+    ///
+    /// # Events
+    /// - Emits `TokenStateMigrated` with the versions, sizes, changed fields and authority
+    pub fn migrate_token_state(ctx: Context<MigrateTokenState>) -> Result<()> {
+        // Verify PDA manually (without deserialization)
+        let (expected_pda, _expected_bump) =
+            Pubkey::find_program_address(&[b"state"], ctx.program_id);
+        require!(
+            ctx.accounts.state.key() == expected_pda
+                && ctx.accounts.state.owner == ctx.program_id,
+            TokenError::InvalidTokenAccount
+        );
+
+        // Authority is at offset 8 (after the discriminator)
+        let old_size = ctx.accounts.state.data_len();
+        {
+            let account_data = ctx.accounts.state.try_borrow_data()?;
+            require!(account_data.len() >= 40, TokenError::InvalidTokenAccount);
+            let account_authority = Pubkey::try_from_slice(&account_data[8..40])
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+            require!(
+                account_authority == ctx.accounts.authority.key(),
+                TokenError::Unauthorized
+            );
+        }
+
+        let new_size = 8 + TokenState::LEN;
+        let mut migrated_fields: Vec<String> = Vec::new();
+
+        if old_size < new_size {
+            let rent = Rent::get()?;
+            let new_minimum_balance = rent.minimum_balance(new_size);
+            let current_lamports = ctx.accounts.state.lamports();
+
+            if current_lamports < new_minimum_balance {
+                let additional_lamports = new_minimum_balance
+                    .checked_sub(current_lamports)
+                    .ok_or(TokenError::MathOverflow)?;
+
+                anchor_lang::solana_program::program::invoke(
+                    &anchor_lang::solana_program::system_instruction::transfer(
+                        &ctx.accounts.authority.key(),
+                        &ctx.accounts.state.key(),
+                        additional_lamports,
+                    ),
+                    &[
+                        ctx.accounts.authority.to_account_info(),
+                        ctx.accounts.state.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+
+            // Zero-fill the new space so fields added since the old layout read as defaults
+            let account_info = ctx.accounts.state.to_account_info();
+            #[allow(deprecated)]
+            account_info.realloc(new_size, true)?;
+            migrated_fields.push("account_size".to_string());
+        }
+
+        let mut state = {
+            let account_data = ctx.accounts.state.try_borrow_data()?;
+            TokenState::try_deserialize(&mut &account_data[..])?
+        };
+
+        let from_version = state.version;
+        if state.version != TokenState::CURRENT_VERSION {
+            state.version = TokenState::CURRENT_VERSION;
+            migrated_fields.push("version".to_string());
+        }
+        if state.min_compatible_version != TokenState::MIN_COMPATIBLE_VERSION {
+            state.min_compatible_version = TokenState::MIN_COMPATIBLE_VERSION;
+            migrated_fields.push("min_compatible_version".to_string());
+        }
+
+        require!(!migrated_fields.is_empty(), TokenError::AlreadyMigrated);
+
+        {
+            let mut account_data = ctx.accounts.state.try_borrow_mut_data()?;
+            state.try_serialize(&mut &mut account_data[..])?;
+        }
+
+        emit!(TokenStateMigrated {
+            authority: ctx.accounts.authority.key(),
+            from_version,
+            to_version: state.version,
+            old_size: old_size as u64,
+            new_size: new_size as u64,
+            migrated_fields,
+        });
+
+        msg!(
+            "Token state migrated from version {} to {}",
+            from_version,
+            state.version
+        );
+        Ok(())
+    }
+
     /// Proposes a governance change with cooldown period
     ///
     /// Initiates a two-step governance transfer process. The change must be executed
@@ -313,6 +647,75 @@ pub mod spl_project {
         Ok(())
     }
 
+    /// Allows governance mint/burn/seize while the token is emergency paused
+    ///
+    /// When enabled, `mint_tokens`, `mint_tokens_batch`, `burn_tokens` and
+    /// `governance_transfer` keep working during an emergency pause so responders
+    /// can act on the supply without first unpausing. User transfers stay frozen.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetEmergencyPause context (requires governance signer)
+    /// - `value`: `true` to let governance operations run while paused
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the flag is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance authority
+    ///
+    /// # Events
+    /// - Emits `GovernanceOpsDuringPauseChanged` with the new value
+    pub fn set_governance_ops_during_pause(ctx: Context<SetEmergencyPause>, value: bool) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        state.governance_ops_during_pause = value;
+
+        emit!(GovernanceOpsDuringPauseChanged {
+            enabled: value,
+        });
+
+        msg!("Governance operations during pause set to: {}", value);
+        Ok(())
+    }
+
+    /// Turns whitelist mode on or off
+    ///
+    /// While enabled, `transfer_tokens` requires both sender and recipient to be
+    /// whitelisted (see `set_whitelist`).
+    ///
+    /// # Parameters
+    /// - `ctx`: ToggleWhitelistMode context (requires governance signer)
+    /// - `enabled`: `true` to restrict transfers to whitelisted addresses
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if whitelist mode is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance authority
+    ///
+    /// # Events
+    /// - Emits `WhitelistModeChanged` with the new value
+    pub fn toggle_whitelist_mode(ctx: Context<ToggleWhitelistMode>, enabled: bool) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        state.whitelist_mode = enabled;
+
+        emit!(WhitelistModeChanged { enabled });
+
+        msg!("Whitelist mode set to: {}", enabled);
+        Ok(())
+    }
+
     /// Sets blacklist status for an address
     ///
     /// Blacklisted addresses cannot send or receive tokens. This is enforced
@@ -328,12 +731,14 @@ pub mod spl_project {
     ///
     /// # Errors
     /// - `TokenError::Unauthorized` if caller is not governance or attempting to overwrite existing blacklist
+    /// - `TokenError::CannotBlacklistProtected` if blacklisting the authority or a protected address
     ///
     /// # Events
     /// - Emits `BlacklistChanged` with account and status
     ///
     /// # Security
     /// - Prevents silent overwrite of existing blacklist entries
+    /// - Protected addresses can be unblacklisted but never blacklisted
     pub fn set_blacklist(ctx: Context<SetBlacklist>, account: Pubkey, value: bool) -> Result<()> {
         let state = &ctx.accounts.state;
 
@@ -343,6 +748,10 @@ pub mod spl_project {
             state.authority == ctx.accounts.governance.key(),
             TokenError::Unauthorized
         );
+        require!(
+            !value || !state.is_protected(&account),
+            TokenError::CannotBlacklistProtected
+        );
         
         // Prevent silent overwrite - require explicit unblacklist if already blacklisted
         if !value && ctx.accounts.blacklist.is_blacklisted {
@@ -416,25 +825,30 @@ pub mod spl_project {
     /// Sets sell limit exemption for an address
     ///
     /// Exempted addresses can sell unlimited amounts to liquidity pools without
-    /// being subject to the 10% per 24-hour sell limit.
+    /// being subject to the 10% per 24-hour sell limit. A grant may be time-bounded
+    /// (e.g. an exchange listing window); after `expires_at` it no longer applies.
     ///
     /// # Parameters
     /// - `ctx`: SetNoSellLimit context (requires governance signer)
     /// - `account`: The address to grant/revoke exemption
     /// - `value`: `true` to grant exemption, `false` to revoke
+    /// - `expires_at`: Unix timestamp the exemption lapses after (`None` = permanent)
     ///
     /// # Returns
     /// - `Result<()>`: Success if exemption is updated
     ///
     /// # Errors
     /// - `TokenError::Unauthorized` if caller is not governance authority
+    /// - `TokenError::InvalidExemptionExpiry` if `expires_at` is not in the future,
+    ///   or is set while revoking
     ///
     /// # Events
-    /// - Emits `NoSellLimitChanged` with account and exemption status
+    /// - Emits `NoSellLimitChanged` with account, exemption status and expiry
     pub fn set_no_sell_limit(
         ctx: Context<SetNoSellLimit>,
         account: Pubkey,
         value: bool,
+        expires_at: Option<i64>,
     ) -> Result<()> {
         let state = &ctx.accounts.state;
 
@@ -444,20 +858,94 @@ pub mod spl_project {
             state.authority == ctx.accounts.governance.key(),
             TokenError::Unauthorized
         );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            match expires_at {
+                Some(expiry) => value && expiry > now,
+                None => true,
+            },
+            TokenError::InvalidExemptionExpiry
+        );
+
+        // Entries created before expiry tracking are one byte short of a set expiry
+        let new_size = 8 + NoSellLimit::LEN;
+        let exemption_info = ctx.accounts.no_sell_limit.to_account_info();
+        if exemption_info.data_len() < new_size {
+            let new_minimum_balance = Rent::get()?.minimum_balance(new_size);
+            let current_lamports = exemption_info.lamports();
+            if current_lamports < new_minimum_balance {
+                anchor_lang::solana_program::program::invoke(
+                    &anchor_lang::solana_program::system_instruction::transfer(
+                        &ctx.accounts.payer.key(),
+                        &exemption_info.key(),
+                        new_minimum_balance - current_lamports,
+                    ),
+                    &[
+                        ctx.accounts.payer.to_account_info(),
+                        exemption_info.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            #[allow(deprecated)]
+            exemption_info.realloc(new_size, true)?;
+        }
+
         let exemption = &mut ctx.accounts.no_sell_limit;
         exemption.account = account;
         exemption.has_exemption = value;
+        exemption.expires_at = expires_at;
         
         // Emit event
         emit!(NoSellLimitChanged {
             account,
             has_exemption: value,
+            expires_at,
         });
         
         msg!("No sell limit exemption set for {}: {}", account, value);
         Ok(())
     }
 
+    /// Clears a sell limit exemption whose expiry has passed
+    ///
+    /// Expired exemptions are already ignored by `transfer_tokens`; this resets the
+    /// entry so its stored flag matches. Callable by anyone.
+    ///
+    /// # Parameters
+    /// - `ctx`: CleanupExpiredNoSellLimit context
+    /// - `account`: The address whose exemption expired
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the exemption is cleared
+    ///
+    /// # Errors
+    /// - `TokenError::ExemptionNotExpired` if the exemption is permanent, revoked,
+    ///   or not yet past its expiry
+    ///
+    /// # Events
+    /// - Emits `NoSellLimitExemptionExpired`
+    pub fn cleanup_expired_no_sell_limit(
+        ctx: Context<CleanupExpiredNoSellLimit>,
+        account: Pubkey,
+    ) -> Result<()> {
+        let exemption = &mut ctx.accounts.no_sell_limit;
+        let expired_at = match exemption.expires_at {
+            Some(expiry) if exemption.has_exemption && Clock::get()?.unix_timestamp > expiry => {
+                expiry
+            }
+            _ => return Err(TokenError::ExemptionNotExpired.into()),
+        };
+
+        exemption.has_exemption = false;
+        exemption.expires_at = None;
+
+        emit!(NoSellLimitExemptionExpired { account, expired_at });
+
+        msg!("Expired sell limit exemption cleared for {}", account);
+        Ok(())
+    }
+
     /// Sets restricted status for an address
     ///
     /// Restricted addresses cannot send or receive tokens. This is separate from
@@ -639,80 +1127,451 @@ pub mod spl_project {
         Ok(())
     }
 
-    /// Mints new tokens to a recipient
+    /// Sets or removes the token supply cap
     ///
-    /// Creates new tokens and transfers them to the specified recipient.
-    /// Subject to supply cap if one is set, and blacklist checks.
+    /// Once a cap is set it can only be lowered toward `current_supply`, never
+    /// raised. Removing the cap entirely re-enables unlimited minting, so it must
+    /// be explicitly confirmed.
     ///
     /// # Parameters
-    /// - `ctx`: MintTokens context (requires governance signer)
-    /// - `amount`: Amount of tokens to mint (in token's base units)
+    /// - `ctx`: SetMaxSupply context (requires governance signer)
+    /// - `cap`: New supply cap, or `None` for unlimited
+    /// - `confirm_unlimited`: Must be true when `cap` is `None`
     ///
     /// # Returns
-    /// - `Result<()>`: Success if tokens are minted
+    /// - `Result<()>`: Success if the cap is updated
     ///
     /// # Errors
-    /// - `TokenError::EmergencyPaused` if protocol is paused
     /// - `TokenError::Unauthorized` if caller is not governance
-    /// - `TokenError::Blacklisted` if recipient is blacklisted
-    /// - `TokenError::MathOverflow` if minting would exceed supply cap
+    /// - `TokenError::InvalidMaxSupply` if the cap is below current supply or above the existing cap
+    /// - `TokenError::MaxSupplyRemovalNotConfirmed` if removing the cap without confirmation
     ///
     /// # Events
-    /// - Emits `TokenMinted` with amount and recipient
-    ///
-    /// # Security
-    /// - Only governance can mint
-    /// - Supply cap enforced if set
-    /// - Blacklist check prevents minting to blocked addresses
-    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
-        // Extract bump and get account info before mutable borrow to avoid borrow checker issues
-        let bump = ctx.accounts.state.bump;
-        let state_account_info = ctx.accounts.state.to_account_info();
-        
+    /// - Emits `MaxSupplyChanged` with the old and new cap
+    pub fn set_max_supply(
+        ctx: Context<SetMaxSupply>,
+        cap: Option<u64>,
+        confirm_unlimited: bool,
+    ) -> Result<()> {
         let state = &mut ctx.accounts.state;
 
         require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
-        
-        // Check emergency pause
-        require!(!state.emergency_paused, TokenError::EmergencyPaused);
-        
-        // Verify that the caller is the governance authority
+
         require!(
             state.authority == ctx.accounts.governance.key(),
             TokenError::Unauthorized
         );
-
-        // Extract recipient owner and validate accounts in a scoped block
-        // This ensures all borrows are dropped before the CPI call
-        let recipient_owner = {
-            // Check if recipient is blacklisted
-            // Get token account owner from account data (SPL token account layout: owner at offset 32)
-            // to is UncheckedAccount, so we need to read raw data
-            let to_account_data = ctx.accounts.to.try_borrow_data()?;
-            // require!(
-            //     to_account_data.len() >= 64,
-            //     TokenError::Unauthorized
-            // );
-            let token_account = SplTokenAccount::unpack(&to_account_data)
-                .map_err(|_| TokenError::InvalidTokenAccount)?;
-
-            require!(token_account.mint == ctx.accounts.mint.key(), TokenError::InvalidTokenAccount);
-
-            // let owner = Pubkey::try_from_slice(&to_account_data[32..64])
-            //     .map_err(|_| TokenError::Unauthorized)?;
-            let owner = token_account.owner;
-
-            // Check blacklist if account is provided and not default
-            if ctx.accounts.recipient_blacklist.key() != Pubkey::default() {
-                let blacklist_data = ctx.accounts.recipient_blacklist.try_borrow_data()?;
-                if blacklist_data.len() >= 41 {
-                    // Account discriminator (8) + account Pubkey (32) + is_blacklisted bool (1) = offset 40
-                    let is_blacklisted = blacklist_data[40] != 0;
-                    require!(!is_blacklisted, TokenError::Blacklisted);
+        match cap {
+            Some(new_cap) => {
+                require!(new_cap >= state.current_supply, TokenError::InvalidMaxSupply);
+                if let Some(old_cap) = state.max_supply {
+                    require!(new_cap <= old_cap, TokenError::InvalidMaxSupply);
                 }
             }
+            None => require!(confirm_unlimited, TokenError::MaxSupplyRemovalNotConfirmed),
+        }
 
-            // Validate mint authority matches state PDA
+        let old = state.max_supply;
+        state.max_supply = cap;
+
+        emit!(MaxSupplyChanged { old, new: cap });
+        msg!("Max supply updated from {:?} to {:?}", old, cap);
+        Ok(())
+    }
+
+    /// Enables or disables known-pools-only mode
+    ///
+    /// While enabled, transfers to a recipient that looks like a liquidity pool
+    /// (its owner account is owned by a registered DEX program) are rejected
+    /// unless the pool is registered via `set_liquidity_pool`.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetPoolRestriction context (requires governance signer)
+    /// - `value`: `true` to only allow registered pools
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the mode is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    ///
+    /// # Events
+    /// - Emits `KnownPoolsModeChanged`
+    pub fn set_restrict_to_known_pools(
+        ctx: Context<SetPoolRestriction>,
+        value: bool,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        state.restrict_to_known_pools = value;
+
+        emit!(KnownPoolsModeChanged { enabled: value });
+        msg!("Restrict to known pools: {}", value);
+        Ok(())
+    }
+
+    /// Freezes or unfreezes sell-limit enforcement
+    ///
+    /// Maintenance mode for the rate-limiting subsystem: while frozen, transfers
+    /// to pools skip the sell-limit check and leave sell trackers untouched, so
+    /// limit parameters or trackers can be changed without mixed enforcement.
+    /// Pause, blacklist and restricted checks still apply.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetSellLimitsFrozen context (requires governance signer)
+    /// - `frozen`: `true` to suspend sell-limit enforcement
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the flag is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    ///
+    /// # Events
+    /// - Emits `SellLimitsFrozenChanged`
+    pub fn set_sell_limits_frozen(ctx: Context<SetSellLimitsFrozen>, frozen: bool) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        state.sell_limits_frozen = frozen;
+
+        emit!(SellLimitsFrozenChanged { frozen });
+        msg!("Sell limits frozen: {}", frozen);
+        Ok(())
+    }
+
+    /// Chooses whether sell limits are tracked per wallet or per token account
+    ///
+    /// In `PerWallet` mode all token accounts signed for by the same wallet share
+    /// one sell tracker; in `PerTokenAccount` mode each source token account has its
+    /// own. Trackers of the other mode are left as they are and resume counting if
+    /// the mode is switched back.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetSellLimitsFrozen context (requires governance signer)
+    /// - `mode`: How the sell tracker PDA is derived in `transfer_tokens`
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the mode is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    ///
+    /// # Events
+    /// - Emits `SellTrackerModeChanged`
+    pub fn set_sell_tracker_mode(ctx: Context<SetSellLimitsFrozen>, mode: SellTrackerMode) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        state.sell_tracker_mode = mode;
+
+        emit!(SellTrackerModeChanged { mode });
+        msg!("Sell tracker mode: {:?}", mode);
+        Ok(())
+    }
+
+    /// Adds or removes a DEX program id used for pool detection
+    ///
+    /// # Parameters
+    /// - `ctx`: SetPoolRestriction context (requires governance signer)
+    /// - `program_id`: DEX program whose accounts are treated as pools (must not be default)
+    /// - `value`: `true` to add, `false` to remove
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the list is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance or program id is default
+    /// - `TokenError::TooManyDexPrograms` if the list already holds `MAX_DEX_PROGRAMS` entries
+    ///
+    /// # Events
+    /// - Emits `DexProgramChanged`
+    pub fn set_dex_program(
+        ctx: Context<SetPoolRestriction>,
+        program_id: Pubkey,
+        value: bool,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            program_id != Pubkey::default(),
+            TokenError::Unauthorized
+        );
+        let listed = state.dex_programs.contains(&program_id);
+        if value && !listed {
+            require!(
+                state.dex_programs.len() < TokenState::MAX_DEX_PROGRAMS,
+                TokenError::TooManyDexPrograms
+            );
+            state.dex_programs.push(program_id);
+        } else if !value && listed {
+            state.dex_programs.retain(|id| id != &program_id);
+        }
+
+        emit!(DexProgramChanged {
+            program_id,
+            is_dex: value,
+        });
+        msg!("DEX program set for {}: {}", program_id, value);
+        Ok(())
+    }
+
+    /// Adds or removes an address that can never be blacklisted
+    ///
+    /// Meant for accounts the protocol itself depends on (presale vaults, treasury),
+    /// so a mistaken blacklist transaction cannot lock them out. The authority is
+    /// always protected and does not need to be listed.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetPoolRestriction context (requires governance signer)
+    /// - `address`: Address to protect (must not be default)
+    /// - `value`: `true` to protect, `false` to remove protection
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the list is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance or address is default
+    /// - `TokenError::TooManyProtectedAddresses` if the list already holds `MAX_PROTECTED_ADDRESSES` entries
+    ///
+    /// # Events
+    /// - Emits `ProtectedAddressChanged`
+    pub fn set_protected_address(
+        ctx: Context<SetPoolRestriction>,
+        address: Pubkey,
+        value: bool,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            address != Pubkey::default(),
+            TokenError::Unauthorized
+        );
+        let listed = state.protected_addresses.contains(&address);
+        if value && !listed {
+            require!(
+                state.protected_addresses.len() < TokenState::MAX_PROTECTED_ADDRESSES,
+                TokenError::TooManyProtectedAddresses
+            );
+            state.protected_addresses.push(address);
+        } else if !value && listed {
+            state.protected_addresses.retain(|a| a != &address);
+        }
+
+        emit!(ProtectedAddressChanged {
+            address,
+            is_protected: value,
+        });
+        msg!("Protected address set for {}: {}", address, value);
+        Ok(())
+    }
+
+    /// Sets the fee charged on `transfer_tokens`
+    ///
+    /// The fee is taken out of the transferred amount, so the recipient receives
+    /// `amount - fee`. It is either burned or sent to a token account owned by
+    /// `treasury_address` (the fee collector). A fee of 0 disables the fee entirely.
+    /// Senders with a no-sell-limit exemption never pay the fee.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetPoolRestriction context (requires governance signer)
+    /// - `fee_bps`: Fee in basis points (max `MAX_TRANSFER_FEE_BPS`)
+    /// - `burn`: `true` to burn the fee instead of sending it to the treasury
+    /// - `treasury_address`: Owner of the token account receiving the fee (required unless burning)
+    /// - `whitelist_exempt`: `true` to skip the fee for whitelisted senders
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the fee is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidTransferFee` if the fee is too high or has no destination
+    ///
+    /// # Events
+    /// - Emits `TransferFeeChanged`
+    pub fn set_transfer_fee(
+        ctx: Context<SetPoolRestriction>,
+        fee_bps: u16,
+        burn: bool,
+        treasury_address: Pubkey,
+        whitelist_exempt: bool,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            fee_bps <= TokenState::MAX_TRANSFER_FEE_BPS,
+            TokenError::InvalidTransferFee
+        );
+        require!(
+            fee_bps == 0 || burn || treasury_address != Pubkey::default(),
+            TokenError::InvalidTransferFee
+        );
+
+        state.transfer_fee_bps = fee_bps;
+        state.transfer_fee_burn = burn;
+        state.treasury_address = treasury_address;
+        state.fee_whitelist_exempt = whitelist_exempt;
+
+        emit!(TransferFeeChanged {
+            fee_bps,
+            burn,
+            treasury_address,
+            whitelist_exempt,
+        });
+        msg!("Transfer fee set to {} bps (burn: {})", fee_bps, burn);
+        Ok(())
+    }
+
+    /// Enables or disables the circuit breaker for large single transfers
+    ///
+    /// While enabled, `transfer_tokens` rejects any transfer larger than
+    /// `current_supply * threshold_bps / 10_000`. Senders with a no-sell-limit
+    /// exemption bypass the breaker.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetPoolRestriction context (requires governance signer)
+    /// - `enabled`: `true` to enforce the threshold
+    /// - `threshold_bps`: Largest transfer as basis points of current supply
+    ///   (`MIN_CIRCUIT_BREAKER_BPS` to 10000)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the circuit breaker is updated
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidCircuitBreakerThreshold` if the threshold is out of range
+    ///
+    /// # Events
+    /// - Emits `CircuitBreakerChanged`
+    pub fn set_circuit_breaker(
+        ctx: Context<SetPoolRestriction>,
+        enabled: bool,
+        threshold_bps: u16,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            (TokenState::MIN_CIRCUIT_BREAKER_BPS..=10_000).contains(&threshold_bps),
+            TokenError::InvalidCircuitBreakerThreshold
+        );
+
+        state.circuit_breaker_enabled = enabled;
+        state.circuit_breaker_threshold_bps = threshold_bps;
+
+        emit!(CircuitBreakerChanged {
+            enabled,
+            threshold_bps,
+        });
+        msg!("Circuit breaker {} at {} bps", if enabled { "enabled" } else { "disabled" }, threshold_bps);
+        Ok(())
+    }
+
+    /// Mints new tokens to a recipient
+    ///
+    /// Creates new tokens and transfers them to the specified recipient.
+    /// Subject to supply cap if one is set, and blacklist checks.
+    ///
+    /// # Parameters
+    /// - `ctx`: MintTokens context (requires governance signer)
+    /// - `amount`: Amount of tokens to mint (in token's base units)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if tokens are minted
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused and `governance_ops_during_pause` is off
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::Blacklisted` if recipient is blacklisted
+    /// - `TokenError::MathOverflow` if minting would exceed supply cap
+    ///
+    /// # Events
+    /// - Emits `TokenMinted` with amount and recipient
+    ///
+    /// # Security
+    /// - Only governance can mint
+    /// - Supply cap enforced if set
+    /// - Blacklist check prevents minting to blocked addresses
+    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
+        // Extract bump and get account info before mutable borrow to avoid borrow checker issues
+        let bump = ctx.accounts.state.bump;
+        let state_account_info = ctx.accounts.state.to_account_info();
+        
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        
+        // Check emergency pause
+        require!(!state.governance_ops_paused(), TokenError::EmergencyPaused);
+        
+        // Verify that the caller is the governance authority
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+
+        // Extract recipient owner and validate accounts in a scoped block
+        // This ensures all borrows are dropped before the CPI call
+        let recipient_owner = {
+            // Check if recipient is blacklisted
+            // Get token account owner from account data (SPL token account layout: owner at offset 32)
+            // to is UncheckedAccount, so we need to read raw data
+            let to_account_data = ctx.accounts.to.try_borrow_data()?;
+            // require!(
+            //     to_account_data.len() >= 64,
+            //     TokenError::Unauthorized
+            // );
+            let token_account = SplTokenAccount::unpack(&to_account_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+
+            require!(token_account.mint == ctx.accounts.mint.key(), TokenError::InvalidTokenAccount);
+
+            // let owner = Pubkey::try_from_slice(&to_account_data[32..64])
+            //     .map_err(|_| TokenError::Unauthorized)?;
+            let owner = token_account.owner;
+
+            // Check blacklist if account is provided and not default
+            if ctx.accounts.recipient_blacklist.key() != Pubkey::default() {
+                let blacklist_data = ctx.accounts.recipient_blacklist.try_borrow_data()?;
+                if blacklist_data.len() >= 41 {
+                    // Account discriminator (8) + account Pubkey (32) + is_blacklisted bool (1) = offset 40
+                    let is_blacklisted = blacklist_data[40] != 0;
+                    require!(!is_blacklisted, TokenError::Blacklisted);
+                }
+            }
+
+            // Validate mint authority matches state PDA
             // SPL Mint layout: mint (32) + supply (8) + decimals (1) + mint_authority (36) + freeze_authority (36)
             // mint_authority starts at offset 0, but we need to check it's the state PDA
             let mint_data = ctx.accounts.mint.try_borrow_data()?;
@@ -771,6 +1630,119 @@ pub mod spl_project {
         msg!("Successfully minted {} tokens", amount);
         Ok(())
     }
+
+    /// Mints tokens to several recipients in one instruction
+    ///
+    /// Each recipient is passed through remaining accounts as
+    /// `[token_account, recipient_blacklist]`, in the same order as `amounts`.
+    /// Every recipient is validated and the supply cap is checked against the
+    /// summed total before anything is minted; any failure aborts the whole batch.
+    ///
+    /// # Parameters
+    /// - `ctx`: MintTokensBatch context (requires governance signer), plus two
+    ///   remaining accounts per recipient
+    /// - `amounts`: Amount to mint to each recipient (1 to `MAX_MINT_BATCH` entries)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if every recipient is minted to
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused and `governance_ops_during_pause` is off
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidMintBatch` if the batch size, amounts or account count are invalid
+    /// - `TokenError::InvalidTokenAccount` if a token account is not for this mint
+    /// - `TokenError::InvalidListAccount` if a blacklist account is not the owner's PDA
+    /// - `TokenError::Blacklisted` if a recipient is blacklisted
+    /// - `TokenError::MathOverflow` if the total would exceed the supply cap
+    ///
+    /// # Events
+    /// - Emits `TokenMinted` for each recipient
+    pub fn mint_tokens_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintTokensBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        let bump = ctx.accounts.state.bump;
+        let state_account_info = ctx.accounts.state.to_account_info();
+
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(!state.governance_ops_paused(), TokenError::EmergencyPaused);
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            !amounts.is_empty()
+                && amounts.len() <= TokenState::MAX_MINT_BATCH
+                && amounts.iter().all(|amount| *amount > 0)
+                && ctx.remaining_accounts.len() == amounts.len() * 2,
+            TokenError::InvalidMintBatch
+        );
+
+        // Validate every recipient before minting anything
+        let mut recipients = Vec::with_capacity(amounts.len());
+        for accounts in ctx.remaining_accounts.chunks_exact(2) {
+            let owner = {
+                let to_account_data = accounts[0].try_borrow_data()?;
+                let token_account = SplTokenAccount::unpack(&to_account_data)
+                    .map_err(|_| TokenError::InvalidTokenAccount)?;
+                require!(token_account.mint == ctx.accounts.mint.key(), TokenError::InvalidTokenAccount);
+                token_account.owner
+            };
+            let recipient_blacklist = UncheckedAccount::try_from(&accounts[1]);
+            require!(
+                !list_flag(&recipient_blacklist, b"blacklist", &owner)?,
+                TokenError::Blacklisted
+            );
+            recipients.push(owner);
+        }
+
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(TokenError::MathOverflow)?;
+        let new_supply = state.current_supply
+            .checked_add(total)
+            .ok_or(TokenError::MathOverflow)?;
+        if let Some(max_supply) = state.max_supply {
+            require!(new_supply <= max_supply, TokenError::MathOverflow);
+        }
+
+        let state_seed = b"state";
+        let bump_seed = [bump];
+        let seeds = &[state_seed.as_ref(), &bump_seed[..]];
+        let signer = &[&seeds[..]];
+
+        for ((accounts, amount), recipient) in ctx
+            .remaining_accounts
+            .chunks_exact(2)
+            .zip(amounts.iter())
+            .zip(recipients)
+        {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: accounts[0].clone(),
+                        authority: state_account_info.clone(),
+                    },
+                    signer,
+                ),
+                *amount,
+            )?;
+            emit!(TokenMinted {
+                amount: *amount,
+                recipient,
+            });
+        }
+
+        state.current_supply = new_supply;
+
+        msg!("Minted {} tokens to {} recipients", total, amounts.len());
+        Ok(())
+    }
     /// Burns tokens from a token account
     ///
     /// Permanently removes tokens from circulation. The tokens must be owned
@@ -784,7 +1756,7 @@ pub mod spl_project {
     /// - `Result<()>`: Success if tokens are burned
     ///
     /// # Errors
-    /// - `TokenError::EmergencyPaused` if protocol is paused
+    /// - `TokenError::EmergencyPaused` if protocol is paused and `governance_ops_during_pause` is off
     /// - `TokenError::Unauthorized` if caller is not governance
     /// - `TokenError::MathOverflow` if burning would cause underflow
     ///
@@ -804,7 +1776,7 @@ pub mod spl_project {
         require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
         
         // Check emergency pause
-        require!(!state.emergency_paused, TokenError::EmergencyPaused);
+        require!(!state.governance_ops_paused(), TokenError::EmergencyPaused);
         
         // Verify that the caller is the governance authority
         require!(
@@ -860,10 +1832,87 @@ pub mod spl_project {
         // Emit event
         emit!(TokenBurned {
             amount,
-            from: owner,
+            from: owner,
+        });
+
+        msg!("Successfully burned {} tokens", amount);
+        Ok(())
+    }
+
+    /// Moves tokens out of a blacklisted holder's account (compliance seizure)
+    ///
+    /// The state PDA signs the transfer, so it must be the source account's owner or
+    /// delegate, as with `burn_tokens`. Only works against owners currently on the
+    /// blacklist, so it cannot be used to move arbitrary holders' tokens.
+    ///
+    /// # Parameters
+    /// - `ctx`: GovernanceTransfer context (requires governance signer)
+    /// - `amount`: Amount of tokens to move (in token's base units)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the tokens are moved
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused and `governance_ops_during_pause` is off
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidTokenAccount` if either account is not for this mint
+    /// - `TokenError::InvalidListAccount` if `source_blacklist` is not the source owner's PDA
+    /// - `TokenError::SourceNotBlacklisted` if the source owner is not blacklisted
+    ///
+    /// # Events
+    /// - Emits `TokensSeized` with the source owner, destination owner and amount
+    pub fn governance_transfer(ctx: Context<GovernanceTransfer>, amount: u64) -> Result<()> {
+        let state = &ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(!state.governance_ops_paused(), TokenError::EmergencyPaused);
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+
+        let (from_owner, to_owner) = {
+            let from_account_data = ctx.accounts.from.try_borrow_data()?;
+            let from_token = SplTokenAccount::unpack(&from_account_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+            let to_account_data = ctx.accounts.to.try_borrow_data()?;
+            let to_token = SplTokenAccount::unpack(&to_account_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+            require!(
+                from_token.mint == ctx.accounts.mint.key() && to_token.mint == ctx.accounts.mint.key(),
+                TokenError::InvalidTokenAccount
+            );
+            (from_token.owner, to_token.owner)
+        };
+        require!(
+            list_flag(&ctx.accounts.source_blacklist, b"blacklist", &from_owner)?,
+            TokenError::SourceNotBlacklisted
+        );
+
+        let bump_seed = [state.bump];
+        let seeds = &[b"state".as_ref(), &bump_seed[..]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: ctx.accounts.state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(TokensSeized {
+            from: from_owner,
+            to: to_owner,
+            amount,
         });
 
-        msg!("Successfully burned {} tokens", amount);
+        msg!("Seized {} tokens from {} to {}", amount, from_owner, to_owner);
         Ok(())
     }
 
@@ -875,27 +1924,55 @@ pub mod spl_project {
     /// - Restricted status (sender and recipient)
     /// - Whitelist mode (if enabled)
     /// - Sell limits (10% per 24h when selling to liquidity pools)
+    /// - Transfer fee (if set), deducted from the amount and burned or sent to the treasury;
+    ///   senders with a no-sell-limit exemption pay no fee
     ///
     /// # Parameters
     /// - `ctx`: TransferTokens context with all required accounts
     /// - `amount`: Amount of tokens to transfer (in token's base units)
+    /// - `expected_decimals`: Decimals the caller expects the mint to have (None = skip the check)
     ///
     /// # Returns
     /// - `Result<()>`: Success if transfer completes
     ///
     /// # Errors
     /// - `TokenError::EmergencyPaused` if protocol is paused
+    /// - `TokenError::InvalidTokenAccount` if `mint` is not an SPL mint
+    /// - `TokenError::DecimalsMismatch` if `expected_decimals` differs from the mint's decimals
     /// - `TokenError::Blacklisted` if sender or recipient is blacklisted
     /// - `TokenError::Restricted` if sender or recipient is restricted
     /// - `TokenError::Unauthorized` if whitelist mode is enabled and addresses not whitelisted
     /// - `TokenError::SellLimitExceeded` if selling to pool exceeds 10% limit
+    /// - `TokenError::UnknownPool` if known-pools mode is enabled and the recipient
+    ///   looks like an unregistered pool (or `recipient_owner` is missing)
+    /// - `TokenError::InvalidFeeDestination` if a fee is sent to the treasury and
+    ///   `fee_destination` is missing or not a treasury token account
+    /// - `TokenError::CircuitBreakerTriggered` if the circuit breaker is enabled and
+    ///   `amount` exceeds its share of current supply (no-sell-limit senders are exempt)
+    /// - `TokenError::InvalidVelocityTracker` if a remaining account is passed that is not
+    ///   the writable `VelocityTracker` PDA
     /// - `TokenError::MathOverflow` if calculations overflow
     ///
+    /// # Remaining Accounts
+    /// - `[0]` (optional): `VelocityTracker` PDA; when passed, the gross `amount` is added
+    ///   to the current hourly period's volume
+    ///
+    /// # Events
+    /// - Emits `TokensTransferred` once the transfer succeeds
+    /// - Emits `CircuitBreakerTriggered` (in the failed transaction's logs) when blocked
+    /// - Emits `TransferFeeCharged` when a fee is deducted
+    /// - Emits `FeeCollected` when the fee is sent to the treasury
+    /// - Emits `VelocityUpdated` on every 100th transfer of a velocity period
+    ///
     /// # Security
     /// - All restrictions are enforced before transfer
     /// - Sell limits calculated based on actual token balance
     /// - Rolling 24-hour window for sell limit tracking
-    pub fn transfer_tokens(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
+    pub fn transfer_tokens(
+        ctx: Context<TransferTokens>,
+        amount: u64,
+        expected_decimals: Option<u8>,
+    ) -> Result<()> {
         let state = &mut ctx.accounts.state;
 
         require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
@@ -903,6 +1980,17 @@ pub mod spl_project {
         // Check emergency pause
         require!(!state.emergency_paused, TokenError::EmergencyPaused);
 
+        // Decimals for transfer_checked, read from the mint rather than trusted from the caller
+        let decimals = {
+            let mint_data = ctx.accounts.mint.try_borrow_data()?;
+            SplMint::unpack(&mint_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?
+                .decimals
+        };
+        if let Some(expected) = expected_decimals {
+            require!(expected == decimals, TokenError::DecimalsMismatch);
+        }
+
         // Get sender and recipient addresses from token accounts
         // Validate and extract owner from token account data
         // let from_account_data = ctx.accounts.from_account.try_borrow_data()?;
@@ -961,96 +2049,80 @@ pub mod spl_project {
         to_token.owner
     };
 
-        // Check sender blacklist
-        if ctx.accounts.sender_blacklist.key() != Pubkey::default() {
-            let blacklist_data = ctx.accounts.sender_blacklist.try_borrow_data()?;
-            if blacklist_data.len() >= 41 {
-                let is_blacklisted = blacklist_data[40] != 0;
-                require!(!is_blacklisted, TokenError::Blacklisted);
-            }
-        }
+        // Every list account must be the PDA derived from the token account owners,
+        // so a caller cannot skip a check by passing an unrelated account
+        let sender_blacklisted = list_flag(&ctx.accounts.sender_blacklist, b"blacklist", &sender)?;
+        require!(!sender_blacklisted, TokenError::Blacklisted);
 
-        // Check recipient blacklist
-        if ctx.accounts.recipient_blacklist.key() != Pubkey::default() {
-            let blacklist_data = ctx.accounts.recipient_blacklist.try_borrow_data()?;
-            if blacklist_data.len() >= 41 {
-                let is_blacklisted = blacklist_data[40] != 0;
-                require!(!is_blacklisted, TokenError::Blacklisted);
-            }
-        }
+        let recipient_blacklisted =
+            list_flag(&ctx.accounts.recipient_blacklist, b"blacklist", &recipient)?;
+        require!(!recipient_blacklisted, TokenError::Blacklisted);
 
-        // Check sender restricted
-        if ctx.accounts.sender_restricted.key() != Pubkey::default() {
-            let restricted_data = ctx.accounts.sender_restricted.try_borrow_data()?;
-            if restricted_data.len() >= 41 {
-                let is_restricted = restricted_data[40] != 0;
-                require!(!is_restricted, TokenError::Restricted);
-            }
-        }
+        let sender_restricted = list_flag(&ctx.accounts.sender_restricted, b"restricted", &sender)?;
+        require!(!sender_restricted, TokenError::Restricted);
 
-        // Check recipient restricted
-        if ctx.accounts.recipient_restricted.key() != Pubkey::default() {
-            let restricted_data = ctx.accounts.recipient_restricted.try_borrow_data()?;
-            if restricted_data.len() >= 41 {
-                let is_restricted = restricted_data[40] != 0;
-                require!(!is_restricted, TokenError::Restricted);
-            }
-        }
+        let recipient_restricted =
+            list_flag(&ctx.accounts.recipient_restricted, b"restricted", &recipient)?;
+        require!(!recipient_restricted, TokenError::Restricted);
 
         // Check whitelist mode - if enabled, both sender and recipient must be whitelisted
         if state.whitelist_mode {
-            // Check sender whitelist
-            if ctx.accounts.sender_whitelist.key() != Pubkey::default() {
-                let whitelist_data = ctx.accounts.sender_whitelist.try_borrow_data()?;
-                if whitelist_data.len() >= 41 {
-                    let is_whitelisted = whitelist_data[40] != 0;
-                    require!(is_whitelisted, TokenError::Unauthorized);
-                } else {
-                    require!(false, TokenError::Unauthorized);
-                }
-            } else {
-                require!(false, TokenError::Unauthorized);
-            }
-            
-            // Check recipient whitelist
-            if ctx.accounts.recipient_whitelist.key() != Pubkey::default() {
-                let whitelist_data = ctx.accounts.recipient_whitelist.try_borrow_data()?;
-                if whitelist_data.len() >= 41 {
-                    let is_whitelisted = whitelist_data[40] != 0;
-                    require!(is_whitelisted, TokenError::Unauthorized);
-                } else {
-                    require!(false, TokenError::Unauthorized);
-                }
-            } else {
-                require!(false, TokenError::Unauthorized);
-            }
+            let sender_whitelisted =
+                list_flag(&ctx.accounts.sender_whitelist, b"whitelist", &sender)?;
+            require!(sender_whitelisted, TokenError::Unauthorized);
+
+            let recipient_whitelisted =
+                list_flag(&ctx.accounts.recipient_whitelist, b"whitelist", &recipient)?;
+            require!(recipient_whitelisted, TokenError::Unauthorized);
         }
 
         // Check if recipient is a liquidity pool
-        let is_pool = if ctx.accounts.liquidity_pool.key() != Pubkey::default() {
-            let pool_data = ctx.accounts.liquidity_pool.try_borrow_data()?;
-            if pool_data.len() >= 41 {
-                pool_data[40] != 0 // is_pool is at offset 40
-            } else {
-                false
+        let is_pool = list_flag(&ctx.accounts.liquidity_pool, b"liquiditypool", &recipient)?;
+
+        // Known-pools mode: a recipient whose owner account belongs to a DEX program
+        // is treated as a pool and must be registered via set_liquidity_pool
+        if state.restrict_to_known_pools {
+            let pool_info = &ctx.accounts.liquidity_pool;
+            let is_registered_pool = is_pool
+                && LiquidityPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])
+                    .is_ok_and(|p| p.is_pool && (p.pool == recipient || p.pool == ctx.accounts.to_account.key()));
+
+            if !is_registered_pool {
+                let recipient_owner = ctx.accounts.recipient_owner.as_ref()
+                    .ok_or(TokenError::UnknownPool)?;
+                require!(
+                    recipient_owner.key() == recipient,
+                    TokenError::InvalidTokenAccount
+                );
+                require!(
+                    !state.dex_programs.contains(recipient_owner.owner),
+                    TokenError::UnknownPool
+                );
             }
-        } else {
-            false
-        };
+        }
+
+        // Circuit breaker: reject abnormally large single transfers
+        if state.circuit_breaker_enabled
+            && !no_sell_limit_active(&ctx.accounts.no_sell_limit, &sender)?
+        {
+            let threshold = (state.current_supply as u128)
+                .checked_mul(state.circuit_breaker_threshold_bps as u128)
+                .and_then(|x| x.checked_div(10_000))
+                .ok_or(TokenError::MathOverflow)? as u64;
+            if amount > threshold {
+                emit!(CircuitBreakerTriggered {
+                    from: sender,
+                    amount,
+                    threshold,
+                });
+                return Err(TokenError::CircuitBreakerTriggered.into());
+            }
+        }
 
-        // If selling to pool, check sell limits
-        if is_pool {
+        // If selling to pool, check sell limits (skipped while frozen for maintenance)
+        if is_pool && !state.sell_limits_frozen {
             // Check if sender has no-sell-limit exemption
-            let has_exemption = if ctx.accounts.no_sell_limit.key() != Pubkey::default() {
-                let exemption_data = ctx.accounts.no_sell_limit.try_borrow_data()?;
-                if exemption_data.len() >= 41 {
-                    exemption_data[40] != 0 // has_exemption is at offset 40
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+            let has_exemption = no_sell_limit_active(&ctx.accounts.no_sell_limit, &sender)?;
 
             if !has_exemption {
                 // Check 10% sell limit within 24 hours
@@ -1059,7 +2131,8 @@ pub mod spl_project {
 
                 // Initialize tracker if needed
                 if sell_tracker.account == Pubkey::default() {
-                    sell_tracker.account = sender;
+                    sell_tracker.account =
+                        state.sell_tracker_key(&sender, &ctx.accounts.from_account.key());
                     sell_tracker.last_reset = current_time;
                     sell_tracker.total_sold_24h = 0;
                 }
@@ -1100,21 +2173,182 @@ pub mod spl_project {
             }
         }
 
-        msg!("Transferring {} tokens", amount);
+        // Transfer fee, rounded down (sell limits above count the gross amount).
+        // Senders with a no-sell-limit exemption (pools, treasury, bridge) pay no fee.
+        let mut fee = 0u64;
+        if state.transfer_fee_bps > 0 {
+            let exempt = no_sell_limit_active(&ctx.accounts.no_sell_limit, &sender)?
+                || (state.fee_whitelist_exempt
+                    && list_flag(&ctx.accounts.sender_whitelist, b"whitelist", &sender)?);
+            if !exempt {
+                fee = (amount as u128)
+                    .checked_mul(state.transfer_fee_bps as u128)
+                    .and_then(|x| x.checked_div(10_000))
+                    .ok_or(TokenError::MathOverflow)? as u64;
+            }
+        }
+        let net_amount = amount.checked_sub(fee).ok_or(TokenError::MathOverflow)?;
+
+        msg!("Transferring {} tokens", net_amount);
 
-        token::transfer(
+        token::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.from_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.to_account.to_account_info(),
                     authority: ctx.accounts.authority.to_account_info(),
                 },
             ),
-            amount,
+            net_amount,
+            decimals,
         )?;
 
-        msg!("Successfully transferred {} tokens", amount);
+        if fee > 0 {
+            if state.transfer_fee_burn {
+                token::burn(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            from: ctx.accounts.from_account.to_account_info(),
+                            authority: ctx.accounts.authority.to_account_info(),
+                        },
+                    ),
+                    fee,
+                )?;
+                state.current_supply = state.current_supply
+                    .checked_sub(fee)
+                    .ok_or(TokenError::MathOverflow)?;
+            } else {
+                let fee_destination = ctx.accounts.fee_destination.as_ref()
+                    .ok_or(TokenError::InvalidFeeDestination)?;
+                {
+                    let fee_account_data = fee_destination.try_borrow_data()?;
+                    let fee_token = SplTokenAccount::unpack(&fee_account_data)
+                        .map_err(|_| TokenError::InvalidFeeDestination)?;
+                    require!(
+                        fee_token.mint == ctx.accounts.mint.key()
+                            && fee_token.owner == state.treasury_address,
+                        TokenError::InvalidFeeDestination
+                    );
+                }
+                token::transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.from_account.to_account_info(),
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: fee_destination.to_account_info(),
+                            authority: ctx.accounts.authority.to_account_info(),
+                        },
+                    ),
+                    fee,
+                    decimals,
+                )?;
+
+                emit!(FeeCollected {
+                    from: sender,
+                    fee_collector: state.treasury_address,
+                    fee_destination: fee_destination.key(),
+                    amount: fee,
+                });
+            }
+
+            emit!(TransferFeeCharged {
+                from: sender,
+                to: recipient,
+                amount,
+                fee,
+            });
+        }
+
+        emit!(TokensTransferred {
+            from_owner: sender,
+            to_owner: recipient,
+            amount,
+            is_pool_sale: is_pool,
+            sell_limit_consumed: if is_pool {
+                ctx.accounts.sell_tracker.total_sold_24h
+            } else {
+                0
+            },
+        });
+
+        // Hourly transfer volume for monitoring, when the tracker is passed
+        if let Some(velocity_tracker) = ctx.remaining_accounts.first() {
+            record_velocity(velocity_tracker, amount, Clock::get()?.unix_timestamp)?;
+        }
+
+        msg!("Successfully transferred {} tokens", net_amount);
+        Ok(())
+    }
+
+    /// Transfers tokens only if executed before a deadline
+    ///
+    /// Same as `transfer_tokens`, but rejects the transaction if it lands after
+    /// `deadline`. Protects against a delayed transaction executing in a
+    /// materially different state than when it was signed (e.g. after the
+    /// recipient was marked as a liquidity pool and sell limits now apply).
+    ///
+    /// # Parameters
+    /// - `ctx`: TransferTokens context with all required accounts
+    /// - `amount`: Amount of tokens to transfer (in token's base units)
+    /// - `expected_decimals`: Decimals the caller expects the mint to have (None = skip the check)
+    /// - `deadline`: Unix timestamp after which the transfer must not execute
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if transfer completes
+    ///
+    /// # Errors
+    /// - `TokenError::DeadlineExceeded` if current time is past `deadline`
+    /// - Any error returned by `transfer_tokens`
+    pub fn transfer_tokens_with_deadline(
+        ctx: Context<TransferTokens>,
+        amount: u64,
+        expected_decimals: Option<u8>,
+        deadline: i64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= deadline,
+            TokenError::DeadlineExceeded
+        );
+
+        transfer_tokens(ctx, amount, expected_decimals)
+    }
+
+    /// Creates or resets the transfer velocity tracker
+    ///
+    /// Starts a fresh period at the current time with no volume or transfers.
+    /// `transfer_tokens` only updates the tracker when it is passed as a remaining
+    /// account, so it must be created here first.
+    ///
+    /// # Parameters
+    /// - `ctx`: ResetVelocityTracker context (requires governance signer)
+    ///
+    /// # Returns
+    /// - `Result<()>`: Success if the tracker is reset
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance authority
+    ///
+    /// # Events
+    /// - Emits `VelocityTrackerReset`
+    pub fn reset_velocity_tracker(ctx: Context<ResetVelocityTracker>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tracker = &mut ctx.accounts.velocity_tracker;
+        tracker.period_start = now;
+        tracker.period_volume = 0;
+        tracker.period_transfers = 0;
+        tracker.last_updated = now;
+
+        emit!(VelocityTrackerReset {
+            by: ctx.accounts.governance.key(),
+            period_start: now,
+        });
+
+        msg!("Velocity tracker reset at {}", now);
         Ok(())
     }
 
@@ -1145,47 +2379,409 @@ pub mod spl_project {
         
         // Check emergency pause
         require!(!state.emergency_paused, TokenError::EmergencyPaused);
-        
-        // Require governance signer
+        
+        // Require governance signer
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+
+        msg!(
+            "Revoking mint authority for : {:?}",
+            ctx.accounts.mint.key()
+        );
+
+        // Create PDA signer
+        let bump = state.bump;
+        let state_seed = b"state";
+        let bump_seed = [bump];
+        let seeds = &[state_seed.as_ref(), &bump_seed[..]];
+        let signer = &[&seeds[..]];
+
+        // Call SPL Tokens set authority via CPI
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                    current_authority: ctx.accounts.state.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+        
+        // Emit event
+        emit!(MintAuthorityRevoked {
+            mint: ctx.accounts.mint.key(),
+        });
+        
+        msg!("Mint authority successfully revoked!");
+        Ok(())
+    }
+
+    /// Configures the time-based emission schedule
+    ///
+    /// Emissions accrue at `rate_per_second` from `start_time` (or now, if later)
+    /// until `end_time` and are minted by `mint_scheduled_emission`. Replacing a
+    /// schedule restarts accrual, so anything due under the old one should be
+    /// minted first. A rate of 0 stops emissions.
+    ///
+    /// # Parameters
+    /// - `ctx`: SetEmissionSchedule context (requires governance signer)
+    /// - `rate_per_second`: Tokens (base units) emitted per second
+    /// - `recipient`: Token account of this mint that receives emissions
+    /// - `start_time`: Unix time emissions start accruing
+    /// - `end_time`: Unix time emissions stop accruing (must be after `start_time`)
+    ///
+    /// # Errors
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidEmissionSchedule` if `end_time <= start_time` or recipient is default
+    ///
+    /// # Events
+    /// - Emits `EmissionScheduleSet`
+    pub fn set_emission_schedule(
+        ctx: Context<SetEmissionSchedule>,
+        rate_per_second: u64,
+        recipient: Pubkey,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        let state = &ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            end_time > start_time && recipient != Pubkey::default(),
+            TokenError::InvalidEmissionSchedule
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.emission_schedule;
+        schedule.rate_per_second = rate_per_second;
+        schedule.recipient = recipient;
+        schedule.last_minted = start_time.max(now);
+        schedule.end_time = end_time;
+        schedule.bump = ctx.bumps.emission_schedule;
+
+        emit!(EmissionScheduleSet {
+            rate_per_second,
+            recipient,
+            start_time: schedule.last_minted,
+            end_time,
+        });
+        msg!(
+            "Emission schedule set: {} per second to {} until {}",
+            rate_per_second,
+            recipient,
+            end_time
+        );
+        Ok(())
+    }
+
+    /// Mints tokens into a vesting vault for a beneficiary (e.g. team allocations)
+    ///
+    /// The tokens are minted to a vault owned by the beneficiary's `VestingSchedule`
+    /// PDA, not to the beneficiary, and unlock linearly over `duration_seconds`
+    /// from now. Nothing is claimable before `cliff_seconds`. Each beneficiary
+    /// holds at most one schedule.
+    ///
+    /// # Parameters
+    /// - `ctx`: MintWithVesting context (requires governance signer)
+    /// - `beneficiary`: Wallet allowed to claim the vested tokens
+    /// - `amount`: Tokens (base units) to lock
+    /// - `cliff_seconds`: Time before anything unlocks (at most `duration_seconds`)
+    /// - `duration_seconds`: Time until everything has unlocked
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused and `governance_ops_during_pause` is off
+    /// - `TokenError::Unauthorized` if caller is not governance
+    /// - `TokenError::InvalidVestingSchedule` if amount or duration is 0, or the cliff is out of range
+    /// - `TokenError::MathOverflow` if minting would exceed supply cap
+    ///
+    /// # Events
+    /// - Emits `VestingScheduleCreated` and `TokenMinted`
+    pub fn mint_with_vesting(
+        ctx: Context<MintWithVesting>,
+        beneficiary: Pubkey,
+        amount: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        let bump = ctx.accounts.state.bump;
+        let state_account_info = ctx.accounts.state.to_account_info();
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(!state.governance_ops_paused(), TokenError::EmergencyPaused);
+        require!(
+            state.authority == ctx.accounts.governance.key(),
+            TokenError::Unauthorized
+        );
+        require!(
+            amount > 0 && duration_seconds > 0 && (0..=duration_seconds).contains(&cliff_seconds),
+            TokenError::InvalidVestingSchedule
+        );
+
+        let new_supply = state.current_supply
+            .checked_add(amount)
+            .ok_or(TokenError::MathOverflow)?;
+        if let Some(max_supply) = state.max_supply {
+            require!(new_supply <= max_supply, TokenError::MathOverflow);
+        }
+
+        let bump_seed = [bump];
+        let seeds = &[b"state".as_ref(), &bump_seed[..]];
+        let signer = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vesting_vault.to_account_info(),
+                    authority: state_account_info,
+                },
+                signer,
+            ),
+            amount,
+        )?;
+        state.current_supply = new_supply;
+
+        let start_time = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.beneficiary = beneficiary;
+        schedule.total_amount = amount;
+        schedule.claimed_amount = 0;
+        schedule.start_time = start_time;
+        schedule.cliff_seconds = cliff_seconds;
+        schedule.duration_seconds = duration_seconds;
+        schedule.bump = ctx.bumps.vesting_schedule;
+
+        emit!(VestingScheduleCreated {
+            beneficiary,
+            amount,
+            start_time,
+            cliff_seconds,
+            duration_seconds,
+        });
+        emit!(TokenMinted {
+            amount,
+            recipient: ctx.accounts.vesting_vault.key(),
+        });
+
+        msg!(
+            "Minted {} tokens vesting to {} over {}s (cliff {}s)",
+            amount,
+            beneficiary,
+            duration_seconds,
+            cliff_seconds
+        );
+        Ok(())
+    }
+
+    /// Releases vested tokens from the beneficiary's vesting vault
+    ///
+    /// Transfers everything vested so far and not yet claimed to a token account
+    /// owned by the beneficiary.
+    ///
+    /// # Parameters
+    /// - `ctx`: ClaimVesting context (requires beneficiary signer)
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused
+    /// - `TokenError::InvalidTokenAccount` if `to` is not the beneficiary's account for this mint
+    /// - `TokenError::Blacklisted` if the beneficiary is blacklisted
+    /// - `TokenError::NothingVested` if the cliff has not passed or everything vested is claimed
+    ///
+    /// # Events
+    /// - Emits `VestingClaimed`
+    pub fn claim_vesting(ctx: Context<ClaimVesting>) -> Result<()> {
+        let state = &ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(!state.emergency_paused, TokenError::EmergencyPaused);
+
+        let beneficiary = ctx.accounts.beneficiary.key();
+        {
+            let to_account_data = ctx.accounts.to.try_borrow_data()?;
+            let token_account = SplTokenAccount::unpack(&to_account_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+            require!(
+                token_account.mint == ctx.accounts.mint.key() && token_account.owner == beneficiary,
+                TokenError::InvalidTokenAccount
+            );
+        }
+        require!(
+            !list_flag(&ctx.accounts.beneficiary_blacklist, b"blacklist", &beneficiary)?,
+            TokenError::Blacklisted
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &ctx.accounts.vesting_schedule;
+        let claimable = schedule
+            .vested_amount(now)?
+            .saturating_sub(schedule.claimed_amount);
+        require!(claimable > 0, TokenError::NothingVested);
+
+        let bump_seed = [schedule.bump];
+        let seeds = &[b"vesting".as_ref(), beneficiary.as_ref(), &bump_seed[..]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: ctx.accounts.vesting_schedule.to_account_info(),
+                },
+                signer,
+            ),
+            claimable,
+        )?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.claimed_amount = schedule.claimed_amount
+            .checked_add(claimable)
+            .ok_or(TokenError::MathOverflow)?;
+
+        emit!(VestingClaimed {
+            beneficiary,
+            amount: claimable,
+            total_claimed: schedule.claimed_amount,
+        });
+        msg!("Claimed {} vested tokens for {}", claimable, beneficiary);
+        Ok(())
+    }
+
+    /// Mints the emissions accrued since the last mint to the schedule's recipient
+    ///
+    /// Callable by anyone. Mints `rate_per_second * (now - last_minted)`, with `now`
+    /// capped at the schedule's `end_time`, then advances `last_minted` so the same
+    /// period is never minted twice. The amount is clipped to the remaining supply
+    /// cap; emissions clipped this way are forfeited, not carried over.
+    ///
+    /// # Parameters
+    /// - `ctx`: MintScheduledEmission context
+    ///
+    /// # Errors
+    /// - `TokenError::EmergencyPaused` if protocol is paused
+    /// - `TokenError::InvalidTokenAccount` if `to` is not the schedule's recipient for this mint
+    /// - `TokenError::Blacklisted` if the recipient's owner is blacklisted
+    /// - `TokenError::NoEmissionDue` if nothing has accrued or the supply cap is reached
+    ///
+    /// # Events
+    /// - Emits `EmissionMinted` and `TokenMinted`
+    pub fn mint_scheduled_emission(ctx: Context<MintScheduledEmission>) -> Result<()> {
+        let bump = ctx.accounts.state.bump;
+        let state_account_info = ctx.accounts.state.to_account_info();
+        let state = &mut ctx.accounts.state;
+
+        require!(state.version >= state.min_compatible_version, TokenError::IncompatibleVersion);
+        require!(!state.emergency_paused, TokenError::EmergencyPaused);
+
+        let schedule = &mut ctx.accounts.emission_schedule;
         require!(
-            state.authority == ctx.accounts.governance.key(),
-            TokenError::Unauthorized
+            ctx.accounts.to.key() == schedule.recipient,
+            TokenError::InvalidTokenAccount
         );
-
-        msg!(
-            "Revoking mint authority for : {:?}",
-            ctx.accounts.mint.key()
+        let recipient_owner = {
+            let to_account_data = ctx.accounts.to.try_borrow_data()?;
+            let token_account = SplTokenAccount::unpack(&to_account_data)
+                .map_err(|_| TokenError::InvalidTokenAccount)?;
+            require!(token_account.mint == ctx.accounts.mint.key(), TokenError::InvalidTokenAccount);
+            token_account.owner
+        };
+        require!(
+            !list_flag(&ctx.accounts.recipient_blacklist, b"blacklist", &recipient_owner)?,
+            TokenError::Blacklisted
         );
 
-        // Create PDA signer
-        let bump = state.bump;
+        let now = Clock::get()?.unix_timestamp.min(schedule.end_time);
+        require!(now > schedule.last_minted, TokenError::NoEmissionDue);
+        let elapsed = (now - schedule.last_minted) as u64;
+        let mut amount = schedule
+            .rate_per_second
+            .checked_mul(elapsed)
+            .ok_or(TokenError::MathOverflow)?;
+        if let Some(max_supply) = state.max_supply {
+            amount = amount.min(max_supply.saturating_sub(state.current_supply));
+        }
+
+        // Advance before minting so the period can't be minted again
+        schedule.last_minted = now;
+        require!(amount > 0, TokenError::NoEmissionDue);
+
         let state_seed = b"state";
         let bump_seed = [bump];
         let seeds = &[state_seed.as_ref(), &bump_seed[..]];
         let signer = &[&seeds[..]];
 
-        // Call SPL Tokens set authority via CPI
-        token::set_authority(
+        token::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                SetAuthority {
-                    account_or_mint: ctx.accounts.mint.to_account_info(),
-                    current_authority: ctx.accounts.state.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: state_account_info,
                 },
                 signer,
             ),
-            AuthorityType::MintTokens,
-            None,
+            amount,
         )?;
-        
-        // Emit event
-        emit!(MintAuthorityRevoked {
-            mint: ctx.accounts.mint.key(),
+
+        state.current_supply = state.current_supply
+            .checked_add(amount)
+            .ok_or(TokenError::MathOverflow)?;
+
+        emit!(EmissionMinted {
+            amount,
+            recipient: recipient_owner,
+            minted_until: now,
         });
-        
-        msg!("Mint authority successfully revoked!");
+        emit!(TokenMinted {
+            amount,
+            recipient: recipient_owner,
+        });
+
+        msg!("Minted {} scheduled emission tokens up to {}", amount, now);
         Ok(())
     }
+
+    /// Reads every restriction flag of an address in one call
+    ///
+    /// Read-only view for compliance tooling: returns the blacklist, restricted,
+    /// whitelist, no-sell-limit and liquidity-pool flags of `account` (plus whether
+    /// it is protected from blacklisting) as return data. List entries that were
+    /// never created read as not set.
+    ///
+    /// # Parameters
+    /// - `ctx`: GetAddressProfile context with the address and its list PDAs
+    ///
+    /// # Returns
+    /// - `Result<AddressProfile>`: The address's flags
+    ///
+    /// # Errors
+    /// - `TokenError::InvalidListAccount` if a list account is not the address's PDA
+    pub fn get_address_profile(ctx: Context<GetAddressProfile>) -> Result<AddressProfile> {
+        let account = ctx.accounts.account.key();
+        let profile = AddressProfile {
+            account,
+            is_blacklisted: list_flag(&ctx.accounts.blacklist, b"blacklist", &account)?,
+            is_restricted: list_flag(&ctx.accounts.restricted, b"restricted", &account)?,
+            is_whitelisted: list_flag(&ctx.accounts.whitelist, b"whitelist", &account)?,
+            has_no_sell_limit: no_sell_limit_active(&ctx.accounts.no_sell_limit, &account)?,
+            is_liquidity_pool: list_flag(&ctx.accounts.liquidity_pool, b"liquiditypool", &account)?,
+            is_protected: ctx.accounts.state.is_protected(&account),
+        };
+        Ok(profile)
+    }
 }
 
 // Context Structures
@@ -1208,6 +2804,19 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// MigrateTokenState - Resize and re-version the state PDA
+#[derive(Accounts)]
+pub struct MigrateTokenState<'info> {
+    #[account(mut)]
+    /// CHECK: PDA, owner and authority are verified manually in the function to handle old layouts
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 // ProposeGovernanceChange - Propose new governance (requires cooldown)
 #[derive(Accounts)]
 pub struct ProposeGovernanceChange<'info> {
@@ -1235,12 +2844,209 @@ pub struct SetGovernance<'info> {
 
     pub authority: Signer<'info>,
 
-    pub clock: Sysvar<'info, Clock>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// MintTokens
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token account (validated by token program)
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    /// CHECK: Optional blacklist account for recipient (validated in function)
+    pub recipient_blacklist: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokensBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// BurnTokens
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token account (validated by token program)
+    #[account(mut)]
+    pub from: UncheckedAccount<'info>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct GovernanceTransfer<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (validated in function)
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Source token account (validated in function)
+    #[account(mut)]
+    pub from: UncheckedAccount<'info>,
+
+    /// CHECK: Destination token account (validated in function)
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+
+    /// CHECK: Blacklist PDA of the source owner (validated in function)
+    pub source_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// TransferTokens with restrictions
+#[derive(Accounts)]
+pub struct TransferTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (decimals read in function, validated by transfer_checked)
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token account for sender (validated by token program)
+    /// Using UncheckedAccount and validating manually to avoid derive macro issues
+    #[account(mut)]
+    pub from_account: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token account for recipient (validated by token program)
+    /// Using UncheckedAccount and validating manually to avoid derive macro issues
+    #[account(mut)]
+    pub to_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SellTracker::LEN,
+        seeds = [
+            b"selltracker",
+            state.sell_tracker_key(&authority.key(), &from_account.key()).as_ref()
+        ],
+        bump
+    )]
+    pub sell_tracker: Account<'info, SellTracker>,
+
+    /// CHECK: Blacklist PDA of the sender (validated in function, may be uninitialized)
+    pub sender_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Blacklist PDA of the recipient (validated in function, may be uninitialized)
+    pub recipient_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Restricted PDA of the sender (validated in function, may be uninitialized)
+    pub sender_restricted: UncheckedAccount<'info>,
+
+    /// CHECK: Restricted PDA of the recipient (validated in function, may be uninitialized)
+    pub recipient_restricted: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the recipient owner (validated in function, may be uninitialized)
+    pub liquidity_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Account of the recipient token account's owner, used for pool detection
+    /// (required if restrict_to_known_pools enabled, validated in function)
+    pub recipient_owner: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: No-sell-limit PDA of the sender (validated in function, may be uninitialized)
+    pub no_sell_limit: UncheckedAccount<'info>,
+
+    /// CHECK: Whitelist PDA of the sender (validated in function if whitelist_mode enabled)
+    pub sender_whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: Whitelist PDA of the recipient (validated in function if whitelist_mode enabled)
+    pub recipient_whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: Treasury token account receiving the transfer fee
+    /// (required if a fee is charged and not burned, validated in function)
+    #[account(mut)]
+    pub fee_destination: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeMintAuthority<'info> {
+    #[account(
+        seeds=[b"state"],
+        bump=state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
 }
 
-// MintTokens
 #[derive(Accounts)]
-pub struct MintTokens<'info> {
+pub struct SetEmissionSchedule<'info> {
     #[account(
         seeds = [b"state"],
         bump = state.bump,
@@ -1248,26 +3054,27 @@ pub struct MintTokens<'info> {
     )]
     pub state: Account<'info, TokenState>,
 
-    /// CHECK: SPL Token mint account (validated by token program)
-    #[account(mut)]
-    pub mint: UncheckedAccount<'info>,
-
-    /// CHECK: SPL Token account (validated by token program)
-    #[account(mut)]
-    pub to: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + EmissionSchedule::LEN,
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
 
     /// CHECK: Governance program or authority (validated by constraint)
     pub governance: Signer<'info>,
 
-    /// CHECK: Optional blacklist account for recipient (validated in function)
-    pub recipient_blacklist: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-// BurnTokens
 #[derive(Accounts)]
-pub struct BurnTokens<'info> {
+#[instruction(beneficiary: Pubkey)]
+pub struct MintWithVesting<'info> {
     #[account(
         mut,
         seeds = [b"state"],
@@ -1276,103 +3083,130 @@ pub struct BurnTokens<'info> {
     )]
     pub state: Account<'info, TokenState>,
 
-    /// CHECK: SPL Token mint account (validated by token program)
     #[account(mut)]
-    pub mint: UncheckedAccount<'info>,
+    pub mint: Account<'info, token::Mint>,
 
-    /// CHECK: SPL Token account (validated by token program)
-    #[account(mut)]
-    pub from: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VestingSchedule::LEN,
+        seeds = [b"vesting", beneficiary.as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = vesting_schedule,
+        seeds = [b"vesting_vault", beneficiary.as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
 
     /// CHECK: Governance program or authority (validated by constraint)
     pub governance: Signer<'info>,
 
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-// TransferTokens with restrictions
 #[derive(Accounts)]
-pub struct TransferTokens<'info> {
+pub struct ClaimVesting<'info> {
     #[account(
-        mut,
         seeds = [b"state"],
         bump = state.bump
     )]
     pub state: Account<'info, TokenState>,
 
-    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(
+        mut,
+        seeds = [b"vesting", beneficiary.key().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", beneficiary.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: SPL Token mint account (compared against `to` in function)
     pub mint: UncheckedAccount<'info>,
 
-    /// CHECK: SPL Token account for sender (validated by token program)
-    /// Using UncheckedAccount and validating manually to avoid derive macro issues
+    /// CHECK: Beneficiary token account receiving the tokens (validated in function)
     #[account(mut)]
-    pub from_account: UncheckedAccount<'info>,
+    pub to: UncheckedAccount<'info>,
 
-    /// CHECK: SPL Token account for recipient (validated by token program)
-    /// Using UncheckedAccount and validating manually to avoid derive macro issues
-    #[account(mut)]
-    pub to_account: UncheckedAccount<'info>,
+    /// CHECK: Blacklist PDA of the beneficiary (validated in function, may be uninitialized)
+    pub beneficiary_blacklist: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    pub beneficiary: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+}
 
+#[derive(Accounts)]
+pub struct MintScheduledEmission<'info> {
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = 8 + SellTracker::LEN,
-        seeds = [b"selltracker", authority.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
     )]
-    pub sell_tracker: Account<'info, SellTracker>,
-
-    /// CHECK: Optional blacklist account for sender
-    pub sender_blacklist: UncheckedAccount<'info>,
-
-    /// CHECK: Optional blacklist account for recipient
-    pub recipient_blacklist: UncheckedAccount<'info>,
-
-    /// CHECK: Optional restricted account for sender
-    pub sender_restricted: UncheckedAccount<'info>,
-
-    /// CHECK: Optional restricted account for recipient
-    pub recipient_restricted: UncheckedAccount<'info>,
-
-    /// CHECK: Optional liquidity pool account
-    pub liquidity_pool: UncheckedAccount<'info>,
+    pub state: Account<'info, TokenState>,
 
-    /// CHECK: Optional no-sell-limit exemption account
-    pub no_sell_limit: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"emission_schedule"],
+        bump = emission_schedule.bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
 
-    /// CHECK: Optional whitelist account for sender (required if whitelist_mode enabled)
-    pub sender_whitelist: UncheckedAccount<'info>,
+    /// CHECK: SPL Token mint account (validated by token program)
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
 
-    /// CHECK: Optional whitelist account for recipient (required if whitelist_mode enabled)
-    pub recipient_whitelist: UncheckedAccount<'info>,
+    /// CHECK: Schedule recipient token account (validated in function)
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    /// CHECK: Blacklist PDA of the recipient's owner (validated in function, may be uninitialized)
+    pub recipient_blacklist: UncheckedAccount<'info>,
 
-    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct RevokeMintAuthority<'info> {
+pub struct GetAddressProfile<'info> {
     #[account(
-        seeds=[b"state"],
-        bump=state.bump,
-        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+        seeds = [b"state"],
+        bump = state.bump
     )]
     pub state: Account<'info, TokenState>,
 
-    /// CHECK: SPL Token mint account (validated by token program)
-    #[account(mut)]
-    pub mint: UncheckedAccount<'info>,
+    /// CHECK: Address being profiled (only its key is read)
+    pub account: UncheckedAccount<'info>,
 
-    /// CHECK: Governance program or authority (validated by constraint)
-    pub governance: Signer<'info>,
+    /// CHECK: Blacklist PDA of the address (validated in function, may be uninitialized)
+    pub blacklist: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Restricted PDA of the address (validated in function, may be uninitialized)
+    pub restricted: UncheckedAccount<'info>,
+
+    /// CHECK: Whitelist PDA of the address (validated in function, may be uninitialized)
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: No-sell-limit PDA of the address (validated in function, may be uninitialized)
+    pub no_sell_limit: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidity pool PDA of the address (validated in function, may be uninitialized)
+    pub liquidity_pool: UncheckedAccount<'info>,
 }
 
 // Account structures
@@ -1393,6 +3227,18 @@ pub struct TokenState {
     pub whitelist_mode: bool, // If true, only whitelisted addresses can transfer
     pub version: u16,
     pub min_compatible_version: u16,
+    pub restrict_to_known_pools: bool, // If true, transfers to unregistered pools are rejected
+    pub dex_programs: Vec<Pubkey>, // DEX program ids used to detect pools (max MAX_DEX_PROGRAMS)
+    pub sell_limits_frozen: bool, // If true, sell-limit enforcement is suspended
+    pub protected_addresses: Vec<Pubkey>, // Addresses that cannot be blacklisted (max MAX_PROTECTED_ADDRESSES)
+    pub sell_tracker_mode: SellTrackerMode, // Whether sell limits are tracked per wallet or per token account
+    pub transfer_fee_bps: u16, // Fee deducted from transfers in basis points (0 = no fee)
+    pub transfer_fee_burn: bool, // If true, the fee is burned instead of sent to the treasury
+    pub treasury_address: Pubkey, // Owner of the token account receiving transfer fees
+    pub fee_whitelist_exempt: bool, // If true, whitelisted senders pay no transfer fee
+    pub circuit_breaker_enabled: bool, // If true, transfers above the threshold are rejected
+    pub circuit_breaker_threshold_bps: u16, // Largest single transfer, in bps of current_supply
+    pub governance_ops_during_pause: bool, // If true, governance mint/burn/seize run while paused
 }
 
 impl TokenState {
@@ -1400,7 +3246,32 @@ impl TokenState {
     // Size: 8 (discriminator) + 32 (authority) + 1 (bump) + 1 (emergency_paused) + 1 (sell_limit_percent) + 8 (sell_limit_period) + 32 (bridge_address) + 32 (bond_address) + 33 (Option<Pubkey>) + 9 (Option<i64>) + 9 (Option<u64>) + 8 (u64) + 1 (bool)
     pub const CURRENT_VERSION: u16 = 1;
     pub const MIN_COMPATIBLE_VERSION: u16 = 1;
-    pub const LEN: usize = 8 + 32 + 1 + 1 + 1 + 8 + 32 + 32 + 33 + 9 + 9 + 8 + 1 + 2 + 2;
+    pub const MAX_DEX_PROGRAMS: usize = 8;
+    pub const MAX_PROTECTED_ADDRESSES: usize = 8;
+    pub const MAX_TRANSFER_FEE_BPS: u16 = 200; // 2%
+    pub const MAX_MINT_BATCH: usize = 10; // Recipients per mint_tokens_batch, bounded by compute
+    pub const MIN_CIRCUIT_BREAKER_BPS: u16 = 50; // 0.5% of supply
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 1 + 8 + 32 + 32 + 33 + 9 + 9 + 8 + 1 + 2 + 2 + 1 + (4 + 32 * Self::MAX_DEX_PROGRAMS) + 1
+        + (4 + 32 * Self::MAX_PROTECTED_ADDRESSES) + 1 + 2 + 1 + 32 + 1 + 1 + 2 + 1;
+
+    /// Key the sell tracker PDA is derived from: the signing wallet, or the source
+    /// token account in `PerTokenAccount` mode
+    pub fn sell_tracker_key(&self, authority: &Pubkey, from_account: &Pubkey) -> Pubkey {
+        match self.sell_tracker_mode {
+            SellTrackerMode::PerWallet => *authority,
+            SellTrackerMode::PerTokenAccount => *from_account,
+        }
+    }
+
+    /// Whether governance supply operations are blocked by the emergency pause
+    pub fn governance_ops_paused(&self) -> bool {
+        self.emergency_paused && !self.governance_ops_during_pause
+    }
+
+    /// Whether `account` may never be blacklisted: the authority (governance) or a listed address
+    pub fn is_protected(&self, account: &Pubkey) -> bool {
+        *account == self.authority || self.protected_addresses.contains(account)
+    }
 }
 
 #[account]
@@ -1427,10 +3298,11 @@ impl Whitelist {
 pub struct NoSellLimit {
     pub account: Pubkey,
     pub has_exemption: bool,
+    pub expires_at: Option<i64>, // Exemption lapses after this time (None = permanent)
 }
 
 impl NoSellLimit {
-    pub const LEN: usize = 8 + 32 + 1; // [8 discriminator + 32 Pubkey + 1 bool]
+    pub const LEN: usize = 8 + 32 + 1 + 9; // [8 discriminator + 32 Pubkey + 1 bool + 9 Option<i64>]
 }
 
 #[account]
@@ -1453,6 +3325,105 @@ impl LiquidityPool {
     pub const LEN: usize = 8 + 32 + 1; // [8 discriminator + 32 Pubkey + 1 bool]
 }
 
+#[account]
+pub struct EmissionSchedule {
+    pub rate_per_second: u64, // Tokens (base units) emitted per second
+    pub last_minted: i64, // Emissions up to this time have been minted
+    pub recipient: Pubkey, // Token account receiving emissions
+    pub end_time: i64, // Emissions stop accruing at this time
+    pub bump: u8,
+}
+
+impl EmissionSchedule {
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 1; // rate + last_minted + recipient + end_time + bump
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub total_amount: u64, // Tokens locked in the vesting vault at creation
+    pub claimed_amount: u64, // Tokens released to the beneficiary so far
+    pub start_time: i64,
+    pub cliff_seconds: i64, // Nothing unlocks before start_time + cliff_seconds
+    pub duration_seconds: i64, // Everything has unlocked at start_time + duration_seconds
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1; // beneficiary + amounts + times + bump
+
+    /// Tokens unlocked at `now`: none before the cliff, then linear over the duration
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_time);
+        if elapsed < self.cliff_seconds {
+            return Ok(0);
+        }
+        if elapsed >= self.duration_seconds {
+            return Ok(self.total_amount);
+        }
+        Ok((self.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|x| x.checked_div(self.duration_seconds as u128))
+            .ok_or(TokenError::MathOverflow)? as u64)
+    }
+}
+
+/// Restriction flags of one address, returned by `get_address_profile`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AddressProfile {
+    pub account: Pubkey,
+    pub is_blacklisted: bool,
+    pub is_restricted: bool,
+    pub is_whitelisted: bool,
+    pub has_no_sell_limit: bool,
+    pub is_liquidity_pool: bool,
+    pub is_protected: bool, // Can never be blacklisted (authority or protected address)
+}
+
+/// How `transfer_tokens` derives the sell tracker PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SellTrackerMode {
+    /// `[b"selltracker", authority]`: token accounts of one wallet share a limit
+    PerWallet,
+    /// `[b"selltracker", from_account]`: each token account has its own limit
+    PerTokenAccount,
+}
+
+#[account]
+pub struct VelocityTracker {
+    pub period_start: i64,
+    pub period_volume: u128,
+    pub period_transfers: u64,
+    pub last_updated: i64,
+}
+
+impl VelocityTracker {
+    pub const LEN: usize = 8 + 16 + 8 + 8; // period_start + period_volume + period_transfers + last_updated
+    /// Length of a velocity period
+    pub const PERIOD_SECONDS: i64 = 3600;
+    /// `VelocityUpdated` is emitted every this many transfers within a period
+    pub const EMIT_INTERVAL: u64 = 100;
+
+    /// Adds a transfer of `amount` at `now`, starting a new period once the current one is over
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now - self.period_start > Self::PERIOD_SECONDS {
+            self.period_volume = 0;
+            self.period_transfers = 0;
+            self.period_start = now;
+        }
+        self.period_volume = self
+            .period_volume
+            .checked_add(amount as u128)
+            .ok_or(TokenError::MathOverflow)?;
+        self.period_transfers = self
+            .period_transfers
+            .checked_add(1)
+            .ok_or(TokenError::MathOverflow)?;
+        self.last_updated = now;
+        Ok(())
+    }
+}
+
 #[account]
 pub struct SellTracker {
     pub account: Pubkey,
@@ -1479,6 +3450,19 @@ pub struct SetEmergencyPause<'info> {
     pub governance: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ToggleWhitelistMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: Governance program or authority (validated in function)
+    pub governance: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetBlacklist<'info> {
     #[account(
@@ -1572,6 +3556,17 @@ pub struct SetNoSellLimit<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct CleanupExpiredNoSellLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"noselllimit", account.as_ref()],
+        bump
+    )]
+    pub no_sell_limit: Account<'info, NoSellLimit>,
+}
+
 #[derive(Accounts)]
 pub struct SetRestricted<'info> {
     #[account(
@@ -1648,6 +3643,48 @@ pub struct SetBridgeAddress<'info> {
     pub governance: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetMaxSupply<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolRestriction<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSellLimitsFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetBondAddress<'info> {
     #[account(
@@ -1661,3 +3698,89 @@ pub struct SetBondAddress<'info> {
     /// CHECK: Governance program or authority (validated by constraint)
     pub governance: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct ResetVelocityTracker<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.authority == governance.key() @ TokenError::Unauthorized
+    )]
+    pub state: Account<'info, TokenState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VelocityTracker::LEN,
+        seeds = [b"velocity"],
+        bump
+    )]
+    pub velocity_tracker: Account<'info, VelocityTracker>,
+
+    /// CHECK: Governance program or authority (validated by constraint)
+    pub governance: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records a transfer of `amount` on the `VelocityTracker` passed as a remaining account
+fn record_velocity(account: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+    let (expected_pda, _) = Pubkey::find_program_address(&[b"velocity"], &crate::ID);
+    require!(
+        account.key() == expected_pda && account.owner == &crate::ID && account.is_writable,
+        TokenError::InvalidVelocityTracker
+    );
+    let mut data = account.try_borrow_mut_data()?;
+    let mut tracker = VelocityTracker::try_deserialize(&mut &data[..])?;
+    tracker.record(amount, now)?;
+    tracker.try_serialize(&mut &mut data[..])?;
+
+    if tracker.period_transfers % VelocityTracker::EMIT_INTERVAL == 0 {
+        emit!(VelocityUpdated {
+            period_volume: tracker.period_volume,
+            period_transfers: tracker.period_transfers,
+            period_start: tracker.period_start,
+        });
+    }
+    Ok(())
+}
+
+/// Reads the flag of a list entry (blacklist, restricted, whitelist, pool, no-sell-limit)
+///
+/// `account` must be the PDA `[seed, key]` of this program. An entry that was never
+/// created counts as unflagged.
+fn list_flag(account: &UncheckedAccount, seed: &[u8], key: &Pubkey) -> Result<bool> {
+    let (expected_pda, _) = Pubkey::find_program_address(&[seed, key.as_ref()], &crate::ID);
+    require!(
+        account.key() == expected_pda,
+        TokenError::InvalidListAccount
+    );
+    if account.owner != &crate::ID {
+        return Ok(false);
+    }
+    let data = account.try_borrow_data()?;
+    // Account discriminator (8) + Pubkey (32) + flag bool (1) = offset 40
+    Ok(data.len() >= 41 && data[40] != 0)
+}
+
+/// Reads a no-sell-limit entry like `list_flag`, treating an expired exemption as none
+fn no_sell_limit_active(account: &UncheckedAccount, key: &Pubkey) -> Result<bool> {
+    if !list_flag(account, b"noselllimit", key)? {
+        return Ok(false);
+    }
+    let data = account.try_borrow_data()?;
+    // Option<i64> expiry follows the flag: tag at offset 41, timestamp at 42..50.
+    // Entries created before expiry tracking read a zero tag (permanent).
+    if data.len() < 50 || data[41] == 0 {
+        return Ok(true);
+    }
+    let expires_at = i64::from_le_bytes(
+        data[42..50]
+            .try_into()
+            .map_err(|_| TokenError::InvalidListAccount)?,
+    );
+    Ok(Clock::get()?.unix_timestamp <= expires_at)
+}
@@ -99,6 +99,11 @@ const COOLDOWN_PERIOD = parseInt(
   cliArgs.cooldownPeriod || process.env.COOLDOWN_PERIOD || "1800"
 );
 
+// Bootstrap mode: setup transactions skip cooldown during this window after init
+// (0 disables; max 86400 seconds / 20 transactions, fixed at init)
+const BOOTSTRAP_WINDOW_SECONDS = parseInt(process.env.BOOTSTRAP_WINDOW_SECONDS || "0");
+const BOOTSTRAP_MAX_TRANSACTIONS = parseInt(process.env.BOOTSTRAP_MAX_TRANSACTIONS || "0");
+
 // Parse signers
 let SIGNERS: PublicKey[] = [];
 const signersInput = cliArgs.signers || process.env.SIGNERS;
@@ -685,7 +690,13 @@ async function main() {
 
   try {
     const govTx = await governanceProgram.methods
-      .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), SIGNERS)
+      .initialize(
+        REQUIRED_APPROVALS,
+        new anchor.BN(COOLDOWN_PERIOD),
+        SIGNERS,
+        new anchor.BN(BOOTSTRAP_WINDOW_SECONDS),
+        BOOTSTRAP_MAX_TRANSACTIONS
+      )
       .accountsPartial({
         governanceState: governanceStatePda,
         authority: walletKeypair.publicKey,
@@ -29,6 +29,11 @@ const COOLDOWN_PERIOD = parseInt(
   cliArgs.cooldownPeriod || process.env.COOLDOWN_PERIOD || "1800"
 ); // 30 minutes in seconds
 
+// Bootstrap mode: setup transactions skip cooldown during this window after init
+// (0 disables; max 86400 seconds / 20 transactions, fixed at init)
+const BOOTSTRAP_WINDOW_SECONDS = parseInt(process.env.BOOTSTRAP_WINDOW_SECONDS || "0");
+const BOOTSTRAP_MAX_TRANSACTIONS = parseInt(process.env.BOOTSTRAP_MAX_TRANSACTIONS || "0");
+
 // Parse signers from CLI args or environment
 // Format: --signers "pubkey1,pubkey2,pubkey3" or SIGNERS="pubkey1,pubkey2,pubkey3"
 let SIGNERS: PublicKey[] = [];
@@ -156,7 +161,13 @@ async function main() {
   // Initialize governance
   try {
     const tx = await program.methods
-      .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), SIGNERS)
+      .initialize(
+        REQUIRED_APPROVALS,
+        new anchor.BN(COOLDOWN_PERIOD),
+        SIGNERS,
+        new anchor.BN(BOOTSTRAP_WINDOW_SECONDS),
+        BOOTSTRAP_MAX_TRANSACTIONS
+      )
       .accountsPartial({
         governanceState: governanceStatePda,
         authority: walletKeypair.publicKey,
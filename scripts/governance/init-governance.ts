@@ -36,6 +36,8 @@ async function main() {
   // Configuration
   const REQUIRED_APPROVALS = 2; // Minimum 2-of-N
   const COOLDOWN_PERIOD = 1800; // 30 minutes in seconds
+  const BOOTSTRAP_WINDOW_SECONDS = 0; // Setup txs skip cooldown during this window (0 = disabled, max 24h)
+  const BOOTSTRAP_MAX_TRANSACTIONS = 0; // Max cooldown-exempt setup txs (max 20)
   const SIGNERS = [
     walletKeypair.publicKey, // Add more signer addresses here
     // new PublicKey("SIGNER_2_ADDRESS"),
@@ -49,7 +51,13 @@ async function main() {
   console.log("   Signers:", SIGNERS.length);
 
   const tx = await program.methods
-    .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), SIGNERS)
+    .initialize(
+      REQUIRED_APPROVALS,
+      new anchor.BN(COOLDOWN_PERIOD),
+      SIGNERS,
+      new anchor.BN(BOOTSTRAP_WINDOW_SECONDS),
+      BOOTSTRAP_MAX_TRANSACTIONS
+    )
     .accountsPartial({
       governanceState: governanceStatePda,
       authority: walletKeypair.publicKey,
@@ -99,7 +99,7 @@ async function main() {
     // The actual implementation would need to handle the transaction PDA derivation
     // For now, we'll use a simplified approach
     const tx = await program.methods
-      .queueSetTreasuryAddress(TREASURY_ADDRESS)
+      .queueSetTreasuryAddress(TREASURY_ADDRESS, "Update treasury address")
       .accountsPartial({
         governanceState: governanceStatePda,
         transaction: transactionPda,
@@ -117,6 +117,36 @@ async function main() {
     program.programId
   );
 
+  // Each purchase creates a PurchaseRecord indexed by the buyer's purchase count
+  const userPurchase = await program.account.userPurchase.fetchNullable(userPurchasePda);
+  const purchaseIndex = Buffer.alloc(4);
+  purchaseIndex.writeUInt32LE(userPurchase ? userPurchase.purchaseCount : 0);
+  const [purchaseRecordPda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("purchase_record"),
+      presaleStatePda.toBuffer(),
+      walletKeypair.publicKey.toBuffer(),
+      purchaseIndex,
+    ],
+    program.programId
+  );
+
+  // The presale checks the buyer's blacklist PDA in the token program
+  const [buyerBlacklistPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("blacklist"), walletKeypair.publicKey.toBuffer()],
+    tokenProgramId
+  );
+
+  // SOL purchases are tracked under the default pubkey
+  const [solRaisedTrackerPda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("raised_tracker"),
+      presaleStatePda.toBuffer(),
+      PublicKey.default.toBuffer(),
+    ],
+    program.programId
+  );
+
   // Chainlink SOL/USD feed addresses
   // Mainnet: CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt
   // Devnet: 99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR
@@ -134,20 +164,30 @@ async function main() {
 
   try {
     const tx = await program.methods
-      .buyWithSol(new anchor.BN(solAmountLamports))
+      .buyWithSol(new anchor.BN(solAmountLamports), null, null, new anchor.BN(0), [], false)
       .accountsPartial({
         presaleState: presaleStatePda,
         buyer: walletKeypair.publicKey,
         tokenState: tokenStatePda,
-        buyerBlacklist: SystemProgram.programId,
+        buyerBlacklist: buyerBlacklistPda,
         solVault: solVault,
         presaleTokenVault: presaleTokenVault,
         presaleTokenVaultPda: presaleTokenVaultPda,
         buyerTokenAccount: buyerTokenAccount,
         chainlinkFeed: chainlinkFeed, // Add Chainlink feed account
+        presaleTokenMint: presaleTokenMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         userPurchase: userPurchasePda,
+        purchaseRecord: purchaseRecordPda,
+        raisedTracker: solRaisedTrackerPda,
+        chainlinkFeedBackup: null,
+        presaleRound: null,
+        nextPresaleRound: null,
+        buyerWhitelist: null,
+        referralAccount: null,
+        referrerBlacklist: null,
+        vestingAccount: null,
       })
       .rpc();
 
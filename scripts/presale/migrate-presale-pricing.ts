@@ -284,7 +284,7 @@ async function main() {
     console.log("   2. Chainlink SOL/USD feeds:");
     console.log("      - Mainnet: CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt");
     console.log("      - Devnet: 99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR");
-    console.log("   3. Program validates feed owner (Chainlink OCR2), not specific address");
+    console.log("   3. Pin the feed with set_oracle_type; buy_with_sol rejects any other feed");
     console.log("   4. Test buy_with_sol with the Chainlink feed account");
 
   } catch (err: any) {
@@ -255,8 +255,17 @@ async function main() {
   const feedAddress = isMainnet ? CHAINLINK_FEED_MAINNET : CHAINLINK_FEED_DEVNET;
 
   try {
+    const state = await program.account.presaleState.fetch(presaleStatePda);
     const feedAccount = await connection.getAccountInfo(feedAddress);
-    if (feedAccount && feedAccount.data.length > 0) {
+    if (!state.oracleFeed.equals(feedAddress)) {
+      checks.push({
+        check: "Oracle",
+        passed: false,
+        message: "❌ Oracle feed is not configured on the presale",
+        details: `Pin it with set_oracle_type (configured: ${state.oracleFeed.toString()}, expected: ${feedAddress.toString()})`,
+      });
+      console.log("   ❌ Oracle feed is not configured on the presale");
+    } else if (feedAccount && feedAccount.data.length > 0) {
       checks.push({
         check: "Oracle",
         passed: true,
@@ -244,6 +244,27 @@ describe("SPL Token & Governance Tests - Fixed", () => {
           console.log("✓ Correctly prevented double initialization");
         }
       });
+
+      it("Rejects migrating a state that is already current", async () => {
+        try {
+          await tokenProgram.methods
+            .migrateTokenState()
+            .accounts({
+              state: tokenStatePda,
+              authority: authority.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([authority])
+            .rpc();
+
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("AlreadyMigrated");
+          const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+          expect(stateAccount.version).to.equal(1);
+          console.log("✓ Correctly rejected migrating a current state");
+        }
+      });
     });
 
     describe("Mint Setup", () => {
@@ -490,6 +511,316 @@ describe("SPL Token & Governance Tests - Fixed", () => {
       }
     });
 
+    it("Fails to batch mint tokens from non-authority", async () => {
+      const [recipientBlacklistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), user.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+
+      try {
+        await tokenProgram.methods
+          .mintTokensBatch([new anchor.BN(1)])
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            governance: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([
+            { pubkey: userTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: recipientBlacklistPda, isSigner: false, isWritable: false },
+          ])
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        const errMsg = err.toString().toLowerCase();
+        expect(errMsg.includes("unauthorized") || errMsg.includes("constraint")).to.be.true;
+        console.log("✓ Correctly prevented unauthorized batch minting");
+      }
+    });
+
+    it("Fails to seize tokens from non-authority", async () => {
+      const [sourceBlacklistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), user.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+
+      try {
+        await tokenProgram.methods
+          .governanceTransfer(new anchor.BN(1))
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            from: userTokenAccount,
+            to: userTokenAccount,
+            sourceBlacklist: sourceBlacklistPda,
+            governance: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        const errMsg = err.toString().toLowerCase();
+        expect(errMsg.includes("unauthorized") || errMsg.includes("constraint")).to.be.true;
+        console.log("✓ Correctly prevented unauthorized seizure");
+      }
+    });
+
+    it("Fails to mint with vesting from non-authority", async () => {
+      const beneficiary = Keypair.generate().publicKey;
+      const [vestingSchedulePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting"), beneficiary.toBuffer()],
+        tokenProgram.programId
+      );
+      const [vestingVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting_vault"), beneficiary.toBuffer()],
+        tokenProgram.programId
+      );
+
+      try {
+        await tokenProgram.methods
+          .mintWithVesting(beneficiary, new anchor.BN(1_000), new anchor.BN(60), new anchor.BN(3_600))
+          .accountsPartial({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            vestingSchedule: vestingSchedulePda,
+            vestingVault: vestingVaultPda,
+            governance: user.publicKey,
+            payer: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        const errMsg = err.toString().toLowerCase();
+        expect(errMsg.includes("unauthorized") || errMsg.includes("constraint")).to.be.true;
+        const schedule = await tokenProgram.account.vestingSchedule.fetchNullable(vestingSchedulePda);
+        expect(schedule).to.be.null;
+        console.log("✓ Correctly prevented unauthorized vesting mint");
+      }
+    });
+
+    it("Fails to set max supply from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setMaxSupply(new anchor.BN(1), false)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.maxSupply).to.be.null;
+        console.log("✓ Correctly prevented unauthorized supply cap change");
+      }
+    });
+
+    it("Fails to enable known-pools mode from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setRestrictToKnownPools(true)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.restrictToKnownPools).to.be.false;
+        expect(stateAccount.dexPrograms).to.have.length(0);
+        console.log("✓ Correctly prevented unauthorized known-pools mode change");
+      }
+    });
+
+    it("Fails to freeze sell limits from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setSellLimitsFrozen(true)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.sellLimitsFrozen).to.be.false;
+        console.log("✓ Correctly prevented unauthorized sell-limit freeze");
+      }
+    });
+
+    it("Fails to change the sell tracker mode from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setSellTrackerMode({ perTokenAccount: {} })
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.sellTrackerMode.perWallet).to.not.be.undefined;
+        console.log("✓ Correctly prevented unauthorized sell tracker mode change");
+      }
+    });
+
+    it("Fails to set the transfer fee from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setTransferFee(100, true, PublicKey.default, false)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.transferFeeBps).to.equal(0);
+        console.log("✓ Correctly prevented unauthorized transfer fee change");
+      }
+    });
+
+    it("Fails to set the circuit breaker from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setCircuitBreaker(true, 100)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.circuitBreakerEnabled).to.be.false;
+        console.log("✓ Correctly prevented unauthorized circuit breaker change");
+      }
+    });
+
+    it("Fails to allow governance operations during pause from non-authority", async () => {
+      try {
+        await tokenProgram.methods
+          .setGovernanceOpsDuringPause(true)
+          .accounts({
+            state: tokenStatePda,
+            governance: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        expect(stateAccount.governanceOpsDuringPause).to.be.false;
+        console.log("✓ Correctly prevented unauthorized pause exemption change");
+      }
+    });
+
+    it("Reads the restriction profile of an address", async () => {
+      const profileAccounts = (account: PublicKey) => {
+        const pda = (seed: string) =>
+          PublicKey.findProgramAddressSync([Buffer.from(seed), account.toBuffer()], tokenProgram.programId)[0];
+        return {
+          state: tokenStatePda,
+          account,
+          blacklist: pda("blacklist"),
+          restricted: pda("restricted"),
+          whitelist: pda("whitelist"),
+          noSellLimit: pda("noselllimit"),
+          liquidityPool: pda("liquiditypool"),
+        };
+      };
+
+      // An address with no list entries reads as unflagged
+      const fresh = Keypair.generate().publicKey;
+      const profile = await tokenProgram.methods.getAddressProfile()
+        .accounts(profileAccounts(fresh))
+        .view();
+      expect(profile.account.equals(fresh)).to.be.true;
+      expect(profile.isBlacklisted).to.be.false;
+      expect(profile.isRestricted).to.be.false;
+      expect(profile.isWhitelisted).to.be.false;
+      expect(profile.hasNoSellLimit).to.be.false;
+      expect(profile.isLiquidityPool).to.be.false;
+      expect(profile.isProtected).to.be.false;
+
+      const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      const authorityProfile = await tokenProgram.methods.getAddressProfile()
+        .accounts(profileAccounts(stateAccount.authority))
+        .view();
+      expect(authorityProfile.isProtected).to.be.true;
+
+      // List accounts must be the address's own PDAs
+      try {
+        await tokenProgram.methods.getAddressProfile()
+          .accounts({ ...profileAccounts(fresh), blacklist: profileAccounts(user.publicKey).blacklist })
+          .view();
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidListAccount");
+      }
+      console.log("✓ Address profile read in one call");
+    });
+
+    it("Fails to set an emission schedule from non-authority", async () => {
+      const [emissionSchedulePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("emission_schedule")],
+        tokenProgram.programId
+      );
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await tokenProgram.methods
+          .setEmissionSchedule(new anchor.BN(1), userTokenAccount, new anchor.BN(now), new anchor.BN(now + 3600))
+          .accountsPartial({
+            state: tokenStatePda,
+            emissionSchedule: emissionSchedulePda,
+            governance: user.publicKey,
+            payer: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should fail");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+        expect(await connection.getAccountInfo(emissionSchedulePda)).to.be.null;
+        console.log("✓ Correctly prevented unauthorized emission schedule");
+      }
+    });
+
     describe("Transfer Tokens", () => {
       it("Transfers tokens between accounts", async () => {
         const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
@@ -560,14 +891,14 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         const [recipientRestrictedPda] = PublicKey.findProgramAddressSync([Buffer.from("restricted"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         const [senderWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), user.publicKey.toBuffer()], tokenProgram.programId);
         const [recipientWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), recipient.publicKey.toBuffer()], tokenProgram.programId);
-        const [liquidityPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("liquiditypool"), PublicKey.default.toBuffer()], tokenProgram.programId);
+        const [liquidityPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("liquiditypool"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         const [noSellLimitPda] = PublicKey.findProgramAddressSync([Buffer.from("noselllimit"), user.publicKey.toBuffer()], tokenProgram.programId);
 
         const senderBefore = await getAccount(connection, userTokenAccount);
         const recipientBefore = await getAccount(connection, recipientTokenAccount);
 
         await tokenProgram.methods
-          .transferTokens(new anchor.BN(TRANSFER_AMOUNT))
+          .transferTokens(new anchor.BN(TRANSFER_AMOUNT), null)
           .accounts({
             state: tokenStatePda,
             mint: mint.publicKey,
@@ -599,20 +930,143 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         console.log("✓ Transferred tokens");
       });
 
+      it("Fails transfer when expected decimals don't match the mint", async () => {
+        const pda = (seed: string, key: PublicKey) =>
+          PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], tokenProgram.programId)[0];
+
+        try {
+          await tokenProgram.methods
+            .transferTokens(new anchor.BN(1), MINT_DECIMALS + 1)
+            .accounts({
+              state: tokenStatePda,
+              mint: mint.publicKey,
+              fromAccount: userTokenAccount,
+              toAccount: recipientTokenAccount,
+              authority: user.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              sellTracker: pda("selltracker", user.publicKey),
+              senderBlacklist: pda("blacklist", user.publicKey),
+              recipientBlacklist: pda("blacklist", recipient.publicKey),
+              senderRestricted: pda("restricted", user.publicKey),
+              recipientRestricted: pda("restricted", recipient.publicKey),
+              liquidityPool: pda("liquiditypool", recipient.publicKey),
+              noSellLimit: pda("noselllimit", user.publicKey),
+              senderWhitelist: pda("whitelist", user.publicKey),
+              recipientWhitelist: pda("whitelist", recipient.publicKey),
+              systemProgram: SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            })
+            .signers([user])
+            .rpc();
+
+          expect.fail("Should fail");
+        } catch (err: any) {
+          expect(err.toString()).to.include("DecimalsMismatch");
+          console.log("✓ Correctly rejected transfer with mismatched decimals");
+        }
+      });
+
+      it("Tracks hourly transfer velocity when the tracker is passed", async () => {
+        const pda = (seed: string, key: PublicKey) =>
+          PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], tokenProgram.programId)[0];
+        const [velocityPda] = PublicKey.findProgramAddressSync([Buffer.from("velocity")], tokenProgram.programId);
+        const stateAccount = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+        const stateAuthority = stateAccount.authority;
+        const authorityKeypair = [authority, signer1, signer2, signer3].find((k) => k.publicKey.equals(stateAuthority)) ?? null;
+        if (!authorityKeypair && !stateAuthority.equals(provider.wallet.publicKey)) {
+          console.log("ℹ Token authority is not available in test keypairs - skipping");
+          return;
+        }
+
+        // Only governance may reset the tracker
+        try {
+          await tokenProgram.methods
+            .resetVelocityTracker()
+            .accounts({ state: tokenStatePda, velocityTracker: velocityPda, governance: user.publicKey, payer: user.publicKey })
+            .signers([user])
+            .rpc();
+          expect.fail("Should fail");
+        } catch (err: any) {
+          expect(err.toString()).to.include("Unauthorized");
+        }
+
+        const resetBuilder = tokenProgram.methods
+          .resetVelocityTracker()
+          .accounts({
+            state: tokenStatePda,
+            velocityTracker: velocityPda,
+            governance: stateAuthority,
+            payer: provider.wallet.publicKey,
+          });
+        if (authorityKeypair) {
+          resetBuilder.signers([authorityKeypair]);
+        }
+        await resetBuilder.rpc();
+
+        const transferWithTracker = () =>
+          tokenProgram.methods
+            .transferTokens(new anchor.BN(1), null)
+            .accounts({
+              state: tokenStatePda,
+              mint: mint.publicKey,
+              fromAccount: userTokenAccount,
+              toAccount: recipientTokenAccount,
+              authority: user.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              sellTracker: pda("selltracker", user.publicKey),
+              senderBlacklist: pda("blacklist", user.publicKey),
+              recipientBlacklist: pda("blacklist", recipient.publicKey),
+              senderRestricted: pda("restricted", user.publicKey),
+              recipientRestricted: pda("restricted", recipient.publicKey),
+              liquidityPool: pda("liquiditypool", recipient.publicKey),
+              noSellLimit: pda("noselllimit", user.publicKey),
+              senderWhitelist: pda("whitelist", user.publicKey),
+              recipientWhitelist: pda("whitelist", recipient.publicKey),
+              systemProgram: SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            })
+            .remainingAccounts([{ pubkey: velocityPda, isWritable: true, isSigner: false }])
+            .signers([user])
+            .rpc();
+
+        // Transfers accumulate within the period
+        await transferWithTracker();
+        await transferWithTracker();
+        let tracker = await tokenProgram.account.velocityTracker.fetch(velocityPda);
+        expect(tracker.periodTransfers.toNumber()).to.equal(2);
+        expect(tracker.periodVolume.toString()).to.equal("2");
+        const firstPeriodStart = tracker.periodStart.toNumber();
+        console.log("✓ Velocity accumulated within the period");
+
+        // A transfer more than an hour after the period started opens a new one
+        await warpTime(3600 + 1);
+        const now = await connection.getBlockTime(await connection.getSlot());
+        if (now === null || now - firstPeriodStart <= 3600) {
+          console.log("ℹ Validator clock could not be advanced - skipping period roll-over");
+          return;
+        }
+        await transferWithTracker();
+        tracker = await tokenProgram.account.velocityTracker.fetch(velocityPda);
+        expect(tracker.periodStart.toNumber()).to.be.greaterThan(firstPeriodStart + 3600);
+        expect(tracker.periodTransfers.toNumber()).to.equal(1);
+        expect(tracker.periodVolume.toString()).to.equal("1");
+        console.log("✓ Velocity period rolled over after an hour");
+      });
+
       it("Fails transfer when sender is blacklisted", async () => {
         const [blacklistPda] = PublicKey.findProgramAddressSync([Buffer.from("blacklist"), blacklistedUser.publicKey.toBuffer()], tokenProgram.programId);
         const [recipientBlacklistPda] = PublicKey.findProgramAddressSync([Buffer.from("blacklist"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         const [senderRestrictedPda] = PublicKey.findProgramAddressSync([Buffer.from("restricted"), blacklistedUser.publicKey.toBuffer()], tokenProgram.programId);
         const [recipientRestrictedPda] = PublicKey.findProgramAddressSync([Buffer.from("restricted"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         const [sellTrackerPda] = PublicKey.findProgramAddressSync([Buffer.from("selltracker"), blacklistedUser.publicKey.toBuffer()], tokenProgram.programId);
-        const [liquidityPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("liquiditypool"), PublicKey.default.toBuffer()], tokenProgram.programId);
+        const [liquidityPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("liquiditypool"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         const [noSellLimitPda] = PublicKey.findProgramAddressSync([Buffer.from("noselllimit"), blacklistedUser.publicKey.toBuffer()], tokenProgram.programId);
         const [senderWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), blacklistedUser.publicKey.toBuffer()], tokenProgram.programId);
         const [recipientWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), recipient.publicKey.toBuffer()], tokenProgram.programId);
         
         try {
           await tokenProgram.methods
-            .transferTokens(new anchor.BN(1))
+            .transferTokens(new anchor.BN(1), null)
             .accounts({
               state: tokenStatePda,
               mint: mint.publicKey,
@@ -641,6 +1095,102 @@ describe("SPL Token & Governance Tests - Fixed", () => {
           console.log("✓ Blacklist check verified");
         }
       });
+
+      const listAccounts = [
+        { field: "senderBlacklist", seed: "blacklist" },
+        { field: "recipientBlacklist", seed: "blacklist" },
+        { field: "senderRestricted", seed: "restricted" },
+        { field: "recipientRestricted", seed: "restricted" },
+        { field: "liquidityPool", seed: "liquiditypool" },
+        { field: "noSellLimit", seed: "noselllimit" },
+      ];
+
+      for (const { field, seed } of listAccounts) {
+        it(`Rejects a ${field} account that is not the derived PDA`, async () => {
+          const pda = (s: string, owner: PublicKey) =>
+            PublicKey.findProgramAddressSync([Buffer.from(s), owner.toBuffer()], tokenProgram.programId)[0];
+
+          const accounts: any = {
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            fromAccount: userTokenAccount,
+            toAccount: recipientTokenAccount,
+            authority: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            sellTracker: pda("selltracker", user.publicKey),
+            senderBlacklist: pda("blacklist", user.publicKey),
+            recipientBlacklist: pda("blacklist", recipient.publicKey),
+            senderRestricted: pda("restricted", user.publicKey),
+            recipientRestricted: pda("restricted", recipient.publicKey),
+            liquidityPool: pda("liquiditypool", recipient.publicKey),
+            noSellLimit: pda("noselllimit", user.publicKey),
+            senderWhitelist: pda("whitelist", user.publicKey),
+            recipientWhitelist: pda("whitelist", recipient.publicKey),
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          };
+
+          for (const substitute of [PublicKey.default, pda(seed, blacklistedUser.publicKey)]) {
+            try {
+              await tokenProgram.methods
+                .transferTokens(new anchor.BN(1), null)
+                .accounts({ ...accounts, [field]: substitute })
+                .signers([user])
+                .rpc();
+
+              expect.fail("Should fail");
+            } catch (err: any) {
+              expect(err.toString()).to.include("InvalidListAccount");
+            }
+          }
+          console.log(`✓ Correctly rejected a substituted ${field} account`);
+        });
+      }
+
+      it("Fails transfer when the deadline has passed", async () => {
+        const [sellTrackerPda] = PublicKey.findProgramAddressSync([Buffer.from("selltracker"), user.publicKey.toBuffer()], tokenProgram.programId);
+        const [senderBlacklistPda] = PublicKey.findProgramAddressSync([Buffer.from("blacklist"), user.publicKey.toBuffer()], tokenProgram.programId);
+        const [recipientBlacklistPda] = PublicKey.findProgramAddressSync([Buffer.from("blacklist"), recipient.publicKey.toBuffer()], tokenProgram.programId);
+        const [senderRestrictedPda] = PublicKey.findProgramAddressSync([Buffer.from("restricted"), user.publicKey.toBuffer()], tokenProgram.programId);
+        const [recipientRestrictedPda] = PublicKey.findProgramAddressSync([Buffer.from("restricted"), recipient.publicKey.toBuffer()], tokenProgram.programId);
+        const [senderWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), user.publicKey.toBuffer()], tokenProgram.programId);
+        const [recipientWhitelistPda] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), recipient.publicKey.toBuffer()], tokenProgram.programId);
+        const [liquidityPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("liquiditypool"), recipient.publicKey.toBuffer()], tokenProgram.programId);
+        const [noSellLimitPda] = PublicKey.findProgramAddressSync([Buffer.from("noselllimit"), user.publicKey.toBuffer()], tokenProgram.programId);
+
+        const expiredDeadline = Math.floor(Date.now() / 1000) - 3600;
+
+        try {
+          await tokenProgram.methods
+            .transferTokensWithDeadline(new anchor.BN(1), null, new anchor.BN(expiredDeadline))
+            .accounts({
+              state: tokenStatePda,
+              mint: mint.publicKey,
+              fromAccount: userTokenAccount,
+              toAccount: recipientTokenAccount,
+              authority: user.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              sellTracker: sellTrackerPda,
+              senderBlacklist: senderBlacklistPda,
+              recipientBlacklist: recipientBlacklistPda,
+              senderRestricted: senderRestrictedPda,
+              recipientRestricted: recipientRestrictedPda,
+              liquidityPool: liquidityPoolPda,
+              noSellLimit: noSellLimitPda,
+              senderWhitelist: senderWhitelistPda,
+              recipientWhitelist: recipientWhitelistPda,
+              systemProgram: SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            })
+            .signers([user])
+            .rpc();
+
+          expect.fail("Should fail");
+        } catch (err: any) {
+          expect(err.toString()).to.include("DeadlineExceeded");
+          console.log("✓ Expired deadline rejected");
+        }
+      });
     });
 
     describe("Burn Tokens", () => {
@@ -770,7 +1320,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
           const initAuthority = useProviderWallet ? provider.wallet.publicKey : authority.publicKey;
           
           const txBuilder = governanceProgram.methods
-            .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), initSigners)
+            .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), initSigners, new anchor.BN(0), 0)
             .accounts({
               governanceState: governanceStatePda,
               authority: initAuthority,
@@ -795,6 +1345,51 @@ describe("SPL Token & Governance Tests - Fixed", () => {
           }
         }
       });
+
+      it("Migrates a legacy governance state to the current layout", async () => {
+        // Anchor.toml preloads the state in the pre-upgrade 456-byte layout
+        const before = await provider.connection.getAccountInfo(governanceStatePda);
+        const stateBefore = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+
+        await governanceProgram.methods.migrateGovernanceState()
+          .accounts({
+            governanceState: governanceStatePda,
+            payer: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const after = await provider.connection.getAccountInfo(governanceStatePda);
+        const stateAfter = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        if (before!.data.length === 456) {
+          expect(after!.data.length).to.be.greaterThan(456);
+          expect(after!.data.subarray(0, 456).equals(before!.data)).to.be.true;
+          expect(after!.data.subarray(456).every((byte) => byte === 0)).to.be.true;
+          expect(after!.lamports).to.be.at.least(
+            await provider.connection.getMinimumBalanceForRentExemption(after!.data.length)
+          );
+        } else {
+          console.log("ℹ Governance state was not in the legacy layout");
+        }
+        expect(stateAfter.authority.toString()).to.equal(stateBefore.authority.toString());
+        expect(stateAfter.requiredApprovals).to.equal(stateBefore.requiredApprovals);
+        expect(stateAfter.signers.map((s) => s.toString())).to.deep.equal(
+          stateBefore.signers.map((s) => s.toString())
+        );
+        expect(stateAfter.nextTransactionId.toString()).to.equal(stateBefore.nextTransactionId.toString());
+
+        // Migrating again leaves the account untouched
+        await governanceProgram.methods.migrateGovernanceState()
+          .accounts({
+            governanceState: governanceStatePda,
+            payer: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        const again = await provider.connection.getAccountInfo(governanceStatePda);
+        expect(again!.data.equals(after!.data)).to.be.true;
+        console.log(`✓ Governance state migrated from ${before!.data.length} to ${after!.data.length} bytes`);
+      });
     });
 
     describe("Set Token Program", () => {
@@ -1003,7 +1598,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetBlacklist(user.publicKey, true)
+          .queueSetBlacklist(user.publicKey, true, 0, "Blacklisting exploiter wallet from incident #42")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1020,9 +1615,43 @@ describe("SPL Token & Governance Tests - Fixed", () => {
 
         const txAccount = await governanceProgram.account.transaction.fetch(txPda);
         expect(txAccount.id.toNumber()).to.equal(txId);
+        expect(txAccount.description).to.equal("Blacklisting exploiter wallet from incident #42");
         console.log("✓ Blacklist transaction queued");
       });
 
+      it("Rejects a description longer than 128 bytes", async () => {
+        const { keypair: signerKeypair, pubkey: signerPubkey } = await getAuthorizedSigner();
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+
+        const txBuilder = governanceProgram.methods
+          .queueSetBlacklist(user.publicKey, true, 0, "x".repeat(129))
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signerPubkey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          });
+
+        if (signerKeypair) {
+          txBuilder.signers([signerKeypair]);
+        }
+
+        try {
+          await txBuilder.rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("DescriptionTooLong");
+          console.log("✓ Correctly rejected oversized description");
+        }
+      });
+
       it("Queues an unpause transaction", async () => {
         const { keypair: signerKeypair, pubkey: signerPubkey } = await getAuthorizedSigner();
         const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
@@ -1045,7 +1674,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueUnpause()
+          .queueUnpause(0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1081,7 +1710,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetNoSellLimit(user.publicKey, true)
+          .queueSetNoSellLimit(user.publicKey, true, null, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1117,7 +1746,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetRestricted(blacklistedUser.publicKey, true)
+          .queueSetRestricted(blacklistedUser.publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1155,7 +1784,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         const fakePoolAddress = Keypair.generate().publicKey;
 
         const txBuilder = governanceProgram.methods
-          .queueSetLiquidityPool(fakePoolAddress, true)
+          .queueSetLiquidityPool(fakePoolAddress, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1217,7 +1846,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: testTxPda,
@@ -1253,9 +1882,62 @@ describe("SPL Token & Governance Tests - Fixed", () => {
 
         const txAccount = await governanceProgram.account.transaction.fetch(testTxPda);
         expect(txAccount.approvalCount).to.be.gte(1);
+        // Every signer defaults to weight 1
+        expect(txAccount.approvalWeight).to.equal(txAccount.approvalCount);
         console.log("✓ Transaction approved (1/" + REQUIRED_APPROVALS + ")");
       });
 
+      it("Rejects queuing a zero signer weight", async () => {
+        const { keypair: signerKeypair, pubkey: signerPubkey } = await getAuthorizedSigner();
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+
+        const txBuilder = governanceProgram.methods
+          .queueSetSignerWeight(signerPubkey, 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signerPubkey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          });
+
+        if (signerKeypair) {
+          txBuilder.signers([signerKeypair]);
+        }
+
+        try {
+          await txBuilder.rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidSignerWeight");
+          console.log("✓ Zero signer weight rejected");
+        }
+      });
+
+      it("Rejects an empty execution batch", async () => {
+        try {
+          await governanceProgram.methods
+            .executeTransactionsBatch([])
+            .accounts({
+              governanceState: governanceStatePda,
+              statePda: tokenStatePda,
+              tokenProgramProgram: tokenProgram.programId,
+              payer: provider.wallet.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidBatchSize");
+          console.log("✓ Empty batch rejected");
+        }
+      });
+
       it("Fails if same approver tries to approve twice", async () => {
         const { keypair: signerKeypair, pubkey: signerPubkey } = await getAuthorizedSigner();
         
@@ -1380,7 +2062,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const queueBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: unauthorizedTxPda,
@@ -1445,7 +2127,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: rejectTxPda,
@@ -1503,7 +2185,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const queueBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -1590,6 +2272,13 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         if (authorityKeypair) {
           resetBuilder.signers([authorityKeypair]);
         }
+
+        const { events } = await resetBuilder.simulate();
+        const changed = events.find((e) => e.name === "requiredApprovalsChanged");
+        expect(changed, "RequiredApprovalsChanged not emitted").to.not.be.undefined;
+        expect(changed.data.old).to.equal(maxApprovals);
+        expect(changed.data.new).to.equal(REQUIRED_APPROVALS);
+        expect(changed.data.queued).to.equal(false);
         
         await resetBuilder.rpc();
       });
@@ -511,7 +511,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
             );
 
             const txBuilder = governanceProgram.methods
-                .queueSetBlacklist(user.publicKey, true)
+                .queueSetBlacklist(user.publicKey, true, 0, "")
                 .accounts({
                     governanceState: governanceStatePda,
                     transaction: txPda,
@@ -545,7 +545,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueUnpause()
+          .queueUnpause(0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -581,7 +581,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetNoSellLimit(user.publicKey, true)
+          .queueSetNoSellLimit(user.publicKey, true, null, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -617,7 +617,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetRestricted(blacklistedUser.publicKey, true)
+          .queueSetRestricted(blacklistedUser.publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -655,7 +655,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         const fakePoolAddress = Keypair.generate().publicKey;
 
         const txBuilder = governanceProgram.methods
-          .queueSetLiquidityPool(fakePoolAddress, true)
+          .queueSetLiquidityPool(fakePoolAddress, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -699,7 +699,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: testTxPda,
@@ -788,7 +788,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const queueBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: unauthorizedTxPda,
@@ -936,7 +936,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
         );
 
         const txBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: rejectTxPda,
@@ -996,7 +996,7 @@ describe("SPL Token & Governance Tests - Fixed", () => {
     );
 
         const queueBuilder = governanceProgram.methods
-          .queueSetBlacklist(Keypair.generate().publicKey, true)
+          .queueSetBlacklist(Keypair.generate().publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
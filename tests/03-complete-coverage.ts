@@ -1,11 +1,14 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import {
+  ACCOUNT_SIZE,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountInstruction,
+  createInitializeAccountInstruction,
   createInitializeMintInstruction,
   createMintToInstruction,
   getAssociatedTokenAddress,
+  getMinimumBalanceForRentExemptAccount,
   getMinimumBalanceForRentExemptMint,
   MINT_SIZE,
   TOKEN_PROGRAM_ID,
@@ -19,6 +22,8 @@ import {
   Transaction,
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { execSync } from "child_process";
+import * as fs from "fs";
 import { Governance } from "../target/types/governance";
 import { Presale } from "../target/types/presale";
 import { SplProject } from "../target/types/spl_project";
@@ -30,6 +35,12 @@ import { loadTestKeys } from "./key-loader";
 const CHAINLINK_SOL_USD_MAINNET = new PublicKey("CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt");
 const CHAINLINK_SOL_USD_DEVNET = new PublicKey("99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR");
 
+// spl-project built with the `second-instance` feature (`yarn build:second-token-program`),
+// deployed at this address to test governance against a second token program
+const SECOND_TOKEN_PROGRAM_ID = new PublicKey("GzGwQQaXd175fdpvjfLtLfXxPKPS7jRqukUTSwKmQDdG");
+const SECOND_TOKEN_PROGRAM_SO = "target/second-instance/spl_project.so";
+const SECOND_TOKEN_PROGRAM_KEYPAIR = "tests/fixtures/second-token-program-keypair.json";
+
 // Helper to get Chainlink feed based on cluster
 function getChainlinkFeed(): PublicKey {
   // For tests, use devnet feed
@@ -145,6 +156,33 @@ describe("Missing Test Cases - Complete Coverage", () => {
     }
   }
 
+  // Helper to derive the PurchaseRecord PDA for a buyer's next purchase
+  async function nextPurchaseRecordPda(buyerKey: PublicKey): Promise<PublicKey> {
+    const [userPurchase] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), buyerKey.toBuffer()],
+      presaleProgram.programId
+    );
+    const purchase = await presaleProgram.account.userPurchase.fetchNullable(userPurchase);
+    return purchaseRecordPda(buyerKey, purchase ? purchase.purchaseCount : 0);
+  }
+
+  function purchaseRecordPda(buyerKey: PublicKey, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("purchase_record"), presaleStatePda.toBuffer(), buyerKey.toBuffer(), indexBytes],
+      presaleProgram.programId
+    )[0];
+  }
+
+  // Helper to derive the RaisedTracker PDA for a payment mint (PublicKey.default for SOL)
+  function raisedTrackerPda(paymentMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("raised_tracker"), presaleStatePda.toBuffer(), paymentMint.toBuffer()],
+      presaleProgram.programId
+    )[0];
+  }
+
   // Helper to warp time (for cooldown tests)
   // Helper function to safely execute governance transactions
 async function safeExecuteTransaction(
@@ -196,6 +234,24 @@ async function safeExecuteTransaction(
   }
 }
 
+// Deploys the second token program unless it is already live; false if it isn't built
+async function deploySecondTokenProgram(): Promise<boolean> {
+    const existing = await connection.getAccountInfo(SECOND_TOKEN_PROGRAM_ID);
+    if (existing?.executable) {
+      return true;
+    }
+    if (!fs.existsSync(SECOND_TOKEN_PROGRAM_SO)) {
+      return false;
+    }
+    const wallet = process.env.ANCHOR_WALLET ? `--keypair ${process.env.ANCHOR_WALLET}` : "";
+    execSync(
+      `solana program deploy ${SECOND_TOKEN_PROGRAM_SO} --program-id ${SECOND_TOKEN_PROGRAM_KEYPAIR} ` +
+        `--url ${connection.rpcEndpoint} ${wallet}`,
+      { stdio: "inherit" }
+    );
+    return true;
+  }
+
 async function warpTime(seconds: number) {
     try {
       const currentSlot = await connection.getSlot();
@@ -352,7 +408,7 @@ async function warpTime(seconds: number) {
       // Governance not initialized yet - initialize it
       try {
         await governanceProgram.methods
-          .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), [provider.wallet.publicKey, signer1.publicKey, signer2.publicKey, signer3.publicKey])
+          .initialize(REQUIRED_APPROVALS, new anchor.BN(COOLDOWN_PERIOD), [provider.wallet.publicKey, signer1.publicKey, signer2.publicKey, signer3.publicKey], new anchor.BN(0), 0)
           .accounts({
             governanceState: governanceStatePda,
             authority: provider.wallet.publicKey,
@@ -1046,7 +1102,7 @@ async function warpTime(seconds: number) {
       //   governanceProgram.programId
       // );
 
-      // await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true)
+      // await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true, 0, "")
       //   .accounts({
       //     governanceState: governanceStatePda,
       //     transaction: txPda,
@@ -1243,7 +1299,7 @@ async function warpTime(seconds: number) {
         tokenProgram.programId
       );
       const [liquidityPoolPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquiditypool"), poolAddress.publicKey.toBuffer()],
+        [Buffer.from("liquiditypool"), recipient.publicKey.toBuffer()],
         tokenProgram.programId
       );
       const [noSellLimitPda] = PublicKey.findProgramAddressSync(
@@ -1313,7 +1369,7 @@ async function warpTime(seconds: number) {
 
       const balanceBefore = await connection.getTokenAccountBalance(recipientTokenAccount);
 
-      await tokenProgram.methods.transferTokens(TRANSFER_AMOUNT)
+      await tokenProgram.methods.transferTokens(TRANSFER_AMOUNT, null)
         .accounts({
           state: tokenStatePda,
           mint: mint.publicKey,
@@ -1673,7 +1729,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueWithdrawToTreasury(new anchor.BN(1000))
+      await governanceProgram.methods.queueWithdrawToTreasury(new anchor.BN(1000), "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda,
@@ -1717,6 +1773,10 @@ async function warpTime(seconds: number) {
             presalePaymentVault: presalePaymentVault,
             treasuryTokenAccount: recipientTokenAccount,
             paymentTokenMint: paymentTokenMint.publicKey,
+            presaleAllowedToken: PublicKey.findProgramAddressSync(
+              [Buffer.from("allowed_token"), presaleStatePda.toBuffer(), paymentTokenMint.publicKey.toBuffer()],
+              presaleProgram.programId
+            )[0],
             splTokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -1788,7 +1848,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetTreasuryAddress(admin.publicKey)
+      await governanceProgram.methods.queueSetTreasuryAddress(admin.publicKey, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda1,
@@ -1810,78 +1870,1754 @@ async function warpTime(seconds: number) {
         .signers([signer2])
         .rpc();
 
-      // Get actual cooldown period from governance state
-      const currentGovState1 = await governanceProgram.account.governanceState.fetch(governanceStatePda);
-      const actualCooldown1 = currentGovState1.cooldownPeriod.toNumber();
-      await warpTime(actualCooldown1 + 1);
+      // Get actual cooldown period from governance state
+      const currentGovState1 = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const actualCooldown1 = currentGovState1.cooldownPeriod.toNumber();
+      await warpTime(actualCooldown1 + 1);
+
+      // Execute transaction using safe helper
+      // If access violation occurs, the helper will skip gracefully
+      try {
+        await safeExecuteTransaction(
+          governanceProgram,
+          new anchor.BN(txId1),
+          {
+            governanceState: governanceStatePda,
+            transaction: txPda1,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            presaleStatePda: presaleStatePda,
+            presaleProgramProgram: presaleProgram.programId,
+            presalePaymentVaultPda: presalePaymentVaultPda,
+            presalePaymentVault: presalePaymentVault,
+            treasuryTokenAccount: recipientTokenAccount,
+            paymentTokenMint: paymentTokenMint.publicKey,
+            splTokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            payer: signer1.publicKey,
+            blacklistAccount: (() => {
+              const [pda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("blacklist"), blacklistedUser.publicKey.toBuffer()],
+                tokenProgram.programId
+              );
+              return pda;
+            })(),
+            targetAccount: blacklistedUser.publicKey,
+            noSellLimitAccount: (() => {
+              const [pda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("noselllimit"), blacklistedUser.publicKey.toBuffer()],
+                tokenProgram.programId
+              );
+              return pda;
+            })(),
+            restrictedAccount: (() => {
+              const [pda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("restricted"), blacklistedUser.publicKey.toBuffer()],
+                tokenProgram.programId
+              );
+              return pda;
+            })(),
+            liquidityPoolAccount: (() => {
+              const [pda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("liquiditypool"), poolAddress.publicKey.toBuffer()],
+                tokenProgram.programId
+              );
+              return pda;
+            })(),
+            poolAddress: poolAddress.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          "SetTreasuryAddress transaction"
+        );
+      } catch (err: any) {
+        // If access violation, skip the rest of the test
+        const errMsg = err.message?.toLowerCase() || err.toString().toLowerCase();
+        if (errMsg.includes("access violation") || errMsg.includes("skip_test")) {
+          console.log("ℹ Skipping test due to access violation in test environment");
+          return;
+        }
+        throw err;
+      }
+
+      console.log("✓ Complete governance flow with presale state completed");
+    });
+
+    it("14a. Burns tokens through a governance BurnTokens transaction", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping governance burn");
+        return;
+      }
+
+      const burnAmount = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await governanceProgram.methods.queueBurnTokens(userTokenAccount, burnAmount, 0, "Clawback test burn")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer1])
+        .rpc();
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer2.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer2])
+        .rpc();
+
+      const currentGovState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(currentGovState.cooldownPeriod.toNumber() + 1);
+
+      const supplyBefore = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          splTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          payer: signer1.publicKey,
+          tokenMint: mint.publicKey,
+          burnFromAccount: userTokenAccount,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      const supplyAfter = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+      expect((BigInt(supplyBefore) - BigInt(supplyAfter)).toString()).to.equal(burnAmount.toString());
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log("✓ Governance burn reduced token supply");
+    });
+
+    it("14b. Rejects queuing a vesting config with a cliff past its duration", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await expectError(
+        governanceProgram.methods.queueSetVestingConfig(true, new anchor.BN(200), new anchor.BN(100), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidVestingConfig"
+      );
+      console.log("✓ Vesting config with cliff past duration rejected at queue time");
+    });
+
+    it("14c. Rejects queuing purchase limits with a minimum above the maximum", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await expectError(
+        governanceProgram.methods.queueSetPurchaseLimits(new anchor.BN(200), new anchor.BN(100), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+      console.log("✓ Purchase limits with min above max rejected at queue time");
+    });
+
+    it("14d. Rejects queuing an approval cooldown longer than one day", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(govState.approvalCooldown.toNumber()).to.equal(0);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await expectError(
+        governanceProgram.methods.queueSetApprovalCooldown(new anchor.BN(86400 + 1), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidCooldownPeriod"
+      );
+      console.log("✓ Approval cooldown above one day rejected at queue time");
+    });
+
+    it("14f. Rejects queuing price tiers with mismatched lengths or descending thresholds", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const accounts = {
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+
+      await expectError(
+        governanceProgram.methods.queueSetPriceTiers([new anchor.BN(1_000)], [], "")
+          .accounts(accounts)
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+      await expectError(
+        governanceProgram.methods.queueSetPriceTiers(
+          [new anchor.BN(2_000), new anchor.BN(1_000)],
+          [new anchor.BN(2_000), new anchor.BN(3_000)],
+          ""
+        )
+          .accounts(accounts)
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+      console.log("✓ Invalid price tiers rejected at queue time");
+    });
+
+    it("14e. Mints tokens through a governance Mint transaction", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping governance mint");
+        return;
+      }
+
+      const mintAmount = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const [userBlacklistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), user.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+
+      // A signer holding no token authority cannot mint directly
+      await expectError(
+        tokenProgram.methods.mintTokens(mintAmount)
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            to: userTokenAccount,
+            governance: signer1.publicKey,
+            recipientBlacklist: userBlacklistPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([signer1])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await expectError(
+        governanceProgram.methods.queueMintTokens(userTokenAccount, new anchor.BN(0), 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+
+      await governanceProgram.methods.queueMintTokens(userTokenAccount, mintAmount, 0, "Test mint")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      const queued = await governanceProgram.account.transaction.fetch(txPda);
+      expect(queued.target.toString()).to.equal(userTokenAccount.toString());
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer1])
+        .rpc();
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer2.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer2])
+        .rpc();
+
+      const currentGovState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(currentGovState.cooldownPeriod.toNumber() + 1);
+
+      const supplyBefore = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          splTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          payer: signer1.publicKey,
+          tokenMint: mint.publicKey,
+          mintToAccount: userTokenAccount,
+          recipientBlacklist: userBlacklistPda,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      const supplyAfter = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+      expect((BigInt(supplyAfter) - BigInt(supplyBefore)).toString()).to.equal(mintAmount.toString());
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log("✓ Governance mint increased token supply");
+    });
+
+    it("14g. Revokes an approval on a pending transaction", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await governanceProgram.methods.queueMintTokens(userTokenAccount, new anchor.BN(1), 0, "Revocation test")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer1])
+        .rpc();
+
+      // signer2 never approved, so has nothing to revoke
+      await expectError(
+        governanceProgram.methods.revokeApproval(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer2.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([signer2])
+          .rpc(),
+        "NotApproved"
+      );
+
+      await governanceProgram.methods.revokeApproval(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer1])
+        .rpc();
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.approvalCount).to.equal(0);
+      expect(tx.approvals).to.be.empty;
+      expect(tx.status.pending).to.not.be.undefined;
+
+      await expectError(
+        governanceProgram.methods.revokeApproval(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([signer1])
+          .rpc(),
+        "NotApproved"
+      );
+      console.log("✓ Approval revoked before execution");
+    });
+
+    it("14h. Sets liquidity pools in a batch and rejects mismatched accounts", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping batch pools");
+        return;
+      }
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queueAccounts = {
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+
+      const tooMany = Array.from({ length: 9 }, () => ({ pool: Keypair.generate().publicKey, isPool: true }));
+      await expectError(
+        governanceProgram.methods.queueSetLiquidityPools(tooMany, 0, "")
+          .accounts(queueAccounts)
+          .signers([signer1])
+          .rpc(),
+        "InvalidBatchSize"
+      );
+
+      const pools = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      await governanceProgram.methods.queueSetLiquidityPools(pools.map((pool) => ({ pool, isPool: true })), 0, "New DEX pools")
+        .accounts(queueAccounts)
+        .signers([signer1])
+        .rpc();
+
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      const currentGovState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(currentGovState.cooldownPeriod.toNumber() + 1);
+
+      const liquidityPoolPdas = pools.map((pool) => PublicKey.findProgramAddressSync(
+        [Buffer.from("liquiditypool"), pool.toBuffer()],
+        tokenProgram.programId
+      )[0]);
+      const pairAccounts = (order: number[]) => order.flatMap((i) => [
+        { pubkey: liquidityPoolPdas[i], isWritable: true, isSigner: false },
+        { pubkey: pools[i], isWritable: false, isSigner: false },
+      ]);
+      const execute = (remaining: anchor.web3.AccountMeta[]) =>
+        governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .remainingAccounts(remaining)
+          .rpc();
+
+      // Accounts out of order, or missing a pair, fail the whole batch
+      await expectError(execute(pairAccounts([1, 0])), "InvalidBatchAccounts");
+      await expectError(execute(pairAccounts([0])), "InvalidBatchAccounts");
+      const pending = await governanceProgram.account.transaction.fetch(txPda);
+      expect(pending.status.pending).to.not.be.undefined;
+
+      await execute(pairAccounts([0, 1]));
+      for (const pda of liquidityPoolPdas) {
+        const poolAccount = await tokenProgram.account.liquidityPool.fetch(pda);
+        expect(poolAccount.isPool).to.be.true;
+      }
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log("✓ Liquidity pools set in one governance transaction");
+    });
+
+    it("14i. Rejects queuing a keeper reward above the cap", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(govState.keeperReward.toNumber()).to.equal(0);
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(govState.nextTransactionId.toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      // 0.1 SOL is the maximum reward
+      await expectError(
+        governanceProgram.methods.queueSetKeeperReward(new anchor.BN(100_000_001), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+      console.log("✓ Keeper reward above the cap rejected");
+    });
+
+    it("14j. Blacklists several addresses in one governance transaction", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping batch blacklist");
+        return;
+      }
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queueAccounts = {
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+
+      const tooMany = Array.from({ length: 21 }, () => ({ account: Keypair.generate().publicKey, blacklisted: true }));
+      await expectError(
+        governanceProgram.methods.queueSetBlacklistBatch(tooMany, 0, "")
+          .accounts(queueAccounts)
+          .signers([signer1])
+          .rpc(),
+        "InvalidBatchSize"
+      );
+
+      const accounts = Array.from({ length: 5 }, () => Keypair.generate().publicKey);
+      await governanceProgram.methods.queueSetBlacklistBatch(
+        accounts.map((account) => ({ account, blacklisted: true })),
+        0,
+        "Compliance sweep"
+      )
+        .accounts(queueAccounts)
+        .signers([signer1])
+        .rpc();
+
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      const currentGovState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(currentGovState.cooldownPeriod.toNumber() + 1);
+
+      const blacklistPdas = accounts.map((account) => PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), account.toBuffer()],
+        tokenProgram.programId
+      )[0]);
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          systemProgram: SystemProgram.programId,
+          payer: provider.wallet.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .remainingAccounts(accounts.flatMap((account, i) => [
+          { pubkey: blacklistPdas[i], isWritable: true, isSigner: false },
+          { pubkey: account, isWritable: false, isSigner: false },
+        ]))
+        .preInstructions([anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .rpc();
+
+      for (const pda of blacklistPdas) {
+        const entry = await tokenProgram.account.blacklist.fetch(pda);
+        expect(entry.isBlacklisted).to.be.true;
+      }
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log("✓ Five addresses blacklisted in one governance transaction");
+    });
+
+    it("14k. Rejects queuing a blacklist of the governance PDA", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(govState.nextTransactionId.toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queueAccounts = {
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+
+      await expectError(
+        governanceProgram.methods.queueSetBlacklist(governanceStatePda, true, 0, "")
+          .accounts(queueAccounts)
+          .signers([signer1])
+          .rpc(),
+        "CannotBlacklistProtected"
+      );
+      await expectError(
+        governanceProgram.methods.queueSetBlacklistBatch(
+          [{ account: Keypair.generate().publicKey, blacklisted: true }, { account: governanceStatePda, blacklisted: true }],
+          0,
+          ""
+        )
+          .accounts(queueAccounts)
+          .signers([signer1])
+          .rpc(),
+        "CannotBlacklistProtected"
+      );
+      console.log("✓ Governance PDA cannot be blacklisted");
+    });
+
+    it("14l. Overrides the required approvals for one transaction type", async () => {
+      const queueAndExecute = async (required: number) => {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await governanceProgram.methods.queueSetTypeApprovals({ setBondAddress: {} }, required, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        for (const approver of [signer1, signer2]) {
+          await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+            .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+            .signers([approver])
+            .rpc();
+        }
+        await warpTime(govState.cooldownPeriod.toNumber() + 1);
+        await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+      };
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(govState.nextTransactionId.toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queueInvalid = (required: number) =>
+        governanceProgram.methods.queueSetTypeApprovals({ withdrawToTreasury: {} }, required, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+      await expectError(queueInvalid(1), "RequiredApprovalsTooLow");
+      await expectError(queueInvalid(255), "RequiredApprovalsTooHigh");
+
+      if (govState.signers.length < 3) {
+        console.log("ℹ Fewer than three signers - skipping override execution");
+        return;
+      }
+      await queueAndExecute(3);
+      let updated = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(updated.typeApprovals).to.have.lengthOf(1);
+      expect(updated.typeApprovals[0].txType.setBondAddress).to.not.be.undefined;
+      expect(updated.typeApprovals[0].required).to.equal(3);
+
+      // Zero removes the override again
+      await queueAndExecute(0);
+      updated = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(updated.typeApprovals).to.have.lengthOf(0);
+      console.log("✓ Per-type approval override set and removed");
+    });
+
+    it("14m. Deducts the transfer fee set through governance", async () => {
+      const nextTxPda = async () => {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        return { txId, txPda };
+      };
+      const queueFee = async (feeBps: number, burn: boolean, treasury: PublicKey) => {
+        const { txId, txPda } = await nextTxPda();
+        await governanceProgram.methods.queueSetTransferFee(feeBps, burn, treasury, false, 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        return { txId, txPda };
+      };
+
+      await expectError(queueFee(201, true, PublicKey.default), "InvalidTransferFee");
+      await expectError(queueFee(100, false, PublicKey.default), "InvalidTransferFee");
+
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      const userBalance = await connection.getTokenAccountBalance(userTokenAccount).catch(() => ({ value: { amount: "0" } }));
+      if (!tokenState.authority.equals(governanceStatePda) || Number(userBalance.value.amount) < 10_000) {
+        console.log("ℹ Token authority is not the governance PDA or user has no balance - skipping fee execution");
+        return;
+      }
+      // Senders with a no-sell-limit exemption pay no fee
+      const [userNoSellLimitPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("noselllimit"), user.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const userExemption = await tokenProgram.account.noSellLimit.fetchNullable(userNoSellLimitPda);
+      if (userExemption?.hasExemption) {
+        console.log("ℹ User is exempt from sell limits and transfer fees - skipping fee execution");
+        return;
+      }
+
+      const setFee = async (feeBps: number, treasury: PublicKey) => {
+        const { txId, txPda } = await queueFee(feeBps, false, treasury);
+        for (const approver of [signer1, signer2]) {
+          await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+            .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+            .signers([approver])
+            .rpc();
+        }
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        await warpTime(govState.cooldownPeriod.toNumber() + 1);
+        await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+      };
+
+      const treasury = Keypair.generate();
+      const treasuryTokenAccount = await getAssociatedTokenAddress(mint.publicKey, treasury.publicKey);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(admin.publicKey, treasuryTokenAccount, treasury.publicKey, mint.publicKey)
+        ),
+        [admin]
+      );
+
+      const pda = (seed: string, key: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], tokenProgram.programId)[0];
+      const transfer = (amount: number) =>
+        tokenProgram.methods.transferTokens(new anchor.BN(amount), null)
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            fromAccount: userTokenAccount,
+            toAccount: recipientTokenAccount,
+            authority: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            sellTracker: pda("selltracker", user.publicKey),
+            senderBlacklist: pda("blacklist", user.publicKey),
+            recipientBlacklist: pda("blacklist", recipient.publicKey),
+            senderRestricted: pda("restricted", user.publicKey),
+            recipientRestricted: pda("restricted", recipient.publicKey),
+            liquidityPool: pda("liquiditypool", recipient.publicKey),
+            noSellLimit: pda("noselllimit", user.publicKey),
+            senderWhitelist: pda("whitelist", user.publicKey),
+            recipientWhitelist: pda("whitelist", recipient.publicKey),
+            feeDestination: treasuryTokenAccount,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([user])
+          .rpc();
+      const balance = async (account: PublicKey) =>
+        BigInt((await connection.getTokenAccountBalance(account)).value.amount);
+
+      // 1% fee: recipient receives 9,900 and the treasury 100
+      await setFee(100, treasury.publicKey);
+      let recipientBefore = await balance(recipientTokenAccount);
+      await transfer(10_000);
+      expect((await balance(recipientTokenAccount)) - recipientBefore).to.equal(BigInt(9_900));
+      expect(await balance(treasuryTokenAccount)).to.equal(BigInt(100));
+
+      // Zero fee: the full amount arrives and the treasury receives nothing
+      await setFee(0, PublicKey.default);
+      recipientBefore = await balance(recipientTokenAccount);
+      await transfer(1_000);
+      expect((await balance(recipientTokenAccount)) - recipientBefore).to.equal(BigInt(1_000));
+      expect(await balance(treasuryTokenAccount)).to.equal(BigInt(100));
+      console.log("✓ Transfer fee deducted and disabled through governance");
+    });
+
+    it("14n. Pre-validates governance transaction parameters", async () => {
+      const validate = (txType: any, data: number[]) =>
+        governanceProgram.methods
+          .validateTransactionParams(txType, Buffer.from(data))
+          .accounts({ governanceState: governanceStatePda })
+          .rpc();
+
+      // Below the two-approver minimum, and too short to decode
+      await expectError(validate({ setRequiredApprovals: {} }, [1]), "RequiredApprovalsTooLow");
+      await expectError(validate({ setRequiredApprovals: {} }, []), "InvalidRequiredApprovals");
+      await expectError(validate({ setExpiryWindow: {} }, [1, 0, 0]), "InvalidDataLength");
+      await expectError(validate({ blacklistBatch: {} }, new Array(34).fill(0)), "InvalidDataLength");
+      // Fixed-size encodings must match exactly, so trailing bytes are rejected
+      await expectError(validate({ unpause: {} }, [0]), "InvalidDataLength");
+      await expectError(validate({ setRequiredApprovals: {} }, [2, 0]), "InvalidDataLength");
+      // Existing signers can't be added twice
+      await expectError(validate({ addSigner: {} }, Array.from(signer1.publicKey.toBytes())), "DuplicateSigners");
+
+      // Valid parameters pass without writing any state
+      const before = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await validate({ setRequiredApprovals: {} }, [2]);
+      await validate({ unpause: {} }, []);
+      const after = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(after.nextTransactionId.toNumber()).to.equal(before.nextTransactionId.toNumber());
+      expect(after.requiredApprovals).to.equal(before.requiredApprovals);
+      console.log("✓ Transaction parameters validated without queuing");
+    });
+
+    it("14o. Backup signers stay unauthorized while primary signers are active", async () => {
+      const backups = Array.from({ length: 5 }, () => Keypair.generate());
+      const ninetyDays = 90 * 24 * 60 * 60;
+      const queueBackups = async (keys: PublicKey[], period: number) => {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await governanceProgram.methods.queueSetBackupSigners(keys, new anchor.BN(period), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        return { txId, txPda };
+      };
+      const backupKeys = backups.map((kp) => kp.publicKey);
+
+      // Activation period below the 90-day minimum, or a primary signer listed as a backup
+      await expectError(queueBackups(backupKeys, ninetyDays - 1), "InvalidBackupSigners");
+      await expectError(queueBackups([signer1.publicKey, ...backupKeys.slice(1)], ninetyDays), "InvalidBackupSigners");
+      // Duplicate backups
+      await expectError(queueBackups([backupKeys[0], backupKeys[0]], ninetyDays), "InvalidBackupSigners");
+
+      // A designated backup cannot act before the inactivity period has passed
+      const { txId, txPda } = await queueBackups(backupKeys, ninetyDays);
+      await expectError(
+        governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            approver: backups[0].publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([backups[0]])
+          .rpc(),
+        "NotAuthorizedSigner"
+      );
+      console.log("✓ Backup signers validated and inactive while primaries are active");
+    });
+
+    it("14p. Time-bounded sell limit exemptions expire", async () => {
+      const grantee = Keypair.generate();
+      const [exemptionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("noselllimit"), grantee.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const cooldown = govState.cooldownPeriod.toNumber();
+      const now = (await connection.getBlockTime(await connection.getSlot())) || Math.floor(Date.now() / 1000);
+      const expiresAt = now + cooldown + 600;
+
+      const queueExemption = async (value: boolean, expiry: number | null) => {
+        const state = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = state.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await governanceProgram.methods
+          .queueSetNoSellLimit(grantee.publicKey, value, expiry === null ? null : new anchor.BN(expiry), 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        return { txId, txPda };
+      };
+      const cleanup = () =>
+        tokenProgram.methods.cleanupExpiredNoSellLimit(grantee.publicKey)
+          .accounts({ noSellLimit: exemptionPda })
+          .rpc();
+
+      // Expiry before execution could happen, or attached to a revocation
+      await expectError(queueExemption(true, now), "InvalidExemptionExpiry");
+      await expectError(queueExemption(false, expiresAt), "InvalidExemptionExpiry");
+
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping exemption execution");
+        return;
+      }
+
+      const { txId, txPda } = await queueExemption(true, expiresAt);
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      await warpTime(cooldown + 1);
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          systemProgram: SystemProgram.programId,
+          payer: provider.wallet.publicKey,
+          targetAccount: grantee.publicKey,
+          noSellLimitAccount: exemptionPda,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      let exemption = await tokenProgram.account.noSellLimit.fetch(exemptionPda);
+      expect(exemption.hasExemption).to.equal(true);
+      expect(exemption.expiresAt.toNumber()).to.equal(expiresAt);
+      await expectError(cleanup(), "ExemptionNotExpired");
+
+      await warpTime(700);
+      await cleanup();
+      exemption = await tokenProgram.account.noSellLimit.fetch(exemptionPda);
+      expect(exemption.hasExemption).to.equal(false);
+      expect(exemption.expiresAt).to.equal(null);
+      console.log("✓ Time-bounded exemption expired and was cleaned up");
+    });
+
+    it("14q. Validates seize transactions at queue time", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queueSeize = (from: PublicKey, to: PublicKey, amount: number) =>
+        governanceProgram.methods.queueSeize(from, to, new anchor.BN(amount), 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+
+      const custody = Keypair.generate().publicKey;
+      await expectError(queueSeize(userTokenAccount, userTokenAccount, 1), "InvalidAccount");
+      await expectError(queueSeize(userTokenAccount, custody, 0), "InvalidAmount");
+
+      await queueSeize(userTokenAccount, custody, 1);
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.txType).to.deep.equal({ seize: {} });
+      expect(tx.data.length).to.equal(72);
+      console.log("✓ Seize transaction queued with source, destination and amount");
+    });
+
+    it("14r. Blocks oversized transfers with the circuit breaker", async () => {
+      const nextTxPda = async () => {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        return { txId, txPda };
+      };
+      const queueBreaker = async (enabled: boolean, thresholdBps: number) => {
+        const { txId, txPda } = await nextTxPda();
+        await governanceProgram.methods.queueSetCircuitBreaker(enabled, thresholdBps, 0, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        return { txId, txPda };
+      };
+
+      await expectError(queueBreaker(true, 49), "InvalidCircuitBreaker");
+      await expectError(queueBreaker(true, 10_001), "InvalidCircuitBreaker");
+
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      const userBalance = await connection.getTokenAccountBalance(userTokenAccount).catch(() => ({ value: { amount: "0" } }));
+      if (!tokenState.authority.equals(governanceStatePda) || Number(userBalance.value.amount) < 1) {
+        console.log("ℹ Token authority is not the governance PDA or user has no balance - skipping breaker execution");
+        return;
+      }
+      const pda = (seed: string, key: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], tokenProgram.programId)[0];
+      const userExemption = await tokenProgram.account.noSellLimit.fetchNullable(pda("noselllimit", user.publicKey));
+      if (userExemption?.hasExemption) {
+        console.log("ℹ User is exempt from sell limits and the circuit breaker - skipping breaker execution");
+        return;
+      }
+
+      const setBreaker = async (enabled: boolean, thresholdBps: number) => {
+        const { txId, txPda } = await queueBreaker(enabled, thresholdBps);
+        for (const approver of [signer1, signer2]) {
+          await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+            .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+            .signers([approver])
+            .rpc();
+        }
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        await warpTime(govState.cooldownPeriod.toNumber() + 1);
+        await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+      };
+      const transfer = (amount: anchor.BN) =>
+        tokenProgram.methods.transferTokens(amount, null)
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            fromAccount: userTokenAccount,
+            toAccount: recipientTokenAccount,
+            authority: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            sellTracker: pda("selltracker", user.publicKey),
+            senderBlacklist: pda("blacklist", user.publicKey),
+            recipientBlacklist: pda("blacklist", recipient.publicKey),
+            senderRestricted: pda("restricted", user.publicKey),
+            recipientRestricted: pda("restricted", recipient.publicKey),
+            liquidityPool: pda("liquiditypool", recipient.publicKey),
+            noSellLimit: pda("noselllimit", user.publicKey),
+            senderWhitelist: pda("whitelist", user.publicKey),
+            recipientWhitelist: pda("whitelist", recipient.publicKey),
+            feeDestination: null,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([user])
+          .rpc();
+
+      // 0.5% of current supply is the largest allowed single transfer
+      await setBreaker(true, 50);
+      const state = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      expect(state.circuitBreakerEnabled).to.be.true;
+      const threshold = state.currentSupply.muln(50).divn(10_000);
+      await expectError(transfer(threshold.addn(1)), "CircuitBreakerTriggered");
+      await transfer(new anchor.BN(1));
+
+      await setBreaker(false, 50);
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).circuitBreakerEnabled).to.be.false;
+      console.log("✓ Circuit breaker blocked an oversized transfer and was disabled through governance");
+    });
+
+    it("14s. Queues allowing governance operations during pause", async () => {
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await governanceProgram.methods.queueSetGovernanceOpsDuringPause(true, 0, "Allow burns during incident")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.txType).to.deep.equal({ setGovernanceOpsDuringPause: {} });
+      expect(Array.from(tx.data)).to.deep.equal([1]);
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      expect(tokenState.governanceOpsDuringPause).to.be.false;
+      console.log("✓ Pause exemption queued; strict pause stays in effect until executed");
+    });
+
+    it("14t. Routes token transactions to a registered token program", async () => {
+      const addTokenProgram = (programId: PublicKey, signer: Keypair) =>
+        governanceProgram.methods.addTokenProgram(programId)
+          .accounts({ governanceState: governanceStatePda, authority: signer.publicKey })
+          .signers([signer])
+          .rpc();
+      const removeTokenProgram = (programId: PublicKey, signer: Keypair) =>
+        governanceProgram.methods.removeTokenProgram(programId)
+          .accounts({ governanceState: governanceStatePda, authority: signer.publicKey })
+          .signers([signer])
+          .rpc();
+
+      await expectError(addTokenProgram(SECOND_TOKEN_PROGRAM_ID, user), "NotAuthorizedSigner");
+      await expectError(addTokenProgram(tokenProgram.programId, signer1), "DuplicateTokenProgram");
+
+      // The second program is spl-project built with the `second-instance` feature
+      if (!(await deploySecondTokenProgram())) {
+        console.log("ℹ Second token program not built (run `yarn build:second-token-program`) - skipping");
+        return;
+      }
+      const secondProgram = new Program<SplProject>(
+        { ...tokenProgram.idl, address: SECOND_TOKEN_PROGRAM_ID.toBase58() },
+        provider
+      );
+      const [secondStatePda] = PublicKey.findProgramAddressSync([Buffer.from("state")], SECOND_TOKEN_PROGRAM_ID);
+
+      await addTokenProgram(SECOND_TOKEN_PROGRAM_ID, signer1);
+      let govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const index = govState.tokenPrograms.findIndex((p: PublicKey) => p.equals(SECOND_TOKEN_PROGRAM_ID));
+      expect(index).to.equal(1);
+      expect(govState.tokenPrograms[0].equals(govState.tokenProgram)).to.be.true;
+
+      // Initialize the second token paused, then hand its authority to governance
+      await secondProgram.methods.initialize()
+        .accounts({ state: secondStatePda, authority: admin.publicKey, systemProgram: SystemProgram.programId })
+        .signers([admin])
+        .rpc();
+      await secondProgram.methods.setEmergencyPause(true)
+        .accounts({ state: secondStatePda, governance: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      await secondProgram.methods.proposeGovernanceChange(governanceStatePda)
+        .accounts({ state: secondStatePda, authority: admin.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([admin])
+        .rpc();
+      await warpTime(604800 + 1);
+      await secondProgram.methods.setGovernance(governanceStatePda)
+        .accounts({ state: secondStatePda, authority: admin.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([admin])
+        .rpc();
+      let secondState = await secondProgram.account.tokenState.fetch(secondStatePda);
+      expect(secondState.authority.equals(governanceStatePda)).to.be.true;
+      expect(secondState.emergencyPaused).to.be.true;
+
+      const queueUnpause = async (tokenProgramIndex: number) => {
+        const state = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = state.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await governanceProgram.methods.queueUnpause(tokenProgramIndex, "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+        return { txId, txPda };
+      };
+
+      await expectError(queueUnpause(4), "InvalidTokenProgramIndex");
+      const { txId, txPda } = await queueUnpause(index);
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.tokenProgramIndex).to.equal(index);
+      expect(tx.target.equals(SECOND_TOKEN_PROGRAM_ID)).to.be.true;
+
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(govState.cooldownPeriod.toNumber() + 1);
+      const execute = (programId: PublicKey, statePda: PublicKey) =>
+        governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda,
+            tokenProgram: programId,
+            tokenProgramProgram: programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+
+      // Executing through any other program than the one chosen at queue time fails
+      const primaryPausedBefore = (await tokenProgram.account.tokenState.fetch(tokenStatePda)).emergencyPaused;
+      await expectError(execute(tokenProgram.programId, tokenStatePda), "InvalidTokenProgramIndex");
+
+      // Executing through the second program unpauses it and leaves the primary untouched
+      await execute(SECOND_TOKEN_PROGRAM_ID, secondStatePda);
+      secondState = await secondProgram.account.tokenState.fetch(secondStatePda);
+      expect(secondState.emergencyPaused).to.be.false;
+      const primaryState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      expect(primaryState.emergencyPaused).to.equal(primaryPausedBefore);
+      expect((await governanceProgram.account.transaction.fetch(txPda)).status.executed).to.not.be.undefined;
+
+      // Removed slots are cleared, so transactions queued for them can no longer resolve a program
+      await expectError(removeTokenProgram(govState.tokenProgram, signer1), "InvalidTokenProgramIndex");
+      await removeTokenProgram(SECOND_TOKEN_PROGRAM_ID, signer1);
+      govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      expect(govState.tokenPrograms[index].equals(PublicKey.default)).to.be.true;
+      await expectError(queueUnpause(index), "InvalidTokenProgramIndex");
+      console.log("✓ Unpause queued for token program index 1 executed against the second program");
+    });
+
+    it("14u. Enables whitelist mode with one signer but queues disabling it", async () => {
+      const enableWhitelistMode = (signer: Keypair) =>
+        governanceProgram.methods.emergencyEnableWhitelistMode()
+          .accounts({
+            governanceState: governanceStatePda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            authority: signer.publicKey,
+          })
+          .signers([signer])
+          .rpc();
+
+      await expectError(enableWhitelistMode(user), "NotAuthorizedSigner");
+      await enableWhitelistMode(signer1);
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).whitelistMode).to.be.true;
+
+      let govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      await governanceProgram.methods.queueToggleWhitelistMode(false, 0, "Reopen transfers")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      const execute = () =>
+        governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            systemProgram: SystemProgram.programId,
+            payer: provider.wallet.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+
+      // Disabling waits out the cooldown like any other queued transaction
+      await expectError(execute(), "CooldownNotExpired");
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).whitelistMode).to.be.true;
+      govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(govState.cooldownPeriod.toNumber() + 1);
+      await execute();
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).whitelistMode).to.be.false;
+      console.log("✓ Whitelist mode enabled by one signer and disabled through the queue");
+    });
+
+    it("14v. Mints vesting tokens through governance and releases them over time", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping governance vesting mint");
+        return;
+      }
+
+      // A fresh beneficiary each run, since the vesting PDAs are derived from it
+      const beneficiary = Keypair.generate();
+      const total = new anchor.BN(30).mul(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)));
+      const cliffSeconds = 600;
+      const durationSeconds = 3600;
+      const [vestingSchedulePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting"), beneficiary.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const [vestingVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting_vault"), beneficiary.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const [beneficiaryBlacklistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), beneficiary.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const beneficiaryTokenAccount = await getAssociatedTokenAddress(mint.publicKey, beneficiary.publicKey);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(admin.publicKey, beneficiaryTokenAccount, beneficiary.publicKey, mint.publicKey)
+        ),
+        [admin]
+      );
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queue = (cliff: number, duration: number) =>
+        governanceProgram.methods
+          .queueMintWithVesting(beneficiary.publicKey, total, new anchor.BN(cliff), new anchor.BN(duration), 0, "Team vesting")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+
+      await expectError(queue(durationSeconds + 1, durationSeconds), "InvalidVestingConfig");
+      await queue(cliffSeconds, durationSeconds);
+
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      await warpTime(govState.cooldownPeriod.toNumber() + 1);
+
+      const supplyBefore = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          splTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          payer: provider.wallet.publicKey,
+          tokenMint: mint.publicKey,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      const supplyAfter = (await connection.getTokenSupply(mint.publicKey)).value.amount;
+      expect((BigInt(supplyAfter) - BigInt(supplyBefore)).toString()).to.equal(total.toString());
+      let schedule = await tokenProgram.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.beneficiary.toString()).to.equal(beneficiary.publicKey.toString());
+      expect(schedule.totalAmount.toString()).to.equal(total.toString());
+      expect(schedule.cliffSeconds.toNumber()).to.equal(cliffSeconds);
+      expect(schedule.durationSeconds.toNumber()).to.equal(durationSeconds);
+      expect(schedule.claimedAmount.toNumber()).to.equal(0);
+      expect((await connection.getTokenAccountBalance(vestingVaultPda)).value.amount).to.equal(total.toString());
+
+      const claim = () =>
+        tokenProgram.methods.claimVesting()
+          .accounts({
+            state: tokenStatePda,
+            vestingSchedule: vestingSchedulePda,
+            vestingVault: vestingVaultPda,
+            mint: mint.publicKey,
+            to: beneficiaryTokenAccount,
+            beneficiaryBlacklist: beneficiaryBlacklistPda,
+            beneficiary: beneficiary.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([beneficiary])
+          .rpc();
+      const beneficiaryBalance = async () =>
+        BigInt((await connection.getTokenAccountBalance(beneficiaryTokenAccount)).value.amount);
+
+      // Nothing unlocks before the cliff
+      await expectError(claim(), "NothingVested");
+
+      // Past the cliff, the elapsed share of the total unlocks linearly
+      await warpTime(cliffSeconds * 2);
+      await claim();
+      schedule = await tokenProgram.account.vestingSchedule.fetch(vestingSchedulePda);
+      const claimed = BigInt(schedule.claimedAmount.toString());
+      const totalAmount = BigInt(total.toString());
+      expect(claimed >= (totalAmount * BigInt(cliffSeconds)) / BigInt(durationSeconds)).to.be.true;
+      expect(claimed < totalAmount).to.be.true;
+      expect((await beneficiaryBalance()).toString()).to.equal(claimed.toString());
+
+      // After the duration, the remainder unlocks and the vault is drained
+      await warpTime(durationSeconds);
+      await claim();
+      schedule = await tokenProgram.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.claimedAmount.toString()).to.equal(total.toString());
+      expect((await beneficiaryBalance()).toString()).to.equal(total.toString());
+      expect((await connection.getTokenAccountBalance(vestingVaultPda)).value.amount).to.equal("0");
+      await expectError(claim(), "NothingVested");
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log(`✓ Governance vesting mint released ${claimed} at the first claim and the rest after the duration`);
+    });
+
+    it("14w. Sets an emission schedule through governance and mints the accrued emissions", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      if (!tokenState.authority.equals(governanceStatePda)) {
+        console.log("ℹ Token authority is not the governance PDA - skipping governance emission schedule");
+        return;
+      }
+
+      const [emissionSchedulePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("emission_schedule")],
+        tokenProgram.programId
+      );
+      const emissionRecipient = Keypair.generate();
+      const recipientTokenAccount = await getAssociatedTokenAddress(mint.publicKey, emissionRecipient.publicKey);
+      const [recipientBlacklistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), emissionRecipient.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(admin.publicKey, recipientTokenAccount, emissionRecipient.publicKey, mint.publicKey)
+        ),
+        [admin]
+      );
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const cooldown = govState.cooldownPeriod.toNumber();
+      const now = (await connection.getBlockTime(await connection.getSlot())) || Math.floor(Date.now() / 1000);
+      const ratePerSecond = new anchor.BN(1_000);
+      const endTime = now + cooldown + 3600;
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+      const queue = (start: number, end: number) =>
+        governanceProgram.methods
+          .queueSetEmissionSchedule(ratePerSecond, recipientTokenAccount, new anchor.BN(start), new anchor.BN(end), 0, "Ecosystem emissions")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc();
+
+      await expectError(queue(endTime, endTime), "InvalidEmissionSchedule");
+      await queue(now, endTime);
+
+      for (const approver of [signer1, signer2]) {
+        await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+          .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+          .signers([approver])
+          .rpc();
+      }
+      await warpTime(cooldown + 1);
+      await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+        .accountsPartial({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          systemProgram: SystemProgram.programId,
+          payer: provider.wallet.publicKey,
+          emissionSchedule: emissionSchedulePda,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      let schedule = await tokenProgram.account.emissionSchedule.fetch(emissionSchedulePda);
+      expect(schedule.ratePerSecond.toString()).to.equal(ratePerSecond.toString());
+      expect(schedule.recipient.toString()).to.equal(recipientTokenAccount.toString());
+      expect(schedule.endTime.toNumber()).to.equal(endTime);
+      // Accrual starts at execution, since the queued start time has passed by then
+      expect(schedule.lastMinted.toNumber()).to.be.greaterThan(now);
+
+      const mintEmission = () =>
+        tokenProgram.methods.mintScheduledEmission()
+          .accounts({
+            state: tokenStatePda,
+            emissionSchedule: emissionSchedulePda,
+            mint: mint.publicKey,
+            to: recipientTokenAccount,
+            recipientBlacklist: recipientBlacklistPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      const recipientBalance = async () =>
+        BigInt((await connection.getTokenAccountBalance(recipientTokenAccount)).value.amount);
+
+      // Each mint pays the rate times the seconds since the previous mint
+      await warpTime(600);
+      let lastMinted = schedule.lastMinted.toNumber();
+      await mintEmission();
+      schedule = await tokenProgram.account.emissionSchedule.fetch(emissionSchedulePda);
+      const firstMint = BigInt(ratePerSecond.toString()) * BigInt(schedule.lastMinted.toNumber() - lastMinted);
+      expect(firstMint > BigInt(0)).to.be.true;
+      expect((await recipientBalance()).toString()).to.equal(firstMint.toString());
+
+      // Accrual stops at the end time, after which nothing more is due
+      await warpTime(endTime - schedule.lastMinted.toNumber() + 60);
+      lastMinted = schedule.lastMinted.toNumber();
+      await mintEmission();
+      schedule = await tokenProgram.account.emissionSchedule.fetch(emissionSchedulePda);
+      expect(schedule.lastMinted.toNumber()).to.equal(endTime);
+      const secondMint = BigInt(ratePerSecond.toString()) * BigInt(endTime - lastMinted);
+      expect((await recipientBalance()).toString()).to.equal((firstMint + secondMint).toString());
+      await expectError(mintEmission(), "NoEmissionDue");
+
+      const tx = await governanceProgram.account.transaction.fetch(txPda);
+      expect(tx.status.executed).to.not.be.undefined;
+      console.log(`✓ Governance emission schedule minted ${firstMint + secondMint} up to its end time`);
+    });
+
+    it("14x. Tracks sell limits per wallet or per token account as set through governance", async () => {
+      const tokenState = await tokenProgram.account.tokenState.fetch(tokenStatePda);
+      const userBalance = await connection.getTokenAccountBalance(userTokenAccount).catch(() => ({ value: { amount: "0" } }));
+      if (!tokenState.authority.equals(governanceStatePda) || Number(userBalance.value.amount) < 10_000) {
+        console.log("ℹ Token authority is not the governance PDA or user has no balance - skipping sell tracker modes");
+        return;
+      }
+      if (tokenState.sellLimitsFrozen) {
+        console.log("ℹ Sell limits are frozen - skipping sell tracker modes");
+        return;
+      }
 
-      // Execute transaction using safe helper
-      // If access violation occurs, the helper will skip gracefully
-      try {
-        await safeExecuteTransaction(
-          governanceProgram,
-          new anchor.BN(txId1),
-          {
+      const pda = (seed: string, key: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], tokenProgram.programId)[0];
+      const runGovernance = async (
+        queue: (txPda: PublicKey) => Promise<string>,
+        remaining: anchor.web3.AccountMeta[] = []
+      ) => {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await queue(txPda);
+        for (const approver of [signer1, signer2]) {
+          await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+            .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+            .signers([approver])
+            .rpc();
+        }
+        await warpTime(govState.cooldownPeriod.toNumber() + 1);
+        await governanceProgram.methods.executeTransaction(new anchor.BN(txId))
+          .accountsPartial({
             governanceState: governanceStatePda,
-            transaction: txPda1,
+            transaction: txPda,
             statePda: tokenStatePda,
             tokenProgram: tokenProgram.programId,
             tokenProgramProgram: tokenProgram.programId,
-            presaleStatePda: presaleStatePda,
-            presaleProgramProgram: presaleProgram.programId,
-            presalePaymentVaultPda: presalePaymentVaultPda,
-            presalePaymentVault: presalePaymentVault,
-            treasuryTokenAccount: recipientTokenAccount,
-            paymentTokenMint: paymentTokenMint.publicKey,
-            splTokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
-            payer: signer1.publicKey,
-            blacklistAccount: (() => {
-              const [pda] = PublicKey.findProgramAddressSync(
-                [Buffer.from("blacklist"), blacklistedUser.publicKey.toBuffer()],
-                tokenProgram.programId
-              );
-              return pda;
-            })(),
-            targetAccount: blacklistedUser.publicKey,
-            noSellLimitAccount: (() => {
-              const [pda] = PublicKey.findProgramAddressSync(
-                [Buffer.from("noselllimit"), blacklistedUser.publicKey.toBuffer()],
-                tokenProgram.programId
-              );
-              return pda;
-            })(),
-            restrictedAccount: (() => {
-              const [pda] = PublicKey.findProgramAddressSync(
-                [Buffer.from("restricted"), blacklistedUser.publicKey.toBuffer()],
-                tokenProgram.programId
-              );
-              return pda;
-            })(),
-            liquidityPoolAccount: (() => {
-              const [pda] = PublicKey.findProgramAddressSync(
-                [Buffer.from("liquiditypool"), poolAddress.publicKey.toBuffer()],
-                tokenProgram.programId
-              );
-              return pda;
-            })(),
-            poolAddress: poolAddress.publicKey,
+            payer: provider.wallet.publicKey,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
-          "SetTreasuryAddress transaction"
+          })
+          .remainingAccounts(remaining)
+          .rpc();
+      };
+      const queueAccounts = (txPda: PublicKey) => ({
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      });
+      const setPerTokenAccount = (perTokenAccount: boolean) =>
+        runGovernance((txPda) =>
+          governanceProgram.methods.queueSetSellTrackerMode(perTokenAccount, 0, "")
+            .accounts(queueAccounts(txPda))
+            .signers([signer1])
+            .rpc()
         );
-      } catch (err: any) {
-        // If access violation, skip the rest of the test
-        const errMsg = err.message?.toLowerCase() || err.toString().toLowerCase();
-        if (errMsg.includes("access violation") || errMsg.includes("skip_test")) {
-          console.log("ℹ Skipping test due to access violation in test environment");
-          return;
-        }
-        throw err;
-      }
 
-      console.log("✓ Complete governance flow with presale state completed");
+      // A registered pool to sell into
+      const pool = Keypair.generate();
+      await runGovernance(
+        (txPda) =>
+          governanceProgram.methods.queueSetLiquidityPools([{ pool: pool.publicKey, isPool: true }], 0, "Sell tracker pool")
+            .accounts(queueAccounts(txPda))
+            .signers([signer1])
+            .rpc(),
+        [
+          { pubkey: pda("liquiditypool", pool.publicKey), isWritable: true, isSigner: false },
+          { pubkey: pool.publicKey, isWritable: false, isSigner: false },
+        ]
+      );
+      const poolTokenAccount = await getAssociatedTokenAddress(mint.publicKey, pool.publicKey);
+
+      // One seller wallet holding two token accounts of the same mint
+      const seller = Keypair.generate();
+      const airdrop = await connection.requestAirdrop(seller.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdrop);
+      const sellerAta = await getAssociatedTokenAddress(mint.publicKey, seller.publicKey);
+      const sellerSecondAccount = Keypair.generate();
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(admin.publicKey, poolTokenAccount, pool.publicKey, mint.publicKey),
+          createAssociatedTokenAccountInstruction(admin.publicKey, sellerAta, seller.publicKey, mint.publicKey),
+          SystemProgram.createAccount({
+            fromPubkey: admin.publicKey,
+            newAccountPubkey: sellerSecondAccount.publicKey,
+            space: ACCOUNT_SIZE,
+            lamports: await getMinimumBalanceForRentExemptAccount(connection),
+            programId: TOKEN_PROGRAM_ID,
+          }),
+          createInitializeAccountInstruction(sellerSecondAccount.publicKey, mint.publicKey, seller.publicKey)
+        ),
+        [admin, sellerSecondAccount]
+      );
+
+      const transfer = (
+        owner: Keypair,
+        from: PublicKey,
+        to: PublicKey,
+        toOwner: PublicKey,
+        amount: bigint,
+        trackerKey: PublicKey
+      ) =>
+        tokenProgram.methods.transferTokens(new anchor.BN(amount.toString()), null)
+          .accounts({
+            state: tokenStatePda,
+            mint: mint.publicKey,
+            fromAccount: from,
+            toAccount: to,
+            authority: owner.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            sellTracker: pda("selltracker", trackerKey),
+            senderBlacklist: pda("blacklist", owner.publicKey),
+            recipientBlacklist: pda("blacklist", toOwner),
+            senderRestricted: pda("restricted", owner.publicKey),
+            recipientRestricted: pda("restricted", toOwner),
+            liquidityPool: pda("liquiditypool", toOwner),
+            noSellLimit: pda("noselllimit", owner.publicKey),
+            senderWhitelist: pda("whitelist", owner.publicKey),
+            recipientWhitelist: pda("whitelist", toOwner),
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([owner])
+          .rpc();
+      const balance = async (account: PublicKey) =>
+        BigInt((await connection.getTokenAccountBalance(account)).value.amount);
+      for (const account of [sellerAta, sellerSecondAccount.publicKey]) {
+        await transfer(user, userTokenAccount, account, seller.publicKey, BigInt(2_000), user.publicKey);
+      }
+      const percent = BigInt(tokenState.sellLimitPercent);
+      const limitOf = async (account: PublicKey) => ((await balance(account)) * percent) / BigInt(100);
+      const sell = (from: PublicKey, amount: bigint, trackerKey: PublicKey) =>
+        transfer(seller, from, poolTokenAccount, pool.publicKey, amount, trackerKey);
+
+      // Per token account: each account has its own tracker and its own limit
+      await setPerTokenAccount(true);
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).sellTrackerMode.perTokenAccount).to.not.be.undefined;
+      const firstLimit = await limitOf(sellerAta);
+      await sell(sellerAta, firstLimit, sellerAta);
+      await expectError(sell(sellerAta, BigInt(1), sellerAta), "SellLimitExceeded");
+      const secondLimit = await limitOf(sellerSecondAccount.publicKey);
+      await sell(sellerSecondAccount.publicKey, secondLimit, sellerSecondAccount.publicKey);
+      const ataTracker = await tokenProgram.account.sellTracker.fetch(pda("selltracker", sellerAta));
+      const secondTracker = await tokenProgram.account.sellTracker.fetch(pda("selltracker", sellerSecondAccount.publicKey));
+      expect(ataTracker.account.toString()).to.equal(sellerAta.toString());
+      expect(ataTracker.totalSold24h.toString()).to.equal(firstLimit.toString());
+      expect(secondTracker.account.toString()).to.equal(sellerSecondAccount.publicKey.toString());
+      expect(secondTracker.totalSold24h.toString()).to.equal(secondLimit.toString());
+      expect(await connection.getAccountInfo(pda("selltracker", seller.publicKey))).to.be.null;
+
+      // Per wallet: sales from both accounts count against one tracker
+      await setPerTokenAccount(false);
+      expect((await tokenProgram.account.tokenState.fetch(tokenStatePda)).sellTrackerMode.perWallet).to.not.be.undefined;
+      const half = (await limitOf(sellerAta)) / BigInt(2) + BigInt(1);
+      await sell(sellerAta, half, seller.publicKey);
+      await expectError(sell(sellerSecondAccount.publicKey, half, seller.publicKey), "SellLimitExceeded");
+      const walletTracker = await tokenProgram.account.sellTracker.fetch(pda("selltracker", seller.publicKey));
+      expect(walletTracker.account.toString()).to.equal(seller.publicKey.toString());
+      expect(walletTracker.totalSold24h.toString()).to.equal(half.toString());
+      console.log("✓ Sell trackers isolated per token account and shared per wallet");
     });
   });
 
@@ -1909,7 +3645,7 @@ async function warpTime(seconds: number) {
           governanceProgram.programId
         );
 
-        await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true)
+        await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -2045,7 +3781,7 @@ async function warpTime(seconds: number) {
       );
 
       await expectError(
-        tokenProgram.methods.transferTokens(transferAmount)
+        tokenProgram.methods.transferTokens(transferAmount, null)
           .accounts({
             state: tokenStatePda,
             mint: mint.publicKey,
@@ -2089,7 +3825,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetRestricted(restrictedUser.publicKey, true)
+      await governanceProgram.methods.queueSetRestricted(restrictedUser.publicKey, true, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda,
@@ -2173,7 +3909,7 @@ async function warpTime(seconds: number) {
       await expectError(
         tokenProgram.methods.transferTokens(new anchor.BN(100).mul(
             new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-          ))
+          ), null)
           .accounts({
             state: tokenStatePda,
             mint: mint.publicKey,
@@ -2235,7 +3971,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetLiquidityPool(poolAddress.publicKey, true)
+      await governanceProgram.methods.queueSetLiquidityPool(poolAddress.publicKey, true, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda,
@@ -2388,7 +4124,7 @@ async function warpTime(seconds: number) {
 
       // This should fail with SellLimitExceeded
       await expectError(
-        tokenProgram.methods.transferTokens(sellLimitAmount)
+        tokenProgram.methods.transferTokens(sellLimitAmount, null)
           .accounts({
             state: tokenStatePda,
             mint: mint.publicKey,
@@ -2523,7 +4259,7 @@ async function warpTime(seconds: number) {
       await expectError(
         tokenProgram.methods.transferTokens(new anchor.BN(100).mul(
             new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-          ))
+          ), null)
           .accounts({
             state: tokenStatePda,
             mint: mint.publicKey,
@@ -2562,7 +4298,7 @@ async function warpTime(seconds: number) {
             })(),
             liquidityPool: (() => {
               const [pda] = PublicKey.findProgramAddressSync(
-                [Buffer.from("liquiditypool"), PublicKey.default.toBuffer()],
+                [Buffer.from("liquiditypool"), recipient.publicKey.toBuffer()],
                 tokenProgram.programId
               );
               return pda;
@@ -2781,7 +4517,7 @@ async function warpTime(seconds: number) {
       await expectError(
         presaleProgram.methods.buy( new anchor.BN(100).mul(
             new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-          ))
+          ), null, null, [], false)
           .accounts({
             presaleState: presaleStatePda,
             tokenState: tokenStatePda,
@@ -2794,10 +4530,21 @@ async function warpTime(seconds: number) {
             presaleTokenVault: presaleTokenVault,
             buyerTokenAccount: buyerPresaleTokenAccount,
             paymentTokenMint: paymentTokenMint.publicKey,
+            presaleTokenMint: mint.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             userPurchase: userPurchasePda,
+            purchaseRecord: await nextPurchaseRecordPda(user.publicKey),
+            raisedTracker: raisedTrackerPda(paymentTokenMint.publicKey),
             buyerBlacklist: buyerBlacklistPda,
+            presaleRound: null,
+            nextPresaleRound: null,
+            buyerWhitelist: null,
+            referralAccount: null,
+            referrerBlacklist: null,
+            vestingAccount: null,
+            instructionsSysvar: null,
+            purchaseCode: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([user])
@@ -2938,7 +4685,7 @@ async function warpTime(seconds: number) {
       await expectError(
         presaleProgram.methods.buy( new anchor.BN(100).mul(
             new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-          ))
+          ), null, null, [], false)
           .accounts({
             presaleState: presaleStatePda,
             tokenState: tokenStatePda,
@@ -2951,10 +4698,21 @@ async function warpTime(seconds: number) {
             presaleTokenVault: presaleTokenVault,
             buyerTokenAccount: buyerPresaleTokenAccount,
             paymentTokenMint: paymentTokenMint.publicKey,
+            presaleTokenMint: mint.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             userPurchase: userPurchasePda,
+            purchaseRecord: await nextPurchaseRecordPda(user.publicKey),
+            raisedTracker: raisedTrackerPda(paymentTokenMint.publicKey),
             buyerBlacklist: buyerBlacklistPda,
+            presaleRound: null,
+            nextPresaleRound: null,
+            buyerWhitelist: null,
+            referralAccount: null,
+            referrerBlacklist: null,
+            vestingAccount: null,
+            instructionsSysvar: null,
+            purchaseCode: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([user])
@@ -2992,7 +4750,7 @@ async function warpTime(seconds: number) {
           governanceProgram.programId
         );
 
-        await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true)
+        await governanceProgram.methods.queueSetBlacklist(blacklistedUser.publicKey, true, 0, "")
           .accounts({
             governanceState: governanceStatePda,
             transaction: txPda,
@@ -3165,7 +4923,7 @@ async function warpTime(seconds: number) {
       await expectError(
         presaleProgram.methods.buy( new anchor.BN(100).mul(
             new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-          ))
+          ), null, null, [], false)
           .accounts({
             presaleState: presaleStatePda,
             tokenState: tokenStatePda,
@@ -3178,10 +4936,21 @@ async function warpTime(seconds: number) {
             presaleTokenVault: presaleTokenVault,
             buyerTokenAccount: blacklistedBuyerPresaleAccount,
             paymentTokenMint: paymentTokenMint.publicKey,
+            presaleTokenMint: mint.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             userPurchase: userPurchasePda,
+            purchaseRecord: await nextPurchaseRecordPda(blacklistedUser.publicKey),
+            raisedTracker: raisedTrackerPda(paymentTokenMint.publicKey),
             buyerBlacklist: buyerBlacklistPda,
+            presaleRound: null,
+            nextPresaleRound: null,
+            buyerWhitelist: null,
+            referralAccount: null,
+            referrerBlacklist: null,
+            vestingAccount: null,
+            instructionsSysvar: null,
+            purchaseCode: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([blacklistedUser])
@@ -3204,7 +4973,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetBridgeAddress(bridgeAddress)
+      await governanceProgram.methods.queueSetBridgeAddress(bridgeAddress, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda1,
@@ -3294,7 +5063,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetBondAddress(bondAddress)
+      await governanceProgram.methods.queueSetBondAddress(bondAddress, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda,
@@ -3476,7 +5245,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetNoSellLimit(user.publicKey, true)
+      await governanceProgram.methods.queueSetNoSellLimit(user.publicKey, true, null, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda,
@@ -3568,7 +5337,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetBlacklist(testUser.publicKey, true)
+      await governanceProgram.methods.queueSetBlacklist(testUser.publicKey, true, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda1,
@@ -3663,7 +5432,7 @@ async function warpTime(seconds: number) {
         governanceProgram.programId
       );
 
-      await governanceProgram.methods.queueSetBridgeAddress(bridgeAddress)
+      await governanceProgram.methods.queueSetBridgeAddress(bridgeAddress, 0, "")
         .accounts({
           governanceState: governanceStatePda,
           transaction: txPda2,
@@ -3868,6 +5637,140 @@ async function warpTime(seconds: number) {
       console.log("✓ Governance set for presale program");
     });
 
+    it("33b. Updates the token price through a governance transaction", async () => {
+      const stateBefore = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      const newPrice = stateBefore.tokenPriceUsdMicro.addn(1);
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      // A zero price is rejected at queue time
+      await expectError(
+        governanceProgram.methods.queueUpdateTokenPrice(new anchor.BN(0), "")
+          .accounts({
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            presaleState: presaleStatePda,
+            initiator: signer1.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([signer1])
+          .rpc(),
+        "InvalidAmount"
+      );
+
+      await governanceProgram.methods.queueUpdateTokenPrice(newPrice, "Reprice presale")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          presaleState: presaleStatePda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      const queued = await governanceProgram.account.transaction.fetch(txPda);
+      expect(queued.txType.updateTokenPrice).to.not.be.undefined;
+      expect(new anchor.BN(queued.data, "le").toString()).to.equal(newPrice.toString());
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer1.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer1])
+        .rpc();
+
+      await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+        .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: signer2.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([signer2])
+        .rpc();
+
+      const currentGovState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      await warpTime(currentGovState.cooldownPeriod.toNumber() + 1);
+
+      await safeExecuteTransaction(
+        governanceProgram,
+        new anchor.BN(txId),
+        {
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          statePda: tokenStatePda,
+          tokenProgram: tokenProgram.programId,
+          tokenProgramProgram: tokenProgram.programId,
+          presaleStatePda: presaleStatePda,
+          presaleProgramProgram: presaleProgram.programId,
+          presalePaymentVaultPda: presalePaymentVaultPda,
+          presalePaymentVault: presalePaymentVault,
+          treasuryTokenAccount: recipientTokenAccount,
+          paymentTokenMint: paymentTokenMint.publicKey,
+          splTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          payer: signer1.publicKey,
+          blacklistAccount: PublicKey.default,
+          targetAccount: PublicKey.default,
+          noSellLimitAccount: PublicKey.default,
+          restrictedAccount: PublicKey.default,
+          liquidityPoolAccount: PublicKey.default,
+          poolAddress: PublicKey.default,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        "UpdateTokenPrice transaction"
+      );
+
+      const executed = await governanceProgram.account.transaction.fetch(txPda);
+      if (executed.status.executed === undefined) {
+        console.log("ℹ Cooldown not elapsed in this environment - skipping price verification");
+        return;
+      }
+      const stateAfter = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(stateAfter.tokenPriceUsdMicro.toString()).to.equal(newPrice.toString());
+      console.log("✓ Token price updated through governance");
+    });
+
+    it("33c. Requires a governance transaction to start the presale", async () => {
+      // The admin key alone can no longer start the sale once governance is set
+      await expectError(
+        presaleProgram.methods.startPresale()
+          .accounts({
+            presaleState: presaleStatePda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+      const txId = govState.nextTransactionId.toNumber();
+      const [txPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+        governanceProgram.programId
+      );
+
+      await governanceProgram.methods.queueStartPresale("Open the sale")
+        .accounts({
+          governanceState: governanceStatePda,
+          transaction: txPda,
+          initiator: signer1.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([signer1])
+        .rpc();
+
+      const queued = await governanceProgram.account.transaction.fetch(txPda);
+      expect(queued.txType.startPresale).to.not.be.undefined;
+      expect(queued.status.pending).to.not.be.undefined;
+      expect(queued.target.toString()).to.equal(presaleProgram.programId.toString());
+      console.log("✓ Presale start queued for governance approval");
+    });
+
     it("34. Allows updating presale cap after initialization", async () => {
       const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
       
@@ -3920,6 +5823,108 @@ async function warpTime(seconds: number) {
       console.log("✓ Presale cap updated successfully");
     });
 
+    it("34b. Updates the presale cap and max_per_user through governance transactions", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.governanceSet || Object.keys(state.status)[0] === "stopped") {
+        console.log("ℹ Presale governance not set or presale stopped - skipping");
+        return;
+      }
+
+      // Queue, approve and execute a single-value governance transaction
+      async function runGovernanceUpdate(
+        queue: (txPda: PublicKey) => Promise<string>,
+        description: string
+      ): Promise<PublicKey> {
+        const govState = await governanceProgram.account.governanceState.fetch(governanceStatePda);
+        const txId = govState.nextTransactionId.toNumber();
+        const [txPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("transaction"), Buffer.from(new anchor.BN(txId).toArray("le", 8))],
+          governanceProgram.programId
+        );
+        await queue(txPda);
+
+        for (const approver of [signer1, signer2]) {
+          await governanceProgram.methods.approveTransaction(new anchor.BN(txId))
+            .accounts({ governanceState: governanceStatePda, transaction: txPda, approver: approver.publicKey, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+            .signers([approver])
+            .rpc();
+        }
+
+        await warpTime(govState.cooldownPeriod.toNumber() + 1);
+
+        await safeExecuteTransaction(
+          governanceProgram,
+          new anchor.BN(txId),
+          {
+            governanceState: governanceStatePda,
+            transaction: txPda,
+            statePda: tokenStatePda,
+            tokenProgram: tokenProgram.programId,
+            tokenProgramProgram: tokenProgram.programId,
+            presaleStatePda: presaleStatePda,
+            presaleProgramProgram: presaleProgram.programId,
+            presalePaymentVaultPda: presalePaymentVaultPda,
+            presalePaymentVault: presalePaymentVault,
+            treasuryTokenAccount: recipientTokenAccount,
+            paymentTokenMint: paymentTokenMint.publicKey,
+            splTokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            payer: signer1.publicKey,
+            blacklistAccount: PublicKey.default,
+            targetAccount: PublicKey.default,
+            noSellLimitAccount: PublicKey.default,
+            restrictedAccount: PublicKey.default,
+            liquidityPoolAccount: PublicKey.default,
+            poolAddress: PublicKey.default,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          description
+        );
+        return txPda;
+      }
+
+      const queueAccounts = (txPda: PublicKey) => ({
+        governanceState: governanceStatePda,
+        transaction: txPda,
+        initiator: signer1.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      });
+
+      const newCap = state.maxPresaleCap.isZero()
+        ? new anchor.BN(8_000_000).mul(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)))
+        : state.maxPresaleCap.addn(1);
+      const capTx = await runGovernanceUpdate(
+        (txPda) => governanceProgram.methods.queueUpdatePresaleCap(newCap, "Raise presale cap")
+          .accounts(queueAccounts(txPda))
+          .signers([signer1])
+          .rpc(),
+        "UpdatePresaleCap transaction"
+      );
+
+      // Matching the cap keeps later purchase tests unconstrained
+      const newMax = newCap;
+      const maxTx = await runGovernanceUpdate(
+        (txPda) => governanceProgram.methods.queueUpdateMaxPerUser(newMax, "Adjust per-user limit")
+          .accounts(queueAccounts(txPda))
+          .signers([signer1])
+          .rpc(),
+        "UpdateMaxPerUser transaction"
+      );
+
+      const capExecuted = await governanceProgram.account.transaction.fetch(capTx);
+      const maxExecuted = await governanceProgram.account.transaction.fetch(maxTx);
+      if (capExecuted.status.executed === undefined || maxExecuted.status.executed === undefined) {
+        console.log("ℹ Cooldown not elapsed in this environment - skipping limit verification");
+        return;
+      }
+      const stateAfter = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(stateAfter.maxPresaleCap.toString()).to.equal(newCap.toString());
+      expect(stateAfter.maxPerUser.toString()).to.equal(newMax.toString());
+      console.log("✓ Presale cap and max_per_user updated through governance");
+    });
+
     it("35. Allows updating max_per_user after initialization", async () => {
       const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
       
@@ -4215,7 +6220,7 @@ async function warpTime(seconds: number) {
       try {
         await presaleProgram.methods.buy(new anchor.BN(50).mul(
           new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
-        ))
+        ), null, null, [], false)
           .accounts({
             presaleState: presaleStatePda,
             tokenState: tokenStatePda,
@@ -4228,10 +6233,21 @@ async function warpTime(seconds: number) {
             presaleTokenVault: presaleTokenVault,
             buyerTokenAccount: buyerPresaleTokenAccount,
             paymentTokenMint: paymentTokenMint.publicKey,
+            presaleTokenMint: mint.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             userPurchase: userPurchasePda,
+            purchaseRecord: await nextPurchaseRecordPda(user.publicKey),
+            raisedTracker: raisedTrackerPda(paymentTokenMint.publicKey),
             buyerBlacklist: buyerBlacklistPda,
+            presaleRound: null,
+            nextPresaleRound: null,
+            buyerWhitelist: null,
+            referralAccount: null,
+            referrerBlacklist: null,
+            vestingAccount: null,
+            instructionsSysvar: null,
+            purchaseCode: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([user])
@@ -4317,6 +6333,7 @@ async function warpTime(seconds: number) {
               presaleTokenVault: presaleTokenVault,
               destinationTokenAccount: destinationTokenAccount,
               destination: admin.publicKey,
+              presaleTokenMint: mint.publicKey,
               tokenProgram: TOKEN_PROGRAM_ID,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             })
@@ -4404,6 +6421,7 @@ async function warpTime(seconds: number) {
           presaleTokenVault: presaleTokenVault,
           destinationTokenAccount: destinationTokenAccount,
           destination: admin.publicKey,
+          presaleTokenMint: mint.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         });
@@ -4462,6 +6480,7 @@ async function warpTime(seconds: number) {
               presaleTokenVault: presaleTokenVault,
               destinationTokenAccount: destinationTokenAccount,
               destination: admin.publicKey,
+              presaleTokenMint: mint.publicKey,
               tokenProgram: TOKEN_PROGRAM_ID,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             })
@@ -4489,6 +6508,7 @@ async function warpTime(seconds: number) {
           presaleTokenVault: presaleTokenVault,
           destinationTokenAccount: destinationTokenAccount,
           destination: admin.publicKey,
+          presaleTokenMint: mint.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         });
@@ -4524,6 +6544,7 @@ async function warpTime(seconds: number) {
             presaleTokenVault: presaleTokenVault,
             destinationTokenAccount: destinationTokenAccount,
             destination: user.publicKey,
+            presaleTokenMint: mint.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           })
@@ -4624,7 +6645,7 @@ async function warpTime(seconds: number) {
       // Test 1: When whitelist mode is disabled, transfers work normally
       const balanceBefore = await connection.getTokenAccountBalance(recipientTokenAccount);
       
-      await tokenProgram.methods.transferTokens(TRANSFER_AMOUNT)
+      await tokenProgram.methods.transferTokens(TRANSFER_AMOUNT, null)
         .accounts({
           state: tokenStatePda,
           mint: mint.publicKey,
@@ -0,0 +1,1584 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createMintToInstruction,
+  getAssociatedTokenAddress,
+  getMinimumBalanceForRentExemptMint,
+  MINT_SIZE,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import { Presale } from "../target/types/presale";
+import { SplProject } from "../target/types/spl_project";
+import { loadTestKeys } from "./key-loader";
+
+/**
+ * PRESALE FEATURE TESTS
+ *
+ * Exercises purchase options and presale configuration on an active presale:
+ * referrals, rounds, oracle settings, payment tokens, pricing, whitelists,
+ * allocations, purchase codes, treasury withdrawals and admin rotation.
+ *
+ * NOTE: Must run before 06-presale-refunds.ts, which marks the presale as Failed.
+ */
+
+describe("Presale Features", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const presaleProgram = anchor.workspace.Presale as Program<Presale>;
+  const tokenProgram = anchor.workspace.SplProject as Program<SplProject>;
+  const connection = provider.connection;
+
+  const keys = loadTestKeys();
+  const admin = keys.authority;
+  const mint = keys.mint;
+
+  // Fresh buyer and payment mint so earlier test files don't affect purchase records
+  const buyer = Keypair.generate();
+  const paymentMint = Keypair.generate();
+
+  let tokenStatePda: PublicKey;
+  let presaleStatePda: PublicKey;
+  let presaleTokenVaultPda: PublicKey;
+  let presaleTokenVault: PublicKey;
+  let presalePaymentVaultPda: PublicKey;
+  let presalePaymentVault: PublicKey;
+  let allowedTokenPda: PublicKey;
+  let userPurchasePda: PublicKey;
+  let buyerBlacklistPda: PublicKey;
+  let buyerPaymentTokenAccount: PublicKey;
+  let buyerPresaleTokenAccount: PublicKey;
+
+  const MINT_DECIMALS = 9;
+  const PAYMENT_AMOUNT = new anchor.BN(10).mul(
+    new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
+  );
+
+  // Pyth v2 SOL/USD price accounts preloaded by Anchor.toml, all at $150
+  const PYTH_SOL_USD_FEED = new PublicKey("HEfV2tqreAeDPCbYJ1smZ4wbgSZWcKByud1dfnD65GaB"); // expo -6
+  const PYTH_SOL_USD_EXPO_8_FEED = new PublicKey("7yM14n6Gy2mTZ1t678sraNpS2fZxscazb1mZThq7qzsn");
+  const PYTH_WIDE_CONFIDENCE_FEED = new PublicKey("CjmcSzBQC3w322AEszB8jf3hYopoT1fUHpXXiRQ3MeBN"); // 3% confidence
+  const PYTH_HALTED_FEED = new PublicKey("BvLPsjD4qhjc5E9ys9Pc75ME6TVAkhcPT3EEF6BVYCNi");
+  const PYTH_SOL_PRICE_8_DECIMALS = new anchor.BN(15_000_000_000);
+
+  // Helper to expect Anchor errors
+  async function expectError(promise: Promise<any>, errorMsg: string) {
+    try {
+      await promise;
+      expect.fail(`Expected error containing "${errorMsg}" but transaction succeeded`);
+    } catch (err: any) {
+      const errStr = err.toString().toLowerCase();
+      expect(errStr).to.include(errorMsg.toLowerCase());
+    }
+  }
+
+  // Derive the PurchaseRecord PDA for a buyer's next purchase
+  async function nextPurchaseRecordPda(buyerKey: PublicKey): Promise<PublicKey> {
+    const [userPurchase] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), buyerKey.toBuffer()],
+      presaleProgram.programId
+    );
+    const purchase = await presaleProgram.account.userPurchase.fetchNullable(userPurchase);
+    return purchaseRecordPda(buyerKey, purchase ? purchase.purchaseCount : 0);
+  }
+
+  function purchaseRecordPda(buyerKey: PublicKey, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("purchase_record"), presaleStatePda.toBuffer(), buyerKey.toBuffer(), indexBytes],
+      presaleProgram.programId
+    )[0];
+  }
+
+  // Derive the RaisedTracker PDA for a payment mint (PublicKey.default for SOL)
+  function raisedTrackerPda(paymentMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("raised_tracker"), presaleStatePda.toBuffer(), paymentMint.toBuffer()],
+      presaleProgram.programId
+    )[0];
+  }
+
+  async function buyAccounts() {
+    return {
+      presaleState: presaleStatePda,
+      tokenState: tokenStatePda,
+      allowedToken: allowedTokenPda,
+      buyer: buyer.publicKey,
+      buyerPaymentTokenAccount: buyerPaymentTokenAccount,
+      presalePaymentVaultPda: presalePaymentVaultPda,
+      presalePaymentVault: presalePaymentVault,
+      presaleTokenVaultPda: presaleTokenVaultPda,
+      presaleTokenVault: presaleTokenVault,
+      buyerTokenAccount: buyerPresaleTokenAccount,
+      paymentTokenMint: paymentMint.publicKey,
+      presaleTokenMint: mint.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      userPurchase: userPurchasePda,
+      purchaseRecord: await nextPurchaseRecordPda(buyer.publicKey),
+      raisedTracker: raisedTrackerPda(paymentMint.publicKey),
+      buyerBlacklist: buyerBlacklistPda,
+      presaleRound: null,
+      nextPresaleRound: null,
+      buyerWhitelist: null,
+      referralAccount: null,
+      referrerBlacklist: null,
+      vestingAccount: null,
+      instructionsSysvar: null,
+      purchaseCode: null,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  // Fresh buyer with a presale token account, for SOL purchases; the main buyer pays in
+  // payment tokens and a buyer must stick to one payment method
+  async function newSolBuyer(): Promise<Keypair> {
+    const solBuyer = Keypair.generate();
+    const sig = await connection.requestAirdrop(solBuyer.publicKey, 2 * LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(sig);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          admin.publicKey,
+          await getAssociatedTokenAddress(mint.publicKey, solBuyer.publicKey),
+          solBuyer.publicKey,
+          mint.publicKey
+        )
+      ),
+      [admin]
+    );
+    return solBuyer;
+  }
+
+  async function solBuyAccounts(solBuyer: PublicKey, feed: PublicKey) {
+    return {
+      presaleState: presaleStatePda,
+      tokenState: tokenStatePda,
+      buyer: solBuyer,
+      solVault: PublicKey.findProgramAddressSync(
+        [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+        presaleProgram.programId
+      )[0],
+      presaleTokenVaultPda: presaleTokenVaultPda,
+      presaleTokenVault: presaleTokenVault,
+      buyerTokenAccount: await getAssociatedTokenAddress(mint.publicKey, solBuyer),
+      presaleTokenMint: mint.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      userPurchase: PublicKey.findProgramAddressSync(
+        [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), solBuyer.toBuffer()],
+        presaleProgram.programId
+      )[0],
+      purchaseRecord: await nextPurchaseRecordPda(solBuyer),
+      raisedTracker: raisedTrackerPda(PublicKey.default),
+      buyerBlacklist: PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), solBuyer.toBuffer()],
+        tokenProgram.programId
+      )[0],
+      chainlinkFeed: feed,
+      chainlinkFeedBackup: null,
+      presaleRound: null,
+      nextPresaleRound: null,
+      buyerWhitelist: null,
+      referralAccount: null,
+      referrerBlacklist: null,
+      vestingAccount: null,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  before(async () => {
+    for (const account of [admin, buyer]) {
+      const balance = await connection.getBalance(account.publicKey);
+      if (balance < 2 * LAMPORTS_PER_SOL) {
+        const sig = await connection.requestAirdrop(account.publicKey, 5 * LAMPORTS_PER_SOL);
+        await connection.confirmTransaction(sig);
+      }
+    }
+
+    [tokenStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state")], tokenProgram.programId
+    );
+    [presaleStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_state")], presaleProgram.programId
+    );
+    [presaleTokenVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_token_vault_pda"), mint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [presalePaymentVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_payment_vault_pda"), presaleStatePda.toBuffer(), paymentMint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [allowedTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("allowed_token"), presaleStatePda.toBuffer(), paymentMint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [userPurchasePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), buyer.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [buyerBlacklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), buyer.publicKey.toBuffer()],
+      tokenProgram.programId
+    );
+
+    presaleTokenVault = await getAssociatedTokenAddress(mint.publicKey, presaleTokenVaultPda, true);
+    presalePaymentVault = await getAssociatedTokenAddress(paymentMint.publicKey, presalePaymentVaultPda, true);
+    buyerPaymentTokenAccount = await getAssociatedTokenAddress(paymentMint.publicKey, buyer.publicKey);
+    buyerPresaleTokenAccount = await getAssociatedTokenAddress(mint.publicKey, buyer.publicKey);
+
+    // Create payment mint, buyer ATAs and payment vault ATA
+    const mintRent = await getMinimumBalanceForRentExemptMint(connection);
+    const setupTx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: paymentMint.publicKey,
+        space: MINT_SIZE,
+        lamports: mintRent,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMintInstruction(
+        paymentMint.publicKey, MINT_DECIMALS, admin.publicKey, null
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, buyerPaymentTokenAccount, buyer.publicKey, paymentMint.publicKey
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, buyerPresaleTokenAccount, buyer.publicKey, mint.publicKey
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, presalePaymentVault, presalePaymentVaultPda, paymentMint.publicKey
+      ),
+      createMintToInstruction(
+        paymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(PAYMENT_AMOUNT.toString())
+      )
+    );
+    await sendAndConfirmTransaction(connection, setupTx, [admin, paymentMint]);
+
+    // Allow the payment token and make sure presale is active
+    await presaleProgram.methods.allowPaymentToken(paymentMint.publicKey)
+      .accountsPartial({
+        presaleState: presaleStatePda,
+        allowedToken: allowedTokenPda,
+        admin: admin.publicKey,
+        paymentTokenMintAccount: paymentMint.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (Object.keys(state.status)[0] !== "active") {
+      await presaleProgram.methods.startPresale()
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    }
+
+    // Limit and repeat-buyer checks below start from an existing purchase
+    await presaleProgram.methods.buy(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)), null, null, [], false)
+      .accountsPartial(await buyAccounts())
+      .signers([buyer])
+      .rpc();
+  });
+
+  describe("Purchases", () => {
+    it("Reports aggregated presale stats", async () => {
+      const initial = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (
+        !initial.authority.equals(admin.publicKey) ||
+        initial.activeRoundId !== null ||
+        initial.priceTiers.length > 0
+      ) {
+        console.log("ℹ Authority moved to governance, a round is active or price tiers are set - skipping");
+        return;
+      }
+      if (!(await connection.getAccountInfo(PYTH_SOL_USD_FEED))) {
+        console.log("ℹ Pyth fixtures not loaded - skipping");
+        return;
+      }
+      const oneToken = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      // USD value of a purchase at the flat token price (token base units have 8 decimals)
+      const usdMicro = (tokens: anchor.BN) =>
+        tokens.mul(initial.tokenPriceUsdMicro).div(new anchor.BN(100_000_000));
+      let purchasedTokens = new anchor.BN(0);
+      let purchasedUsdMicro = new anchor.BN(0);
+      const recordPurchase = async (record: PublicKey) => {
+        const tokens = (await presaleProgram.account.purchaseRecord.fetch(record)).amountTokens;
+        purchasedTokens = purchasedTokens.add(tokens);
+        purchasedUsdMicro = purchasedUsdMicro.add(usdMicro(tokens));
+      };
+
+      // Two payment-token purchases
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createMintToInstruction(
+            paymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(oneToken.muln(3).toString())
+          )
+        ),
+        [admin]
+      );
+      for (const amount of [oneToken, oneToken.muln(2)]) {
+        const accounts = await buyAccounts();
+        await presaleProgram.methods.buy(amount, null, null, [], false)
+          .accountsPartial(accounts)
+          .signers([buyer])
+          .rpc();
+        await recordPurchase(accounts.purchaseRecord);
+      }
+
+      // Two SOL purchases, priced by the $150 Pyth fixture
+      const solBuyer = await newSolBuyer();
+      await presaleProgram.methods.setOracleType({ pyth: {} }, PYTH_SOL_USD_FEED)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        for (const lamports of [LAMPORTS_PER_SOL / 100, LAMPORTS_PER_SOL / 50]) {
+          const accounts = await solBuyAccounts(solBuyer.publicKey, PYTH_SOL_USD_FEED);
+          await presaleProgram.methods.buyWithSol(new anchor.BN(lamports), null, null, new anchor.BN(0), [], false)
+            .accountsPartial(accounts)
+            .signers([solBuyer])
+            .rpc();
+          await recordPurchase(accounts.purchaseRecord);
+        }
+      } finally {
+        await presaleProgram.methods.setOracleType(initial.oracleType, initial.oracleFeed)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      const { events } = await presaleProgram.methods.getPresaleStats()
+        .accounts({ presaleState: presaleStatePda })
+        .simulate();
+      const stats = events.find((event) => event.name === "presaleStats")?.data;
+      expect(stats, "PresaleStats event").to.not.be.undefined;
+
+      expect(Object.keys(stats.status)[0]).to.equal(Object.keys(state.status)[0]);
+      expect(stats.totalTokensSold.toString()).to.equal(
+        initial.totalTokensSold.add(purchasedTokens).toString()
+      );
+      expect(stats.tokenPriceUsdMicro.toString()).to.equal(state.tokenPriceUsdMicro.toString());
+      // Lamports and payment-token units are each valued at the token price they bought at
+      expect(stats.totalRaisedUsdMicro.toString()).to.equal(
+        initial.totalRaisedUsdMicro.add(purchasedUsdMicro).toString()
+      );
+      if (state.maxPresaleCap.isZero()) {
+        expect(stats.remainingCap).to.be.null;
+        expect(stats.percentCompleteBps).to.equal(0);
+      } else {
+        const progress = state.capInUsd ? state.totalRaisedUsdMicro : state.totalTokensSold.add(state.referralBonusTotal);
+        const remaining = state.maxPresaleCap.gt(progress) ? state.maxPresaleCap.sub(progress) : new anchor.BN(0);
+        const bps = Math.min(progress.muln(10_000).div(state.maxPresaleCap).toNumber(), 10_000);
+        expect(stats.remainingCap.toString()).to.equal(remaining.toString());
+        expect(stats.percentCompleteBps).to.equal(bps);
+      }
+      console.log("✓ Presale stats match on-chain state");
+    });
+
+    it("Rejects a blacklist account that is not the buyer's blacklist PDA", async () => {
+      // Passing some other empty account must not bypass the blacklist check
+      await expectError(
+        presaleProgram.methods.buy(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)), null, null, [], false)
+          .accountsPartial({ ...(await buyAccounts()), buyerBlacklist: Keypair.generate().publicKey })
+          .signers([buyer])
+          .rpc(),
+        "InvalidAccount"
+      );
+      console.log("✓ Correctly rejected a substituted blacklist account");
+    });
+
+    it("Fills a purchase partially up to the presale cap when allowed", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey) || state.capInUsd) {
+        console.log("ℹ Authority moved to governance or cap is in USD - skipping");
+        return;
+      }
+      const oneToken = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const tokensPerPaymentToken = new anchor.BN(1_000_000).mul(new anchor.BN(100_000_000)).div(state.tokenPriceUsdMicro);
+      const progress = state.totalTokensSold.add(state.referralBonusTotal);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createMintToInstruction(
+            paymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(oneToken.muln(2).toString())
+          )
+        ),
+        [admin]
+      );
+
+      // Room for one payment token's worth of presale tokens, but the order is for two
+      await presaleProgram.methods.updatePresaleCap(progress.add(tokensPerPaymentToken))
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        const buy = async (allowPartial: boolean) =>
+          presaleProgram.methods.buy(oneToken.muln(2), null, null, [], allowPartial)
+            .accountsPartial(await buyAccounts())
+            .signers([buyer]);
+        await expectError((await buy(false)).rpc(), "PresaleCapExceeded");
+
+        // Simulated to read the fill from the PurchaseRecorded event
+        const { events } = await (await buy(true)).simulate();
+        const purchase = events.find((event) => event.name === "purchaseRecorded")?.data;
+        expect(purchase, "PurchaseRecorded event").to.not.be.undefined;
+        expect(purchase.paid.toString()).to.equal(oneToken.toString());
+        expect(purchase.tokens.toString()).to.equal(tokensPerPaymentToken.toString());
+        // A repeat buyer is not counted again
+        expect(purchase.uniqueBuyers.toString()).to.equal(state.uniqueBuyers.toString());
+      } finally {
+        await presaleProgram.methods.updatePresaleCap(state.maxPresaleCap)
+          .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Partial fill charged only for the tokens left under the cap");
+    });
+
+    it("Migrates a legacy UserPurchase account before a repeat buy", async () => {
+      // Preloaded by Anchor.toml in the 40-byte layout (buyer + total_purchased)
+      const legacyBuyer = Keypair.fromSeed(
+        createHash("sha256").update("legacy-user-purchase").digest()
+      );
+      const LEGACY_TOTAL_PURCHASED = new anchor.BN(5_000_000_000);
+      const [legacyPurchasePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), legacyBuyer.publicKey.toBuffer()],
+        presaleProgram.programId
+      );
+      const legacyAccount = await connection.getAccountInfo(legacyPurchasePda);
+      if (!legacyAccount || legacyAccount.data.length !== 8 + 40) {
+        console.log("ℹ Legacy UserPurchase fixture not loaded - skipping");
+        return;
+      }
+
+      const sig = await connection.requestAirdrop(legacyBuyer.publicKey, 2 * LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig);
+      const oneToken = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const legacyPaymentTokenAccount = await getAssociatedTokenAddress(paymentMint.publicKey, legacyBuyer.publicKey);
+      const legacyPresaleTokenAccount = await getAssociatedTokenAddress(mint.publicKey, legacyBuyer.publicKey);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            admin.publicKey, legacyPaymentTokenAccount, legacyBuyer.publicKey, paymentMint.publicKey
+          ),
+          createAssociatedTokenAccountInstruction(
+            admin.publicKey, legacyPresaleTokenAccount, legacyBuyer.publicKey, mint.publicKey
+          ),
+          createMintToInstruction(
+            paymentMint.publicKey, legacyPaymentTokenAccount, admin.publicKey, BigInt(oneToken.toString())
+          )
+        ),
+        [admin]
+      );
+
+      const legacyBuyAccounts = {
+        ...(await buyAccounts()),
+        buyer: legacyBuyer.publicKey,
+        buyerPaymentTokenAccount: legacyPaymentTokenAccount,
+        buyerTokenAccount: legacyPresaleTokenAccount,
+        userPurchase: legacyPurchasePda,
+        purchaseRecord: purchaseRecordPda(legacyBuyer.publicKey, 0),
+        buyerBlacklist: PublicKey.findProgramAddressSync(
+          [Buffer.from("blacklist"), legacyBuyer.publicKey.toBuffer()],
+          tokenProgram.programId
+        )[0],
+      };
+      const buy = () =>
+        presaleProgram.methods.buy(oneToken, null, null, [], false)
+          .accountsPartial(legacyBuyAccounts)
+          .signers([legacyBuyer])
+          .rpc();
+
+      // The legacy layout no longer deserializes
+      await expectError(buy(), "AccountDidNotDeserialize");
+
+      await presaleProgram.methods.migrateUserPurchase()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          buyer: legacyBuyer.publicKey,
+          userPurchase: legacyPurchasePda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      const migrated = await presaleProgram.account.userPurchase.fetch(legacyPurchasePda);
+      expect(migrated.buyer.toString()).to.equal(legacyBuyer.publicKey.toString());
+      expect(migrated.totalPurchased.toString()).to.equal(LEGACY_TOTAL_PURCHASED.toString());
+      expect(migrated.solPaid.toNumber()).to.equal(0);
+      expect(migrated.paymentAmountPaid.toNumber()).to.equal(0);
+      expect(migrated.refunded).to.be.false;
+      expect(migrated.purchaseCount).to.equal(0);
+      expect(migrated.roundNumber.toNumber()).to.equal(0);
+      expect(migrated.owed.toNumber()).to.equal(0);
+
+      await buy();
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      const record = await presaleProgram.account.purchaseRecord.fetch(purchaseRecordPda(legacyBuyer.publicKey, 0));
+      const after = await presaleProgram.account.userPurchase.fetch(legacyPurchasePda);
+      expect(after.purchaseCount).to.equal(1);
+      expect(after.paymentAmountPaid.toString()).to.equal(oneToken.toString());
+      // Legacy totals carry over while the presale is still in its first round
+      const expectedTotal = state.roundNumber.isZero()
+        ? LEGACY_TOTAL_PURCHASED.add(record.amountTokens)
+        : record.amountTokens;
+      expect(after.totalPurchased.toString()).to.equal(expectedTotal.toString());
+      console.log("✓ Legacy UserPurchase migrated and reused for a repeat buy");
+    });
+  });
+
+  describe("Referrals", () => {
+    it("Rejects self-referral", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      await presaleProgram.methods.setReferralConfig(true, 5)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      await expectError(
+        presaleProgram.methods.buy(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)), null, buyer.publicKey, [], false)
+          .accountsPartial(await buyAccounts())
+          .signers([buyer])
+          .rpc(),
+        "SelfReferral"
+      );
+
+      await presaleProgram.methods.setReferralConfig(false, 0)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      console.log("✓ Correctly rejected self-referral");
+    });
+
+    it("Counts referral bonuses against the presale cap", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey) || state.capInUsd) {
+        console.log("ℹ Authority moved to governance or cap is in USD - skipping");
+        return;
+      }
+      const [referralPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), admin.publicKey.toBuffer()],
+        presaleProgram.programId
+      );
+      if (!(await presaleProgram.account.referralAccount.fetchNullable(referralPda))) {
+        await presaleProgram.methods.registerReferrer()
+          .accounts({ referralAccount: referralPda, referrer: admin.publicKey, systemProgram: SystemProgram.programId })
+          .signers([admin])
+          .rpc();
+      }
+      const [referrerBlacklist] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), admin.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+
+      // 1 payment token at $1, with a 20% bonus paid to both buyer and referrer
+      const amount = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const tokens = new anchor.BN(1_000_000).mul(new anchor.BN(100_000_000)).div(state.tokenPriceUsdMicro);
+      const bonus = tokens.muln(20).divn(100);
+      const progress = state.totalTokensSold.add(state.referralBonusTotal);
+
+      await presaleProgram.methods.setReferralConfig(true, 20)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      // Room for the purchase and the buyer's bonus, but not the referrer's
+      await presaleProgram.methods.updatePresaleCap(progress.add(tokens).add(bonus))
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        await expectError(
+          presaleProgram.methods.buy(amount, null, admin.publicKey, [], false)
+            .accountsPartial({ ...(await buyAccounts()), referralAccount: referralPda, referrerBlacklist })
+            .signers([buyer])
+            .rpc(),
+          "PresaleCapExceeded"
+        );
+      } finally {
+        await presaleProgram.methods.updatePresaleCap(state.maxPresaleCap)
+          .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        await presaleProgram.methods.setReferralConfig(false, 0)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Referral bonuses counted against the cap");
+    });
+
+    it("Counts the buyer's referral bonus against the per-user limit", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const [referralPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), admin.publicKey.toBuffer()],
+        presaleProgram.programId
+      );
+      if (!(await presaleProgram.account.referralAccount.fetchNullable(referralPda))) {
+        await presaleProgram.methods.registerReferrer()
+          .accounts({ referralAccount: referralPda, referrer: admin.publicKey, systemProgram: SystemProgram.programId })
+          .signers([admin])
+          .rpc();
+      }
+      const [referrerBlacklist] = PublicKey.findProgramAddressSync(
+        [Buffer.from("blacklist"), admin.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+
+      // 1 payment token at $1, with a 20% bonus for the buyer
+      const amount = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const tokens = new anchor.BN(1_000_000).mul(new anchor.BN(100_000_000)).div(state.tokenPriceUsdMicro);
+      const bonus = tokens.muln(20).divn(100);
+      const purchase = await presaleProgram.account.userPurchase.fetch(userPurchasePda);
+
+      await presaleProgram.methods.setReferralConfig(true, 20)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      // Room for the purchased tokens, but not the bonus on top
+      await presaleProgram.methods.updateMaxPerUser(purchase.totalPurchased.add(tokens).add(bonus).subn(1))
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        await expectError(
+          presaleProgram.methods.buy(amount, null, admin.publicKey, [], false)
+            .accountsPartial({ ...(await buyAccounts()), referralAccount: referralPda, referrerBlacklist })
+            .signers([buyer])
+            .rpc(),
+          "PerUserLimitExceeded"
+        );
+      } finally {
+        await presaleProgram.methods.updateMaxPerUser(state.maxPerUser)
+          .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        await presaleProgram.methods.setReferralConfig(false, 0)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Referral bonus counted against the per-user limit");
+    });
+  });
+
+  describe("Rounds", () => {
+    it("Advances to the next round when a purchase sells out the active one", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey) || state.activeRoundId !== null) {
+        console.log("ℹ Authority moved to governance or a round is already active - skipping");
+        return;
+      }
+      const oneToken = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const tokensPerPaymentToken = new anchor.BN(1_000_000).mul(new anchor.BN(100_000_000)).div(state.tokenPriceUsdMicro);
+      const roundPda = (roundId: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("presale_round"), presaleStatePda.toBuffer(), Buffer.from([roundId])],
+          presaleProgram.programId
+        )[0];
+
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createMintToInstruction(
+            paymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(oneToken.muln(2).toString())
+          )
+        ),
+        [admin]
+      );
+
+      // Round 1 holds one payment token's worth of tokens plus a single base unit,
+      // which is below the minimum purchase and so leaves the round sold out
+      for (const [roundId, price, cap] of [
+        [1, state.tokenPriceUsdMicro, tokensPerPaymentToken.addn(1)],
+        [2, state.tokenPriceUsdMicro.muln(2), tokensPerPaymentToken],
+      ] as const) {
+        await presaleProgram.methods.createRound(roundId, price, cap, null, null)
+          .accountsPartial({ presaleState: presaleStatePda, presaleRound: roundPda(roundId), authority: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      await presaleProgram.methods.setActiveRound(1)
+        .accountsPartial({ presaleState: presaleStatePda, presaleRound: roundPda(1), activeRound: null, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      await presaleProgram.methods.setMinPurchaseAmount(new anchor.BN(2))
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        // Purchases must target the active round
+        await expectError(
+          presaleProgram.methods.buy(oneToken, null, null, [], false)
+            .accountsPartial(await buyAccounts())
+            .signers([buyer])
+            .rpc(),
+          "InvalidRound"
+        );
+
+        // Selling out the round requires the next round's account
+        await expectError(
+          presaleProgram.methods.buy(oneToken, 1, null, [], false)
+            .accountsPartial({ ...(await buyAccounts()), presaleRound: roundPda(1) })
+            .signers([buyer])
+            .rpc(),
+          "NextRoundRequired"
+        );
+
+        // Signed allocations price against the active round as well
+        const allocationSigner = Keypair.generate();
+        const maxAllocation = (await presaleProgram.account.userPurchase.fetch(userPurchasePda))
+          .totalPurchased.add(tokensPerPaymentToken);
+        const allocationIx = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: allocationSigner.secretKey,
+          message: Buffer.concat([buyer.publicKey.toBuffer(), maxAllocation.toArrayLike(Buffer, "le", 8)]),
+        });
+        await presaleProgram.methods.setAllocationAuthority(allocationSigner.publicKey)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        try {
+          const { events } = await presaleProgram.methods
+            .buyWithAllocation(oneToken, maxAllocation, Array.from(allocationIx.data.subarray(48, 112)))
+            .accountsPartial({
+              ...(await buyAccounts()),
+              presaleRound: roundPda(1),
+              nextPresaleRound: roundPda(2),
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            })
+            .preInstructions([allocationIx])
+            .signers([buyer])
+            .simulate();
+          expect(events.find((event) => event.name === "roundAdvanced"), "RoundAdvanced event").to.not.be.undefined;
+        } finally {
+          await presaleProgram.methods.setAllocationAuthority(PublicKey.default)
+            .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        }
+
+        await presaleProgram.methods.buy(oneToken, 1, null, [], false)
+          .accountsPartial({ ...(await buyAccounts()), presaleRound: roundPda(1), nextPresaleRound: roundPda(2) })
+          .signers([buyer])
+          .rpc();
+
+        expect((await presaleProgram.account.presaleState.fetch(presaleStatePda)).activeRoundId).to.equal(2);
+        const soldOutRound = await presaleProgram.account.presaleRound.fetch(roundPda(1));
+        expect(soldOutRound.tokensSold.toString()).to.equal(tokensPerPaymentToken.toString());
+        expect(soldOutRound.isActive).to.be.false;
+        expect((await presaleProgram.account.presaleRound.fetch(roundPda(2))).isActive).to.be.true;
+      } finally {
+        await presaleProgram.methods.setMinPurchaseAmount(state.minPurchaseAmount)
+          .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        const activeRoundId = (await presaleProgram.account.presaleState.fetch(presaleStatePda)).activeRoundId;
+        if (activeRoundId !== null) {
+          await presaleProgram.methods.closeRound(activeRoundId)
+            .accountsPartial({ presaleState: presaleStatePda, presaleRound: roundPda(activeRoundId), authority: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        }
+      }
+      expect((await presaleProgram.account.presaleState.fetch(presaleStatePda)).activeRoundId).to.be.null;
+      console.log("✓ Selling out round 1 advanced the presale to round 2");
+    });
+  });
+
+  describe("Oracle", () => {
+    it("Configures the SOL pricing oracle", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+
+      // Pyth feeds have no fixed owner program, so a feed must be pinned
+      await expectError(
+        presaleProgram.methods.setOracleType({ pyth: {} }, PublicKey.default)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc(),
+        "InvalidPrice"
+      );
+
+      const pythFeed = Keypair.generate().publicKey;
+      await presaleProgram.methods.setOracleType({ pyth: {} }, pythFeed)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      let updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(Object.keys(updated.oracleType)[0]).to.equal("pyth");
+      expect(updated.oracleFeed.equals(pythFeed)).to.be.true;
+
+      // Restore the original Chainlink feed for other tests
+      await presaleProgram.methods.setOracleType({ chainlink: {} }, state.oracleFeed)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(Object.keys(updated.oracleType)[0]).to.equal("chainlink");
+      console.log("✓ Oracle type and feed configured");
+    });
+
+    it("Prices SOL purchases from a Pyth v2 price account", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey) || state.activeRoundId !== null) {
+        console.log("ℹ Authority moved to governance or a round is active - skipping");
+        return;
+      }
+      if (!(await connection.getAccountInfo(PYTH_SOL_USD_FEED))) {
+        console.log("ℹ Pyth fixtures not loaded - skipping");
+        return;
+      }
+      const solBuyer = await newSolBuyer();
+      const solAmount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+      const useFeed = (feed: PublicKey) =>
+        presaleProgram.methods.setOracleType({ pyth: {} }, feed)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      const buyWith = async (feed: PublicKey) =>
+        presaleProgram.methods.buyWithSol(solAmount, null, null, new anchor.BN(0), [], false)
+          .accountsPartial(await solBuyAccounts(solBuyer.publicKey, feed))
+          .signers([solBuyer]);
+      const tokensFrom = async (feed: PublicKey) => {
+        await useFeed(feed);
+        const { events } = await (await buyWith(feed)).simulate();
+        const purchase = events.find((event) => event.name === "purchaseRecorded")?.data;
+        expect(purchase, "PurchaseRecorded event").to.not.be.undefined;
+        return purchase.tokens as anchor.BN;
+      };
+
+      try {
+        const tokens = await tokensFrom(PYTH_SOL_USD_FEED);
+        // $150 at expo -8 rescales to the same 8-decimal price as $150 at expo -6
+        expect((await tokensFrom(PYTH_SOL_USD_EXPO_8_FEED)).toString()).to.equal(tokens.toString());
+        if (state.priceTiers.length === 0) {
+          const expected = solAmount.mul(PYTH_SOL_PRICE_8_DECIMALS).muln(1_000_000)
+            .div(state.tokenPriceUsdMicro.mul(new anchor.BN(LAMPORTS_PER_SOL)));
+          expect(tokens.toString()).to.equal(expected.toString());
+        }
+
+        // A confidence interval wider than 2% of the price is rejected
+        await useFeed(PYTH_WIDE_CONFIDENCE_FEED);
+        await expectError((await buyWith(PYTH_WIDE_CONFIDENCE_FEED)).rpc(), "InvalidPrice");
+
+        // So is an aggregate price that is not trading
+        await useFeed(PYTH_HALTED_FEED);
+        await expectError((await buyWith(PYTH_HALTED_FEED)).rpc(), "InvalidPrice");
+
+        await useFeed(PYTH_SOL_USD_FEED);
+        await (await buyWith(PYTH_SOL_USD_FEED)).rpc();
+        const record = await presaleProgram.account.purchaseRecord.fetch(purchaseRecordPda(solBuyer.publicKey, 0));
+        expect(record.paymentIsSol).to.be.true;
+        expect(record.amountPaid.toString()).to.equal(solAmount.toString());
+        expect(record.amountTokens.toString()).to.equal(tokens.toString());
+      } finally {
+        await presaleProgram.methods.setOracleType(state.oracleType, state.oracleFeed)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Pyth price, exponent, confidence and status applied to SOL purchases");
+    });
+
+    it("Configures a backup Chainlink feed", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const primary = Keypair.generate().publicKey;
+      const backup = Keypair.generate().publicKey;
+      await presaleProgram.methods.setOracleType({ chainlink: {} }, primary)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        // The backup must be a different feed from the primary
+        await expectError(
+          presaleProgram.methods.setOracleBackupFeed(primary)
+            .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc(),
+          "InvalidPrice"
+        );
+
+        await presaleProgram.methods.setOracleBackupFeed(backup)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        const updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+        expect(updated.oracleFeedBackup.equals(backup)).to.be.true;
+      } finally {
+        await presaleProgram.methods.setOracleBackupFeed(state.oracleFeedBackup)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        await presaleProgram.methods.setOracleType({ chainlink: {} }, state.oracleFeed)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Backup oracle feed configured");
+    });
+
+    it("Bounds the configurable price staleness threshold", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const setThreshold = (seconds: number) =>
+        presaleProgram.methods.setPriceStalenessThreshold(new anchor.BN(seconds))
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+
+      await expectError(setThreshold(59), "InvalidStalenessThreshold");
+      await expectError(setThreshold(86_401), "InvalidStalenessThreshold");
+
+      await setThreshold(300);
+      try {
+        const updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+        expect(updated.priceStalenessThreshold.toNumber()).to.equal(300);
+      } finally {
+        await setThreshold(3600);
+      }
+      console.log("✓ Price staleness threshold bounded to 60..=86400 seconds");
+    });
+  });
+
+  describe("Payment Tokens", () => {
+    it("Caps the number of allowed payment tokens", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      expect(state.allowedTokenCount).to.be.greaterThan(0);
+      await presaleProgram.methods.setMaxAllowedTokens(state.allowedTokenCount)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      const extraMint = Keypair.generate().publicKey;
+      const [extraAllowedTokenPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("allowed_token"), presaleStatePda.toBuffer(), extraMint.toBuffer()],
+        presaleProgram.programId
+      );
+      await expectError(
+        presaleProgram.methods.allowPaymentToken(extraMint)
+          .accountsPartial({
+            presaleState: presaleStatePda,
+            allowedToken: extraAllowedTokenPda,
+            admin: admin.publicKey,
+            paymentTokenMintAccount: extraMint,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc(),
+        "TooManyPaymentTokens"
+      );
+
+      // Restore the default limit
+      await presaleProgram.methods.setMaxAllowedTokens(0)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      console.log("✓ Correctly rejected allowing a payment token past the limit");
+    });
+
+    it("Rejects allowing a payment token that is not a mint", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      // A fresh keypair address is an empty system account, not an SPL mint
+      const notAMint = Keypair.generate().publicKey;
+      const [notAMintAllowedTokenPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("allowed_token"), presaleStatePda.toBuffer(), notAMint.toBuffer()],
+        presaleProgram.programId
+      );
+      await expectError(
+        presaleProgram.methods.allowPaymentToken(notAMint)
+          .accountsPartial({
+            presaleState: presaleStatePda,
+            allowedToken: notAMintAllowedTokenPda,
+            admin: admin.publicKey,
+            paymentTokenMintAccount: notAMint,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc(),
+        "InvalidPaymentMint"
+      );
+      console.log("✓ Correctly rejected a non-mint payment token");
+    });
+  });
+
+  describe("Pricing", () => {
+    it("Sets price tiers and rejects unordered tiers", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      if (state.status.stopped || state.status.failed) {
+        console.log("ℹ Presale already closed - skipping");
+        return;
+      }
+      const tier = (sold: number, price: number) => ({
+        soldThreshold: new anchor.BN(sold),
+        priceUsdMicro: new anchor.BN(price),
+      });
+
+      await expectError(
+        presaleProgram.methods.setPriceTiers([tier(2_000, 2_000), tier(1_000, 3_000)])
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc(),
+        "InvalidPriceTiers"
+      );
+
+      const threshold = state.totalTokensSold.add(new anchor.BN(1_000));
+      await presaleProgram.methods.setPriceTiers([{ soldThreshold: threshold, priceUsdMicro: new anchor.BN(2_000) }])
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      let updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.priceTiers).to.have.length(1);
+      expect(updated.priceTiers[0].soldThreshold.toString()).to.equal(threshold.toString());
+
+      // Clear the tiers so later purchases keep the flat price
+      await presaleProgram.methods.setPriceTiers([])
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.priceTiers).to.have.length(0);
+      console.log("✓ Price tiers set, validated and cleared");
+    });
+
+    it("Updates the token price directly as authority", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const originalPrice = state.tokenPriceUsdMicro;
+      await presaleProgram.methods.setTokenPriceUsd(originalPrice.addn(1))
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+      const updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.tokenPriceUsdMicro.toString()).to.equal(originalPrice.addn(1).toString());
+
+      await presaleProgram.methods.setTokenPriceUsd(originalPrice)
+        .accounts({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      console.log("✓ Authority can still set the price without governance");
+    });
+
+    it("Rejects changing the token program after the presale started", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      await expectError(
+        presaleProgram.methods.setTokenProgramId(TOKEN_2022_PROGRAM_ID)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc(),
+        "InvalidStatus"
+      );
+      console.log("✓ Correctly rejected token program change on an active presale");
+    });
+  });
+
+  describe("Whitelist", () => {
+    it("Verifies Merkle whitelist proofs during the whitelist phase", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      // Leaves are sha256(buyer); parents hash their children in ascending byte order
+      const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+      const parent = (a: Buffer, b: Buffer) => (Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a));
+      const leaves = [buyer.publicKey, ...[0, 1, 2].map(() => Keypair.generate().publicKey)]
+        .map((key) => sha256(key.toBuffer()));
+      const left = parent(leaves[0], leaves[1]);
+      const right = parent(leaves[2], leaves[3]);
+      const root = parent(left, right);
+      const buyerProof = [leaves[1], right];
+      const toArg = (proof: Buffer[]) => proof.map((node) => Array.from(node));
+
+      await presaleProgram.methods.setMerkleWhitelistRoot(Array.from(root))
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      await presaleProgram.methods.toggleWhitelistPhase(true)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      const updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(Buffer.from(updated.merkleWhitelistRoot!).equals(root)).to.be.true;
+
+      try {
+        // Proof for another leaf, and an empty proof, must not verify
+        for (const proof of [[leaves[0], right], []]) {
+          await expectError(
+            presaleProgram.methods.buy(new anchor.BN(1), null, null, toArg(proof), false)
+              .accountsPartial(await buyAccounts())
+              .signers([buyer])
+              .rpc(),
+            "InvalidMerkleProof"
+          );
+        }
+
+        // A valid proof passes the whitelist; the dust amount then fails on price conversion
+        await expectError(
+          presaleProgram.methods.buy(new anchor.BN(1), null, null, toArg(buyerProof), false)
+            .accountsPartial(await buyAccounts())
+            .signers([buyer])
+            .rpc(),
+          "InvalidAmount"
+        );
+      } finally {
+        await presaleProgram.methods.toggleWhitelistPhase(false)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        await presaleProgram.methods.setMerkleWhitelistRoot(new Array(32).fill(0))
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      const cleared = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(cleared.merkleWhitelistRoot).to.be.null;
+      console.log("✓ Merkle proofs verified against the whitelist root");
+    });
+
+    it("Sets whitelist entries in a batch", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const buyers = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const entries = buyers.map((key) => PublicKey.findProgramAddressSync(
+        [Buffer.from("allowed_buyer"), presaleStatePda.toBuffer(), key.toBuffer()],
+        presaleProgram.programId
+      )[0]);
+      const remaining = entries.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+
+      await expectError(
+        presaleProgram.methods.setBuyerWhitelistBatch(buyers, true)
+          .accountsPartial({ presaleState: presaleStatePda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+          .remainingAccounts(remaining.slice(0, 1))
+          .signers([admin])
+          .rpc(),
+        "InvalidBatchSize"
+      );
+
+      // First call creates the entries, the second updates them in place
+      for (const value of [true, false]) {
+        await presaleProgram.methods.setBuyerWhitelistBatch(buyers, value)
+          .accountsPartial({ presaleState: presaleStatePda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+          .remainingAccounts(remaining)
+          .signers([admin])
+          .rpc();
+        for (const [i, entry] of entries.entries()) {
+          const allowedBuyer = await presaleProgram.account.allowedBuyer.fetch(entry);
+          expect(allowedBuyer.account.equals(buyers[i])).to.be.true;
+          expect(allowedBuyer.isAllowed).to.equal(value);
+        }
+      }
+      console.log("✓ Whitelist entries created and updated in a batch");
+    });
+  });
+
+  describe("Allocations", () => {
+    it("Enforces signed allocations on buy_with_allocation", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Authority moved to governance - skipping");
+        return;
+      }
+      const allocationSigner = Keypair.generate();
+      const accounts = async () => ({
+        ...(await buyAccounts()),
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      });
+      // Message layout is buyer || max_allocation (u64 little-endian)
+      const allocationIx = (signer: Keypair, buyerKey: PublicKey, maxAllocation: anchor.BN) =>
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message: Buffer.concat([buyerKey.toBuffer(), maxAllocation.toArrayLike(Buffer, "le", 8)]),
+        });
+      // The signature sits after the 16-byte header and the 32-byte public key
+      const signatureOf = (ix: anchor.web3.TransactionInstruction) => Array.from(ix.data.subarray(48, 112));
+
+      const zero = new anchor.BN(0);
+      await expectError(
+        presaleProgram.methods.buyWithAllocation(PAYMENT_AMOUNT, zero, new Array(64).fill(0))
+          .accountsPartial(await accounts())
+          .signers([buyer])
+          .rpc(),
+        "AllocationAuthorityNotSet"
+      );
+
+      await presaleProgram.methods.setAllocationAuthority(allocationSigner.publicKey)
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      try {
+        // No Ed25519 instruction before the buy
+        const validIx = allocationIx(allocationSigner, buyer.publicKey, zero);
+        await expectError(
+          presaleProgram.methods.buyWithAllocation(PAYMENT_AMOUNT, zero, signatureOf(validIx))
+            .accountsPartial(await accounts())
+            .signers([buyer])
+            .rpc(),
+          "InvalidAllocationSignature"
+        );
+
+        // Signed by someone other than the allocation authority
+        const forgedIx = allocationIx(Keypair.generate(), buyer.publicKey, zero);
+        await expectError(
+          presaleProgram.methods.buyWithAllocation(PAYMENT_AMOUNT, zero, signatureOf(forgedIx))
+            .accountsPartial(await accounts())
+            .preInstructions([forgedIx])
+            .signers([buyer])
+            .rpc(),
+          "InvalidAllocationSignature"
+        );
+
+        // A valid allocation of zero tokens verifies, then caps the purchase
+        await expectError(
+          presaleProgram.methods.buyWithAllocation(PAYMENT_AMOUNT, zero, signatureOf(validIx))
+            .accountsPartial(await accounts())
+            .preInstructions([validIx])
+            .signers([buyer])
+            .rpc(),
+          "AllocationExceeded"
+        );
+      } finally {
+        await presaleProgram.methods.setAllocationAuthority(PublicKey.default)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      }
+      console.log("✓ Allocation signatures verified and enforced");
+    });
+  });
+
+  describe("Purchase Codes", () => {
+    it("Redeems purchase codes by preimage with discount and bonus", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.authority.equals(admin.publicKey) || state.activeRoundId !== null) {
+        console.log("ℹ Authority moved to governance or a round is active - skipping");
+        return;
+      }
+      const oneToken = new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS));
+      const code = Buffer.from(`LAUNCH-${Date.now()}`);
+      const codeHash = createHash("sha256").update(code).digest();
+      const [purchaseCodePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("purchase_code"), presaleStatePda.toBuffer(), codeHash],
+        presaleProgram.programId
+      );
+      const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const createCode = (maxUses: number) =>
+        presaleProgram.methods.createPurchaseCode([...codeHash], 2_000, 1_000, maxUses, expiresAt)
+          .accountsPartial({ presaleState: presaleStatePda, purchaseCode: purchaseCodePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createMintToInstruction(
+            paymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(oneToken.toString())
+          )
+        ),
+        [admin]
+      );
+
+      await expectError(createCode(0), "InvalidPurchaseCode");
+      await createCode(5);
+
+      const accounts = async () => ({ ...(await buyAccounts()), purchaseCode: purchaseCodePda });
+      await expectError(
+        presaleProgram.methods.buyWithCode(oneToken, Buffer.from("WRONG-CODE"))
+          .accountsPartial(await accounts())
+          .signers([buyer])
+          .rpc(),
+        "InvalidPurchaseCode"
+      );
+
+      // Both purchases are simulated so they are priced from the same state
+      const base = await presaleProgram.methods.buy(oneToken, null, null, [], false)
+        .accountsPartial(await buyAccounts())
+        .signers([buyer])
+        .simulate();
+      const baseTokens = base.events.find((event) => event.name === "purchaseRecorded")?.data.tokens as anchor.BN;
+      const { events } = await presaleProgram.methods.buyWithCode(oneToken, code)
+        .accountsPartial(await accounts())
+        .signers([buyer])
+        .simulate();
+      const codeTokens = events.find((event) => event.name === "purchaseRecorded")?.data.tokens as anchor.BN;
+      // 20% off the price, then a 10% bonus
+      expect(codeTokens.toString()).to.equal(baseTokens.muln(10_000).divn(8_000).muln(11_000).divn(10_000).toString());
+      const redeemed = events.find((event) => event.name === "purchaseCodeRedeemed")?.data;
+      expect(redeemed, "PurchaseCodeRedeemed event").to.not.be.undefined;
+      expect(redeemed.uses).to.equal(1);
+      console.log("✓ Purchase code verified by hash and applied");
+    });
+  });
+
+  describe("Treasury", () => {
+    it("Allows draining the SOL vault to exactly the rent-exempt floor", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (state.treasuryAddress.equals(PublicKey.default) || !state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Treasury not set or authority moved to governance - skipping");
+        return;
+      }
+      const [solVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+        presaleProgram.programId
+      );
+      const rentFloor = await connection.getMinimumBalanceForRentExemption(0);
+
+      // Make sure the vault is rent-exempt and holds something to withdraw
+      await presaleProgram.methods.fundSolVaultRent()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          solVault: solVault,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: solVault, lamports: LAMPORTS_PER_SOL / 100 })
+        ),
+        [admin]
+      );
+
+      const withdrawAccounts = {
+        presaleState: presaleStatePda,
+        authority: admin.publicKey,
+        solVault: solVault,
+        treasury: state.treasuryAddress,
+        systemProgram: SystemProgram.programId,
+      };
+
+      const vaultBalance = await connection.getBalance(solVault);
+      await presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(vaultBalance - rentFloor))
+        .accountsPartial(withdrawAccounts)
+        .signers([admin])
+        .rpc();
+      expect(await connection.getBalance(solVault)).to.equal(rentFloor);
+
+      await expectError(
+        presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(1))
+          .accountsPartial(withdrawAccounts)
+          .signers([admin])
+          .rpc(),
+        "WouldBreakRentExemption"
+      );
+      console.log("✓ SOL vault drained to exactly the rent floor and no further");
+    });
+
+    it("Splits treasury withdrawals with the fee collector", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      // Balances are compared exactly, so the treasury must not also pay the fees
+      if (state.treasuryAddress.equals(PublicKey.default) || state.treasuryAddress.equals(admin.publicKey)
+          || !state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Treasury not set, is the admin, or authority moved to governance - skipping");
+        return;
+      }
+      const [solVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+        presaleProgram.programId
+      );
+      const feeCollector = Keypair.generate().publicKey;
+      const setFee = (bps: number) =>
+        presaleProgram.methods.setWithdrawalFee(bps, feeCollector)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+
+      await expectError(setFee(1001), "WithdrawalFeeTooHigh");
+      await setFee(500);
+
+      const withdrawAccounts = {
+        presaleState: presaleStatePda,
+        authority: admin.publicKey,
+        solVault: solVault,
+        treasury: state.treasuryAddress,
+        systemProgram: SystemProgram.programId,
+      };
+      const amount = LAMPORTS_PER_SOL / 10;
+      const fundVault = () => sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: solVault, lamports: amount })
+        ),
+        [admin]
+      );
+
+      // A fee is set, so the fee collector account is required
+      await fundVault();
+      await expectError(
+        presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(amount))
+          .accountsPartial({ ...withdrawAccounts, feeCollector: null })
+          .signers([admin])
+          .rpc(),
+        "InvalidFeeCollector"
+      );
+
+      const treasuryBefore = await connection.getBalance(state.treasuryAddress);
+      await presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(amount))
+        .accountsPartial({ ...withdrawAccounts, feeCollector })
+        .signers([admin])
+        .rpc();
+      expect(await connection.getBalance(feeCollector)).to.equal(amount * 500 / 10_000);
+      expect(await connection.getBalance(state.treasuryAddress) - treasuryBefore).to.equal(amount * 9_500 / 10_000);
+
+      // Zero fee sends everything to the treasury
+      await setFee(0);
+      await fundVault();
+      const treasuryBeforeNoFee = await connection.getBalance(state.treasuryAddress);
+      await presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(amount))
+        .accountsPartial({ ...withdrawAccounts, feeCollector: null })
+        .signers([admin])
+        .rpc();
+      expect(await connection.getBalance(state.treasuryAddress) - treasuryBeforeNoFee).to.equal(amount);
+      expect(await connection.getBalance(feeCollector)).to.equal(amount * 500 / 10_000);
+      console.log("✓ Withdrawal fee split, zero fee and fee cap all enforced");
+    });
+
+    it("Rejects treasury swaps without a valid swap venue", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (state.treasuryAddress.equals(PublicKey.default) || !state.authority.equals(admin.publicKey)) {
+        console.log("ℹ Treasury not set or authority moved to governance - skipping");
+        return;
+      }
+      // The presale's own program can never be the swap venue
+      await expectError(
+        presaleProgram.methods.setSwapProgram(presaleProgram.programId)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc(),
+        "InvalidSwapProgram"
+      );
+      expect(state.swapProgram.equals(PublicKey.default)).to.be.true;
+
+      await expectError(
+        presaleProgram.methods.withdrawAndSwap(new anchor.BN(1), new anchor.BN(1), Buffer.from([]))
+          .accountsPartial({
+            presaleState: presaleStatePda,
+            authority: admin.publicKey,
+            presalePaymentVaultPda,
+            presalePaymentVault,
+            treasuryTokenAccount: buyerPresaleTokenAccount,
+            paymentTokenMint: paymentMint.publicKey,
+            swapProgram: tokenProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            feeCollectorTokenAccount: null,
+          })
+          .signers([admin])
+          .rpc(),
+        "SwapProgramNotSet"
+      );
+      console.log("✓ Treasury swaps rejected without a configured venue");
+    });
+  });
+
+  describe("Admin", () => {
+    it("Rotates the admin in two steps", async () => {
+      const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!state.admin.equals(admin.publicKey)) {
+        console.log("ℹ Admin has already been rotated - skipping");
+        return;
+      }
+      const newAdmin = Keypair.generate();
+      const propose = (signer: Keypair, candidate: PublicKey) =>
+        presaleProgram.methods.proposeAdminChange(candidate)
+          .accounts({ presaleState: presaleStatePda, authority: signer.publicKey })
+          .signers([signer])
+          .rpc();
+      const accept = (signer: Keypair) =>
+        presaleProgram.methods.acceptAdminChange()
+          .accounts({ presaleState: presaleStatePda, signer: signer.publicKey })
+          .signers([signer])
+          .rpc();
+
+      await expectError(accept(newAdmin), "NoPendingAdmin");
+      await expectError(propose(newAdmin, newAdmin.publicKey), "Unauthorized");
+      await expectError(propose(admin, PublicKey.default), "InvalidAccount");
+
+      // Proposing alone does not change the admin
+      await propose(admin, newAdmin.publicKey);
+      let updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.admin.equals(admin.publicKey)).to.be.true;
+      expect(updated.pendingAdmin?.equals(newAdmin.publicKey)).to.be.true;
+      await expectError(accept(admin), "Unauthorized");
+
+      await accept(newAdmin);
+      updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.admin.equals(newAdmin.publicKey)).to.be.true;
+      expect(updated.pendingAdmin).to.be.null;
+      if (!state.governanceSet) {
+        expect(updated.authority.equals(newAdmin.publicKey)).to.be.true;
+      }
+
+      // Rotate back so the remaining suites keep their admin
+      await propose(newAdmin, admin.publicKey);
+      await accept(admin);
+      updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      expect(updated.admin.equals(admin.publicKey)).to.be.true;
+      expect(updated.authority.equals(state.authority)).to.be.true;
+      console.log("✓ Admin rotated only after the proposed admin accepted");
+    });
+  });
+});
@@ -0,0 +1,626 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddress,
+  getMinimumBalanceForRentExemptMint,
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { Presale } from "../target/types/presale";
+import { SplProject } from "../target/types/spl_project";
+import { loadTestKeys } from "./key-loader";
+
+/**
+ * PRESALE REFUND TESTS
+ *
+ * Exercises the end-time and failed-presale refund path:
+ * initialize -> buy -> end time passes -> finalize -> fail presale -> claim refund
+ *
+ * NOTE: Marking the presale as Failed is terminal, so this file must run
+ * after all other presale test files.
+ */
+
+describe("Presale Refunds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const presaleProgram = anchor.workspace.Presale as Program<Presale>;
+  const tokenProgram = anchor.workspace.SplProject as Program<SplProject>;
+  const connection = provider.connection;
+
+  const keys = loadTestKeys();
+  const admin = keys.authority;
+  const mint = keys.mint;
+
+  // Fresh buyer and payment mint so earlier test files don't affect purchase records
+  const buyer = Keypair.generate();
+  const refundPaymentMint = Keypair.generate();
+
+  let tokenStatePda: PublicKey;
+  let presaleStatePda: PublicKey;
+  let presaleTokenVaultPda: PublicKey;
+  let presaleTokenVault: PublicKey;
+  let presalePaymentVaultPda: PublicKey;
+  let presalePaymentVault: PublicKey;
+  let allowedTokenPda: PublicKey;
+  let userPurchasePda: PublicKey;
+  let buyerBlacklistPda: PublicKey;
+  let buyerPaymentTokenAccount: PublicKey;
+  let buyerPresaleTokenAccount: PublicKey;
+
+  const MINT_DECIMALS = 9;
+  const PAYMENT_AMOUNT = new anchor.BN(10).mul(
+    new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS))
+  );
+
+  // Helper to expect Anchor errors
+  async function expectError(promise: Promise<any>, errorMsg: string) {
+    try {
+      await promise;
+      expect.fail(`Expected error containing "${errorMsg}" but transaction succeeded`);
+    } catch (err: any) {
+      const errStr = err.toString().toLowerCase();
+      expect(errStr).to.include(errorMsg.toLowerCase());
+    }
+  }
+
+  // Wait until the validator clock is past the given unix timestamp
+  async function waitForClockPast(ts: number) {
+    while (true) {
+      const blockTime = await connection.getBlockTime(await connection.getSlot());
+      if (blockTime !== null && blockTime > ts) return;
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  }
+
+  // Derive the PurchaseRecord PDA for a buyer's next purchase
+  async function nextPurchaseRecordPda(buyerKey: PublicKey): Promise<PublicKey> {
+    const [userPurchase] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), buyerKey.toBuffer()],
+      presaleProgram.programId
+    );
+    const purchase = await presaleProgram.account.userPurchase.fetchNullable(userPurchase);
+    return purchaseRecordPda(buyerKey, purchase ? purchase.purchaseCount : 0);
+  }
+
+  function purchaseRecordPda(buyerKey: PublicKey, index: number): PublicKey {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("purchase_record"), presaleStatePda.toBuffer(), buyerKey.toBuffer(), indexBytes],
+      presaleProgram.programId
+    )[0];
+  }
+
+  // Derive the RaisedTracker PDA for a payment mint (PublicKey.default for SOL)
+  function raisedTrackerPda(paymentMint: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("raised_tracker"), presaleStatePda.toBuffer(), paymentMint.toBuffer()],
+      presaleProgram.programId
+    )[0];
+  }
+
+  async function buyAccounts() {
+    return {
+      presaleState: presaleStatePda,
+      tokenState: tokenStatePda,
+      allowedToken: allowedTokenPda,
+      buyer: buyer.publicKey,
+      buyerPaymentTokenAccount: buyerPaymentTokenAccount,
+      presalePaymentVaultPda: presalePaymentVaultPda,
+      presalePaymentVault: presalePaymentVault,
+      presaleTokenVaultPda: presaleTokenVaultPda,
+      presaleTokenVault: presaleTokenVault,
+      buyerTokenAccount: buyerPresaleTokenAccount,
+      paymentTokenMint: refundPaymentMint.publicKey,
+      presaleTokenMint: mint.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      userPurchase: userPurchasePda,
+      purchaseRecord: await nextPurchaseRecordPda(buyer.publicKey),
+      raisedTracker: raisedTrackerPda(refundPaymentMint.publicKey),
+      buyerBlacklist: buyerBlacklistPda,
+      presaleRound: null,
+      nextPresaleRound: null,
+      buyerWhitelist: null,
+      referralAccount: null,
+      referrerBlacklist: null,
+      vestingAccount: null,
+      instructionsSysvar: null,
+      purchaseCode: null,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  function refundAccounts() {
+    return {
+      presaleState: presaleStatePda,
+      buyer: buyer.publicKey,
+      userPurchase: userPurchasePda,
+      presalePaymentVaultPda: presalePaymentVaultPda,
+      presalePaymentVault: presalePaymentVault,
+      buyerPaymentTokenAccount: buyerPaymentTokenAccount,
+      paymentTokenMint: refundPaymentMint.publicKey,
+      presaleTokenVaultPda: presaleTokenVaultPda,
+      presaleTokenVault: presaleTokenVault,
+      buyerTokenAccount: buyerPresaleTokenAccount,
+      vestingAccount: null,
+      presaleTokenMint: mint.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  before(async () => {
+    for (const account of [admin, buyer]) {
+      const balance = await connection.getBalance(account.publicKey);
+      if (balance < 2 * LAMPORTS_PER_SOL) {
+        const sig = await connection.requestAirdrop(account.publicKey, 5 * LAMPORTS_PER_SOL);
+        await connection.confirmTransaction(sig);
+      }
+    }
+
+    [tokenStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state")], tokenProgram.programId
+    );
+    [presaleStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_state")], presaleProgram.programId
+    );
+    [presaleTokenVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_token_vault_pda"), mint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [presalePaymentVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_payment_vault_pda"), presaleStatePda.toBuffer(), refundPaymentMint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [allowedTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("allowed_token"), presaleStatePda.toBuffer(), refundPaymentMint.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [userPurchasePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), buyer.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    [buyerBlacklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), buyer.publicKey.toBuffer()],
+      tokenProgram.programId
+    );
+
+    presaleTokenVault = await getAssociatedTokenAddress(mint.publicKey, presaleTokenVaultPda, true);
+    presalePaymentVault = await getAssociatedTokenAddress(refundPaymentMint.publicKey, presalePaymentVaultPda, true);
+    buyerPaymentTokenAccount = await getAssociatedTokenAddress(refundPaymentMint.publicKey, buyer.publicKey);
+    buyerPresaleTokenAccount = await getAssociatedTokenAddress(mint.publicKey, buyer.publicKey);
+
+    // Create payment mint, buyer ATAs and payment vault ATA
+    const mintRent = await getMinimumBalanceForRentExemptMint(connection);
+    const setupTx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: refundPaymentMint.publicKey,
+        space: MINT_SIZE,
+        lamports: mintRent,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMintInstruction(
+        refundPaymentMint.publicKey, MINT_DECIMALS, admin.publicKey, null
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, buyerPaymentTokenAccount, buyer.publicKey, refundPaymentMint.publicKey
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, buyerPresaleTokenAccount, buyer.publicKey, mint.publicKey
+      ),
+      createAssociatedTokenAccountInstruction(
+        admin.publicKey, presalePaymentVault, presalePaymentVaultPda, refundPaymentMint.publicKey
+      ),
+      createMintToInstruction(
+        refundPaymentMint.publicKey, buyerPaymentTokenAccount, admin.publicKey, BigInt(PAYMENT_AMOUNT.toString())
+      )
+    );
+    await sendAndConfirmTransaction(connection, setupTx, [admin, refundPaymentMint]);
+
+    // Allow the payment token and make sure presale is active
+    await presaleProgram.methods.allowPaymentToken(refundPaymentMint.publicKey)
+      .accountsPartial({
+        presaleState: presaleStatePda,
+        allowedToken: allowedTokenPda,
+        admin: admin.publicKey,
+        paymentTokenMintAccount: refundPaymentMint.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (Object.keys(state.status)[0] !== "active") {
+      await presaleProgram.methods.startPresale()
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    }
+  });
+
+  it("Records payment details on purchase", async () => {
+    await presaleProgram.methods.buy(PAYMENT_AMOUNT, null, null, [], false)
+      .accountsPartial(await buyAccounts())
+      .signers([buyer])
+      .rpc();
+
+    const purchase = await presaleProgram.account.userPurchase.fetch(userPurchasePda);
+    expect(purchase.paymentAmountPaid.toString()).to.equal(PAYMENT_AMOUNT.toString());
+    expect(purchase.paymentMintUsed.equals(refundPaymentMint.publicKey)).to.be.true;
+    expect(purchase.solPaid.toNumber()).to.equal(0);
+    expect(purchase.refunded).to.be.false;
+    expect(purchase.purchaseCount).to.equal(1);
+
+    // Payment tokens are valued at $1 and converted at the presale price (8-decimal token)
+    const allowedToken = await presaleProgram.account.allowedToken.fetch(allowedTokenPda);
+    expect(allowedToken.decimals).to.equal(MINT_DECIMALS);
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    const usdMicro = PAYMENT_AMOUNT.mul(new anchor.BN(1_000_000)).div(new anchor.BN(10).pow(new anchor.BN(MINT_DECIMALS)));
+    const expectedTokens = usdMicro.mul(new anchor.BN(100_000_000)).div(state.tokenPriceUsdMicro);
+    expect(purchase.totalPurchased.toString()).to.equal(expectedTokens.toString());
+
+    const record = await presaleProgram.account.purchaseRecord.fetch(purchaseRecordPda(buyer.publicKey, 0));
+    expect(record.buyer.equals(buyer.publicKey)).to.be.true;
+    expect(record.purchaseIndex).to.equal(0);
+    expect(record.amountPaid.toString()).to.equal(PAYMENT_AMOUNT.toString());
+    expect(record.amountTokens.toString()).to.equal(purchase.totalPurchased.toString());
+    expect(record.paymentIsSol).to.be.false;
+    expect(record.paymentMint.equals(refundPaymentMint.publicKey)).to.be.true;
+
+    const tracker = await presaleProgram.account.raisedTracker.fetch(raisedTrackerPda(refundPaymentMint.publicKey));
+    expect(tracker.totalRaised.toString()).to.equal(PAYMENT_AMOUNT.toString());
+    expect(tracker.purchaseCount.toNumber()).to.equal(1);
+    console.log("✓ Purchase recorded payment amount and mint");
+  });
+
+  it("Sets a soft cap", async () => {
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (!state.authority.equals(admin.publicKey)) {
+      console.log("ℹ Authority moved to governance - skipping");
+      return;
+    }
+    const softCap = state.totalRaisedUsdMicro.add(new anchor.BN(1_000_000_000));
+    await presaleProgram.methods.setSoftCap(softCap)
+      .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    const updated = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    expect(updated.softCap.toString()).to.equal(softCap.toString());
+    console.log("✓ Soft cap set above the amount raised");
+  });
+
+  it("Rejects refund while presale has not failed", async () => {
+    await expectError(
+      presaleProgram.methods.claimRefundPaymentToken()
+        .accountsPartial(refundAccounts())
+        .signers([buyer])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Correctly rejected refund before presale failed");
+  });
+
+  it("Rejects marking presale failed from non-authority", async () => {
+    await expectError(
+      presaleProgram.methods.failPresale()
+        .accounts({ presaleState: presaleStatePda, admin: buyer.publicKey })
+        .signers([buyer])
+        .rpc(),
+      "Unauthorized"
+    );
+    console.log("✓ Correctly rejected unauthorized fail_presale");
+  });
+
+  it("Rejects purchases before the start timestamp while Active", async () => {
+    const now = await connection.getBlockTime(await connection.getSlot());
+    await presaleProgram.methods.setStartTimestamp(new anchor.BN(now! + 3600))
+      .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    await expectError(
+      presaleProgram.methods.buy(new anchor.BN(1), null, null, [], false)
+        .accountsPartial(await buyAccounts())
+        .signers([buyer])
+        .rpc(),
+      "PresaleNotStarted"
+    );
+
+    await presaleProgram.methods.setStartTimestamp(null)
+      .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+    console.log("✓ Correctly rejected purchase before start time");
+  });
+
+  it("Rejects an end timestamp before the start timestamp", async () => {
+    const now = await connection.getBlockTime(await connection.getSlot());
+    await presaleProgram.methods.setStartTimestamp(new anchor.BN(now! + 7200))
+      .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    await expectError(
+      presaleProgram.methods.setEndTimestamp(new anchor.BN(now! + 3600))
+        .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc(),
+      "InvalidTimestamp"
+    );
+
+    await presaleProgram.methods.setStartTimestamp(null)
+      .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+    console.log("✓ Correctly rejected end time before start time");
+  });
+
+  it("Rejects an end timestamp in the past", async () => {
+    await expectError(
+      presaleProgram.methods.setEndTimestamp(new anchor.BN(1))
+        .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+        .signers([admin])
+        .rpc(),
+      "InvalidTimestamp"
+    );
+    console.log("✓ Correctly rejected end timestamp in the past");
+  });
+
+  it("Rejects purchases after the end timestamp while still Active", async () => {
+    const now = await connection.getBlockTime(await connection.getSlot());
+    const endTime = now! + 2;
+    await presaleProgram.methods.setEndTimestamp(new anchor.BN(endTime))
+      .accountsPartial({ presaleState: presaleStatePda, authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    await waitForClockPast(endTime);
+
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    expect(Object.keys(state.status)[0]).to.equal("active");
+
+    await expectError(
+      presaleProgram.methods.buy(new anchor.BN(1), null, null, [], false)
+        .accountsPartial(await buyAccounts())
+        .signers([buyer])
+        .rpc(),
+      "PresaleEnded"
+    );
+    console.log("✓ Correctly rejected purchase after end time");
+  });
+
+  it("Rejects stop_presale after the end timestamp", async () => {
+    await expectError(
+      presaleProgram.methods.stopPresale()
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc(),
+      "PresaleEnded"
+    );
+    console.log("✓ Correctly rejected stop_presale after end time");
+  });
+
+  it("Allows anyone to finalize the presale after the end timestamp", async () => {
+    await presaleProgram.methods.finalizePresale()
+      .accountsPartial({ presaleState: presaleStatePda, caller: buyer.publicKey })
+      .signers([buyer])
+      .rpc();
+
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    expect(Object.keys(state.status)[0]).to.equal("stopped");
+    console.log("✓ Presale finalized after end time");
+  });
+
+  it("Rejects soft cap changes once the presale stopped", async () => {
+    await expectError(
+      presaleProgram.methods.setSoftCap(new anchor.BN(0))
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Correctly rejected soft cap change after stop");
+  });
+
+  it("Rejects resetting for a new round while refunds are open", async () => {
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (!state.authority.equals(admin.publicKey)) {
+      console.log("ℹ Authority moved to governance - skipping");
+      return;
+    }
+    const roundNumber = Buffer.alloc(8);
+    roundNumber.writeBigUInt64LE(BigInt(state.roundNumber.toString()));
+    const [roundArchive] = PublicKey.findProgramAddressSync(
+      [Buffer.from("round_archive"), presaleStatePda.toBuffer(), roundNumber],
+      presaleProgram.programId
+    );
+    const [solVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+      presaleProgram.programId
+    );
+    // Stopped below the soft cap set earlier, so buyers can still claim refunds
+    await expectError(
+      presaleProgram.methods.resetForNewRound()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          roundArchive,
+          solVault,
+          authority: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([{ pubkey: presalePaymentVault, isWritable: false, isSigner: false }])
+        .signers([admin])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Correctly rejected round reset while refunds are open");
+  });
+
+  it("Rejects closing the purchase tracker while refunds are open", async () => {
+    const purchase = await presaleProgram.account.userPurchase.fetch(userPurchasePda);
+    const records = [];
+    for (let i = 0; i < purchase.purchaseCount; i++) {
+      const index = Buffer.alloc(4);
+      index.writeUInt32LE(i);
+      const [record] = PublicKey.findProgramAddressSync(
+        [Buffer.from("purchase_record"), presaleStatePda.toBuffer(), buyer.publicKey.toBuffer(), index],
+        presaleProgram.programId
+      );
+      records.push({ pubkey: record, isWritable: true, isSigner: false });
+    }
+    await expectError(
+      presaleProgram.methods.closeUserPurchase()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          buyer: buyer.publicKey,
+          userPurchase: userPurchasePda,
+        })
+        .remainingAccounts(records)
+        .signers([buyer])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Correctly rejected closing the purchase tracker while refunds are open");
+  });
+
+  it("Rejects claim mode changes after the sale and claims while refunds are open", async () => {
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    expect(state.claimMode).to.be.false;
+    if (state.authority.equals(admin.publicKey)) {
+      await expectError(
+        presaleProgram.methods.setClaimMode(true)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc(),
+        "InvalidStatus"
+      );
+    }
+    await expectError(
+      presaleProgram.methods.claimTokens()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          userPurchase: userPurchasePda,
+          buyer: buyer.publicKey,
+          presaleTokenVaultPda,
+          presaleTokenVault,
+          buyerTokenAccount: buyerPresaleTokenAccount,
+          presaleTokenMint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Claim mode locked after the sale and claims blocked while refunds are open");
+  });
+
+  it("Marks presale as failed", async () => {
+    await presaleProgram.methods.failPresale()
+      .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    expect(Object.keys(state.status)[0]).to.equal("failed");
+    console.log("✓ Presale marked as failed");
+  });
+
+  it("Rejects SOL refund for a buyer who paid with tokens", async () => {
+    const [solVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+      presaleProgram.programId
+    );
+    await expectError(
+      presaleProgram.methods.claimRefundSol()
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          buyer: buyer.publicKey,
+          userPurchase: userPurchasePda,
+          solVault: solVault,
+          presaleTokenVaultPda: presaleTokenVaultPda,
+          presaleTokenVault: presaleTokenVault,
+          buyerTokenAccount: buyerPresaleTokenAccount,
+          vestingAccount: null,
+          presaleTokenMint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc(),
+      "NothingToRefund"
+    );
+    console.log("✓ Correctly rejected SOL refund without SOL payment");
+  });
+
+  it("Refunds payment tokens and reclaims presale tokens", async () => {
+    const paymentBefore = await getAccount(connection, buyerPaymentTokenAccount);
+
+    await presaleProgram.methods.claimRefundPaymentToken()
+      .accountsPartial(refundAccounts())
+      .signers([buyer])
+      .rpc();
+
+    const paymentAfter = await getAccount(connection, buyerPaymentTokenAccount);
+    const presaleTokensAfter = await getAccount(connection, buyerPresaleTokenAccount);
+    expect((paymentAfter.amount - paymentBefore.amount).toString()).to.equal(PAYMENT_AMOUNT.toString());
+    expect(presaleTokensAfter.amount.toString()).to.equal("0");
+
+    const purchase = await presaleProgram.account.userPurchase.fetch(userPurchasePda);
+    expect(purchase.refunded).to.be.true;
+    console.log("✓ Refund claimed successfully");
+  });
+
+  it("Rejects a second refund claim", async () => {
+    await expectError(
+      presaleProgram.methods.claimRefundPaymentToken()
+        .accountsPartial(refundAccounts())
+        .signers([buyer])
+        .rpc(),
+      "AlreadyRefunded"
+    );
+    console.log("✓ Correctly rejected double refund");
+  });
+
+  it("Blocks treasury withdrawals after presale failed", async () => {
+    const state = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (state.treasuryAddress.equals(PublicKey.default) || !state.authority.equals(admin.publicKey)) {
+      console.log("ℹ Treasury not set or authority moved to governance - skipping");
+      return;
+    }
+    const [solVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
+      presaleProgram.programId
+    );
+    await expectError(
+      presaleProgram.methods.withdrawSolToTreasury(new anchor.BN(1))
+        .accountsPartial({
+          presaleState: presaleStatePda,
+          authority: admin.publicKey,
+          solVault: solVault,
+          treasury: state.treasuryAddress,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc(),
+      "InvalidStatus"
+    );
+    console.log("✓ Correctly blocked treasury withdrawal on failed presale");
+  });
+});
@@ -70,14 +70,61 @@ describe("Presale Buy Function Test", () => {
   let presaleTokenVault: PublicKey;
   let solVault: PublicKey;
   let buyerPresaleTokenAccount: PublicKey;
+  let buyerBlacklistPda: PublicKey;
+  let userPurchasePda: PublicKey;
+  let solRaisedTrackerPda: PublicKey;
 
   // Chainlink SOL/USD feed for devnet (tests run on devnet/localnet)
   // Mainnet: CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt
   const CHAINLINK_SOL_USD_FEED = new PublicKey("99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR");
+  // Chainlink BTC/USD feed for devnet, a real feed for a different pair
+  const CHAINLINK_BTC_USD_FEED = new PublicKey("6PxBx93S8x3tno1TsFZwT5VqP8drrRCbCXygEXYNkFJe");
+  // Chainlink OCR2 store program, owner of both feeds
+  const CHAINLINK_PROGRAM_ID = new PublicKey("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
 
   // SOL amount to spend (0.01 SOL)
   const SOL_AMOUNT_LAMPORTS = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
 
+  // PurchaseRecord for a purchase is indexed by the buyer's purchase count at the time
+  const purchaseRecordAt = (index: number) => {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("purchase_record"), presaleStatePda.toBuffer(), user.publicKey.toBuffer(), indexBytes],
+      presaleProgram.programId
+    )[0];
+  };
+
+  // Accounts for a buy_with_sol by `user`, recorded as purchase number `purchaseIndex`
+  const solBuyAccounts = (purchaseIndex: number) => ({
+    presaleState: presaleStatePda,
+    tokenState: tokenStatePda,
+    buyer: user.publicKey,
+    solVault: solVault,
+    presaleTokenVaultPda: presaleTokenVaultPda,
+    presaleTokenVault: presaleTokenVault,
+    buyerTokenAccount: buyerPresaleTokenAccount,
+    presaleTokenMint: mint.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    userPurchase: userPurchasePda,
+    purchaseRecord: purchaseRecordAt(purchaseIndex),
+    raisedTracker: solRaisedTrackerPda,
+    buyerBlacklist: buyerBlacklistPda,
+    chainlinkFeed: CHAINLINK_SOL_USD_FEED,
+    chainlinkFeedBackup: null,
+    presaleRound: null,
+    nextPresaleRound: null,
+    buyerWhitelist: null,
+    referralAccount: null,
+    referrerBlacklist: null,
+    vestingAccount: null,
+    systemProgram: SystemProgram.programId,
+  });
+
+  const nextPurchaseIndex = async () =>
+    (await presaleProgram.account.userPurchase.fetchNullable(userPurchasePda))?.purchaseCount ?? 0;
+
   before(async () => {
     // Check balances - skip airdrop if already funded
     const accounts = [admin, user];
@@ -115,6 +162,19 @@ describe("Presale Buy Function Test", () => {
       [Buffer.from("presale_sol_vault"), presaleStatePda.toBuffer()],
       presaleProgram.programId
     );
+    [buyerBlacklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), user.publicKey.toBuffer()],
+      tokenProgram.programId
+    );
+    [userPurchasePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), user.publicKey.toBuffer()],
+      presaleProgram.programId
+    );
+    // SOL purchases are tracked under the default pubkey
+    [solRaisedTrackerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("raised_tracker"), presaleStatePda.toBuffer(), PublicKey.default.toBuffer()],
+      presaleProgram.programId
+    );
 
     // Initialize token program if needed
     try {
@@ -157,7 +217,7 @@ describe("Presale Buy Function Test", () => {
       // Governance not initialized - initialize it
       try {
         await governanceProgram.methods
-          .initialize(2, new anchor.BN(1800), [provider.wallet.publicKey, keys.signer1.publicKey, keys.signer2.publicKey, keys.signer3.publicKey])
+          .initialize(2, new anchor.BN(1800), [provider.wallet.publicKey, keys.signer1.publicKey, keys.signer2.publicKey, keys.signer3.publicKey], new anchor.BN(0), 0)
           .accounts({
             governanceState: governanceStatePda,
             authority: provider.wallet.publicKey,
@@ -244,6 +304,20 @@ describe("Presale Buy Function Test", () => {
     } catch (err: any) {
       console.log("ℹ Could not check presale status:", err.message);
     }
+
+    // buy_with_sol only prices against the feed pinned on the presale
+    try {
+      const presaleState = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+      if (!presaleState.oracleFeed.equals(CHAINLINK_SOL_USD_FEED)) {
+        await presaleProgram.methods.setOracleType({ chainlink: {} }, CHAINLINK_SOL_USD_FEED)
+          .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+          .signers([admin])
+          .rpc();
+        console.log("✓ Chainlink SOL/USD feed pinned");
+      }
+    } catch (err: any) {
+      console.log("ℹ Could not pin the oracle feed:", err.message);
+    }
   });
 
   it("Buy presale tokens with SOL", async () => {
@@ -279,37 +353,38 @@ describe("Presale Buy Function Test", () => {
       );
     }
 
-    // Derive required PDAs
-    const [buyerBlacklistPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("blacklist"), user.publicKey.toBuffer()],
-      tokenProgram.programId
-    );
-
-    const [userPurchasePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_purchase"), presaleStatePda.toBuffer(), user.publicKey.toBuffer()],
-      presaleProgram.programId
-    );
+    const trackerBefore = await presaleProgram.account.raisedTracker.fetchNullable(solRaisedTrackerPda);
+    const purchaseIndex = await nextPurchaseIndex();
 
     // Get balance before
     const balanceBefore = await connection.getTokenAccountBalance(buyerPresaleTokenAccount).catch(() => ({ value: { amount: "0" } }));
 
-    // Execute buy with SOL
-    await presaleProgram.methods.buyWithSol(SOL_AMOUNT_LAMPORTS)
-      .accounts({
-        presaleState: presaleStatePda,
-        tokenState: tokenStatePda,
-        buyer: user.publicKey,
-        solVault: solVault,
-        presaleTokenVaultPda: presaleTokenVaultPda,
-        presaleTokenVault: presaleTokenVault,
-        buyerTokenAccount: buyerPresaleTokenAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        userPurchase: userPurchasePda,
-        buyerBlacklist: buyerBlacklistPda,
-        chainlinkFeed: CHAINLINK_SOL_USD_FEED,
-        systemProgram: SystemProgram.programId,
-      })
+    const buyAccounts = solBuyAccounts(purchaseIndex);
+
+    // Quote the purchase: a u64::MAX minimum always trips the slippage check, and the
+    // SlippageProtectionTriggered event in the simulation logs carries the actual amount
+    let quotedTokens: anchor.BN | null = null;
+    try {
+      await presaleProgram.methods.buyWithSol(SOL_AMOUNT_LAMPORTS, null, null, new anchor.BN("18446744073709551615"), [], false)
+        .accounts(buyAccounts)
+        .signers([user])
+        .simulate();
+    } catch (err: any) {
+      const logs: string[] = err.simulationResponse?.logs ?? err.logs ?? [];
+      for (const log of logs) {
+        if (!log.startsWith("Program data: ")) continue;
+        const event = presaleProgram.coder.events.decode(log.slice("Program data: ".length));
+        if (event?.name === "slippageProtectionTriggered") {
+          quotedTokens = event.data.actualReceived as anchor.BN;
+        }
+      }
+    }
+    expect(quotedTokens, "slippage quote").to.not.be.null;
+
+    // Execute buy with SOL, accepting 0.5% slippage from the quote
+    const minTokensOut = quotedTokens!.muln(995).divn(1000);
+    await presaleProgram.methods.buyWithSol(SOL_AMOUNT_LAMPORTS, null, null, minTokensOut, [], false)
+      .accounts(buyAccounts)
       .signers([user])
       .rpc();
 
@@ -318,6 +393,24 @@ describe("Presale Buy Function Test", () => {
 
     // Verify tokens were received
     expect(Number(balanceAfter.value.amount)).to.be.greaterThan(Number(balanceBefore.value.amount));
+
+    // Walk the buyer's purchase history off-chain; records are created sequentially
+    const purchaseAfter = await presaleProgram.account.userPurchase.fetch(userPurchasePda);
+    expect(purchaseAfter.purchaseCount).to.equal(purchaseIndex + 1);
+    for (let i = 0; i < purchaseAfter.purchaseCount; i++) {
+      const record = await presaleProgram.account.purchaseRecord.fetch(purchaseRecordAt(i));
+      expect(record.purchaseIndex).to.equal(i);
+      expect(record.buyer.equals(user.publicKey)).to.be.true;
+    }
+    const latest = await presaleProgram.account.purchaseRecord.fetch(purchaseRecordAt(purchaseIndex));
+    expect(latest.paymentIsSol).to.be.true;
+    expect(latest.amountPaid.toString()).to.equal(SOL_AMOUNT_LAMPORTS.toString());
+
+    // SOL raised is accounted separately from payment tokens, in lamports
+    const trackerAfter = await presaleProgram.account.raisedTracker.fetch(solRaisedTrackerPda);
+    const raisedBefore = trackerBefore ? trackerBefore.totalRaised : new anchor.BN(0);
+    expect(trackerAfter.totalRaised.sub(raisedBefore).toString()).to.equal(SOL_AMOUNT_LAMPORTS.toString());
+    expect(trackerAfter.paymentMint.equals(PublicKey.default)).to.be.true;
     
     console.log("✓ Buy with SOL transaction completed successfully");
     console.log(`  SOL spent: ${SOL_AMOUNT_LAMPORTS.toString()} lamports (${Number(SOL_AMOUNT_LAMPORTS.toString()) / LAMPORTS_PER_SOL} SOL)`);
@@ -325,4 +418,95 @@ describe("Presale Buy Function Test", () => {
     console.log(`  Balance after: ${balanceAfter.value.amount}`);
     console.log(`  Tokens received: ${Number(balanceAfter.value.amount) - Number(balanceBefore.value.amount)}`);
   });
+
+  it("Rejects a SOL purchase priced by a feed other than the configured one", async () => {
+    // A genuine Chainlink feed for another pair, so only the feed pin can reject it
+    const feedInfo = await connection.getAccountInfo(CHAINLINK_BTC_USD_FEED);
+    expect(feedInfo, "BTC/USD feed account").to.not.be.null;
+    expect(feedInfo!.owner.equals(CHAINLINK_PROGRAM_ID)).to.be.true;
+    try {
+      await presaleProgram.methods.buyWithSol(SOL_AMOUNT_LAMPORTS, null, null, new anchor.BN(0), [], false)
+        .accounts({ ...solBuyAccounts(await nextPurchaseIndex()), chainlinkFeed: CHAINLINK_BTC_USD_FEED })
+        .signers([user])
+        .simulate();
+      expect.fail("Expected InvalidOracleFeed");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidOracleFeed");
+    }
+    console.log("✓ Purchase priced by the BTC/USD feed rejected");
+  });
+
+  it("Applies the configured price staleness threshold", async () => {
+    const presaleState = await presaleProgram.account.presaleState.fetch(presaleStatePda);
+    if (!presaleState.authority.equals(admin.publicKey)) {
+      console.log("ℹ Authority moved to governance - skipping");
+      return;
+    }
+    // 0 falls back to the default threshold and cannot be set again afterwards
+    const previous = presaleState.priceStalenessThreshold;
+    if (previous.isZero()) {
+      console.log("ℹ Staleness threshold uses the built-in default - skipping");
+      return;
+    }
+    const setThreshold = (seconds: number) =>
+      presaleProgram.methods.setPriceStalenessThreshold(new anchor.BN(seconds))
+        .accounts({ presaleState: presaleStatePda, admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+    // Simulated buy at `threshold`: "stale", the PriceNearStale event, or "fresh"
+    const outcomeAt = async (threshold: number) => {
+      await setThreshold(threshold);
+      try {
+        const { events } = await presaleProgram.methods
+          .buyWithSol(SOL_AMOUNT_LAMPORTS, null, null, new anchor.BN(0), [], false)
+          .accounts(solBuyAccounts(await nextPurchaseIndex()))
+          .signers([user])
+          .simulate();
+        return events.find((event) => event.name === "priceNearStale")?.data ?? "fresh";
+      } catch (err: any) {
+        expect(err.toString()).to.include("StalePrice");
+        return "stale";
+      }
+    };
+    // Publish time of the feed's latest round. Chainlink store layout: 8-byte
+    // discriminator, 192-byte header (live_length at 140, live_cursor at 144), then
+    // a ring buffer of 48-byte transmissions with the u32 timestamp at offset 8
+    const publishTime = async () => {
+      const data = (await connection.getAccountInfo(CHAINLINK_SOL_USD_FEED))!.data;
+      expect(data[8 + 130], "feed decimals").to.equal(8);
+      const liveLength = data.readUInt32LE(8 + 140);
+      const latest = (data.readUInt32LE(8 + 144) + liveLength - 1) % liveLength;
+      return data.readUInt32LE(8 + 192 + latest * 48 + 8);
+    };
+    const clusterTime = async () => (await connection.getBlockTime(await connection.getSlot()))!;
+
+    try {
+      // Measure the price age, and measure again if the feed updates mid-test
+      for (let attempt = 1; ; attempt++) {
+        const publishedAt = await publishTime();
+        const age = (await clusterTime()) - publishedAt;
+
+        // Well inside the threshold: accepted without a warning
+        const fresh = await outcomeAt(Math.min(age + 600, 86_400));
+        // Older than the 60 second minimum plus grace: rejected. Otherwise a threshold
+        // equal to the age puts the price within the grace band: accepted with a warning
+        const boundary = age > 180 ? await outcomeAt(60) : await outcomeAt(Math.max(age, 60));
+
+        if ((await publishTime()) !== publishedAt && attempt < 3) continue;
+        expect(fresh).to.equal("fresh");
+        if (age > 180) {
+          expect(boundary).to.equal("stale");
+        } else {
+          expect(boundary, "PriceNearStale event").to.be.an("object");
+          expect(boundary.threshold.toNumber()).to.equal(Math.max(age, 60));
+          expect(boundary.priceAge.toNumber()).to.be.within(age, age + 60);
+        }
+        console.log(`  Price age: ${age}s`);
+        break;
+      }
+    } finally {
+      await setThreshold(previous.toNumber());
+    }
+    console.log("✓ Staleness checked against the configured threshold");
+  });
 });